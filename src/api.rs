@@ -1,4 +1,14 @@
+pub mod groups;
+pub mod hooks;
+pub mod issues;
 pub mod keys;
+pub mod license;
+pub mod merge_requests;
 pub mod personal_access_tokens;
+pub mod projects;
+pub mod request;
+pub mod statistics;
+pub mod todos;
 pub mod user;
 pub mod users;
+pub mod version;
@@ -0,0 +1,9 @@
+pub mod delete;
+pub mod iterations;
+pub mod projects;
+pub mod transfer;
+
+pub use self::delete::DeleteGroup;
+pub use self::iterations::GroupIterations;
+pub use self::projects::GroupProjects;
+pub use self::transfer::TransferGroup;
@@ -0,0 +1,28 @@
+use derive_builder::Builder;
+use gitlab::api::Endpoint;
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Delete a group with its subgroups and projects
+#[derive(Debug, Builder)]
+pub struct DeleteGroup {
+	/// The group id
+	pub group: u64,
+}
+
+impl DeleteGroup {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> DeleteGroupBuilder {
+		DeleteGroupBuilder::default()
+	}
+}
+
+impl Endpoint for DeleteGroup {
+	fn method(&self) -> Method {
+		Method::DELETE
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("groups/{}", self.group).into()
+	}
+}
@@ -0,0 +1,44 @@
+use derive_builder::Builder;
+use gitlab::api::{Endpoint, Pageable, QueryParams};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// List the iterations of a group
+#[derive(Debug, Builder)]
+pub struct GroupIterations<'a> {
+	/// The group id
+	pub group: u64,
+	/// Only iterations in this state (opened, upcoming, current, closed or all)
+	#[builder(default)]
+	pub state: Option<&'a str>,
+	/// Include the iterations of the parent groups
+	#[builder(default)]
+	pub include_ancestors: bool,
+}
+
+impl<'a> GroupIterations<'a> {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> GroupIterationsBuilder<'a> {
+		GroupIterationsBuilder::default()
+	}
+}
+
+impl Endpoint for GroupIterations<'_> {
+	fn method(&self) -> Method {
+		Method::GET
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("groups/{}/iterations", self.group).into()
+	}
+
+	fn parameters(&self) -> QueryParams<'_> {
+		let mut params = QueryParams::default();
+		params
+			.push_opt("state", self.state)
+			.push("include_ancestors", self.include_ancestors);
+		params
+	}
+}
+
+impl Pageable for GroupIterations<'_> {}
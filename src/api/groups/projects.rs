@@ -0,0 +1,47 @@
+use derive_builder::Builder;
+use gitlab::api::{Endpoint, Pageable, QueryParams};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// List the projects of a group and its subgroups
+#[derive(Debug, Builder)]
+pub struct GroupProjects {
+	/// The group id
+	pub group: u64,
+	/// Include the project statistics (size of repository, artifacts, ...)
+	#[builder(default)]
+	pub statistics: bool,
+}
+
+impl GroupProjects {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> GroupProjectsBuilder {
+		GroupProjectsBuilder::default()
+	}
+}
+
+impl Endpoint for GroupProjects {
+	fn method(&self) -> Method {
+		Method::GET
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("groups/{}/projects", self.group).into()
+	}
+
+	fn parameters(&self) -> QueryParams<'_> {
+		let mut params = QueryParams::default();
+		params
+			.push("include_subgroups", true)
+			.push("statistics", self.statistics)
+			// keyset pagination needs an ordering by id (newest first like the default ordering)
+			.push("order_by", "id");
+		params
+	}
+}
+
+impl Pageable for GroupProjects {
+	fn use_keyset_pagination(&self) -> bool {
+		true
+	}
+}
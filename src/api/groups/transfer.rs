@@ -0,0 +1,37 @@
+use derive_builder::Builder;
+use gitlab::api::{Endpoint, QueryParams};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Move a group under another group or to the top level
+#[derive(Debug, Builder)]
+pub struct TransferGroup {
+	/// The group id
+	pub group: u64,
+	/// The new parent group id (None to make it a top level group)
+	#[builder(default)]
+	pub parent: Option<u64>,
+}
+
+impl TransferGroup {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> TransferGroupBuilder {
+		TransferGroupBuilder::default()
+	}
+}
+
+impl Endpoint for TransferGroup {
+	fn method(&self) -> Method {
+		Method::POST
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("groups/{}/transfer", self.group).into()
+	}
+
+	fn parameters(&self) -> QueryParams<'_> {
+		let mut params = QueryParams::default();
+		params.push_opt("group_id", self.parent);
+		params
+	}
+}
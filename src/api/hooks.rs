@@ -0,0 +1,9 @@
+pub mod create;
+pub mod delete;
+pub mod list;
+pub mod test;
+
+pub use self::create::CreateSystemHook;
+pub use self::delete::DeleteSystemHook;
+pub use self::list::SystemHooks;
+pub use self::test::TestSystemHook;
@@ -0,0 +1,63 @@
+use derive_builder::Builder;
+use gitlab::api::{Endpoint, QueryParams};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Add a system hook to the instance (admin only)
+#[derive(Debug, Builder)]
+pub struct CreateSystemHook<'a> {
+	/// The url called on the events
+	pub url: &'a str,
+	/// The name of the hook
+	#[builder(default)]
+	pub name: Option<&'a str>,
+	/// The secret sent in the X-Gitlab-Token header
+	#[builder(default)]
+	pub token: Option<&'a str>,
+	/// Trigger the hook on pushes
+	#[builder(default)]
+	pub push_events: bool,
+	/// Trigger the hook on tag pushes
+	#[builder(default)]
+	pub tag_push_events: bool,
+	/// Trigger the hook on merge requests
+	#[builder(default)]
+	pub merge_requests_events: bool,
+	/// Trigger the hook on repository updates
+	#[builder(default)]
+	pub repository_update_events: bool,
+	/// Verify the SSL certificate of the url
+	#[builder(default = "true")]
+	pub enable_ssl_verification: bool,
+}
+
+impl<'a> CreateSystemHook<'a> {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> CreateSystemHookBuilder<'a> {
+		CreateSystemHookBuilder::default()
+	}
+}
+
+impl Endpoint for CreateSystemHook<'_> {
+	fn method(&self) -> Method {
+		Method::POST
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		"hooks".into()
+	}
+
+	fn parameters(&self) -> QueryParams<'_> {
+		let mut params = QueryParams::default();
+		params
+			.push("url", self.url)
+			.push_opt("name", self.name)
+			.push_opt("token", self.token)
+			.push("push_events", self.push_events)
+			.push("tag_push_events", self.tag_push_events)
+			.push("merge_requests_events", self.merge_requests_events)
+			.push("repository_update_events", self.repository_update_events)
+			.push("enable_ssl_verification", self.enable_ssl_verification);
+		params
+	}
+}
@@ -0,0 +1,28 @@
+use derive_builder::Builder;
+use gitlab::api::Endpoint;
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Delete a system hook of the instance (admin only)
+#[derive(Debug, Builder)]
+pub struct DeleteSystemHook {
+	/// The hook id
+	pub hook_id: u64,
+}
+
+impl DeleteSystemHook {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> DeleteSystemHookBuilder {
+		DeleteSystemHookBuilder::default()
+	}
+}
+
+impl Endpoint for DeleteSystemHook {
+	fn method(&self) -> Method {
+		Method::DELETE
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("hooks/{}", self.hook_id).into()
+	}
+}
@@ -0,0 +1,26 @@
+use gitlab::api::{Endpoint, Pageable};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// List the system hooks of the instance (admin only)
+#[derive(Debug)]
+pub struct SystemHooks;
+
+impl SystemHooks {
+	/// Create the endpoint.
+	pub fn build() -> Self {
+		SystemHooks {}
+	}
+}
+
+impl Endpoint for SystemHooks {
+	fn method(&self) -> Method {
+		Method::GET
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		"hooks".into()
+	}
+}
+
+impl Pageable for SystemHooks {}
@@ -0,0 +1,28 @@
+use derive_builder::Builder;
+use gitlab::api::Endpoint;
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Send a test event to a system hook of the instance (admin only)
+#[derive(Debug, Builder)]
+pub struct TestSystemHook {
+	/// The hook id
+	pub hook_id: u64,
+}
+
+impl TestSystemHook {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> TestSystemHookBuilder {
+		TestSystemHookBuilder::default()
+	}
+}
+
+impl Endpoint for TestSystemHook {
+	fn method(&self) -> Method {
+		Method::POST
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("hooks/{}", self.hook_id).into()
+	}
+}
@@ -0,0 +1,63 @@
+use derive_builder::Builder;
+use gitlab::api::{Endpoint, Pageable, QueryParams};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Order of the issues
+#[derive(Debug, Clone, Copy)]
+pub enum IssuesOrderBy {
+	/// Soonest due date first, issues without due date last
+	DueDate,
+	/// Highest label priority first
+	Priority,
+}
+
+impl IssuesOrderBy {
+	fn as_str(&self) -> &'static str {
+		match self {
+			IssuesOrderBy::DueDate => "due_date",
+			IssuesOrderBy::Priority => "priority",
+		}
+	}
+}
+
+/// List the opened issues assigned to the current user across all projects
+#[derive(Debug, Builder)]
+pub struct AssignedIssues {
+	/// The order of the issues (last created first by default)
+	#[builder(default)]
+	pub order_by: Option<IssuesOrderBy>,
+}
+
+impl AssignedIssues {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> AssignedIssuesBuilder {
+		AssignedIssuesBuilder::default()
+	}
+}
+
+impl Endpoint for AssignedIssues {
+	fn method(&self) -> Method {
+		Method::GET
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		"issues".into()
+	}
+
+	fn parameters(&self) -> QueryParams<'_> {
+		let mut params = QueryParams::default();
+		params
+			.push("state", "opened")
+			.push("scope", "assigned_to_me");
+		if let Some(order_by) = self.order_by {
+			params
+				.push("order_by", order_by.as_str())
+				.push("sort", "asc");
+		}
+
+		params
+	}
+}
+
+impl Pageable for AssignedIssues {}
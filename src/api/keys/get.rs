@@ -25,7 +25,7 @@ impl<'a> Endpoint for GetKey<'a> {
 		"keys".into()
 	}
 
-	fn parameters(&self) -> QueryParams {
+	fn parameters(&self) -> QueryParams<'_> {
 		let mut params = QueryParams::default();
 		params.push("fingerprint", self.fingerprint);
 
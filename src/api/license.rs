@@ -0,0 +1,24 @@
+use gitlab::api::Endpoint;
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Get the license of the instance (admin only)
+#[derive(Debug)]
+pub struct License;
+
+impl License {
+	/// Create the endpoint.
+	pub fn build() -> Self {
+		License {}
+	}
+}
+
+impl Endpoint for License {
+	fn method(&self) -> Method {
+		Method::GET
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		"license".into()
+	}
+}
@@ -0,0 +1,61 @@
+use derive_builder::Builder;
+use gitlab::api::{Endpoint, Pageable, QueryParams};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Merge requests to list among the ones visible by the current user
+#[derive(Debug, Clone, Copy)]
+pub enum MergeRequestsScope {
+	CreatedByMe,
+	AssignedToMe,
+	All,
+}
+
+impl MergeRequestsScope {
+	fn as_str(&self) -> &'static str {
+		match self {
+			MergeRequestsScope::CreatedByMe => "created_by_me",
+			MergeRequestsScope::AssignedToMe => "assigned_to_me",
+			MergeRequestsScope::All => "all",
+		}
+	}
+}
+
+/// List the opened merge requests across all projects
+#[derive(Debug, Builder)]
+pub struct OpenedMergeRequests {
+	/// The merge requests to consider
+	pub scope: MergeRequestsScope,
+	/// Only list the merge requests this user is a reviewer of
+	#[builder(default)]
+	pub reviewer_id: Option<u64>,
+}
+
+impl OpenedMergeRequests {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> OpenedMergeRequestsBuilder {
+		OpenedMergeRequestsBuilder::default()
+	}
+}
+
+impl Endpoint for OpenedMergeRequests {
+	fn method(&self) -> Method {
+		Method::GET
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		"merge_requests".into()
+	}
+
+	fn parameters(&self) -> QueryParams<'_> {
+		let mut params = QueryParams::default();
+		params
+			.push("state", "opened")
+			.push("scope", self.scope.as_str())
+			.push_opt("reviewer_id", self.reviewer_id);
+
+		params
+	}
+}
+
+impl Pageable for OpenedMergeRequests {}
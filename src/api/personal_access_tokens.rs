@@ -1,7 +1,9 @@
+pub mod current;
 pub mod get;
 pub mod revoke;
 pub mod rotate;
 
+pub use self::current::CurrentPersonalAccessToken;
 pub use self::get::PersonalAccessTokenState;
 pub use self::get::PersonalAccessTokens;
 pub use self::revoke::RevokePersonalAccessToken;
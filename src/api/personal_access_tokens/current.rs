@@ -0,0 +1,18 @@
+use std::borrow::Cow;
+
+use gitlab::api::Endpoint;
+use reqwest::Method;
+
+/// Get the personal access token used to authenticate the request
+#[derive(Debug, Default)]
+pub struct CurrentPersonalAccessToken;
+
+impl Endpoint for CurrentPersonalAccessToken {
+	fn method(&self) -> Method {
+		Method::GET
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		"personal_access_tokens/self".into()
+	}
+}
@@ -2,7 +2,7 @@ use std::{borrow::Cow, str::FromStr};
 
 use anyhow::{anyhow, Error};
 use derive_builder::Builder;
-use gitlab::api::{Endpoint, QueryParams};
+use gitlab::api::{Endpoint, Pageable, QueryParams};
 use reqwest::Method;
 
 #[derive(Debug, Clone, Copy)]
@@ -67,7 +67,7 @@ impl<'a> Endpoint for PersonalAccessTokens<'a> {
 		"personal_access_tokens".into()
 	}
 
-	fn parameters(&self) -> QueryParams {
+	fn parameters(&self) -> QueryParams<'_> {
 		let mut params = QueryParams::default();
 		params.push("user_id", self.user_id);
 		params.push_opt("revoked", self.revoked);
@@ -80,3 +80,5 @@ impl<'a> Endpoint for PersonalAccessTokens<'a> {
 		params
 	}
 }
+
+impl<'a> Pageable for PersonalAccessTokens<'a> {}
@@ -30,7 +30,7 @@ impl Endpoint for RotatePersonalAccessToken {
         format!("personal_access_tokens/{}/rotate", self.token_id).into()
     }
 
-    fn parameters(&self) -> QueryParams {
+    fn parameters(&self) -> QueryParams<'_> {
         let mut params = QueryParams::default();
         params.push_opt("expires_at", self.expires_at);
 
@@ -0,0 +1,20 @@
+pub mod boards;
+pub mod deployments;
+pub mod edit_settings;
+pub mod environments;
+pub mod error_tracking;
+pub mod hooks;
+pub mod issues;
+pub mod jobs;
+pub mod merge_requests;
+pub mod remote_mirrors;
+pub mod repository;
+pub mod secure_files;
+pub mod star;
+pub mod triggers;
+pub mod unstar;
+pub mod variables;
+
+pub use self::edit_settings::EditProjectSettings;
+pub use self::star::StarProject;
+pub use self::unstar::UnstarProject;
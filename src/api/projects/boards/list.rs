@@ -0,0 +1,30 @@
+use derive_builder::Builder;
+use gitlab::api::{Endpoint, Pageable};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// List the issue boards of a project with their lists
+#[derive(Debug, Builder)]
+pub struct ProjectBoards {
+	/// The project id
+	pub project: u64,
+}
+
+impl ProjectBoards {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> ProjectBoardsBuilder {
+		ProjectBoardsBuilder::default()
+	}
+}
+
+impl Endpoint for ProjectBoards {
+	fn method(&self) -> Method {
+		Method::GET
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("projects/{}/boards", self.project).into()
+	}
+}
+
+impl Pageable for ProjectBoards {}
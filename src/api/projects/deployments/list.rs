@@ -0,0 +1,46 @@
+use derive_builder::Builder;
+use gitlab::api::{Endpoint, Pageable, QueryParams};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// List the deployments of a project, most recent first
+#[derive(Debug, Builder)]
+pub struct Deployments<'a> {
+	/// The project id
+	pub project: u64,
+	/// Only deployments to this environment
+	#[builder(default)]
+	pub environment: Option<&'a str>,
+	/// Only deployments with this status (success, failed...)
+	#[builder(default)]
+	pub status: Option<&'a str>,
+}
+
+impl<'a> Deployments<'a> {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> DeploymentsBuilder<'a> {
+		DeploymentsBuilder::default()
+	}
+}
+
+impl<'a> Endpoint for Deployments<'a> {
+	fn method(&self) -> Method {
+		Method::GET
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("projects/{}/deployments", self.project).into()
+	}
+
+	fn parameters(&self) -> QueryParams<'_> {
+		let mut params = QueryParams::default();
+		params
+			.push_opt("environment", self.environment)
+			.push_opt("status", self.status)
+			.push("order_by", "id")
+			.push("sort", "desc");
+		params
+	}
+}
+
+impl<'a> Pageable for Deployments<'a> {}
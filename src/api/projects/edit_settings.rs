@@ -0,0 +1,37 @@
+use crate::types::ProjectSettings;
+
+use derive_builder::Builder;
+use gitlab::api::{BodyError, Endpoint};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Change the settings of a project, the unset settings being left unchanged
+#[derive(Debug, Builder)]
+pub struct EditProjectSettings<'a> {
+	/// The project id
+	pub project: u64,
+	/// The settings to change
+	pub settings: &'a ProjectSettings,
+}
+
+impl<'a> EditProjectSettings<'a> {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> EditProjectSettingsBuilder<'a> {
+		EditProjectSettingsBuilder::default()
+	}
+}
+
+impl Endpoint for EditProjectSettings<'_> {
+	fn method(&self) -> Method {
+		Method::PUT
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("projects/{}", self.project).into()
+	}
+
+	fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+		let body = serde_json::to_vec(self.settings)?;
+		Ok(Some(("application/json", body)))
+	}
+}
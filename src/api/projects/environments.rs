@@ -0,0 +1,3 @@
+pub mod stop;
+
+pub use self::stop::StopEnvironment;
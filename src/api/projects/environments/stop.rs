@@ -0,0 +1,34 @@
+use derive_builder::Builder;
+use gitlab::api::Endpoint;
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Stop an environment of a project
+#[derive(Debug, Builder)]
+pub struct StopEnvironment {
+	/// The project id
+	pub project: u64,
+	/// The environment id
+	pub environment: u64,
+}
+
+impl StopEnvironment {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> StopEnvironmentBuilder {
+		StopEnvironmentBuilder::default()
+	}
+}
+
+impl Endpoint for StopEnvironment {
+	fn method(&self) -> Method {
+		Method::POST
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!(
+			"projects/{}/environments/{}/stop",
+			self.project, self.environment
+		)
+		.into()
+	}
+}
@@ -0,0 +1,5 @@
+pub mod detailed_error;
+pub mod errors;
+
+pub use self::detailed_error::SentryDetailedError;
+pub use self::errors::SentryErrors;
@@ -0,0 +1,78 @@
+use crate::types::DetailedError;
+
+use graphql_client::{GraphQLQuery, QueryBody};
+use serde::{Deserialize, Serialize};
+
+/// Get the details of an error group of a project (GraphQL only)
+pub struct SentryDetailedError;
+
+/// The variables of the query
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SentryDetailedErrorVariables {
+	/// The project path with its namespace
+	pub full_path: String,
+	/// The global id of the error
+	pub id: String,
+}
+
+impl SentryDetailedErrorVariables {
+	/// Variables for an error given by its sentry id
+	pub fn new(full_path: &str, sentry_id: u64) -> Self {
+		Self {
+			full_path: full_path.to_owned(),
+			id: format!(
+				"gid://gitlab/Gitlab::ErrorTracking::DetailedError/{}",
+				sentry_id
+			),
+		}
+	}
+}
+
+/// The response of the query
+#[derive(Deserialize)]
+pub struct SentryDetailedErrorData {
+	project: Option<SentryDetailedErrorProject>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SentryDetailedErrorProject {
+	sentry_errors: Option<SentryDetailedErrorCollection>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SentryDetailedErrorCollection {
+	detailed_error: Option<DetailedError>,
+}
+
+impl SentryDetailedErrorData {
+	/// The error of the response
+	pub fn error(self) -> Option<DetailedError> {
+		self.project?.sentry_errors?.detailed_error
+	}
+}
+
+impl GraphQLQuery for SentryDetailedError {
+	type Variables = SentryDetailedErrorVariables;
+	type ResponseData = SentryDetailedErrorData;
+
+	fn build_query(variables: Self::Variables) -> QueryBody<Self::Variables> {
+		QueryBody {
+			variables,
+			query: r#"
+query sentryDetailedError($fullPath: ID!, $id: GitlabErrorTrackingDetailedErrorID!) {
+	project(fullPath: $fullPath) {
+		sentryErrors {
+			detailedError(id: $id) {
+				sentryId title type count userCount firstSeen lastSeen status culprit
+				externalUrl message firstReleaseVersion lastReleaseVersion gitlabIssuePath
+			}
+		}
+	}
+}"#,
+			operation_name: "sentryDetailedError",
+		}
+	}
+}
@@ -0,0 +1,78 @@
+use crate::types::SentryError;
+
+use graphql_client::{GraphQLQuery, QueryBody};
+use serde::{Deserialize, Serialize};
+
+/// List the error groups of a project (GraphQL only)
+pub struct SentryErrors;
+
+/// The variables of the query
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SentryErrorsVariables {
+	/// The project path with its namespace
+	pub full_path: String,
+	/// The maximum number of errors
+	pub first: u32,
+	/// Only the errors matching a search term
+	pub search_term: Option<String>,
+	/// last_seen, first_seen or frequency
+	pub sort: Option<String>,
+}
+
+/// The response of the query
+#[derive(Deserialize)]
+pub struct SentryErrorsData {
+	project: Option<SentryErrorsProject>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SentryErrorsProject {
+	sentry_errors: Option<SentryErrorsCollection>,
+}
+
+#[derive(Deserialize)]
+struct SentryErrorsCollection {
+	errors: Option<SentryErrorsConnection>,
+}
+
+#[derive(Deserialize)]
+struct SentryErrorsConnection {
+	nodes: Vec<SentryError>,
+}
+
+impl SentryErrorsData {
+	/// The errors of the response
+	pub fn errors(self) -> Option<Vec<SentryError>> {
+		self.project?
+			.sentry_errors?
+			.errors
+			.map(|errors| errors.nodes)
+	}
+}
+
+impl GraphQLQuery for SentryErrors {
+	type Variables = SentryErrorsVariables;
+	type ResponseData = SentryErrorsData;
+
+	fn build_query(variables: Self::Variables) -> QueryBody<Self::Variables> {
+		QueryBody {
+			variables,
+			query: r#"
+query sentryErrors($fullPath: ID!, $first: Int, $searchTerm: String, $sort: String) {
+	project(fullPath: $fullPath) {
+		sentryErrors {
+			errors(first: $first, searchTerm: $searchTerm, sort: $sort) {
+				nodes {
+					sentryId title type count userCount firstSeen lastSeen status culprit
+					externalUrl
+				}
+			}
+		}
+	}
+}"#,
+			operation_name: "sentryErrors",
+		}
+	}
+}
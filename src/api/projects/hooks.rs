@@ -0,0 +1,5 @@
+pub mod events;
+pub mod test;
+
+pub use self::events::ProjectHookEvents;
+pub use self::test::TestProjectHook;
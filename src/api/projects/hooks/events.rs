@@ -0,0 +1,41 @@
+use derive_builder::Builder;
+use gitlab::api::{Endpoint, Pageable, QueryParams};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// List the recent deliveries of a project webhook (the last 7 days)
+#[derive(Debug, Builder)]
+pub struct ProjectHookEvents<'a> {
+	/// The project id
+	pub project: u64,
+	/// The hook id
+	pub hook_id: u64,
+	/// A response status code or successful, client_failure or server_failure
+	#[builder(default)]
+	pub status: Option<&'a str>,
+}
+
+impl<'a> ProjectHookEvents<'a> {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> ProjectHookEventsBuilder<'a> {
+		ProjectHookEventsBuilder::default()
+	}
+}
+
+impl Endpoint for ProjectHookEvents<'_> {
+	fn method(&self) -> Method {
+		Method::GET
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("projects/{}/hooks/{}/events", self.project, self.hook_id).into()
+	}
+
+	fn parameters(&self) -> QueryParams<'_> {
+		let mut params = QueryParams::default();
+		params.push_opt("status", self.status);
+		params
+	}
+}
+
+impl Pageable for ProjectHookEvents<'_> {}
@@ -0,0 +1,36 @@
+use derive_builder::Builder;
+use gitlab::api::Endpoint;
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Send a test event to a project webhook
+#[derive(Debug, Builder)]
+pub struct TestProjectHook<'a> {
+	/// The project id
+	pub project: u64,
+	/// The hook id
+	pub hook_id: u64,
+	/// The event sent (push_events, tag_push_events, issues_events, ...)
+	pub trigger: &'a str,
+}
+
+impl<'a> TestProjectHook<'a> {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> TestProjectHookBuilder<'a> {
+		TestProjectHookBuilder::default()
+	}
+}
+
+impl Endpoint for TestProjectHook<'_> {
+	fn method(&self) -> Method {
+		Method::POST
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!(
+			"projects/{}/hooks/{}/test/{}",
+			self.project, self.hook_id, self.trigger
+		)
+		.into()
+	}
+}
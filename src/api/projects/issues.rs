@@ -0,0 +1,9 @@
+pub mod add_spent_time;
+pub mod subscribe;
+pub mod time_estimate;
+pub mod unsubscribe;
+
+pub use self::add_spent_time::AddIssueSpentTime;
+pub use self::subscribe::SubscribeIssue;
+pub use self::time_estimate::SetIssueTimeEstimate;
+pub use self::unsubscribe::UnsubscribeIssue;
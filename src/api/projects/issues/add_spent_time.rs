@@ -0,0 +1,43 @@
+use derive_builder::Builder;
+use gitlab::api::{Endpoint, QueryParams};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Add spent time to an issue
+#[derive(Debug, Builder)]
+pub struct AddIssueSpentTime<'a> {
+	/// The project id
+	pub project: u64,
+	/// The issue iid
+	pub issue: u64,
+	/// The spent time in the gitlab format (ex: 1h30m), negative to subtract time
+	pub duration: &'a str,
+	/// A summary of how the time was spent
+	#[builder(default)]
+	pub summary: Option<&'a str>,
+}
+
+impl<'a> AddIssueSpentTime<'a> {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> AddIssueSpentTimeBuilder<'a> {
+		AddIssueSpentTimeBuilder::default()
+	}
+}
+
+impl Endpoint for AddIssueSpentTime<'_> {
+	fn method(&self) -> Method {
+		Method::POST
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("projects/{}/issues/{}/add_spent_time", self.project, self.issue).into()
+	}
+
+	fn parameters(&self) -> QueryParams<'_> {
+		let mut params = QueryParams::default();
+		params
+			.push("duration", self.duration)
+			.push_opt("summary", self.summary);
+		params
+	}
+}
@@ -0,0 +1,30 @@
+use derive_builder::Builder;
+use gitlab::api::Endpoint;
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Subscribe the current user to the notifications of an issue
+#[derive(Debug, Builder)]
+pub struct SubscribeIssue {
+	/// The project id
+	pub project: u64,
+	/// The issue iid
+	pub issue: u64,
+}
+
+impl SubscribeIssue {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> SubscribeIssueBuilder {
+		SubscribeIssueBuilder::default()
+	}
+}
+
+impl Endpoint for SubscribeIssue {
+	fn method(&self) -> Method {
+		Method::POST
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("projects/{}/issues/{}/subscribe", self.project, self.issue).into()
+	}
+}
@@ -0,0 +1,38 @@
+use derive_builder::Builder;
+use gitlab::api::{Endpoint, QueryParams};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Set the time estimate of an issue
+#[derive(Debug, Builder)]
+pub struct SetIssueTimeEstimate<'a> {
+	/// The project id
+	pub project: u64,
+	/// The issue iid
+	pub issue: u64,
+	/// The estimate in the gitlab format (ex: 3h30m)
+	pub duration: &'a str,
+}
+
+impl<'a> SetIssueTimeEstimate<'a> {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> SetIssueTimeEstimateBuilder<'a> {
+		SetIssueTimeEstimateBuilder::default()
+	}
+}
+
+impl Endpoint for SetIssueTimeEstimate<'_> {
+	fn method(&self) -> Method {
+		Method::POST
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("projects/{}/issues/{}/time_estimate", self.project, self.issue).into()
+	}
+
+	fn parameters(&self) -> QueryParams<'_> {
+		let mut params = QueryParams::default();
+		params.push("duration", self.duration);
+		params
+	}
+}
@@ -0,0 +1,30 @@
+use derive_builder::Builder;
+use gitlab::api::Endpoint;
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Unsubscribe the current user from the notifications of an issue
+#[derive(Debug, Builder)]
+pub struct UnsubscribeIssue {
+	/// The project id
+	pub project: u64,
+	/// The issue iid
+	pub issue: u64,
+}
+
+impl UnsubscribeIssue {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> UnsubscribeIssueBuilder {
+		UnsubscribeIssueBuilder::default()
+	}
+}
+
+impl Endpoint for UnsubscribeIssue {
+	fn method(&self) -> Method {
+		Method::POST
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("projects/{}/issues/{}/unsubscribe", self.project, self.issue).into()
+	}
+}
@@ -0,0 +1,5 @@
+pub mod delete_artifacts;
+pub mod keep_artifacts;
+
+pub use self::delete_artifacts::DeleteJobArtifacts;
+pub use self::keep_artifacts::KeepJobArtifacts;
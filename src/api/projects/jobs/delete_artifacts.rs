@@ -0,0 +1,30 @@
+use derive_builder::Builder;
+use gitlab::api::Endpoint;
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Delete the artifacts of a job
+#[derive(Debug, Builder)]
+pub struct DeleteJobArtifacts {
+	/// The project id
+	pub project: u64,
+	/// The job id
+	pub job: u64,
+}
+
+impl DeleteJobArtifacts {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> DeleteJobArtifactsBuilder {
+		DeleteJobArtifactsBuilder::default()
+	}
+}
+
+impl Endpoint for DeleteJobArtifacts {
+	fn method(&self) -> Method {
+		Method::DELETE
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("projects/{}/jobs/{}/artifacts", self.project, self.job).into()
+	}
+}
@@ -0,0 +1,30 @@
+use derive_builder::Builder;
+use gitlab::api::Endpoint;
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Prevent the artifacts of a job from being deleted when they expire
+#[derive(Debug, Builder)]
+pub struct KeepJobArtifacts {
+	/// The project id
+	pub project: u64,
+	/// The job id
+	pub job: u64,
+}
+
+impl KeepJobArtifacts {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> KeepJobArtifactsBuilder {
+		KeepJobArtifactsBuilder::default()
+	}
+}
+
+impl Endpoint for KeepJobArtifacts {
+	fn method(&self) -> Method {
+		Method::POST
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("projects/{}/jobs/{}/artifacts/keep", self.project, self.job).into()
+	}
+}
@@ -0,0 +1,11 @@
+pub mod add_spent_time;
+pub mod reset_approvals;
+pub mod subscribe;
+pub mod time_estimate;
+pub mod unsubscribe;
+
+pub use self::add_spent_time::AddMergeRequestSpentTime;
+pub use self::reset_approvals::ResetMergeRequestApprovals;
+pub use self::subscribe::SubscribeMergeRequest;
+pub use self::time_estimate::SetMergeRequestTimeEstimate;
+pub use self::unsubscribe::UnsubscribeMergeRequest;
@@ -0,0 +1,47 @@
+use derive_builder::Builder;
+use gitlab::api::{Endpoint, QueryParams};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Add spent time to a merge request
+#[derive(Debug, Builder)]
+pub struct AddMergeRequestSpentTime<'a> {
+	/// The project id
+	pub project: u64,
+	/// The merge request iid
+	pub merge_request: u64,
+	/// The spent time in the gitlab format (ex: 1h30m), negative to subtract time
+	pub duration: &'a str,
+	/// A summary of how the time was spent
+	#[builder(default)]
+	pub summary: Option<&'a str>,
+}
+
+impl<'a> AddMergeRequestSpentTime<'a> {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> AddMergeRequestSpentTimeBuilder<'a> {
+		AddMergeRequestSpentTimeBuilder::default()
+	}
+}
+
+impl Endpoint for AddMergeRequestSpentTime<'_> {
+	fn method(&self) -> Method {
+		Method::POST
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!(
+			"projects/{}/merge_requests/{}/add_spent_time",
+			self.project, self.merge_request
+		)
+		.into()
+	}
+
+	fn parameters(&self) -> QueryParams<'_> {
+		let mut params = QueryParams::default();
+		params
+			.push("duration", self.duration)
+			.push_opt("summary", self.summary);
+		params
+	}
+}
@@ -0,0 +1,34 @@
+use derive_builder::Builder;
+use gitlab::api::Endpoint;
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Clear all the approvals of a merge request (bot users only)
+#[derive(Debug, Builder)]
+pub struct ResetMergeRequestApprovals {
+	/// The project id
+	pub project: u64,
+	/// The merge request iid
+	pub merge_request: u64,
+}
+
+impl ResetMergeRequestApprovals {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> ResetMergeRequestApprovalsBuilder {
+		ResetMergeRequestApprovalsBuilder::default()
+	}
+}
+
+impl Endpoint for ResetMergeRequestApprovals {
+	fn method(&self) -> Method {
+		Method::PUT
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!(
+			"projects/{}/merge_requests/{}/reset_approvals",
+			self.project, self.merge_request
+		)
+		.into()
+	}
+}
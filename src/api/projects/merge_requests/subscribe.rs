@@ -0,0 +1,30 @@
+use derive_builder::Builder;
+use gitlab::api::Endpoint;
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Subscribe the current user to the notifications of a merge request
+#[derive(Debug, Builder)]
+pub struct SubscribeMergeRequest {
+	/// The project id
+	pub project: u64,
+	/// The merge request iid
+	pub merge_request: u64,
+}
+
+impl SubscribeMergeRequest {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> SubscribeMergeRequestBuilder {
+		SubscribeMergeRequestBuilder::default()
+	}
+}
+
+impl Endpoint for SubscribeMergeRequest {
+	fn method(&self) -> Method {
+		Method::POST
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("projects/{}/merge_requests/{}/subscribe", self.project, self.merge_request).into()
+	}
+}
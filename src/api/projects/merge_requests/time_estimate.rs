@@ -0,0 +1,42 @@
+use derive_builder::Builder;
+use gitlab::api::{Endpoint, QueryParams};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Set the time estimate of a merge request
+#[derive(Debug, Builder)]
+pub struct SetMergeRequestTimeEstimate<'a> {
+	/// The project id
+	pub project: u64,
+	/// The merge request iid
+	pub merge_request: u64,
+	/// The estimate in the gitlab format (ex: 3h30m)
+	pub duration: &'a str,
+}
+
+impl<'a> SetMergeRequestTimeEstimate<'a> {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> SetMergeRequestTimeEstimateBuilder<'a> {
+		SetMergeRequestTimeEstimateBuilder::default()
+	}
+}
+
+impl Endpoint for SetMergeRequestTimeEstimate<'_> {
+	fn method(&self) -> Method {
+		Method::POST
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!(
+			"projects/{}/merge_requests/{}/time_estimate",
+			self.project, self.merge_request
+		)
+		.into()
+	}
+
+	fn parameters(&self) -> QueryParams<'_> {
+		let mut params = QueryParams::default();
+		params.push("duration", self.duration);
+		params
+	}
+}
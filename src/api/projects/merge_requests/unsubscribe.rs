@@ -0,0 +1,34 @@
+use derive_builder::Builder;
+use gitlab::api::Endpoint;
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Unsubscribe the current user from the notifications of a merge request
+#[derive(Debug, Builder)]
+pub struct UnsubscribeMergeRequest {
+	/// The project id
+	pub project: u64,
+	/// The merge request iid
+	pub merge_request: u64,
+}
+
+impl UnsubscribeMergeRequest {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> UnsubscribeMergeRequestBuilder {
+		UnsubscribeMergeRequestBuilder::default()
+	}
+}
+
+impl Endpoint for UnsubscribeMergeRequest {
+	fn method(&self) -> Method {
+		Method::POST
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!(
+			"projects/{}/merge_requests/{}/unsubscribe",
+			self.project, self.merge_request
+		)
+		.into()
+	}
+}
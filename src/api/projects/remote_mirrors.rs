@@ -0,0 +1,11 @@
+pub mod create;
+pub mod delete;
+pub mod list;
+pub mod pull;
+pub mod sync;
+
+pub use self::create::CreateRemoteMirror;
+pub use self::delete::DeleteRemoteMirror;
+pub use self::list::RemoteMirrors;
+pub use self::pull::StartPullMirror;
+pub use self::sync::SyncRemoteMirror;
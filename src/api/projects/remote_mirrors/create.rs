@@ -0,0 +1,47 @@
+use derive_builder::Builder;
+use gitlab::api::{BodyError, Endpoint, FormParams};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Add a push mirror to a project
+#[derive(Debug, Builder)]
+pub struct CreateRemoteMirror<'a> {
+	/// The project id
+	pub project: u64,
+	/// The mirror url, credentials included
+	pub url: &'a str,
+	#[builder(default)]
+	pub enabled: Option<bool>,
+	#[builder(default)]
+	pub only_protected_branches: Option<bool>,
+	#[builder(default)]
+	pub keep_divergent_refs: Option<bool>,
+}
+
+impl<'a> CreateRemoteMirror<'a> {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> CreateRemoteMirrorBuilder<'a> {
+		CreateRemoteMirrorBuilder::default()
+	}
+}
+
+impl<'a> Endpoint for CreateRemoteMirror<'a> {
+	fn method(&self) -> Method {
+		Method::POST
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("projects/{}/remote_mirrors", self.project).into()
+	}
+
+	// sent in the body to keep credentials out of urls
+	fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+		let mut params = FormParams::default();
+		params
+			.push("url", self.url)
+			.push_opt("enabled", self.enabled)
+			.push_opt("only_protected_branches", self.only_protected_branches)
+			.push_opt("keep_divergent_refs", self.keep_divergent_refs);
+		params.into_body()
+	}
+}
@@ -0,0 +1,30 @@
+use derive_builder::Builder;
+use gitlab::api::Endpoint;
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Delete a push mirror of a project
+#[derive(Debug, Builder)]
+pub struct DeleteRemoteMirror {
+	/// The project id
+	pub project: u64,
+	/// The mirror id
+	pub mirror_id: u64,
+}
+
+impl DeleteRemoteMirror {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> DeleteRemoteMirrorBuilder {
+		DeleteRemoteMirrorBuilder::default()
+	}
+}
+
+impl Endpoint for DeleteRemoteMirror {
+	fn method(&self) -> Method {
+		Method::DELETE
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("projects/{}/remote_mirrors/{}", self.project, self.mirror_id).into()
+	}
+}
@@ -0,0 +1,30 @@
+use derive_builder::Builder;
+use gitlab::api::{Endpoint, Pageable};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// List the push mirrors of a project
+#[derive(Debug, Builder)]
+pub struct RemoteMirrors {
+	/// The project id
+	pub project: u64,
+}
+
+impl RemoteMirrors {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> RemoteMirrorsBuilder {
+		RemoteMirrorsBuilder::default()
+	}
+}
+
+impl Endpoint for RemoteMirrors {
+	fn method(&self) -> Method {
+		Method::GET
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("projects/{}/remote_mirrors", self.project).into()
+	}
+}
+
+impl Pageable for RemoteMirrors {}
@@ -0,0 +1,28 @@
+use derive_builder::Builder;
+use gitlab::api::Endpoint;
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Trigger an immediate update of the pull mirror of a project
+#[derive(Debug, Builder)]
+pub struct StartPullMirror {
+	/// The project id
+	pub project: u64,
+}
+
+impl StartPullMirror {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> StartPullMirrorBuilder {
+		StartPullMirrorBuilder::default()
+	}
+}
+
+impl Endpoint for StartPullMirror {
+	fn method(&self) -> Method {
+		Method::POST
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("projects/{}/mirror/pull", self.project).into()
+	}
+}
@@ -0,0 +1,34 @@
+use derive_builder::Builder;
+use gitlab::api::Endpoint;
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Trigger an immediate update of a push mirror
+#[derive(Debug, Builder)]
+pub struct SyncRemoteMirror {
+	/// The project id
+	pub project: u64,
+	/// The mirror id
+	pub mirror_id: u64,
+}
+
+impl SyncRemoteMirror {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> SyncRemoteMirrorBuilder {
+		SyncRemoteMirrorBuilder::default()
+	}
+}
+
+impl Endpoint for SyncRemoteMirror {
+	fn method(&self) -> Method {
+		Method::POST
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!(
+			"projects/{}/remote_mirrors/{}/sync",
+			self.project, self.mirror_id
+		)
+		.into()
+	}
+}
@@ -0,0 +1,5 @@
+pub mod cherry_pick;
+pub mod compare;
+
+pub use self::cherry_pick::CherryPickCommit;
+pub use self::compare::Compare;
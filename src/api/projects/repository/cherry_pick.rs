@@ -0,0 +1,42 @@
+use derive_builder::Builder;
+use gitlab::api::{Endpoint, QueryParams};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Cherry-pick a commit onto a branch
+#[derive(Debug, Builder)]
+pub struct CherryPickCommit<'a> {
+	/// The project id
+	pub project: u64,
+	/// The sha of the commit to cherry-pick
+	pub sha: &'a str,
+	/// The branch to cherry-pick the commit onto
+	pub branch: &'a str,
+}
+
+impl<'a> CherryPickCommit<'a> {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> CherryPickCommitBuilder<'a> {
+		CherryPickCommitBuilder::default()
+	}
+}
+
+impl Endpoint for CherryPickCommit<'_> {
+	fn method(&self) -> Method {
+		Method::POST
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!(
+			"projects/{}/repository/commits/{}/cherry_pick",
+			self.project, self.sha
+		)
+		.into()
+	}
+
+	fn parameters(&self) -> QueryParams<'_> {
+		let mut params = QueryParams::default();
+		params.push("branch", self.branch);
+		params
+	}
+}
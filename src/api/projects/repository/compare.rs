@@ -0,0 +1,44 @@
+use derive_builder::Builder;
+use gitlab::api::{Endpoint, QueryParams};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Compare two references of a repository
+#[derive(Debug, Builder)]
+pub struct Compare<'a> {
+	/// The project id
+	pub project: u64,
+	/// The commit sha or branch name to compare from
+	pub from: &'a str,
+	/// The commit sha or branch name to compare to
+	pub to: &'a str,
+	/// Compare from directly instead of the merge base of from and to
+	#[builder(default)]
+	pub straight: bool,
+}
+
+impl<'a> Compare<'a> {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> CompareBuilder<'a> {
+		CompareBuilder::default()
+	}
+}
+
+impl Endpoint for Compare<'_> {
+	fn method(&self) -> Method {
+		Method::GET
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("projects/{}/repository/compare", self.project).into()
+	}
+
+	fn parameters(&self) -> QueryParams<'_> {
+		let mut params = QueryParams::default();
+		params
+			.push("from", self.from)
+			.push("to", self.to)
+			.push("straight", self.straight);
+		params
+	}
+}
@@ -0,0 +1,9 @@
+pub mod create;
+pub mod delete;
+pub mod download;
+pub mod list;
+
+pub use self::create::CreateSecureFile;
+pub use self::delete::DeleteSecureFile;
+pub use self::download::DownloadSecureFile;
+pub use self::list::SecureFiles;
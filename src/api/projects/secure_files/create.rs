@@ -0,0 +1,61 @@
+use derive_builder::Builder;
+use gitlab::api::{BodyError, Endpoint};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Delimiter of the parts of the multipart body, random enough not to appear in a file
+const BOUNDARY: &str = "gil-secure-file-LDdAMLFJUwfnQd1KmXkvsK3wtPKqVU8Z";
+/// Content type of the body, with the same delimiter
+const CONTENT_TYPE: &str =
+	"multipart/form-data; boundary=gil-secure-file-LDdAMLFJUwfnQd1KmXkvsK3wtPKqVU8Z";
+
+/// Upload a CI secure file to a project. Secure files are limited to 5MB, so the multipart
+/// body is built in memory
+#[derive(Debug, Builder)]
+pub struct CreateSecureFile<'a> {
+	/// The project id
+	pub project: u64,
+	/// The name of the file in the project
+	pub name: &'a str,
+	/// The content of the file
+	pub content: &'a [u8],
+}
+
+impl<'a> CreateSecureFile<'a> {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> CreateSecureFileBuilder<'a> {
+		CreateSecureFileBuilder::default()
+	}
+}
+
+impl<'a> Endpoint for CreateSecureFile<'a> {
+	fn method(&self) -> Method {
+		Method::POST
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("projects/{}/secure_files", self.project).into()
+	}
+
+	fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+		// the quotes delimiting the file name can't be escaped
+		let name = self.name.replace(['"', '\r', '\n'], "_");
+		let mut body = Vec::with_capacity(self.content.len() + 512);
+		body.extend_from_slice(
+			format!(
+				"--{boundary}\r\n\
+				Content-Disposition: form-data; name=\"name\"\r\n\r\n\
+				{name}\r\n\
+				--{boundary}\r\n\
+				Content-Disposition: form-data; name=\"file\"; filename=\"{name}\"\r\n\
+				Content-Type: application/octet-stream\r\n\r\n",
+				boundary = BOUNDARY,
+				name = name,
+			)
+			.as_bytes(),
+		);
+		body.extend_from_slice(self.content);
+		body.extend_from_slice(format!("\r\n--{}--\r\n", BOUNDARY).as_bytes());
+		Ok(Some((CONTENT_TYPE, body)))
+	}
+}
@@ -0,0 +1,30 @@
+use derive_builder::Builder;
+use gitlab::api::Endpoint;
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Delete a CI secure file of a project
+#[derive(Debug, Builder)]
+pub struct DeleteSecureFile {
+	/// The project id
+	pub project: u64,
+	/// The secure file id
+	pub file_id: u64,
+}
+
+impl DeleteSecureFile {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> DeleteSecureFileBuilder {
+		DeleteSecureFileBuilder::default()
+	}
+}
+
+impl Endpoint for DeleteSecureFile {
+	fn method(&self) -> Method {
+		Method::DELETE
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("projects/{}/secure_files/{}", self.project, self.file_id).into()
+	}
+}
@@ -0,0 +1,34 @@
+use derive_builder::Builder;
+use gitlab::api::Endpoint;
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Download the content of a CI secure file
+#[derive(Debug, Builder)]
+pub struct DownloadSecureFile {
+	/// The project id
+	pub project: u64,
+	/// The secure file id
+	pub file_id: u64,
+}
+
+impl DownloadSecureFile {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> DownloadSecureFileBuilder {
+		DownloadSecureFileBuilder::default()
+	}
+}
+
+impl Endpoint for DownloadSecureFile {
+	fn method(&self) -> Method {
+		Method::GET
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!(
+			"projects/{}/secure_files/{}/download",
+			self.project, self.file_id
+		)
+		.into()
+	}
+}
@@ -0,0 +1,30 @@
+use derive_builder::Builder;
+use gitlab::api::{Endpoint, Pageable};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// List the CI secure files of a project
+#[derive(Debug, Builder)]
+pub struct SecureFiles {
+	/// The project id
+	pub project: u64,
+}
+
+impl SecureFiles {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> SecureFilesBuilder {
+		SecureFilesBuilder::default()
+	}
+}
+
+impl Endpoint for SecureFiles {
+	fn method(&self) -> Method {
+		Method::GET
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("projects/{}/secure_files", self.project).into()
+	}
+}
+
+impl Pageable for SecureFiles {}
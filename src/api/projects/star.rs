@@ -0,0 +1,28 @@
+use derive_builder::Builder;
+use gitlab::api::Endpoint;
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Star a project for the current user
+#[derive(Debug, Builder)]
+pub struct StarProject {
+	/// The project id
+	pub project: u64,
+}
+
+impl StarProject {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> StarProjectBuilder {
+		StarProjectBuilder::default()
+	}
+}
+
+impl Endpoint for StarProject {
+	fn method(&self) -> Method {
+		Method::POST
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("projects/{}/star", self.project).into()
+	}
+}
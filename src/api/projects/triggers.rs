@@ -0,0 +1,9 @@
+pub mod create;
+pub mod delete;
+pub mod list;
+pub mod run;
+
+pub use self::create::CreatePipelineTrigger;
+pub use self::delete::DeletePipelineTrigger;
+pub use self::list::PipelineTriggers;
+pub use self::run::TriggerPipeline;
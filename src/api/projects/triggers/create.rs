@@ -0,0 +1,36 @@
+use derive_builder::Builder;
+use gitlab::api::{Endpoint, QueryParams};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Create a pipeline trigger token
+#[derive(Debug, Builder)]
+pub struct CreatePipelineTrigger<'a> {
+	/// The project id
+	pub project: u64,
+	/// The trigger description
+	pub description: &'a str,
+}
+
+impl<'a> CreatePipelineTrigger<'a> {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> CreatePipelineTriggerBuilder<'a> {
+		CreatePipelineTriggerBuilder::default()
+	}
+}
+
+impl<'a> Endpoint for CreatePipelineTrigger<'a> {
+	fn method(&self) -> Method {
+		Method::POST
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("projects/{}/triggers", self.project).into()
+	}
+
+	fn parameters(&self) -> QueryParams<'_> {
+		let mut params = QueryParams::default();
+		params.push("description", self.description);
+		params
+	}
+}
@@ -0,0 +1,30 @@
+use derive_builder::Builder;
+use gitlab::api::Endpoint;
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Delete a pipeline trigger token of a project
+#[derive(Debug, Builder)]
+pub struct DeletePipelineTrigger {
+	/// The project id
+	pub project: u64,
+	/// The trigger id
+	pub trigger_id: u64,
+}
+
+impl DeletePipelineTrigger {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> DeletePipelineTriggerBuilder {
+		DeletePipelineTriggerBuilder::default()
+	}
+}
+
+impl Endpoint for DeletePipelineTrigger {
+	fn method(&self) -> Method {
+		Method::DELETE
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("projects/{}/triggers/{}", self.project, self.trigger_id).into()
+	}
+}
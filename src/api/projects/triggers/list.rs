@@ -0,0 +1,30 @@
+use derive_builder::Builder;
+use gitlab::api::{Endpoint, Pageable};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// List the pipeline trigger tokens of a project
+#[derive(Debug, Builder)]
+pub struct PipelineTriggers {
+	/// The project id
+	pub project: u64,
+}
+
+impl PipelineTriggers {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> PipelineTriggersBuilder {
+		PipelineTriggersBuilder::default()
+	}
+}
+
+impl Endpoint for PipelineTriggers {
+	fn method(&self) -> Method {
+		Method::GET
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("projects/{}/triggers", self.project).into()
+	}
+}
+
+impl Pageable for PipelineTriggers {}
@@ -0,0 +1,45 @@
+use derive_builder::Builder;
+use gitlab::api::{BodyError, Endpoint, FormParams};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Run a pipeline with a trigger token
+#[derive(Debug, Builder)]
+pub struct TriggerPipeline<'a> {
+	/// The project id
+	pub project: u64,
+	/// The trigger token
+	pub token: &'a str,
+	/// The branch or tag to run the pipeline on
+	pub ref_: &'a str,
+	/// Variables passed to the pipeline
+	#[builder(default)]
+	pub variables: &'a [(String, String)],
+}
+
+impl<'a> TriggerPipeline<'a> {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> TriggerPipelineBuilder<'a> {
+		TriggerPipelineBuilder::default()
+	}
+}
+
+impl<'a> Endpoint for TriggerPipeline<'a> {
+	fn method(&self) -> Method {
+		Method::POST
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("projects/{}/trigger/pipeline", self.project).into()
+	}
+
+	// sent in the body to keep the token out of urls
+	fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+		let mut params = FormParams::default();
+		params.push("token", self.token).push("ref", self.ref_);
+		for (key, value) in self.variables {
+			params.push(format!("variables[{}]", key), value);
+		}
+		params.into_body()
+	}
+}
@@ -0,0 +1,28 @@
+use derive_builder::Builder;
+use gitlab::api::Endpoint;
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Unstar a project for the current user
+#[derive(Debug, Builder)]
+pub struct UnstarProject {
+	/// The project id
+	pub project: u64,
+}
+
+impl UnstarProject {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> UnstarProjectBuilder {
+		UnstarProjectBuilder::default()
+	}
+}
+
+impl Endpoint for UnstarProject {
+	fn method(&self) -> Method {
+		Method::POST
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("projects/{}/unstar", self.project).into()
+	}
+}
@@ -0,0 +1,30 @@
+use derive_builder::Builder;
+use gitlab::api::{Endpoint, Pageable};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// List the CI/CD variables of a project
+#[derive(Debug, Builder)]
+pub struct ProjectVariables {
+	/// The project id
+	pub project: u64,
+}
+
+impl ProjectVariables {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> ProjectVariablesBuilder {
+		ProjectVariablesBuilder::default()
+	}
+}
+
+impl Endpoint for ProjectVariables {
+	fn method(&self) -> Method {
+		Method::GET
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("projects/{}/variables", self.project).into()
+	}
+}
+
+impl Pageable for ProjectVariables {}
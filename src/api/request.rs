@@ -0,0 +1,50 @@
+use derive_builder::Builder;
+use gitlab::api::{BodyError, Endpoint, QueryParams};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Any request to the API, for the endpoints not modeled yet
+#[derive(Debug, Builder)]
+pub struct Request {
+	/// The http method
+	pub method: Method,
+	/// The endpoint path relative to the api
+	pub path: String,
+	/// The query parameters
+	#[builder(default)]
+	pub params: Vec<(String, String)>,
+	/// The json body
+	#[builder(default)]
+	pub body: Option<Vec<u8>>,
+}
+
+impl Request {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> RequestBuilder {
+		RequestBuilder::default()
+	}
+}
+
+impl Endpoint for Request {
+	fn method(&self) -> Method {
+		self.method.clone()
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		self.path.clone().into()
+	}
+
+	fn parameters(&self) -> QueryParams<'_> {
+		let mut params = QueryParams::default();
+		params.extend(
+			self.params
+				.iter()
+				.map(|(key, value)| (key.as_str(), value.as_str())),
+		);
+		params
+	}
+
+	fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+		Ok(self.body.clone().map(|body| ("application/json", body)))
+	}
+}
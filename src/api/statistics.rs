@@ -0,0 +1,24 @@
+use gitlab::api::Endpoint;
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Get the object counts of the instance (admin only)
+#[derive(Debug)]
+pub struct ApplicationStatistics;
+
+impl ApplicationStatistics {
+	/// Create the endpoint.
+	pub fn build() -> Self {
+		ApplicationStatistics {}
+	}
+}
+
+impl Endpoint for ApplicationStatistics {
+	fn method(&self) -> Method {
+		Method::GET
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		"application/statistics".into()
+	}
+}
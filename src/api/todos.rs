@@ -0,0 +1,5 @@
+pub mod done;
+pub mod list;
+
+pub use self::done::MarkTodoDone;
+pub use self::list::PendingTodos;
@@ -0,0 +1,28 @@
+use derive_builder::Builder;
+use gitlab::api::Endpoint;
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Mark a todo of the current user as done
+#[derive(Debug, Builder)]
+pub struct MarkTodoDone {
+	/// The todo id
+	pub todo_id: u64,
+}
+
+impl MarkTodoDone {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> MarkTodoDoneBuilder {
+		MarkTodoDoneBuilder::default()
+	}
+}
+
+impl Endpoint for MarkTodoDone {
+	fn method(&self) -> Method {
+		Method::POST
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("todos/{}/mark_as_done", self.todo_id).into()
+	}
+}
@@ -0,0 +1,40 @@
+use derive_builder::Builder;
+use gitlab::api::{Endpoint, Pageable, QueryParams};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// List the pending todos of the current user
+#[derive(Debug, Builder)]
+pub struct PendingTodos {
+	/// Only list the todos of this project
+	#[builder(default)]
+	pub project_id: Option<u64>,
+}
+
+impl PendingTodos {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> PendingTodosBuilder {
+		PendingTodosBuilder::default()
+	}
+}
+
+impl Endpoint for PendingTodos {
+	fn method(&self) -> Method {
+		Method::GET
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		"todos".into()
+	}
+
+	fn parameters(&self) -> QueryParams<'_> {
+		let mut params = QueryParams::default();
+		params
+			.push("state", "pending")
+			.push_opt("project_id", self.project_id);
+
+		params
+	}
+}
+
+impl Pageable for PendingTodos {}
@@ -1,2 +1,9 @@
+pub mod delete;
+pub mod events;
 pub mod keys;
+pub mod memberships;
 pub mod personal_access_tokens;
+
+pub use self::delete::DeleteUser;
+pub use self::events::UserEvents;
+pub use self::memberships::{MembershipSource, UserMemberships};
@@ -0,0 +1,37 @@
+use derive_builder::Builder;
+use gitlab::api::{Endpoint, QueryParams};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Delete a user (admin only)
+#[derive(Debug, Builder)]
+pub struct DeleteUser {
+	/// The user id
+	pub user_id: u64,
+	/// Also delete the contributions of the user instead of moving them to the ghost user
+	#[builder(default)]
+	pub hard_delete: bool,
+}
+
+impl DeleteUser {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> DeleteUserBuilder {
+		DeleteUserBuilder::default()
+	}
+}
+
+impl Endpoint for DeleteUser {
+	fn method(&self) -> Method {
+		Method::DELETE
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("users/{}", self.user_id).into()
+	}
+
+	fn parameters(&self) -> QueryParams<'_> {
+		let mut params = QueryParams::default();
+		params.push("hard_delete", self.hard_delete);
+		params
+	}
+}
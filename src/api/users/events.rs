@@ -0,0 +1,30 @@
+use derive_builder::Builder;
+use gitlab::api::{Endpoint, Pageable};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// List the contribution events of a user
+#[derive(Debug, Builder)]
+pub struct UserEvents {
+	/// The user id
+	pub user_id: u64,
+}
+
+impl UserEvents {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> UserEventsBuilder {
+		UserEventsBuilder::default()
+	}
+}
+
+impl Endpoint for UserEvents {
+	fn method(&self) -> Method {
+		Method::GET
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("users/{}/events", self.user_id).into()
+	}
+}
+
+impl Pageable for UserEvents {}
@@ -1,7 +1,9 @@
 pub mod add;
 pub mod delete;
+pub mod get;
 pub mod list;
 
 pub use self::add::AddKey;
 pub use self::delete::DeleteKey;
+pub use self::get::GetUserKey;
 pub use self::list::ListKeys;
@@ -32,7 +32,7 @@ impl<'a> Endpoint for AddKey<'a> {
 		"user/keys".into()
 	}
 
-	fn parameters(&self) -> QueryParams {
+	fn parameters(&self) -> QueryParams<'_> {
 		let mut params = QueryParams::default();
 		params.push("title", self.title);
 		params.push("key", self.key);
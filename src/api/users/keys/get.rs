@@ -0,0 +1,31 @@
+use derive_builder::Builder;
+use gitlab::api::Endpoint;
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Get a key of a user with all its attributes (the listing of the keys of another user lacks
+/// some)
+#[derive(Debug, Builder)]
+pub struct GetUserKey<'a> {
+	/// The user owning the key
+	pub user: &'a str,
+	/// The key id
+	pub key_id: u64,
+}
+
+impl<'a> GetUserKey<'a> {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> GetUserKeyBuilder<'a> {
+		GetUserKeyBuilder::default()
+	}
+}
+
+impl<'a> Endpoint for GetUserKey<'a> {
+	fn method(&self) -> Method {
+		Method::GET
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("users/{}/keys/{}", self.user, self.key_id).into()
+	}
+}
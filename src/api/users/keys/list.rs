@@ -1,5 +1,5 @@
 use derive_builder::Builder;
-use gitlab::api::Endpoint;
+use gitlab::api::{Endpoint, Pageable};
 use reqwest::Method;
 use std::borrow::Cow;
 
@@ -25,3 +25,5 @@ impl<'a> Endpoint for ListKeys<'a> {
         format!("users/{}/keys", self.user).into()
     }
 }
+
+impl<'a> Pageable for ListKeys<'a> {}
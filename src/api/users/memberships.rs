@@ -0,0 +1,55 @@
+use derive_builder::Builder;
+use gitlab::api::{Endpoint, Pageable, QueryParams};
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Kind of membership source
+#[derive(Debug, Clone, Copy)]
+pub enum MembershipSource {
+	Project,
+	Namespace,
+}
+
+impl MembershipSource {
+	pub(crate) fn as_str(&self) -> &'static str {
+		match self {
+			MembershipSource::Project => "Project",
+			MembershipSource::Namespace => "Namespace",
+		}
+	}
+}
+
+/// List the projects and groups a user is a member of (admin only)
+#[derive(Debug, Builder)]
+pub struct UserMemberships {
+	/// The user id
+	pub user_id: u64,
+	/// Only list memberships of this kind
+	#[builder(default)]
+	pub source_type: Option<MembershipSource>,
+}
+
+impl UserMemberships {
+	/// Create a builder for the endpoint.
+	pub fn builder() -> UserMembershipsBuilder {
+		UserMembershipsBuilder::default()
+	}
+}
+
+impl Endpoint for UserMemberships {
+	fn method(&self) -> Method {
+		Method::GET
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		format!("users/{}/memberships", self.user_id).into()
+	}
+
+	fn parameters(&self) -> QueryParams<'_> {
+		let mut params = QueryParams::default();
+		params.push_opt("type", self.source_type.as_ref().map(MembershipSource::as_str));
+		params
+	}
+}
+
+impl Pageable for UserMemberships {}
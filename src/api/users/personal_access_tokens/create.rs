@@ -47,7 +47,7 @@ impl<'a> Endpoint for CreatePersonalAccessToken<'a> {
         format!("users/{}/personal_access_tokens", self.user_id).into()
     }
 
-    fn parameters(&self) -> QueryParams {
+    fn parameters(&self) -> QueryParams<'_> {
         let mut params = QueryParams::default();
         params.push("user_id", self.user_id);
         params.push("name", self.name.to_string());
@@ -0,0 +1,24 @@
+use gitlab::api::Endpoint;
+use reqwest::Method;
+use std::borrow::Cow;
+
+/// Get the version of the instance
+#[derive(Debug)]
+pub struct Version;
+
+impl Version {
+	/// Create the endpoint.
+	pub fn build() -> Self {
+		Version {}
+	}
+}
+
+impl Endpoint for Version {
+	fn method(&self) -> Method {
+		Method::GET
+	}
+
+	fn endpoint(&self) -> Cow<'static, str> {
+		"version".into()
+	}
+}
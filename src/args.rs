@@ -1,13 +1,24 @@
-use anyhow::Error;
+use anyhow::{anyhow, bail, Error};
 #[cfg(feature = "color")]
 use argh::FromArgValue;
-use argh::{FromArgs, TopLevelCommand};
-use chrono::NaiveDate;
-use gitlab::api::common::NameOrId;
+use argh::{CommandInfo, DynamicSubCommand, EarlyExit, FromArgs, TopLevelCommand};
+use chrono::{Duration, NaiveDate};
+use gitlab::api::{
+	common::{NameOrId, ProtectedAccessLevel, VisibilityLevel},
+	ParamValue,
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use ssh_key::Fingerprint;
-use std::{env, fmt::Display, path::Path, str::FromStr};
+use std::{
+	env,
+	fmt::Display,
+	fs::read_dir,
+	path::{Path, PathBuf},
+	str::FromStr,
+	sync::OnceLock,
+};
 
-use crate::types::{keys::KeyUsage, token::PersonalAccessTokenScope};
+use crate::types::{keys::KeyUsage, token::PersonalAccessTokenScope, StatusState};
 
 /// Color mode
 #[allow(dead_code)]
@@ -36,6 +47,57 @@ impl FromArgValue for ColorChoice {
 	}
 }
 
+/// How dates are shown
+#[derive(Deserialize, Default, Copy, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DateFormat {
+	/// relative to now (3 hours ago)
+	#[default]
+	Relative,
+	/// ISO 8601 timestamp
+	Absolute,
+}
+
+impl FromStr for DateFormat {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"relative" => Ok(Self::Relative),
+			"absolute" => Ok(Self::Absolute),
+			_ => bail!("{} not supported. Use either \"relative\" or \"absolute\"", s),
+		}
+	}
+}
+
+/// How durations are shown
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DurationFormat {
+	/// compact form (1:02:03s)
+	#[default]
+	Human,
+	/// number of seconds (3723)
+	Seconds,
+	/// hours, minutes and seconds (01:02:03)
+	Hms,
+}
+
+impl FromStr for DurationFormat {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"human" => Ok(Self::Human),
+			"seconds" => Ok(Self::Seconds),
+			"hms" => Ok(Self::Hms),
+			_ => bail!(
+				"{} not supported. Use either \"human\", \"seconds\" or \"hms\"",
+				s
+			),
+		}
+	}
+}
+
 /// Interact with Gitlab API
 #[derive(FromArgs)]
 pub struct Opts {
@@ -51,6 +113,14 @@ pub struct Opts {
 	#[argh(switch, short = 'o')]
 	pub open: bool,
 
+	/// print the links instead of opening them (implies -o)
+	#[argh(switch)]
+	pub print_url: bool,
+
+	/// fail instead of asking questions (confirmations, choices)
+	#[argh(switch)]
+	pub non_interactive: bool,
+
 	/// show urls
 	#[argh(switch, short = 'u')]
 	pub url: bool,
@@ -64,6 +134,46 @@ pub struct Opts {
 	#[argh(switch)]
 	pub no_cache: bool,
 
+	/// serve api responses from the local cache without touching the network
+	#[argh(switch)]
+	pub offline: bool,
+
+	/// don't reuse the recent api responses of the lookups (projects, users, branches and tags)
+	#[argh(switch)]
+	pub no_cache_api: bool,
+
+	/// print the time spent in each api endpoint at the end of the command
+	#[argh(switch)]
+	pub timing: bool,
+
+	/// fail when rate limited instead of waiting for the rate limit window to reset
+	#[argh(switch)]
+	pub no_throttle: bool,
+
+	/// maximum number of seconds to wait for the answer of a request (overrides the host config)
+	#[argh(option)]
+	pub timeout: Option<u64>,
+
+	/// don't shorten the long names nor wrap the urls to fit in the terminal
+	#[argh(switch)]
+	pub no_truncate: bool,
+
+	/// show dates as relative (default) or absolute
+	#[argh(option)]
+	pub dates: Option<DateFormat>,
+
+	/// language of the relative dates as a 2 letters code (en by default)
+	#[argh(option)]
+	pub lang: Option<String>,
+
+	/// show durations as human (default), seconds or hms
+	#[argh(option, default = "DurationFormat::Human")]
+	pub duration: DurationFormat,
+
+	/// act on behalf of another user (admin only)
+	#[argh(option)]
+	pub sudo: Option<String>,
+
 	#[argh(subcommand)]
 	pub cmd: SubCommand,
 }
@@ -72,13 +182,138 @@ pub struct Opts {
 #[argh(subcommand)]
 pub enum SubCommand {
 	Tags(Tags),
+	Release(Release),
 	Branches(Branches),
+	Protections(Protections),
 	Pipeline(Pipeline),
 	Archive(Archive),
 	Project(Project),
 	Token(Token),
 	Keys(Keys),
 	Users(Users),
+	Mirror(Mirror),
+	Hook(Hook),
+	Trigger(Trigger),
+	Variable(Variable),
+	SecureFile(SecureFile),
+	Job(Job),
+	Env(Env),
+	Mr(Mr),
+	Issue(Issue),
+	Todo(Todo),
+	Board(Board),
+	Iteration(Iteration),
+	ServiceDesk(ServiceDesk),
+	Errors(Errors),
+	Admin(Admin),
+	Time(Time),
+	Commit(Commit),
+	Group(Group),
+	Clone(CloneGroup),
+	Sync(Sync),
+	Status(Status),
+	Dashboard(Dashboard),
+	Api(Api),
+	SelfUpdate(SelfUpdate),
+	#[argh(dynamic)]
+	Plugin(Plugin),
+}
+
+/// Prefix of the external subcommands executables
+static PLUGIN_PREFIX: &str = "gil-";
+
+/// External subcommand: a gil-<name> executable found on PATH
+pub struct Plugin {
+	/// the subcommand name
+	pub name: String,
+	/// path of the executable
+	pub path: PathBuf,
+	/// arguments passed to the executable
+	pub args: Vec<String>,
+}
+
+impl Plugin {
+	/// Returns the list of (name, path) of all gil-<name> executables found on PATH.
+	/// When several executables have the same name, the first one found on PATH wins.
+	pub fn list() -> &'static [(String, PathBuf)] {
+		static PLUGINS: OnceLock<Vec<(String, PathBuf)>> = OnceLock::new();
+		PLUGINS.get_or_init(|| {
+			let mut plugins: Vec<(String, PathBuf)> = Vec::new();
+			let paths = env::var_os("PATH").unwrap_or_default();
+			for path in env::split_paths(&paths)
+				.filter_map(|dir| read_dir(dir).ok())
+				.flatten()
+				.filter_map(|entry| entry.ok().map(|entry| entry.path()))
+			{
+				// strip the extension (.exe) on windows
+				let name = if cfg!(windows) {
+					path.file_stem()
+				} else {
+					path.file_name()
+				}
+				.and_then(|name| name.to_str())
+				.and_then(|name| name.strip_prefix(PLUGIN_PREFIX))
+				.filter(|name| !name.is_empty())
+				.map(str::to_owned);
+				if let Some(name) = name {
+					if path.is_file() && !plugins.iter().any(|(n, _)| n == &name) {
+						plugins.push((name, path));
+					}
+				}
+			}
+			plugins
+		})
+	}
+
+	/// Find an external subcommand by name
+	fn find(name: &str) -> Option<&'static PathBuf> {
+		Self::list()
+			.iter()
+			.find_map(|(n, path)| (n == name).then_some(path))
+	}
+}
+
+impl DynamicSubCommand for Plugin {
+	fn commands() -> &'static [&'static CommandInfo] {
+		static COMMANDS: OnceLock<Vec<&'static CommandInfo>> = OnceLock::new();
+		COMMANDS.get_or_init(|| {
+			Plugin::list()
+				.iter()
+				.map(|(name, path)| {
+					// argh needs static references
+					&*Box::leak(Box::new(CommandInfo {
+						name: Box::leak(name.clone().into_boxed_str()),
+						description: Box::leak(
+							format!("External command ({})", path.to_string_lossy())
+								.into_boxed_str(),
+						),
+					}))
+				})
+				.collect()
+		})
+	}
+
+	fn try_redact_arg_values(
+		command_name: &[&str],
+		args: &[&str],
+	) -> Option<Result<Vec<String>, EarlyExit>> {
+		command_name
+			.last()
+			.and_then(|name| Self::find(name))
+			.map(|_| Ok(args.iter().map(|arg| arg.to_string()).collect()))
+	}
+
+	fn try_from_args(command_name: &[&str], args: &[&str]) -> Option<Result<Self, EarlyExit>> {
+		command_name.last().and_then(|name| {
+			Self::find(name).map(|path| {
+				Ok(Self {
+					name: name.to_string(),
+					path: path.to_owned(),
+					args: args.iter().map(|arg| arg.to_string()).collect(),
+				})
+			})
+		})
+	}
 }
 
 /// Get and extract archives
@@ -113,15 +348,96 @@ pub struct ArchiveExtract {
 	#[argh(switch, short = 'u')]
 	pub update: bool,
 
+	/// only print one line per project with the number of extracted files and their size
+	#[argh(switch, short = 'q')]
+	pub quiet: bool,
+
+	/// treat the entries that can't be extracted as failures, go on with the other projects when
+	/// an archive fails, and exit with an error and a summary of the failures at the end
+	#[argh(switch)]
+	pub strict: bool,
+
+	/// the commit to extract an archive from instead of a reference
+	#[argh(option)]
+	pub commit: Option<String>,
+
 	/// reference (tag or branch) to extract an archive from
 	#[argh(positional)]
 	pub ref_: Option<String>,
 }
 
+/// Format of an exported lock file
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LockFormat {
+	/// CycloneDX bill of materials (json)
+	CycloneDx,
+	/// one line per project
+	Csv,
+}
+
+impl FromStr for LockFormat {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"cyclonedx" => Ok(Self::CycloneDx),
+			"csv" => Ok(Self::Csv),
+			_ => bail!("{} not supported. Use either \"cyclonedx\" or \"csv\"", s),
+		}
+	}
+}
+
+/// Export the lock file as a dependency manifest
+#[derive(FromArgs)]
+#[argh(subcommand, name = "export")]
+pub struct ArchiveLockExport {
+	/// the batch file the lock file was created for (the lock file of the host by default)
+	#[argh(option, short = 'b')]
+	pub batch: Option<String>,
+
+	/// output format: cyclonedx (default) or csv
+	#[argh(option, short = 'f', default = "LockFormat::CycloneDx")]
+	pub format: LockFormat,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum ArchiveLockCmd {
+	Export(ArchiveLockExport),
+}
+
+/// Handle the lock file of the extracted archives
+#[derive(FromArgs)]
+#[argh(subcommand, name = "lock")]
+pub struct ArchiveLock {
+	/// operate on lock file
+	#[argh(subcommand)]
+	pub cmd: ArchiveLockCmd,
+}
+
+/// Check the extracted archives against the lock file
+#[derive(FromArgs)]
+#[argh(subcommand, name = "verify")]
+pub struct ArchiveVerify {
+	/// the batch file the lock file was created for (the lock file of the host by default)
+	#[argh(option, short = 'b')]
+	pub batch: Option<String>,
+
+	/// directory the archives were extracted to
+	#[argh(option, short = 'd', default = "\"tmp\".to_string()")]
+	pub dir: String,
+
+	/// also check that the extracted files didn't change since their extraction
+	#[argh(switch, short = 'c')]
+	pub content: bool,
+}
+
 #[derive(FromArgs)]
 #[argh(subcommand)]
 pub enum ArchiveCmd {
 	Extract(ArchiveExtract),
+	Lock(ArchiveLock),
+	Verify(ArchiveVerify),
 }
 
 /// Handle project archives
@@ -136,8 +452,40 @@ pub struct Archive {
 #[derive(FromArgs)]
 #[argh(subcommand)]
 pub enum TagsCmd {
+	Create(TagsCreate),
 	Protect(TagsProtect),
 	Unprotect(TagsUnprotect),
+	Protections(TagsProtections),
+}
+
+/// Create an annotated tag and optionally its release
+#[derive(FromArgs)]
+#[argh(subcommand, name = "create")]
+pub struct TagsCreate {
+	/// the project to create the tag on
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+
+	/// branch or commit to tag (the current branch by default)
+	#[argh(option, short = 'r')]
+	pub ref_: Option<String>,
+
+	/// message of the tag (the tag name by default)
+	#[argh(option, short = 'm')]
+	pub message: Option<String>,
+
+	/// create the release of the tag
+	#[argh(switch)]
+	pub release: bool,
+
+	/// release notes, or a changelog from which the section of the tag is taken (implies
+	/// --release)
+	#[argh(option)]
+	pub notes_file: Option<PathBuf>,
+
+	/// the tag name
+	#[argh(positional)]
+	pub tag: String,
 }
 
 /// Protect a project tag(s)
@@ -148,11 +496,25 @@ pub struct TagsProtect {
 	#[argh(option, short = 'p')]
 	pub project: Option<OwnedNameOrId>,
 
+	/// who can create matching tags: developer, maintainer (default), admin, no-access,
+	/// user:<name|id> or group:<path|id>, can be repeated
+	#[argh(option, short = 'c')]
+	pub create_access: Vec<AccessSpec>,
+
 	/// tag expression: '*' (default)
 	#[argh(positional, default = "\"*\".to_string()")]
 	pub tag: String,
 }
 
+/// List the protected tags of a project
+#[derive(FromArgs)]
+#[argh(subcommand, name = "protections")]
+pub struct TagsProtections {
+	/// the project to list protected tags from
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+}
+
 /// Unprotect a project tag(s)
 #[derive(FromArgs)]
 #[argh(subcommand, name = "unprotect")]
@@ -175,6 +537,46 @@ pub struct Tags {
 	pub cmd: TagsCmd,
 }
 
+/// Manage project releases
+#[derive(FromArgs)]
+#[argh(subcommand, name = "release")]
+pub struct Release {
+	/// operate on releases
+	#[argh(subcommand)]
+	pub cmd: ReleaseCmd,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum ReleaseCmd {
+	Notes(ReleaseNotes),
+}
+
+/// Print the merged merge requests between two refs as markdown release notes
+#[derive(FromArgs)]
+#[argh(subcommand, name = "notes")]
+pub struct ReleaseNotes {
+	/// the project to compare refs on
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+
+	/// the previous release (tag, branch or commit)
+	#[argh(option, short = 'f')]
+	pub from: String,
+
+	/// the new release: HEAD (default)
+	#[argh(option, short = 't', default = "\"HEAD\".to_string()")]
+	pub to: String,
+
+	/// list the commit subjects instead of the merged merge requests
+	#[argh(switch, short = 'c')]
+	pub commits: bool,
+
+	/// create a release with the notes for this tag (created on --to if it doesn't exist)
+	#[argh(option, short = 'r')]
+	pub release: Option<String>,
+}
+
 /// Protect a project branche(s)
 #[derive(FromArgs)]
 #[argh(subcommand, name = "protect")]
@@ -187,11 +589,108 @@ pub struct BranchesProtect {
 	#[argh(switch, short = 'f')]
 	pub force_push: bool,
 
+	/// who can push: developer, maintainer (default), admin, no-access, user:<name|id> or
+	/// group:<path|id>
+	#[argh(option)]
+	pub push_access: Option<AccessSpec>,
+
+	/// who can merge: developer, maintainer (default), admin, no-access, user:<name|id> or
+	/// group:<path|id>
+	#[argh(option)]
+	pub merge_access: Option<AccessSpec>,
+
+	/// who can unprotect: developer, maintainer (default), admin, user:<name|id> or
+	/// group:<path|id>
+	#[argh(option)]
+	pub unprotect_access: Option<AccessSpec>,
+
+	/// also allow to push (role, user:<name|id> or group:<path|id>), can be repeated
+	#[argh(option)]
+	pub allowed_to_push: Vec<AccessSpec>,
+
+	/// also allow to merge (role, user:<name|id> or group:<path|id>), can be repeated
+	#[argh(option)]
+	pub allowed_to_merge: Vec<AccessSpec>,
+
+	/// also allow to unprotect (role, user:<name|id> or group:<path|id>), can be repeated
+	#[argh(option)]
+	pub allowed_to_unprotect: Vec<AccessSpec>,
+
+	/// require approval from code owners
+	#[argh(switch)]
+	pub code_owner_approval: bool,
+
 	/// branch expression
 	#[argh(positional)]
 	pub branch: Option<String>,
 }
 
+/// Who can act on a protected branch or tag: a role, a user or a group
+#[derive(Clone)]
+pub enum AccessSpec {
+	Role(ProtectedAccessLevel),
+	User(OwnedNameOrId),
+	Group(OwnedNameOrId),
+}
+
+impl FromStr for AccessSpec {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Ok(if let Some(user) = s.strip_prefix("user:") {
+			Self::User(user.parse()?)
+		} else if let Some(group) = s.strip_prefix("group:") {
+			Self::Group(group.parse()?)
+		} else {
+			Self::Role(match s {
+				"developer" | "30" => ProtectedAccessLevel::Developer,
+				"maintainer" | "40" => ProtectedAccessLevel::Maintainer,
+				"admin" | "60" => ProtectedAccessLevel::Admin,
+				"no-access" | "0" => ProtectedAccessLevel::NoAccess,
+				_ => bail!(
+					"Access is developer, maintainer, admin, no-access, user:<name|id> or group:<path|id> not {}",
+					s
+				),
+			})
+		})
+	}
+}
+
+/// Deserialize an AccessSpec from the same string as on the command line
+impl<'de> Deserialize<'de> for AccessSpec {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		let s = String::deserialize(deserializer)?;
+		s.parse().map_err(de::Error::custom)
+	}
+}
+
+impl Display for AccessSpec {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Role(ProtectedAccessLevel::Developer) => write!(f, "developer"),
+			Self::Role(ProtectedAccessLevel::Maintainer) => write!(f, "maintainer"),
+			Self::Role(ProtectedAccessLevel::Admin) => write!(f, "admin"),
+			Self::Role(ProtectedAccessLevel::NoAccess) => write!(f, "no-access"),
+			Self::Role(level) => write!(f, "{}", level.as_value()),
+			Self::User(user) => write!(f, "user:{}", user),
+			Self::Group(group) => write!(f, "group:{}", group),
+		}
+	}
+}
+
+/// Serialize an AccessSpec to the same string as on the command line
+impl Serialize for AccessSpec {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.collect_str(self)
+	}
+}
+
 /// Unprotect a project brnache(s)
 #[derive(FromArgs)]
 #[argh(subcommand, name = "unprotect")]
@@ -214,6 +713,48 @@ pub struct Branches {
 	pub cmd: BranchesCmd,
 }
 
+/// Manage the branch and tag protection rules of a project as yaml
+#[derive(FromArgs)]
+#[argh(subcommand, name = "protections")]
+pub struct Protections {
+	/// operate on protection rules
+	#[argh(subcommand)]
+	pub cmd: ProtectionsCmd,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum ProtectionsCmd {
+	Export(ProtectionsExport),
+	Apply(ProtectionsApply),
+}
+
+/// Print the protected branches and tags of a project as yaml rules
+#[derive(FromArgs)]
+#[argh(subcommand, name = "export")]
+pub struct ProtectionsExport {
+	/// the project to export protection rules from
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+}
+
+/// Converge the protected branches and tags of a project to yaml rules
+#[derive(FromArgs)]
+#[argh(subcommand, name = "apply")]
+pub struct ProtectionsApply {
+	/// the project to apply protection rules to
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+
+	/// don't ask for confirmation
+	#[argh(switch, short = 'y')]
+	pub yes: bool,
+
+	/// yaml file with the branches and tags protection rules
+	#[argh(positional)]
+	pub rules: PathBuf,
+}
+
 #[derive(FromArgs)]
 #[argh(subcommand)]
 pub enum BranchesCmd {
@@ -230,6 +771,10 @@ pub enum PipelineCmd {
 	Cancel(PipelineCancel),
 	Retry(PipelineRetry),
 	Log(PipelineLog),
+	Delete(PipelineDelete),
+	DeleteArtifacts(PipelineDeleteArtifacts),
+	Branches(PipelineBranches),
+	Schedule(PipelineScheduleArgs),
 }
 
 /// list pipelines
@@ -244,6 +789,10 @@ pub struct PipelineList {
 	#[argh(option, short = 'l', default = "10")]
 	pub limit: usize,
 
+	/// show the signature state of the pipeline commits
+	#[argh(switch, short = 's')]
+	pub signatures: bool,
+
 	/// pipeline id
 	#[argh(positional)]
 	pub id: Option<u64>,
@@ -261,6 +810,22 @@ pub struct PipelineStatus {
 	#[argh(option, short = 'r')]
 	pub ref_: Option<String>,
 
+	/// the merge request iid to get the latest pipeline from
+	#[argh(option, short = 'm')]
+	pub mr: Option<u64>,
+
+	/// show the signature state of the pipeline commit
+	#[argh(switch, short = 's')]
+	pub signature: bool,
+
+	/// show the end of the log of the first failed job when the pipeline failed
+	#[argh(switch, short = 'e')]
+	pub explain_failure: bool,
+
+	/// show what triggered the pipeline and the variables it was run with
+	#[argh(switch)]
+	pub show_vars: bool,
+
 	/// pipeline id
 	#[argh(positional)]
 	pub id: Option<u64>,
@@ -313,6 +878,111 @@ pub struct PipelineRetry {
 	pub id: Option<u64>,
 }
 
+/// Manage pipeline schedules
+#[derive(FromArgs)]
+#[argh(subcommand, name = "schedule")]
+pub struct PipelineScheduleArgs {
+	/// the project which owns the schedules
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+
+	#[argh(subcommand)]
+	/// operate on schedules
+	pub cmd: ScheduleCmd,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum ScheduleCmd {
+	List(ScheduleList),
+	Run(ScheduleRun),
+}
+
+/// List pipeline schedules with their next run
+#[derive(FromArgs)]
+#[argh(subcommand, name = "list")]
+pub struct ScheduleList {}
+
+/// Run a pipeline schedule immediately
+#[derive(FromArgs)]
+#[argh(subcommand, name = "run")]
+pub struct ScheduleRun {
+	/// the schedule id
+	#[argh(positional)]
+	pub id: u64,
+}
+
+/// Show the latest pipeline of each active branch
+#[derive(FromArgs)]
+#[argh(subcommand, name = "branches")]
+pub struct PipelineBranches {
+	/// the project which owns the pipelines
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+
+	/// include branches without commit for more than 3 months
+	#[argh(switch, short = 'a')]
+	pub all: bool,
+
+	/// only show branches matching a glob pattern (ex: 'release/*')
+	#[argh(positional)]
+	pub pattern: Option<String>,
+}
+
+/// Delete a pipeline
+#[derive(FromArgs)]
+#[argh(subcommand, name = "delete")]
+pub struct PipelineDelete {
+	/// the project which owns the pipeline
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+
+	/// delete all pipelines not updated since a period (ex: 30d, 12w, 1y)
+	#[argh(option, short = 'o')]
+	pub older_than: Option<Period>,
+
+	/// only delete pipelines with this status (success, failed, canceled, skipped...)
+	#[argh(option, short = 's')]
+	pub status: Option<StatusState>,
+
+	/// reference (tag or branch) of the pipelines to delete with --older-than
+	#[argh(option, short = 'r')]
+	pub ref_: Option<String>,
+
+	/// don't ask for confirmation
+	#[argh(switch, short = 'y')]
+	pub yes: bool,
+
+	/// pipeline id
+	#[argh(positional)]
+	pub id: Option<u64>,
+}
+
+/// Delete the artifacts of the jobs of a pipeline
+#[derive(FromArgs)]
+#[argh(subcommand, name = "delete-artifacts")]
+pub struct PipelineDeleteArtifacts {
+	/// the project which owns the pipeline
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+
+	/// reference (tag or branch)
+	#[argh(option, short = 'r')]
+	pub ref_: Option<String>,
+
+	/// delete the artifacts of all pipelines not updated since a period (ex: 30d, 12w, 1y)
+	#[argh(option, short = 'o')]
+	pub older_than: Option<Period>,
+
+	/// don't ask for confirmation
+	#[argh(switch, short = 'y')]
+	pub yes: bool,
+
+	/// pipeline id
+	#[argh(positional)]
+	pub id: Option<u64>,
+}
+
 /// Get log from a job
 #[derive(FromArgs)]
 #[argh(subcommand, name = "log")]
@@ -325,6 +995,10 @@ pub struct PipelineLog {
 	#[argh(option, short = 'r')]
 	pub ref_: Option<String>,
 
+	/// the merge request iid to get the latest pipeline from
+	#[argh(option, short = 'm')]
+	pub mr: Option<u64>,
+
 	/// a name that partially match the section name(s) to show in the log: step_script (default)
 	#[argh(option, short = 's', default = "\"step_script\".to_string()")]
 	pub section: String,
@@ -333,6 +1007,11 @@ pub struct PipelineLog {
 	#[argh(option, short = 'j')]
 	pub job_id: Option<u64>,
 
+	/// the name of the job(s) to extract the log from, a glob pattern to print the logs of
+	/// several jobs (a matrix) one after the other
+	#[argh(option, short = 'n')]
+	pub job_name: Option<String>,
+
 	/// show all sections
 	#[argh(switch, short = 'a')]
 	pub all: bool,
@@ -345,9 +1024,37 @@ pub struct PipelineLog {
 	#[argh(switch, short = 'H')]
 	pub only_headers: bool,
 
-	/// the pipeline id
-	#[argh(positional)]
-	pub id: Option<u64>,
+	/// expand a collapsed section given by name or by the index shown in its header (repeatable)
+	#[argh(option, short = 'e')]
+	pub expand: Vec<String>,
+
+	/// ask for the collapsed sections to show after the log
+	#[argh(switch, short = 'i')]
+	pub interactive: bool,
+
+	/// write the raw log (unfiltered with colors) to a file
+	#[argh(option, short = 'o')]
+	pub output: Option<PathBuf>,
+
+	/// also print the filtered log when writing to a file
+	#[argh(switch)]
+	pub tee: bool,
+
+	/// remove all ANSI escape sequences from the printed log
+	#[argh(switch)]
+	pub strip_ansi: bool,
+
+	/// keep ANSI escape sequences in the printed log even when not on a terminal
+	#[argh(switch)]
+	pub keep_ansi: bool,
+
+	/// print only the last lines of the filtered log
+	#[argh(option)]
+	pub tail: Option<usize>,
+
+	/// the pipeline id
+	#[argh(positional)]
+	pub id: Option<u64>,
 }
 
 /// Manage project pipeline
@@ -368,7 +1075,7 @@ pub fn from_env<T: TopLevelCommand>() -> T {
 		.unwrap_or(&args[0]);
 	let args_str: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
 	T::from_args(&[cmd], &args_str[1..]).unwrap_or_else(|early_exit| {
-		println!("{} {}\n", env!("CARGO_BIN_NAME"), env!("CARGO_PKG_VERSION"));
+		println!("{} {}\n", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
 		println!("{}", early_exit.output);
 		std::process::exit(match early_exit.status {
 			Ok(()) => 0,
@@ -396,6 +1103,12 @@ pub enum ProjectCmd {
 	Info(ProjectInfo),
 	Archive(ProjectArchive),
 	Unarchive(ProjectUnarchive),
+	Usage(ProjectUsage),
+	Star(ProjectStar),
+	Unstar(ProjectUnstar),
+	Starred(ProjectStarred),
+	ExportSettings(ProjectExportSettings),
+	ApplySettings(ProjectApplySettings),
 }
 
 /// Display information about project
@@ -410,12 +1123,82 @@ pub struct ProjectInfo {
 /// Archive project
 #[derive(FromArgs)]
 #[argh(subcommand, name = "archive")]
-pub struct ProjectArchive {}
+pub struct ProjectArchive {
+	/// archive all the projects of a group (and subgroups) instead
+	#[argh(option, short = 'g')]
+	pub group: Option<OwnedNameOrId>,
+
+	/// only the group projects whose name or path matches a glob pattern (ex: 'legacy-*')
+	#[argh(option, short = 'm', long = "match")]
+	pub match_: Option<String>,
+
+	/// don't ask for confirmation
+	#[argh(switch, short = 'y')]
+	pub yes: bool,
+}
 
 /// Unarchive project
 #[derive(FromArgs)]
 #[argh(subcommand, name = "unarchive")]
-pub struct ProjectUnarchive {}
+pub struct ProjectUnarchive {
+	/// unarchive all the projects of a group (and subgroups) instead
+	#[argh(option, short = 'g')]
+	pub group: Option<OwnedNameOrId>,
+
+	/// only the group projects whose name or path matches a glob pattern (ex: 'legacy-*')
+	#[argh(option, short = 'm', long = "match")]
+	pub match_: Option<String>,
+
+	/// don't ask for confirmation
+	#[argh(switch, short = 'y')]
+	pub yes: bool,
+}
+
+/// Show the storage used by a project
+#[derive(FromArgs)]
+#[argh(subcommand, name = "usage")]
+pub struct ProjectUsage {
+	/// show the storage used by all the projects of a group sorted by size
+	#[argh(option, short = 'g')]
+	pub group: Option<OwnedNameOrId>,
+}
+
+/// Star project
+#[derive(FromArgs)]
+#[argh(subcommand, name = "star")]
+pub struct ProjectStar {}
+
+/// Unstar project
+#[derive(FromArgs)]
+#[argh(subcommand, name = "unstar")]
+pub struct ProjectUnstar {}
+
+/// List the projects starred by the current user
+#[derive(FromArgs)]
+#[argh(subcommand, name = "starred")]
+pub struct ProjectStarred {
+	/// only the projects whose path matches a glob pattern (ex: 'my-group/*')
+	#[argh(option, short = 'm', long = "match")]
+	pub match_: Option<String>,
+}
+
+/// Print the settings and protection rules of a project as yaml
+#[derive(FromArgs)]
+#[argh(subcommand, name = "export-settings")]
+pub struct ProjectExportSettings {}
+
+/// Change the settings and protection rules of a project from a yaml file
+#[derive(FromArgs)]
+#[argh(subcommand, name = "apply-settings")]
+pub struct ProjectApplySettings {
+	/// don't ask for confirmation
+	#[argh(switch, short = 'y')]
+	pub yes: bool,
+
+	/// yaml file with the settings (as given by export-settings)
+	#[argh(positional)]
+	pub file: PathBuf,
+}
 
 /// Manage user tokens
 #[derive(FromArgs)]
@@ -429,6 +1212,7 @@ pub struct Token {
 #[derive(FromArgs)]
 #[argh(subcommand)]
 pub enum TokenCmd {
+	Info(TokenInfo),
 	List(TokenList),
 	Create(TokenCreate),
 	Revoke(TokenRevoke),
@@ -436,6 +1220,7 @@ pub enum TokenCmd {
 }
 
 /// Owned version of gitlab::api::common::NameOrId
+#[derive(Clone)]
 pub enum OwnedNameOrId {
 	Name(String),
 	Id(u64),
@@ -479,7 +1264,7 @@ impl OwnedNameOrId {
 	/// Like Option::as_ref but for OwnedNameOrId.
 	/// Returns a NameOrId which is a kind of borrowed version of OwnedNameOrId
 	/// It is more readable to call .as_ref than NameOrId::From(&name_or_id)
-	pub fn as_ref(&self) -> NameOrId {
+	pub fn as_ref(&self) -> NameOrId<'_> {
 		self.into()
 	}
 }
@@ -504,6 +1289,10 @@ pub struct TokenCreate {
 	#[argh(switch, short = 'r')]
 	pub revoke: bool,
 
+	/// output format: raw (default), env, dotenv or json
+	#[argh(option, short = 'o', default = "TokenOutput::Raw")]
+	pub output: TokenOutput,
+
 	/// the token name
 	#[argh(positional)]
 	pub name: OwnedNameOrId,
@@ -517,11 +1306,24 @@ pub struct TokenRevoke {
 	#[argh(option, short = 'u')]
 	pub username: Option<String>,
 
+	/// revoke all active tokens whose name matches a glob pattern (ex: 'ci-*')
+	#[argh(option, short = 'm', long = "match")]
+	pub match_: Option<String>,
+
+	/// don't ask for confirmation
+	#[argh(switch, short = 'y')]
+	pub yes: bool,
+
 	/// the token name
 	#[argh(positional)]
-	pub name: OwnedNameOrId,
+	pub name: Option<OwnedNameOrId>,
 }
 
+/// Show the token currently used
+#[derive(FromArgs)]
+#[argh(subcommand, name = "info")]
+pub struct TokenInfo {}
+
 /// List tokens
 #[derive(FromArgs)]
 #[argh(subcommand, name = "list")]
@@ -538,6 +1340,10 @@ pub struct TokenList {
 	#[argh(switch, short = 'a')]
 	pub all: bool,
 
+	/// only list tokens expiring within the given number of days and exit with 1 if any
+	#[argh(option, short = 'e')]
+	pub expiring: Option<u32>,
+
 	/// the pattern of token to search names for
 	#[argh(positional)]
 	pub search: Option<String>,
@@ -555,11 +1361,42 @@ pub struct TokenRotate {
 	#[argh(option, short = 'e')]
 	pub expires_at: Option<NaiveDate>,
 
+	/// output format: raw (default), env, dotenv or json
+	#[argh(option, short = 'o', default = "TokenOutput::Raw")]
+	pub output: TokenOutput,
+
 	/// the token name
 	#[argh(positional)]
 	pub name: OwnedNameOrId,
 }
 
+/// Output format of a new token secret
+#[derive(Clone, Copy)]
+pub enum TokenOutput {
+	/// the secret alone
+	Raw,
+	/// a shell export statement
+	Env,
+	/// a dotenv line
+	Dotenv,
+	/// the token json description
+	Json,
+}
+
+impl FromStr for TokenOutput {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"raw" => Ok(Self::Raw),
+			"env" => Ok(Self::Env),
+			"dotenv" => Ok(Self::Dotenv),
+			"json" => Ok(Self::Json),
+			_ => bail!("Output formats are raw, env, dotenv or json not {}", s),
+		}
+	}
+}
+
 /// Manage user keys
 #[derive(FromArgs)]
 #[argh(subcommand, name = "keys")]
@@ -575,6 +1412,7 @@ pub enum KeysCmd {
 	List(ListKeys),
 	Add(AddKey),
 	Delete(DeleteKey),
+	Prune(PruneKeys),
 }
 
 /// Add a key
@@ -597,9 +1435,17 @@ pub struct AddKey {
 	#[argh(switch, short = 'w')]
 	pub overwrite: bool,
 
+	/// pick the key among the ssh-agent identities
+	#[argh(switch, short = 'a')]
+	pub from_agent: bool,
+
+	/// pick the key among the public keys of a GitHub user
+	#[argh(option, short = 'g')]
+	pub from_github: Option<String>,
+
 	/// key path
 	#[argh(positional)]
-	pub key: String,
+	pub key: Option<String>,
 }
 
 /// List keys
@@ -609,6 +1455,10 @@ pub struct ListKeys {
 	/// username of user id to list the keys from
 	#[argh(option, short = 'u')]
 	pub user: Option<String>,
+
+	/// show fingerprint, type, usage, creation and expiration dates
+	#[argh(switch, short = 'd')]
+	pub detail: bool,
 }
 
 /// Identification of a key for the delete subcommand
@@ -647,11 +1497,81 @@ impl FromStr for KeyIdType {
 #[derive(FromArgs)]
 #[argh(subcommand, name = "delete")]
 pub struct DeleteKey {
+	/// don't ask for confirmation
+	#[argh(switch, short = 'y')]
+	pub yes: bool,
+
 	/// the key id (db id, fingerprint or name) do delete
 	#[argh(positional)]
 	pub id: KeyIdType,
 }
 
+/// Delete expired or unused keys
+#[derive(FromArgs)]
+#[argh(subcommand, name = "prune")]
+pub struct PruneKeys {
+	/// delete expired keys
+	#[argh(switch, short = 'e')]
+	pub expired: bool,
+
+	/// delete keys not used for a period (ex: 90d, 12w, 1y)
+	#[argh(option, short = 'u')]
+	pub unused_since: Option<Period>,
+
+	/// don't ask for confirmation
+	#[argh(switch, short = 'y')]
+	pub yes: bool,
+}
+
+/// A period of time given as a number followed by a unit: h(ours), d(ays), w(eeks) or y(ears)
+#[derive(Clone, Copy)]
+pub struct Period(pub Duration);
+
+impl FromStr for Period {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (n, unit) = s.split_at(s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len()));
+		let n: i64 = n
+			.parse()
+			.map_err(|_| anyhow!("Invalid period {}, use 90d, 12w, 1y ...", s))?;
+		Ok(Self(match unit {
+			"h" => Duration::hours(n),
+			"d" | "" => Duration::days(n),
+			"w" => Duration::weeks(n),
+			"y" => Duration::days(n * 365),
+			_ => bail!("Invalid period unit {}, use h, d, w or y", unit),
+		}))
+	}
+}
+
+/// A tracked time in the gitlab format: a sequence of numbers followed by a unit mo(nths),
+/// w(eeks), d(ays), h(ours), m(inutes) or s(econds) like 1h30m or 2d
+#[derive(Clone)]
+pub struct TrackedTime(pub String);
+
+impl FromStr for TrackedTime {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let mut rest = s.strip_prefix('-').unwrap_or(s);
+		if rest.is_empty() {
+			bail!("Invalid time {}, use 1h30m, 2d, 1w ...", s);
+		}
+		while !rest.is_empty() {
+			let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+			let unit = ["mo", "w", "d", "h", "m", "s"]
+				.into_iter()
+				.find(|unit| rest[digits..].starts_with(unit));
+			match unit {
+				Some(unit) if digits > 0 => rest = &rest[digits + unit.len()..],
+				_ => bail!("Invalid time {}, use 1h30m, 2d, 1w ...", s),
+			}
+		}
+		Ok(Self(s.to_owned()))
+	}
+}
+
 /// Manage users
 #[derive(FromArgs)]
 #[argh(subcommand, name = "users")]
@@ -666,6 +1586,9 @@ pub struct Users {
 pub enum UserCmd {
 	Current(UserCurrent),
 	IsAdmin(UserIsAdmin),
+	Info(UserInfo),
+	Activity(UserActivity),
+	Projects(UserProjects),
 }
 
 /// Get current user name
@@ -677,3 +1600,1543 @@ pub struct UserCurrent {}
 #[derive(FromArgs)]
 #[argh(subcommand, name = "isadmin")]
 pub struct UserIsAdmin {}
+
+/// Display a user profile
+#[derive(FromArgs)]
+#[argh(subcommand, name = "info")]
+pub struct UserInfo {
+	/// the username (current user by default)
+	#[argh(positional)]
+	pub username: Option<String>,
+}
+
+/// Display the recent events of a user
+#[derive(FromArgs)]
+#[argh(subcommand, name = "activity")]
+pub struct UserActivity {
+	/// limit
+	#[argh(option, short = 'l', default = "20")]
+	pub limit: usize,
+
+	/// the username (current user by default)
+	#[argh(positional)]
+	pub username: Option<String>,
+}
+
+/// List the projects a user is a member of (admin only for other users)
+#[derive(FromArgs)]
+#[argh(subcommand, name = "projects")]
+pub struct UserProjects {
+	/// the username (current user by default)
+	#[argh(positional)]
+	pub username: Option<String>,
+}
+
+/// Manage project mirrors
+#[derive(FromArgs)]
+#[argh(subcommand, name = "mirror")]
+pub struct Mirror {
+	/// the project owning the mirrors
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+
+	#[argh(subcommand)]
+	/// operate on mirrors
+	pub cmd: MirrorCmd,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum MirrorCmd {
+	List(MirrorList),
+	Add(MirrorAdd),
+	Delete(MirrorDelete),
+	Sync(MirrorSync),
+}
+
+/// List push mirrors
+#[derive(FromArgs)]
+#[argh(subcommand, name = "list")]
+pub struct MirrorList {}
+
+/// Add a push mirror
+#[derive(FromArgs)]
+#[argh(subcommand, name = "add")]
+pub struct MirrorAdd {
+	/// username to authenticate on the mirror
+	#[argh(option, short = 'u')]
+	pub username: Option<String>,
+
+	/// environment variable containing the password or token to authenticate on the mirror
+	#[argh(option, short = 'e')]
+	pub password_env: Option<String>,
+
+	/// only mirror protected branches
+	#[argh(switch)]
+	pub protected: bool,
+
+	/// don't overwrite refs that diverged on the mirror
+	#[argh(switch)]
+	pub keep_divergent: bool,
+
+	/// add the mirror disabled
+	#[argh(switch)]
+	pub disabled: bool,
+
+	/// the mirror repository url
+	#[argh(positional)]
+	pub url: String,
+}
+
+/// Delete a push mirror
+#[derive(FromArgs)]
+#[argh(subcommand, name = "delete")]
+pub struct MirrorDelete {
+	/// the mirror id
+	#[argh(positional)]
+	pub id: u64,
+}
+
+/// Update a push mirror now, or the pull mirror without id
+#[derive(FromArgs)]
+#[argh(subcommand, name = "sync")]
+pub struct MirrorSync {
+	/// the push mirror id
+	#[argh(positional)]
+	pub id: Option<u64>,
+}
+
+/// Debug the webhooks of a project
+#[derive(FromArgs)]
+#[argh(subcommand, name = "hook")]
+pub struct Hook {
+	/// the project owning the webhooks
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+
+	#[argh(subcommand)]
+	/// operate on webhooks
+	pub cmd: HookCmd,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum HookCmd {
+	List(HookList),
+	Deliveries(HookDeliveries),
+	Test(HookTest),
+}
+
+/// List the webhooks
+#[derive(FromArgs)]
+#[argh(subcommand, name = "list")]
+pub struct HookList {}
+
+/// Show the recent deliveries of a webhook with their response status
+#[derive(FromArgs)]
+#[argh(subcommand, name = "deliveries")]
+pub struct HookDeliveries {
+	/// only the deliveries with a status code, or successful, client_failure or server_failure
+	#[argh(option, short = 's')]
+	pub status: Option<String>,
+
+	/// maximum number of deliveries to show (20 by default)
+	#[argh(option, short = 'n', default = "20")]
+	pub limit: usize,
+
+	/// the webhook id
+	#[argh(positional)]
+	pub id: u64,
+}
+
+/// Send a test event to a webhook
+#[derive(FromArgs)]
+#[argh(subcommand, name = "test")]
+pub struct HookTest {
+	/// the event to send: push (default), tag_push, merge_requests, issues, confidential_issues,
+	/// note, job, pipeline, wiki_page, releases, emoji or resource_access_token
+	#[argh(option, short = 'e', default = "\"push\".to_string()")]
+	pub event: String,
+
+	/// the webhook id
+	#[argh(positional)]
+	pub id: u64,
+}
+
+/// Manage pipeline trigger tokens
+#[derive(FromArgs)]
+#[argh(subcommand, name = "trigger")]
+pub struct Trigger {
+	/// the project owning the triggers
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+
+	#[argh(subcommand)]
+	/// operate on triggers
+	pub cmd: TriggerCmd,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum TriggerCmd {
+	List(TriggerList),
+	Create(TriggerCreate),
+	Delete(TriggerDelete),
+	Run(TriggerRun),
+}
+
+/// List trigger tokens
+#[derive(FromArgs)]
+#[argh(subcommand, name = "list")]
+pub struct TriggerList {}
+
+/// Create a trigger token
+#[derive(FromArgs)]
+#[argh(subcommand, name = "create")]
+pub struct TriggerCreate {
+	/// the trigger description
+	#[argh(positional)]
+	pub description: String,
+}
+
+/// Delete a trigger token
+#[derive(FromArgs)]
+#[argh(subcommand, name = "delete")]
+pub struct TriggerDelete {
+	/// the trigger id
+	#[argh(positional)]
+	pub id: u64,
+}
+
+/// Run a pipeline with a trigger token
+#[derive(FromArgs)]
+#[argh(subcommand, name = "run")]
+pub struct TriggerRun {
+	/// reference (tag or branch)
+	#[argh(option, short = 'r')]
+	pub ref_: Option<String>,
+
+	/// pipeline variable (KEY=VALUE), can be repeated
+	#[argh(option, short = 'v')]
+	pub var: Vec<KeyValue>,
+
+	/// id of a trigger you own to take the token from
+	#[argh(option, short = 'i')]
+	pub id: Option<u64>,
+
+	/// environment variable containing the trigger token (CI_TRIGGER_TOKEN by default)
+	#[argh(option, short = 'e', default = "\"CI_TRIGGER_TOKEN\".to_string()")]
+	pub token_env: String,
+}
+
+/// Manage the CI/CD variables of a project
+#[derive(FromArgs)]
+#[argh(subcommand, name = "variable")]
+pub struct Variable {
+	#[argh(subcommand)]
+	/// operate on variables
+	pub cmd: VariableCmd,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum VariableCmd {
+	Export(VariableExport),
+	Import(VariableImport),
+}
+
+/// Print the variables of a project in the dotenv format
+#[derive(FromArgs)]
+#[argh(subcommand, name = "export")]
+pub struct VariableExport {
+	/// the project owning the variables
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+
+	/// the environment scope of the variables: * (default)
+	#[argh(option, short = 'e', default = "\"*\".to_string()")]
+	pub environment: String,
+}
+
+/// Create or update the variables of a project from a dotenv file
+#[derive(FromArgs)]
+#[argh(subcommand, name = "import")]
+pub struct VariableImport {
+	/// the project owning the variables
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+
+	/// the environment scope of the variables: * (default)
+	#[argh(option, short = 'e', default = "\"*\".to_string()")]
+	pub environment: String,
+
+	/// protect the created variables
+	#[argh(switch)]
+	pub protected: bool,
+
+	/// mask the created variables
+	#[argh(switch)]
+	pub masked: bool,
+
+	/// don't ask for confirmation before updating existing variables
+	#[argh(switch, short = 'y')]
+	pub yes: bool,
+
+	/// the dotenv file (- for the standard input)
+	#[argh(positional)]
+	pub file: String,
+}
+
+/// Manage the CI secure files of a project
+#[derive(FromArgs)]
+#[argh(subcommand, name = "secure-file")]
+pub struct SecureFile {
+	#[argh(subcommand)]
+	/// operate on secure files
+	pub cmd: SecureFileCmd,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum SecureFileCmd {
+	List(SecureFileList),
+	Upload(SecureFileUpload),
+	Download(SecureFileDownload),
+	Delete(SecureFileDelete),
+}
+
+/// List the secure files
+#[derive(FromArgs)]
+#[argh(subcommand, name = "list")]
+pub struct SecureFileList {
+	/// the project owning the secure files
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+}
+
+/// Upload a secure file
+#[derive(FromArgs)]
+#[argh(subcommand, name = "upload")]
+pub struct SecureFileUpload {
+	/// the project owning the secure files
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+
+	/// the name of the secure file (the name of the local file by default)
+	#[argh(option, short = 'n')]
+	pub name: Option<String>,
+
+	/// the local file
+	#[argh(positional)]
+	pub file: PathBuf,
+}
+
+/// Download a secure file
+#[derive(FromArgs)]
+#[argh(subcommand, name = "download")]
+pub struct SecureFileDownload {
+	/// the project owning the secure files
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+
+	/// the local file (the name of the secure file in the current directory by default)
+	#[argh(option, short = 'o')]
+	pub output: Option<PathBuf>,
+
+	/// overwrite the local file if it exists
+	#[argh(switch, short = 'f')]
+	pub force: bool,
+
+	/// the name or id of the secure file
+	#[argh(positional)]
+	pub file: String,
+}
+
+/// Delete a secure file
+#[derive(FromArgs)]
+#[argh(subcommand, name = "delete")]
+pub struct SecureFileDelete {
+	/// the project owning the secure files
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+
+	/// don't ask for confirmation
+	#[argh(switch, short = 'y')]
+	pub yes: bool,
+
+	/// the name or id of the secure file
+	#[argh(positional)]
+	pub file: String,
+}
+
+/// A KEY=VALUE pair
+#[derive(Clone)]
+pub struct KeyValue(pub String, pub String);
+
+impl FromStr for KeyValue {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		s.split_once('=')
+			.filter(|(key, _)| !key.is_empty())
+			.map(|(key, value)| Self(key.to_owned(), value.to_owned()))
+			.ok_or_else(|| anyhow!("Expecting KEY=VALUE not {}", s))
+	}
+}
+
+/// Manage jobs
+#[derive(FromArgs)]
+#[argh(subcommand, name = "job")]
+pub struct Job {
+	/// the project which owns the job
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+
+	#[argh(subcommand)]
+	/// operate on jobs
+	pub cmd: JobCmd,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum JobCmd {
+	Play(JobPlay),
+	KeepArtifacts(JobKeepArtifacts),
+}
+
+/// Run a manual job
+#[derive(FromArgs)]
+#[argh(subcommand, name = "play")]
+pub struct JobPlay {
+	/// the name of the manual job to run instead of its id
+	#[argh(option, short = 'n')]
+	pub name: Option<String>,
+
+	/// reference (tag or branch) of the pipeline to look the named job up in
+	#[argh(option, short = 'r')]
+	pub ref_: Option<String>,
+
+	/// the pipeline to look the named job up in (the last pipeline of the ref by default)
+	#[argh(option, short = 'i')]
+	pub pipeline: Option<u64>,
+
+	/// job variable (KEY=VALUE), can be repeated
+	#[argh(option, short = 'v')]
+	pub var: Vec<KeyValue>,
+
+	/// the job id
+	#[argh(positional)]
+	pub id: Option<u64>,
+}
+
+/// Keep the artifacts of a job from expiring
+#[derive(FromArgs)]
+#[argh(subcommand, name = "keep-artifacts")]
+pub struct JobKeepArtifacts {
+	/// the job id
+	#[argh(positional)]
+	pub id: u64,
+}
+
+/// Manage project environments
+#[derive(FromArgs)]
+#[argh(subcommand, name = "env")]
+pub struct Env {
+	/// the project which owns the environments
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+
+	#[argh(subcommand)]
+	/// operate on environments
+	pub cmd: EnvCmd,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum EnvCmd {
+	List(EnvList),
+	Stop(EnvStop),
+	Rollback(EnvRollback),
+}
+
+/// List environments
+#[derive(FromArgs)]
+#[argh(subcommand, name = "list")]
+pub struct EnvList {}
+
+/// Stop an environment
+#[derive(FromArgs)]
+#[argh(subcommand, name = "stop")]
+pub struct EnvStop {
+	/// the environment name
+	#[argh(positional)]
+	pub name: String,
+}
+
+/// Redeploy a previous deployment of an environment
+#[derive(FromArgs)]
+#[argh(subcommand, name = "rollback")]
+pub struct EnvRollback {
+	/// the deployment id to roll back to (the previous successful one by default)
+	#[argh(option, short = 't')]
+	pub to: Option<u64>,
+
+	/// don't ask for confirmation
+	#[argh(switch, short = 'y')]
+	pub yes: bool,
+
+	/// the environment name
+	#[argh(positional)]
+	pub name: String,
+}
+
+/// Manage merge requests
+#[derive(FromArgs)]
+#[argh(subcommand, name = "mr")]
+pub struct Mr {
+	/// the project which owns the merge requests
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+
+	#[argh(subcommand)]
+	/// operate on merge requests
+	pub cmd: MrCmd,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum MrCmd {
+	Env(MrEnv),
+	Subscribe(MrSubscribe),
+	Unsubscribe(MrUnsubscribe),
+	Spend(MrSpend),
+	Estimate(MrEstimate),
+	Ready(MrReady),
+	Draft(MrDraft),
+	Rebase(MrRebase),
+	Conflicts(MrConflicts),
+	RequestReview(MrRequestReview),
+	CherryPick(MrCherryPick),
+	Approve(MrApprove),
+	Merge(MrMerge),
+	Mine(MrMine),
+}
+
+/// Show the review apps of a merge request
+#[derive(FromArgs)]
+#[argh(subcommand, name = "env")]
+pub struct MrEnv {
+	/// the merge request iid (the opened merge request of the current branch by default)
+	#[argh(positional)]
+	pub iid: Option<u64>,
+}
+
+/// Subscribe to the notifications of a merge request
+#[derive(FromArgs)]
+#[argh(subcommand, name = "subscribe")]
+pub struct MrSubscribe {
+	/// the merge request iid (the opened merge request of the current branch by default)
+	#[argh(positional)]
+	pub iid: Option<u64>,
+}
+
+/// Unsubscribe from the notifications of a merge request
+#[derive(FromArgs)]
+#[argh(subcommand, name = "unsubscribe")]
+pub struct MrUnsubscribe {
+	/// the merge request iid (the opened merge request of the current branch by default)
+	#[argh(positional)]
+	pub iid: Option<u64>,
+}
+
+/// Add spent time to a merge request
+#[derive(FromArgs)]
+#[argh(subcommand, name = "spend")]
+pub struct MrSpend {
+	/// a summary of how the time was spent
+	#[argh(option, short = 's')]
+	pub summary: Option<String>,
+
+	/// the merge request iid
+	#[argh(positional)]
+	pub iid: u64,
+
+	/// the spent time (ex: 1h30m, 2d), negative to subtract time (give it after --)
+	#[argh(positional)]
+	pub time: TrackedTime,
+}
+
+/// Set the time estimate of a merge request
+#[derive(FromArgs)]
+#[argh(subcommand, name = "estimate")]
+pub struct MrEstimate {
+	/// the merge request iid
+	#[argh(positional)]
+	pub iid: u64,
+
+	/// the estimated time (ex: 1h30m, 2d)
+	#[argh(positional)]
+	pub time: TrackedTime,
+}
+
+/// Order of the issues
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IssueSort {
+	/// soonest due date first
+	Due,
+	/// highest label priority first
+	Priority,
+}
+
+impl FromStr for IssueSort {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"due" => Ok(Self::Due),
+			"priority" => Ok(Self::Priority),
+			_ => bail!("{} not supported. Use either \"due\" or \"priority\"", s),
+		}
+	}
+}
+
+/// List the opened issues assigned to the current user across all projects
+#[derive(FromArgs)]
+#[argh(subcommand, name = "mine")]
+pub struct IssueMine {
+	/// sort by due date (due) or label priority (priority) instead of creation date
+	#[argh(option, short = 's')]
+	pub sort: Option<IssueSort>,
+}
+
+/// Manage the todos of the current user
+#[derive(FromArgs)]
+#[argh(subcommand, name = "todo")]
+pub struct Todo {
+	#[argh(subcommand)]
+	/// operate on todos
+	pub cmd: TodoCmd,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum TodoCmd {
+	List(TodoList),
+	Done(TodoDone),
+}
+
+/// List the pending todos
+#[derive(FromArgs)]
+#[argh(subcommand, name = "list")]
+pub struct TodoList {
+	/// only list the todos of a project
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+}
+
+/// Mark a todo, or all the pending todos of a project, as done
+#[derive(FromArgs)]
+#[argh(subcommand, name = "done")]
+pub struct TodoDone {
+	/// mark all the pending todos of a project as done
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+
+	/// don't ask for confirmation
+	#[argh(switch, short = 'y')]
+	pub yes: bool,
+
+	/// the todo id
+	#[argh(positional)]
+	pub id: Option<u64>,
+}
+
+/// Mark a merge request as ready
+#[derive(FromArgs)]
+#[argh(subcommand, name = "ready")]
+pub struct MrReady {
+	/// the merge request iid (the opened merge request of the current branch by default)
+	#[argh(positional)]
+	pub iid: Option<u64>,
+}
+
+/// Mark a merge request as draft
+#[derive(FromArgs)]
+#[argh(subcommand, name = "draft")]
+pub struct MrDraft {
+	/// the merge request iid (the opened merge request of the current branch by default)
+	#[argh(positional)]
+	pub iid: Option<u64>,
+}
+
+/// Approve a merge request
+#[derive(FromArgs)]
+#[argh(subcommand, name = "approve")]
+pub struct MrApprove {
+	/// mark the pending todos of the merge request as done
+	#[argh(switch, short = 't')]
+	pub resolve_todo: bool,
+
+	/// the merge request iid (the opened merge request of the current branch by default)
+	#[argh(positional)]
+	pub iid: Option<u64>,
+}
+
+/// Merge a merge request
+#[derive(FromArgs)]
+#[argh(subcommand, name = "merge")]
+pub struct MrMerge {
+	/// squash the commits of the source branch
+	#[argh(switch, short = 's')]
+	pub squash: bool,
+
+	/// remove the source branch once merged
+	#[argh(switch, short = 'r')]
+	pub remove_source_branch: bool,
+
+	/// mark the pending todos of the merge request as done
+	#[argh(switch, short = 't')]
+	pub resolve_todo: bool,
+
+	/// the merge request iid (the opened merge request of the current branch by default)
+	#[argh(positional)]
+	pub iid: Option<u64>,
+}
+
+/// Rebase the source branch of a merge request onto its target branch
+#[derive(FromArgs)]
+#[argh(subcommand, name = "rebase")]
+pub struct MrRebase {
+	/// don't create a pipeline for the rebased branch
+	#[argh(switch, short = 's')]
+	pub skip_ci: bool,
+
+	/// the merge request iid (the opened merge request of the current branch by default)
+	#[argh(positional)]
+	pub iid: Option<u64>,
+}
+
+/// Show whether a merge request has conflicts and the files that may conflict
+#[derive(FromArgs)]
+#[argh(subcommand, name = "conflicts")]
+pub struct MrConflicts {
+	/// the merge request iid (the opened merge request of the current branch by default)
+	#[argh(positional)]
+	pub iid: Option<u64>,
+}
+
+/// Request a (new) review of a merge request
+#[derive(FromArgs)]
+#[argh(subcommand, name = "request-review")]
+pub struct MrRequestReview {
+	/// username of a reviewer (can be repeated, all the current reviewers by default)
+	#[argh(option, short = 'r')]
+	pub reviewer: Vec<String>,
+
+	/// reset the approvals of the merge request first (needs a bot token)
+	#[argh(switch, short = 'a')]
+	pub reset_approvals: bool,
+
+	/// the merge request iid (the opened merge request of the current branch by default)
+	#[argh(positional)]
+	pub iid: Option<u64>,
+}
+
+/// Cherry-pick a merged merge request onto a branch
+#[derive(FromArgs)]
+#[argh(subcommand, name = "cherry-pick")]
+pub struct MrCherryPick {
+	/// the branch to cherry-pick the merge request onto
+	#[argh(option, short = 'b')]
+	pub branch: String,
+
+	/// cherry-pick onto a new branch and open a backport merge request to the branch instead
+	#[argh(switch, short = 'c')]
+	pub create_mr: bool,
+
+	/// the merge request iid
+	#[argh(positional)]
+	pub iid: u64,
+}
+
+/// List the opened merge requests of the current user across all projects
+#[derive(FromArgs)]
+#[argh(subcommand, name = "mine")]
+pub struct MrMine {
+	/// the merge requests awaiting a review from the current user instead of authored by
+	#[argh(switch, short = 'r')]
+	pub reviewer: bool,
+
+	/// the merge requests assigned to the current user instead of authored by
+	#[argh(switch, short = 'a')]
+	pub assignee: bool,
+}
+
+/// Manage issues
+#[derive(FromArgs)]
+#[argh(subcommand, name = "issue")]
+pub struct Issue {
+	/// the project which owns the issues
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+
+	#[argh(subcommand)]
+	/// operate on issues
+	pub cmd: IssueCmd,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum IssueCmd {
+	Subscribe(IssueSubscribe),
+	Unsubscribe(IssueUnsubscribe),
+	Spend(IssueSpend),
+	Estimate(IssueEstimate),
+	Close(IssueClose),
+	Mine(IssueMine),
+}
+
+/// Subscribe to the notifications of an issue
+#[derive(FromArgs)]
+#[argh(subcommand, name = "subscribe")]
+pub struct IssueSubscribe {
+	/// the issue iid
+	#[argh(positional)]
+	pub iid: u64,
+}
+
+/// Unsubscribe from the notifications of an issue
+#[derive(FromArgs)]
+#[argh(subcommand, name = "unsubscribe")]
+pub struct IssueUnsubscribe {
+	/// the issue iid
+	#[argh(positional)]
+	pub iid: u64,
+}
+
+/// Close an issue
+#[derive(FromArgs)]
+#[argh(subcommand, name = "close")]
+pub struct IssueClose {
+	/// mark the pending todos of the issue as done
+	#[argh(switch, short = 't')]
+	pub resolve_todo: bool,
+
+	/// the issue iid
+	#[argh(positional)]
+	pub iid: u64,
+}
+
+/// Add spent time to an issue
+#[derive(FromArgs)]
+#[argh(subcommand, name = "spend")]
+pub struct IssueSpend {
+	/// a summary of how the time was spent
+	#[argh(option, short = 's')]
+	pub summary: Option<String>,
+
+	/// the issue iid
+	#[argh(positional)]
+	pub iid: u64,
+
+	/// the spent time (ex: 1h30m, 2d), negative to subtract time (give it after --)
+	#[argh(positional)]
+	pub time: TrackedTime,
+}
+
+/// Set the time estimate of an issue
+#[derive(FromArgs)]
+#[argh(subcommand, name = "estimate")]
+pub struct IssueEstimate {
+	/// the issue iid
+	#[argh(positional)]
+	pub iid: u64,
+
+	/// the estimated time (ex: 1h30m, 2d)
+	#[argh(positional)]
+	pub time: TrackedTime,
+}
+
+/// Manage issue boards
+#[derive(FromArgs)]
+#[argh(subcommand, name = "board")]
+pub struct Board {
+	/// the project which owns the boards
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+
+	#[argh(subcommand)]
+	/// operate on boards
+	pub cmd: BoardCmd,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum BoardCmd {
+	Move(BoardMove),
+}
+
+/// Move an issue to another list of a board
+#[derive(FromArgs)]
+#[argh(subcommand, name = "move")]
+pub struct BoardMove {
+	/// the board name or id (the first board of the project by default)
+	#[argh(option, short = 'b')]
+	pub board: Option<String>,
+
+	/// the target list: a label, an assignee username, a milestone title, open or closed
+	#[argh(option, short = 't')]
+	pub to: String,
+
+	/// the issue iid
+	#[argh(positional)]
+	pub iid: u64,
+}
+
+/// Manage iterations (sprints)
+#[derive(FromArgs)]
+#[argh(subcommand, name = "iteration")]
+pub struct Iteration {
+	/// the project which owns the issues
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+
+	/// the group which owns the iterations (the project group by default)
+	#[argh(option, short = 'g')]
+	pub group: Option<OwnedNameOrId>,
+
+	#[argh(subcommand)]
+	/// operate on iterations
+	pub cmd: IterationCmd,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum IterationCmd {
+	List(IterationList),
+	Show(IterationShow),
+	Assign(IterationAssign),
+}
+
+/// List the iterations of a group
+#[derive(FromArgs)]
+#[argh(subcommand, name = "list")]
+pub struct IterationList {
+	/// only the iterations in a state: opened, upcoming, current, closed or all (default opened)
+	#[argh(option, short = 's', default = "\"opened\".to_string()")]
+	pub state: String,
+}
+
+/// Show an iteration and its issues
+#[derive(FromArgs)]
+#[argh(subcommand, name = "show")]
+pub struct IterationShow {
+	/// the iteration id (the current iteration by default)
+	#[argh(positional)]
+	pub id: Option<u64>,
+}
+
+/// Assign issues to an iteration
+#[derive(FromArgs)]
+#[argh(subcommand, name = "assign")]
+pub struct IterationAssign {
+	/// the iteration id (the current iteration by default)
+	#[argh(option, short = 'i')]
+	pub iteration: Option<u64>,
+
+	/// the issues iid
+	#[argh(positional)]
+	pub iids: Vec<u64>,
+}
+
+/// Manage the Service Desk
+#[derive(FromArgs)]
+#[argh(subcommand, name = "servicedesk")]
+pub struct ServiceDesk {
+	/// the project which owns the Service Desk
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+
+	#[argh(subcommand)]
+	/// operate on the Service Desk
+	pub cmd: ServiceDeskCmd,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum ServiceDeskCmd {
+	Issues(ServiceDeskIssues),
+	Settings(ServiceDeskSettings),
+}
+
+/// List the issues created by email through the Service Desk
+#[derive(FromArgs)]
+#[argh(subcommand, name = "issues")]
+pub struct ServiceDeskIssues {
+	/// include the closed issues
+	#[argh(switch, short = 'a')]
+	pub all: bool,
+}
+
+/// Show or change the Service Desk settings
+#[derive(FromArgs)]
+#[argh(subcommand, name = "settings")]
+pub struct ServiceDeskSettings {
+	#[argh(subcommand)]
+	/// operate on the settings
+	pub cmd: ServiceDeskSettingsCmd,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum ServiceDeskSettingsCmd {
+	Get(ServiceDeskSettingsGet),
+	Set(ServiceDeskSettingsSet),
+}
+
+/// Show the Service Desk settings
+#[derive(FromArgs)]
+#[argh(subcommand, name = "get")]
+pub struct ServiceDeskSettingsGet {}
+
+/// Change the Service Desk settings
+#[derive(FromArgs)]
+#[argh(subcommand, name = "set")]
+pub struct ServiceDeskSettingsSet {
+	/// enable (true) or disable (false) the Service Desk
+	#[argh(option, short = 'e')]
+	pub enabled: bool,
+}
+
+/// Inspect the errors of the error tracking
+#[derive(FromArgs)]
+#[argh(subcommand, name = "errors")]
+pub struct Errors {
+	/// the project which owns the errors
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+
+	#[argh(subcommand)]
+	/// operate on errors
+	pub cmd: ErrorsCmd,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum ErrorsCmd {
+	List(ErrorsList),
+	Show(ErrorsShow),
+}
+
+/// List the recent errors with their number of occurrences
+#[derive(FromArgs)]
+#[argh(subcommand, name = "list")]
+pub struct ErrorsList {
+	/// only the errors matching a search term
+	#[argh(option, short = 's')]
+	pub search: Option<String>,
+
+	/// sort by last_seen (default), first_seen or frequency
+	#[argh(option)]
+	pub sort: Option<String>,
+
+	/// maximum number of errors (default 20)
+	#[argh(option, short = 'l', default = "20")]
+	pub limit: u32,
+}
+
+/// Show the details of an error
+#[derive(FromArgs)]
+#[argh(subcommand, name = "show")]
+pub struct ErrorsShow {
+	/// the error id
+	#[argh(positional)]
+	pub id: u64,
+}
+
+/// Administer the instance (admin only)
+#[derive(FromArgs)]
+#[argh(subcommand, name = "admin")]
+pub struct Admin {
+	#[argh(subcommand)]
+	/// administration commands
+	pub cmd: AdminCmd,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum AdminCmd {
+	Hooks(AdminHooks),
+	License(AdminLicense),
+	Stats(AdminStats),
+	Health(AdminHealth),
+	User(AdminUser),
+	Group(AdminGroup),
+}
+
+/// Show the license plan, seats and expiry
+#[derive(FromArgs)]
+#[argh(subcommand, name = "license")]
+pub struct AdminLicense {}
+
+/// Show the number of projects, users, groups... of the instance
+#[derive(FromArgs)]
+#[argh(subcommand, name = "stats")]
+pub struct AdminStats {}
+
+/// Check the liveness and readiness of the instance
+#[derive(FromArgs)]
+#[argh(subcommand, name = "health")]
+pub struct AdminHealth {}
+
+/// Create and delete users
+#[derive(FromArgs)]
+#[argh(subcommand, name = "user")]
+pub struct AdminUser {
+	#[argh(subcommand)]
+	/// operate on users
+	pub cmd: AdminUserCmd,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum AdminUserCmd {
+	Create(AdminUserCreate),
+	Delete(AdminUserDelete),
+}
+
+/// Create a user
+#[derive(FromArgs)]
+#[argh(subcommand, name = "create")]
+pub struct AdminUserCreate {
+	/// the email of the user
+	#[argh(option, short = 'e')]
+	pub email: String,
+
+	/// the full name of the user (the username by default)
+	#[argh(option, short = 'n')]
+	pub name: Option<String>,
+
+	/// make the user an administrator
+	#[argh(switch, short = 'a')]
+	pub admin: bool,
+
+	/// send an email to let the user choose a password (a random password is set otherwise)
+	#[argh(switch, short = 'r')]
+	pub reset_password: bool,
+
+	/// don't ask the user to confirm the email
+	#[argh(switch, short = 's')]
+	pub skip_confirmation: bool,
+
+	/// the username
+	#[argh(positional)]
+	pub username: String,
+}
+
+/// Delete a user
+#[derive(FromArgs)]
+#[argh(subcommand, name = "delete")]
+pub struct AdminUserDelete {
+	/// also delete the contributions (issues, merge requests, notes...) instead of moving them
+	/// to the ghost user
+	#[argh(switch)]
+	pub hard: bool,
+
+	/// don't ask for confirmation
+	#[argh(switch, short = 'y')]
+	pub yes: bool,
+
+	/// the username
+	#[argh(positional)]
+	pub username: String,
+}
+
+/// Manage the system hooks
+#[derive(FromArgs)]
+#[argh(subcommand, name = "hooks")]
+pub struct AdminHooks {
+	#[argh(subcommand)]
+	/// operate on system hooks
+	pub cmd: HooksCmd,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum HooksCmd {
+	List(HooksList),
+	Add(HooksAdd),
+	Delete(HooksDelete),
+	Test(HooksTest),
+}
+
+/// List the system hooks
+#[derive(FromArgs)]
+#[argh(subcommand, name = "list")]
+pub struct HooksList {}
+
+/// Add a system hook
+#[derive(FromArgs)]
+#[argh(subcommand, name = "add")]
+pub struct HooksAdd {
+	/// the name of the hook
+	#[argh(option, short = 'n')]
+	pub name: Option<String>,
+
+	/// the secret token sent in the X-Gitlab-Token header
+	#[argh(option, short = 't')]
+	pub token: Option<String>,
+
+	/// additional events triggering the hook: push, tag_push, merge_requests or
+	/// repository_update (can be repeated)
+	#[argh(option, short = 'e')]
+	pub event: Vec<HookEvent>,
+
+	/// don't verify the SSL certificate of the url
+	#[argh(switch, short = 'k')]
+	pub insecure: bool,
+
+	/// the url called on the events
+	#[argh(positional)]
+	pub url: String,
+}
+
+/// Delete a system hook
+#[derive(FromArgs)]
+#[argh(subcommand, name = "delete")]
+pub struct HooksDelete {
+	/// the hook id
+	#[argh(positional)]
+	pub id: u64,
+}
+
+/// Send a test event to a system hook
+#[derive(FromArgs)]
+#[argh(subcommand, name = "test")]
+pub struct HooksTest {
+	/// the hook id
+	#[argh(positional)]
+	pub id: u64,
+}
+
+/// An event that can trigger a system hook besides the system events
+#[derive(Clone, Copy, PartialEq)]
+pub enum HookEvent {
+	Push,
+	TagPush,
+	MergeRequests,
+	RepositoryUpdate,
+}
+
+impl FromStr for HookEvent {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Ok(match s {
+			"push" => Self::Push,
+			"tag_push" => Self::TagPush,
+			"merge_requests" => Self::MergeRequests,
+			"repository_update" => Self::RepositoryUpdate,
+			_ => bail!(
+				"Invalid event {}, use push, tag_push, merge_requests or repository_update",
+				s
+			),
+		})
+	}
+}
+
+/// Create, delete and move groups
+#[derive(FromArgs)]
+#[argh(subcommand, name = "group")]
+pub struct AdminGroup {
+	#[argh(subcommand)]
+	/// operate on groups
+	pub cmd: AdminGroupCmd,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum AdminGroupCmd {
+	Create(AdminGroupCreate),
+	Delete(AdminGroupDelete),
+	Transfer(AdminGroupTransfer),
+}
+
+/// Create a group
+#[derive(FromArgs)]
+#[argh(subcommand, name = "create")]
+pub struct AdminGroupCreate {
+	/// the name of the group (the path by default)
+	#[argh(option, short = 'n')]
+	pub name: Option<String>,
+
+	/// the parent group (a top level group by default)
+	#[argh(option, short = 'p')]
+	pub parent: Option<OwnedNameOrId>,
+
+	/// the visibility: private (default), internal or public
+	#[argh(option, short = 'v')]
+	pub visibility: Option<Visibility>,
+
+	/// the description of the group
+	#[argh(option, short = 'd')]
+	pub description: Option<String>,
+
+	/// the path of the group
+	#[argh(positional)]
+	pub path: String,
+}
+
+/// Delete a group with its subgroups and projects
+#[derive(FromArgs)]
+#[argh(subcommand, name = "delete")]
+pub struct AdminGroupDelete {
+	/// don't ask for confirmation
+	#[argh(switch, short = 'y')]
+	pub yes: bool,
+
+	/// the group path or id
+	#[argh(positional)]
+	pub group: OwnedNameOrId,
+}
+
+/// Move a group under another group
+#[derive(FromArgs)]
+#[argh(subcommand, name = "transfer")]
+pub struct AdminGroupTransfer {
+	/// the new parent group (the group becomes a top level group by default)
+	#[argh(option, short = 'p')]
+	pub parent: Option<OwnedNameOrId>,
+
+	/// the group path or id
+	#[argh(positional)]
+	pub group: OwnedNameOrId,
+}
+
+/// The visibility of a group or a project
+#[derive(Clone, Copy)]
+pub struct Visibility(pub VisibilityLevel);
+
+impl FromStr for Visibility {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Ok(Self(match s {
+			"private" => VisibilityLevel::Private,
+			"internal" => VisibilityLevel::Internal,
+			"public" => VisibilityLevel::Public,
+			_ => bail!("Visibility is private, internal or public not {}", s),
+		}))
+	}
+}
+
+/// Report tracked time
+#[derive(FromArgs)]
+#[argh(subcommand, name = "time")]
+pub struct Time {
+	/// the project which owns the issues and merge requests
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+
+	#[argh(subcommand)]
+	/// operate on tracked time
+	pub cmd: TimeCmd,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum TimeCmd {
+	Report(TimeReport),
+}
+
+/// Summarize the estimated and spent time per milestone
+#[derive(FromArgs)]
+#[argh(subcommand, name = "report")]
+pub struct TimeReport {
+	/// only the issues and merge requests of a milestone
+	#[argh(option, short = 'm')]
+	pub milestone: Option<String>,
+}
+
+/// Summarize the project, the merge request and the latest pipeline of the current branch
+#[derive(FromArgs)]
+#[argh(subcommand, name = "status")]
+pub struct Status {
+	/// the project to summarize
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+
+	/// reference (tag or branch)
+	#[argh(positional)]
+	pub ref_: Option<String>,
+}
+
+/// Inspect commits
+#[derive(FromArgs)]
+#[argh(subcommand, name = "commit")]
+pub struct Commit {
+	/// the project which owns the commits
+	#[argh(option, short = 'p')]
+	pub project: Option<OwnedNameOrId>,
+
+	#[argh(subcommand)]
+	/// operate on commits
+	pub cmd: CommitCmd,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum CommitCmd {
+	List(ListCommits),
+	Verify(VerifyCommit),
+}
+
+/// List the commits of a reference
+#[derive(FromArgs)]
+#[argh(subcommand, name = "list")]
+pub struct ListCommits {
+	/// reference (tag or branch)
+	#[argh(option, short = 'r')]
+	pub ref_: Option<String>,
+
+	/// limit
+	#[argh(option, short = 'l', default = "10")]
+	pub limit: usize,
+
+	/// show the signature state of the commits
+	#[argh(switch, short = 's')]
+	pub signatures: bool,
+}
+
+/// Show the signature verification state of a commit
+#[derive(FromArgs)]
+#[argh(subcommand, name = "verify")]
+pub struct VerifyCommit {
+	/// the commit sha (or a branch or tag name)
+	#[argh(positional)]
+	pub sha: String,
+}
+
+/// Manage groups
+#[derive(FromArgs)]
+#[argh(subcommand, name = "group")]
+pub struct Group {
+	#[argh(subcommand)]
+	/// operate on groups
+	pub cmd: GroupCmd,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+pub enum GroupCmd {
+	Apply(GroupApply),
+}
+
+/// Apply branch and tag protection rules to all the projects of a group
+#[derive(FromArgs)]
+#[argh(subcommand, name = "apply")]
+pub struct GroupApply {
+	/// the group (and subgroups) containing the projects
+	#[argh(option, short = 'g')]
+	pub group: OwnedNameOrId,
+
+	/// yaml file with the branches and tags protection rules
+	#[argh(option, short = 'r')]
+	pub rules: PathBuf,
+
+	/// only the projects whose name or path matches a glob pattern
+	#[argh(option, short = 'm', long = "match")]
+	pub match_: Option<String>,
+
+	/// don't ask for confirmation
+	#[argh(switch, short = 'y')]
+	pub yes: bool,
+}
+
+/// Clone all the projects of a group
+#[derive(FromArgs)]
+#[argh(subcommand, name = "clone")]
+pub struct CloneGroup {
+	/// directory where to create the namespace tree (current directory by default)
+	#[argh(option, short = 'd')]
+	pub dir: Option<PathBuf>,
+
+	/// fetch the projects already cloned
+	#[argh(switch, short = 'u')]
+	pub update: bool,
+
+	/// the group path optionally followed by a glob pattern matching the project paths
+	/// (ex: my-group, my-group/*, my-group/api-*)
+	#[argh(positional)]
+	pub group: String,
+}
+
+/// Pull the local clones of the projects of a group and clone the new ones
+#[derive(FromArgs)]
+#[argh(subcommand, name = "sync")]
+pub struct Sync {
+	/// directory containing the namespace tree (current directory by default)
+	#[argh(option, short = 'd')]
+	pub dir: Option<PathBuf>,
+
+	/// only fetch and report, don't pull nor clone
+	#[argh(switch, short = 'n')]
+	pub dry_run: bool,
+
+	/// the group path optionally followed by a glob pattern matching the project paths
+	/// (ex: my-group, my-group/*, my-group/api-*)
+	#[argh(positional)]
+	pub group: String,
+}
+
+/// Show the latest pipeline of the default branch of several projects
+#[derive(FromArgs)]
+#[argh(subcommand, name = "dashboard")]
+pub struct Dashboard {
+	/// the group path optionally followed by a glob pattern matching the project paths (the
+	/// projects listed in the configuration by default)
+	#[argh(positional)]
+	pub group: Option<String>,
+}
+
+/// Send an authenticated request to any endpoint of the API and print the response
+#[derive(FromArgs)]
+#[argh(subcommand, name = "api")]
+pub struct Api {
+	/// the http method (GET, POST, PUT, DELETE...)
+	#[argh(positional)]
+	pub method: String,
+
+	/// the endpoint path relative to /api/v4 (ex: projects/my-group%2Fmy-project/variables)
+	#[argh(positional)]
+	pub path: String,
+
+	/// a KEY=VALUE parameter of the request (can be repeated)
+	#[argh(option, short = 'f')]
+	pub field: Vec<KeyValue>,
+
+	/// the json body of the request read from @file, or @- for the standard input
+	#[argh(option)]
+	pub raw_body: Option<String>,
+}
+
+/// Update gil to the latest release
+#[derive(FromArgs)]
+#[argh(subcommand, name = "self-update")]
+pub struct SelfUpdate {
+	/// only check if a new release is available
+	#[argh(switch, short = 'c')]
+	pub check: bool,
+
+	/// reinstall even if the latest release is already installed
+	#[argh(switch, short = 'f')]
+	pub force: bool,
+
+	/// the project to get the releases from: eburghar/gil (default)
+	#[argh(option, short = 'p', default = "\"eburghar/gil\".to_string()")]
+	pub project: String,
+
+	/// get the releases from this GitLab host instead of GitHub
+	#[argh(option, short = 'g')]
+	pub gitlab: Option<String>,
+}
@@ -0,0 +1,137 @@
+//! On disk cache of Gitlab API responses used to serve `--offline` invocations and recent
+//! lookups
+
+use crate::config::ORG;
+
+use anyhow::{Context, Result};
+use bytes::Bytes;
+use directories::ProjectDirs;
+use http::{
+	header::{HeaderName, HeaderValue},
+	Response,
+};
+use sha2::{Digest, Sha256};
+#[cfg(unix)]
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+use std::{
+	collections::BTreeMap,
+	fs::{self, DirBuilder, File, OpenOptions},
+	io::{self, Write},
+	path::{Path, PathBuf},
+	sync::atomic::{AtomicBool, Ordering},
+	time::Duration,
+};
+
+/// Age after which a cached response is neither served nor kept on disk
+const MAX_AGE: Duration = Duration::from_secs(30 * 24 * 3600);
+
+/// Response cache of a Gitlab host. Responses are stored by uri: the body in a file named
+/// after the hash of the uri, and the headers (needed for pagination) in a `.headers` file.
+/// Files are only readable by the user, and removed after [`MAX_AGE`]
+pub struct ResponseCache {
+	dir: PathBuf,
+	/// the expired entries have been removed
+	pruned: AtomicBool,
+}
+
+impl ResponseCache {
+	/// Initializer for the given host. Returns None if there is no cache directory on the system
+	pub fn new(host: &str) -> Option<Self> {
+		ProjectDirs::from("me", ORG, env!("CARGO_PKG_NAME")).map(|dir| Self {
+			dir: dir.cache_dir().join("api").join(host),
+			pruned: AtomicBool::new(false),
+		})
+	}
+
+	/// Path of the cached body of an uri
+	fn path(&self, uri: &str) -> PathBuf {
+		self.dir.join(hex::encode(Sha256::digest(uri)))
+	}
+
+	/// Time elapsed since a file was written
+	fn age(path: &Path) -> Option<Duration> {
+		fs::metadata(path)
+			.and_then(|metadata| metadata.modified())
+			.ok()
+			.and_then(|modified| modified.elapsed().ok())
+	}
+
+	/// Get a cached response
+	pub fn get(&self, uri: &str) -> Option<Response<Bytes>> {
+		self.get_fresh(uri, MAX_AGE)
+	}
+
+	/// Read a cached response whatever its age
+	fn read(&self, uri: &str) -> Option<Response<Bytes>> {
+		let path = self.path(uri);
+		let body = fs::read(&path).ok()?;
+		let headers: BTreeMap<String, String> = File::open(path.with_extension("headers"))
+			.ok()
+			.and_then(|file| serde_yaml::from_reader(file).ok())
+			.unwrap_or_default();
+		let mut rsp = Response::new(Bytes::from(body));
+		for (name, value) in headers {
+			if let (Ok(name), Ok(value)) =
+				(HeaderName::try_from(name), HeaderValue::try_from(value))
+			{
+				rsp.headers_mut().insert(name, value);
+			}
+		}
+		Some(rsp)
+	}
+
+	/// Get a cached response saved less than `max_age` ago
+	pub fn get_fresh(&self, uri: &str, max_age: Duration) -> Option<Response<Bytes>> {
+		let age = Self::age(&self.path(uri))?;
+		if age < max_age {
+			self.read(uri)
+		} else {
+			None
+		}
+	}
+
+	/// Remove the expired entries
+	fn prune(&self) {
+		let Ok(entries) = fs::read_dir(&self.dir) else {
+			return;
+		};
+		for path in entries.flatten().map(|entry| entry.path()) {
+			if Self::age(&path).is_some_and(|age| age >= MAX_AGE) {
+				let _ = fs::remove_file(path);
+			}
+		}
+	}
+
+	/// Save a response to the cache
+	pub fn put(&self, uri: &str, rsp: &Response<Bytes>) -> Result<()> {
+		if !self.pruned.swap(true, Ordering::Relaxed) {
+			self.prune();
+		}
+		let mut builder = DirBuilder::new();
+		builder.recursive(true);
+		#[cfg(unix)]
+		builder.mode(0o700);
+		builder
+			.create(&self.dir)
+			.with_context(|| format!("Can't create cache directory {:?}", &self.dir))?;
+		let path = self.path(uri);
+		let headers: BTreeMap<&str, &str> = rsp
+			.headers()
+			.iter()
+			.filter_map(|(name, value)| value.to_str().ok().map(|value| (name.as_str(), value)))
+			.collect();
+		serde_yaml::to_writer(create(&path.with_extension("headers"))?, &headers)?;
+		create(&path)
+			.and_then(|mut file| file.write_all(rsp.body()))
+			.with_context(|| format!("Can't write {:?}", &path))
+	}
+}
+
+/// Create (or truncate) a file only readable by the user
+fn create(path: &Path) -> io::Result<File> {
+	let mut options = OpenOptions::new();
+	options.write(true).create(true).truncate(true);
+	#[cfg(unix)]
+	options.mode(0o600);
+	options.open(path)
+}
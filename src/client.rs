@@ -0,0 +1,554 @@
+use crate::{
+	args::ColorChoice,
+	cache::ResponseCache,
+	color::StyledStr,
+	config::ApiToken,
+	connection::Connection,
+	fmt::{Colorizer, Stream},
+};
+
+use bytes::Bytes;
+use gitlab::{
+	api::{self, ApiError},
+	GitlabError,
+};
+use graphql_client::{GraphQLQuery, QueryBody};
+use http::{
+	header::{HeaderName, RANGE},
+	request::Builder,
+	HeaderMap, Method, Response, StatusCode,
+};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::{
+	error::Error as StdError,
+	fmt::{self, Display, Formatter},
+	sync::Mutex,
+	thread,
+	time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use url::Url;
+
+/// Maximum number of retries when Gitlab answers with 429 Too Many Requests
+const MAX_RETRIES: usize = 5;
+/// Maximum number of seconds to wait for a rate limit reset
+const MAX_WAIT: u64 = 60;
+/// Age under which a cached lookup response is reused instead of querying the API again
+const LOOKUP_TTL: Duration = Duration::from_secs(60);
+/// Header making an administrator token act as another user
+const SUDO: HeaderName = HeaderName::from_static("sudo");
+
+/// Errors of the rate limit aware client
+#[derive(Debug)]
+pub enum Error<E> {
+	/// still rate limited after all retries
+	RateLimited { retries: usize },
+	/// a request that can't be answered from the cache in offline mode
+	Offline { method: Method, uri: String },
+	/// a response not found in the cache in offline mode
+	NotCached { uri: String },
+	/// a response that is never saved to the cache (secrets, job logs, archives...)
+	NeverCached { uri: String },
+	/// error coming from the wrapped client
+	Inner(E),
+}
+
+impl<E: Display> Display for Error<E> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::RateLimited { retries } => {
+				write!(
+					f,
+					"rate limited by gitlab, giving up after {} retries",
+					retries
+				)
+			}
+			Self::Offline { method, uri } => {
+				write!(
+					f,
+					"{} {} requires the API and can't be done offline",
+					method, uri
+				)
+			}
+			Self::NotCached { uri } => write!(f, "{} not found in the offline cache", uri),
+			Self::NeverCached { uri } => write!(
+				f,
+				"{} is never cached (secrets and raw contents like job logs or archives) and can't \
+				 be read offline",
+				uri
+			),
+			Self::Inner(err) => write!(f, "{}", err),
+		}
+	}
+}
+
+impl<E: StdError + 'static> StdError for Error<E> {
+	fn source(&self) -> Option<&(dyn StdError + 'static)> {
+		match self {
+			Self::Inner(err) => Some(err),
+			_ => None,
+		}
+	}
+}
+
+/// An API request sent to Gitlab and the time it took to answer
+#[derive(Debug, Clone)]
+pub struct Timing {
+	pub method: Method,
+	/// path of the endpoint under the api url, without the query parameters
+	pub endpoint: String,
+	pub duration: Duration,
+}
+
+/// A Gitlab client that reads the rate limit headers of every response, waits when the limit is
+/// reached (typically in paged queries) and retries when rate limited, unless throttling is
+/// disabled.
+/// Successful GET responses of the lookup and pipeline endpoints (see [`is_cacheable`]) are
+/// saved to the cache, and served from it in offline mode
+pub struct GitlabClient<C = Connection> {
+	/// wrapped client (None in offline mode)
+	client: Option<C>,
+	/// base url of the rest api
+	rest_url: Url,
+	cache: Option<ResponseCache>,
+	/// user to impersonate (admin only)
+	sudo: Option<String>,
+	/// hash of the token, as the responses depend on its user
+	token_hash: Option<String>,
+	/// reuse the recent lookup responses from the cache
+	lookups: bool,
+	/// wait for the rate limit window to reset instead of failing
+	throttle: bool,
+	/// requests sent to the network (None when not recorded)
+	timings: Option<Mutex<Vec<Timing>>>,
+	color: ColorChoice,
+}
+
+impl<C> GitlabClient<C> {
+	/// Initializer wrapping an existing client
+	pub fn new(client: C, host: &str, color: ColorChoice) -> Self {
+		Self {
+			client: Some(client),
+			..Self::offline(host, color)
+		}
+	}
+
+	/// Initializer answering requests from the cache only
+	pub fn offline(host: &str, color: ColorChoice) -> Self {
+		Self {
+			client: None,
+			rest_url: Url::parse(&format!("https://{}/api/v4/", host))
+				.expect("host should be valid in an url"),
+			cache: ResponseCache::new(host),
+			sudo: None,
+			token_hash: None,
+			lookups: true,
+			throttle: true,
+			timings: None,
+			color,
+		}
+	}
+
+	/// Send all requests on behalf of another user with the Sudo header
+	pub fn sudo(mut self, user: Option<String>) -> Self {
+		self.sudo = user;
+		self
+	}
+
+	/// Keep the cached responses of a token apart from the ones of the other tokens
+	pub fn token(mut self, token: &ApiToken) -> Self {
+		self.token_hash = Some(hex::encode(Sha256::digest(token.value())));
+		self
+	}
+
+	/// Reuse (or not) the recent lookup responses from the cache
+	pub fn reuse_lookups(mut self, reuse: bool) -> Self {
+		self.lookups = reuse;
+		self
+	}
+
+	/// Wait (or not) for the rate limit window to reset when rate limited
+	pub fn throttle(mut self, enabled: bool) -> Self {
+		self.throttle = enabled;
+		self
+	}
+
+	/// Record (or not) the duration of the requests sent to the network
+	pub fn timing(mut self, enabled: bool) -> Self {
+		self.timings = enabled.then(Mutex::default);
+		self
+	}
+
+	/// Returns the requests sent so far in order, or None when they are not recorded
+	pub fn timings(&self) -> Option<Vec<Timing>> {
+		self.timings
+			.as_ref()
+			.map(|timings| timings.lock().map(|t| t.clone()).unwrap_or_default())
+	}
+
+	/// Record the duration of a request
+	fn record(&self, method: &Method, uri: &str, duration: Duration) {
+		let Some(timings) = &self.timings else {
+			return;
+		};
+		if let Ok(mut timings) = timings.lock() {
+			timings.push(Timing {
+				method: method.clone(),
+				endpoint: self.endpoint(uri),
+				duration,
+			});
+		}
+	}
+
+	/// Path of the endpoint of an uri under the api url
+	fn endpoint(&self, uri: &str) -> String {
+		let path = Url::parse(uri).map_or_else(|_| uri.to_owned(), |url| url.path().to_owned());
+		match path.strip_prefix(self.rest_url.path()) {
+			Some(endpoint) => endpoint.to_owned(),
+			None => path,
+		}
+	}
+
+	/// Client to query data that rarely changes (projects, users, branches...), answering from
+	/// the cache when the response is recent, so that consecutive invocations in scripts don't
+	/// repeat the same queries
+	pub fn lookup(&self) -> Lookup<'_, C> {
+		Lookup { client: self }
+	}
+
+	/// Don't save the responses to the cache
+	pub fn without_cache(mut self) -> Self {
+		self.cache = None;
+		self
+	}
+
+	/// Returns true if the client doesn't access the network
+	pub fn is_offline(&self) -> bool {
+		self.client.is_none()
+	}
+
+	/// Print a warning on stderr
+	fn warn(&self, text: String) {
+		let mut msg = StyledStr::new();
+		msg.warning(text);
+		msg.none("\n");
+		let _ = Colorizer::new(Stream::Stderr, self.color)
+			.with_content(msg)
+			.print();
+	}
+}
+
+impl GitlabClient<Connection> {
+	/// Send a GraphQL query (not cached, so not available offline)
+	pub fn graphql<Q>(
+		&self,
+		query: &QueryBody<Q::Variables>,
+	) -> Result<Q::ResponseData, Error<GitlabError>>
+	where
+		Q: GraphQLQuery,
+		Q::Variables: fmt::Debug,
+		for<'d> Q::ResponseData: Deserialize<'d>,
+	{
+		match &self.client {
+			Some(client) => {
+				let start = Instant::now();
+				let rsp = client.graphql::<Q>(query).map_err(Error::Inner);
+				let endpoint = format!("graphql {}", query.operation_name);
+				self.record(&Method::POST, &endpoint, start.elapsed());
+				rsp
+			}
+			None => Err(Error::Offline {
+				method: Method::POST,
+				uri: format!("graphql {}", query.operation_name),
+			}),
+		}
+	}
+}
+
+/// Returns the value of a numeric header
+fn header_u64(headers: &HeaderMap, name: &str) -> Option<u64> {
+	headers
+		.get(name)
+		.and_then(|value| value.to_str().ok())
+		.and_then(|value| value.trim().parse().ok())
+}
+
+/// Number of seconds until the rate limit window resets
+fn reset_in(headers: &HeaderMap) -> Option<u64> {
+	header_u64(headers, "ratelimit-reset").map(|reset| {
+		let now = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.map(|d| d.as_secs())
+			.unwrap_or_default();
+		reset.saturating_sub(now)
+	})
+}
+
+/// Number of seconds to wait before retrying a rate limited request
+fn retry_after(headers: &HeaderMap) -> u64 {
+	header_u64(headers, "retry-after")
+		.or_else(|| reset_in(headers))
+		.unwrap_or(MAX_WAIT)
+		.clamp(1, MAX_WAIT)
+}
+
+/// Number of seconds to wait if there is no remaining request in the rate limit window
+fn exhausted(headers: &HeaderMap) -> Option<u64> {
+	header_u64(headers, "ratelimit-remaining")
+		.filter(|remaining| *remaining == 0)
+		.and_then(|_| reset_in(headers))
+		.filter(|wait| *wait > 0)
+		.map(|wait| wait.min(MAX_WAIT))
+}
+
+/// Returns true if the responses of an endpoint can be saved to the cache: the lookups and
+/// the pipeline metadata only, never secrets (variables, secure files, tokens...) nor raw
+/// contents (job logs, archives, files...)
+fn is_cacheable(endpoint: &str) -> bool {
+	let segments: Vec<&str> = endpoint.split('/').collect();
+	matches!(
+		segments.as_slice(),
+		["version"]
+			| ["user"]
+			| ["users"]
+			| ["users", _]
+			| ["groups"]
+			| ["groups", _]
+			| ["groups", _, "projects"]
+			| ["projects"]
+			| ["projects", _]
+			| ["projects", _, "repository", "branches" | "tags"]
+			| ["projects", _, "repository", "branches" | "tags", _]
+			| ["projects", _, "pipelines"]
+			| ["projects", _, "pipelines", _]
+			| ["projects", _, "pipelines", _, "jobs"]
+	)
+}
+
+/// Copy a request builder so the request can be sent several times
+fn clone_request(request: &Builder) -> Builder {
+	let mut builder = http::request::Request::builder();
+	if let Some(method) = request.method_ref() {
+		builder = builder.method(method);
+	}
+	if let Some(uri) = request.uri_ref() {
+		builder = builder.uri(uri);
+	}
+	if let Some(version) = request.version_ref() {
+		builder = builder.version(*version);
+	}
+	if let Some(headers) = request.headers_ref() {
+		for (key, value) in headers.iter() {
+			builder = builder.header(key, value);
+		}
+	}
+	builder
+}
+
+impl<C> api::RestClient for GitlabClient<C>
+where
+	C: api::RestClient,
+{
+	type Error = Error<C::Error>;
+
+	fn rest_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<Self::Error>> {
+		match &self.client {
+			Some(client) => client
+				.rest_endpoint(endpoint)
+				.map_err(|e| e.map_client(Error::Inner)),
+			None => Ok(self.rest_url.join(endpoint)?),
+		}
+	}
+
+	fn instance_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<Self::Error>> {
+		match &self.client {
+			Some(client) => client
+				.instance_endpoint(endpoint)
+				.map_err(|e| e.map_client(Error::Inner)),
+			None => Ok(self.rest_url.join("/")?.join(endpoint)?),
+		}
+	}
+}
+
+impl<C> GitlabClient<C> {
+	/// Key of a request in the cache. Responses depend on the user of the token and on the
+	/// impersonated user
+	fn cache_key(&self, request: &Builder) -> String {
+		let mut key = request
+			.uri_ref()
+			.map(ToString::to_string)
+			.unwrap_or_default();
+		if let Some(hash) = &self.token_hash {
+			key = format!("{} token={}", key, hash);
+		}
+		if let Some(user) = &self.sudo {
+			key = format!("{} sudo={}", key, user);
+		}
+		key
+	}
+}
+
+impl<C> api::Client for GitlabClient<C>
+where
+	C: api::Client,
+{
+	fn rest(
+		&self,
+		request: Builder,
+		body: Vec<u8>,
+	) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+		let uri = request
+			.uri_ref()
+			.map(ToString::to_string)
+			.unwrap_or_default();
+		let key = self.cache_key(&request);
+		let method = request.method_ref().cloned().unwrap_or_default();
+		// partial responses are never cached
+		let ranged = request
+			.headers_ref()
+			.is_some_and(|headers| headers.contains_key(RANGE));
+		let cacheable = is_cacheable(&self.endpoint(&uri));
+		let cache = self
+			.cache
+			.as_ref()
+			.filter(|_| method == Method::GET && !ranged && cacheable);
+
+		let Some(client) = &self.client else {
+			return if method != Method::GET {
+				Err(ApiError::client(Error::Offline { method, uri }))
+			} else if !cacheable || ranged {
+				Err(ApiError::client(Error::NeverCached { uri }))
+			} else {
+				cache
+					.and_then(|cache| cache.get(&key))
+					.ok_or_else(|| ApiError::client(Error::NotCached { uri }))
+			};
+		};
+
+		let mut retries = 0;
+		loop {
+			let mut builder = clone_request(&request);
+			if let Some(user) = &self.sudo {
+				builder = builder.header(SUDO, user);
+			}
+			let start = Instant::now();
+			let rsp = client.rest(builder, body.clone());
+			self.record(&method, &uri, start.elapsed());
+			let rsp = rsp.map_err(|e| e.map_client(Error::Inner))?;
+
+			if rsp.status() == StatusCode::TOO_MANY_REQUESTS {
+				if retries == MAX_RETRIES || !self.throttle {
+					return Err(ApiError::client(Error::RateLimited { retries }));
+				}
+				retries += 1;
+				let wait = retry_after(rsp.headers());
+				self.warn(format!("Rate limited, retrying in {}s", wait));
+				thread::sleep(Duration::from_secs(wait));
+				continue;
+			}
+
+			// wait for the next window rather than being rate limited by the next request
+			if let Some(wait) = exhausted(rsp.headers()).filter(|_| self.throttle) {
+				self.warn(format!("Rate limit reached, waiting {}s", wait));
+				thread::sleep(Duration::from_secs(wait));
+			}
+
+			// keep a copy for offline use
+			if let Some(cache) = cache.filter(|_| rsp.status().is_success()) {
+				let _ = cache.put(&key, &rsp);
+			}
+			return Ok(rsp);
+		}
+	}
+}
+
+/// Client answering from the cache when the response is recent enough (see
+/// [`GitlabClient::lookup`])
+pub struct Lookup<'a, C> {
+	client: &'a GitlabClient<C>,
+}
+
+impl<C> api::RestClient for Lookup<'_, C>
+where
+	C: api::RestClient,
+{
+	type Error = Error<C::Error>;
+
+	fn rest_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<Self::Error>> {
+		self.client.rest_endpoint(endpoint)
+	}
+
+	fn instance_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<Self::Error>> {
+		self.client.instance_endpoint(endpoint)
+	}
+}
+
+impl<C> api::Client for Lookup<'_, C>
+where
+	C: api::Client,
+{
+	fn rest(
+		&self,
+		request: Builder,
+		body: Vec<u8>,
+	) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+		if self.client.lookups && request.method_ref() == Some(&Method::GET) {
+			let fresh =
+				self.client.cache.as_ref().and_then(|cache| {
+					cache.get_fresh(&self.client.cache_key(&request), LOOKUP_TTL)
+				});
+			if let Some(rsp) = fresh {
+				return Ok(rsp);
+			}
+		}
+		self.client.rest(request, body)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{is_cacheable, Error, GitlabClient};
+	use crate::{args::ColorChoice, config::ApiToken, connection::Connection};
+	use gitlab::api::{ApiError, RestClient};
+	use http::Request;
+
+	#[test]
+	fn cacheable_endpoints() {
+		assert!(is_cacheable("projects/group%2Fproject"));
+		assert!(is_cacheable("projects/42/repository/branches/feat%2Fx"));
+		assert!(is_cacheable("projects/42/pipelines/7/jobs"));
+		assert!(!is_cacheable("projects/42/variables"));
+		assert!(!is_cacheable("projects/42/secure_files/1/download"));
+		assert!(!is_cacheable("projects/42/jobs/3/trace"));
+		assert!(!is_cacheable("projects/42/repository/archive.tar.gz"));
+		assert!(!is_cacheable("personal_access_tokens"));
+	}
+
+	#[test]
+	fn cache_keys_depend_on_the_token() {
+		let client = |token: &str| {
+			GitlabClient::<()>::offline("gitlab.example.com", ColorChoice::Never)
+				.token(&ApiToken::Private(token.to_owned()))
+		};
+		let request = || Request::builder().uri("https://gitlab.example.com/api/v4/user");
+		let (alice, bob) = (client("glpat-alice"), client("glpat-bob"));
+		assert_eq!(alice.cache_key(&request()), alice.cache_key(&request()));
+		assert_ne!(alice.cache_key(&request()), bob.cache_key(&request()));
+		// the secret itself is not part of the key
+		assert!(!alice.cache_key(&request()).contains("glpat-alice"));
+	}
+
+	#[test]
+	fn logs_are_never_read_offline() {
+		let client = GitlabClient::<Connection>::offline("gitlab.example.com", ColorChoice::Never);
+		let uri = client.rest_endpoint("projects/42/jobs/3/trace").unwrap();
+		let request = Request::builder().uri(uri.as_str());
+		let err = gitlab::api::Client::rest(&client, request, Vec::new()).unwrap_err();
+		assert!(matches!(
+			err,
+			ApiError::Client {
+				source: Error::NeverCached { .. }
+			}
+		));
+	}
+}
@@ -1,8 +1,35 @@
+pub mod admin;
+pub mod api;
 pub mod archive;
+pub mod board;
 pub mod branches;
+pub mod clone;
+pub mod commit;
+pub mod dashboard;
+pub mod env;
+pub mod errors;
+pub mod group;
+pub mod hook;
+pub mod issue;
+pub mod iteration;
+pub mod job;
 pub mod keys;
+pub mod mirror;
+pub mod mr;
 pub mod pipeline;
+pub mod plugin;
 pub mod project;
+pub mod protections;
+pub mod release;
+pub mod secure_file;
+pub mod self_update;
+pub mod servicedesk;
+pub mod status;
+pub mod sync;
 pub mod tags;
+pub mod time;
+pub mod todo;
 pub mod token;
+pub mod trigger;
 pub mod users;
+pub mod variable;
@@ -0,0 +1,215 @@
+use gil::{
+	api::{
+		groups::{DeleteGroup, TransferGroup},
+		hooks::{CreateSystemHook, DeleteSystemHook, SystemHooks, TestSystemHook},
+		license, statistics,
+		users::DeleteUser,
+		version,
+	},
+	args::{self, AdminCmd, AdminGroupCmd, AdminUserCmd, HookEvent, HooksCmd},
+	context::CliContext,
+	types::{ApplicationStatistics, Group, HealthStatus, License, SystemHook, User, Version},
+};
+
+use anyhow::{Context, Result};
+use gitlab::api::{
+	self,
+	common::VisibilityLevel,
+	groups::CreateGroup,
+	users::{CreateUser, NewUserPassword},
+	Pagination, Query,
+};
+use std::process::ExitCode;
+
+/// Manage the system hooks
+fn hooks(args: &args::AdminHooks) -> Result<ExitCode> {
+	match &args.cmd {
+		HooksCmd::List(_) => {
+			let hooks: Vec<SystemHook> = api::paged(SystemHooks::build(), Pagination::All)
+				.query(&CliContext::global().gitlab)
+				.context("Failed to list system hooks")?;
+			CliContext::global().print_system_hooks(&hooks)
+		}
+		HooksCmd::Add(args) => {
+			let endpoint = CreateSystemHook::builder()
+				.url(&args.url)
+				.name(args.name.as_deref())
+				.token(args.token.as_deref())
+				.push_events(args.event.contains(&HookEvent::Push))
+				.tag_push_events(args.event.contains(&HookEvent::TagPush))
+				.merge_requests_events(args.event.contains(&HookEvent::MergeRequests))
+				.repository_update_events(args.event.contains(&HookEvent::RepositoryUpdate))
+				.enable_ssl_verification(!args.insecure)
+				.build()?;
+			let hook: SystemHook = endpoint
+				.query(&CliContext::global().gitlab)
+				.with_context(|| format!("Failed to add system hook {}", &args.url))?;
+			println!("system hook {} added for {}", hook.id, &hook.url);
+			Ok(ExitCode::from(0))
+		}
+		HooksCmd::Delete(args) => {
+			let endpoint = DeleteSystemHook::builder().hook_id(args.id).build()?;
+			api::ignore(endpoint)
+				.query(&CliContext::global().gitlab)
+				.with_context(|| format!("Failed to delete system hook {}", args.id))?;
+			println!("system hook {} has been deleted", args.id);
+			Ok(ExitCode::from(0))
+		}
+		HooksCmd::Test(args) => {
+			let endpoint = TestSystemHook::builder().hook_id(args.id).build()?;
+			api::ignore(endpoint)
+				.query(&CliContext::global().gitlab)
+				.with_context(|| format!("Failed to test system hook {}", args.id))?;
+			println!("test event sent to system hook {}", args.id);
+			Ok(ExitCode::from(0))
+		}
+	}
+}
+
+/// Create and delete users
+fn user(args: &args::AdminUser) -> Result<ExitCode> {
+	match &args.cmd {
+		AdminUserCmd::Create(args) => {
+			let password = if args.reset_password {
+				NewUserPassword::ResetRandom
+			} else {
+				NewUserPassword::Random
+			};
+			let endpoint = CreateUser::builder()
+				.username(args.username.as_str())
+				.name(args.name.as_deref().unwrap_or(&args.username))
+				.email(args.email.as_str())
+				.password(password)
+				.admin(args.admin)
+				.skip_confirmation(args.skip_confirmation)
+				.build()?;
+			let user: User = endpoint
+				.query(&CliContext::global().gitlab)
+				.with_context(|| format!("Failed to create user {}", &args.username))?;
+			println!("user {} ({}) created", &user.username, user.id.value());
+			Ok(ExitCode::from(0))
+		}
+		AdminUserCmd::Delete(args) => {
+			let user = CliContext::global().get_user(Some(&args.username))?;
+			let question = if args.hard {
+				format!("Delete user {} and all its contributions?", &user.username)
+			} else {
+				format!("Delete user {}?", &user.username)
+			};
+			if !args.yes && !CliContext::global().prompt.confirm(&question)? {
+				return Ok(ExitCode::from(1));
+			}
+			let endpoint = DeleteUser::builder()
+				.user_id(user.id.value())
+				.hard_delete(args.hard)
+				.build()?;
+			api::ignore(endpoint)
+				.query(&CliContext::global().gitlab)
+				.with_context(|| format!("Failed to delete user {}", &user.username))?;
+			println!("user {} has been deleted", &user.username);
+			Ok(ExitCode::from(0))
+		}
+	}
+}
+
+/// Create, delete and move groups
+fn group(args: &args::AdminGroup) -> Result<ExitCode> {
+	match &args.cmd {
+		AdminGroupCmd::Create(args) => {
+			let mut builder = CreateGroup::builder();
+			builder
+				.path(args.path.as_str())
+				.name(args.name.as_deref().unwrap_or(&args.path))
+				.visibility(args.visibility.map_or(VisibilityLevel::Private, |v| v.0));
+			if let Some(parent) = &args.parent {
+				builder.parent_id(CliContext::global().get_group(parent)?.id);
+			}
+			if let Some(description) = &args.description {
+				builder.description(description.as_str());
+			}
+			let group: Group = builder
+				.build()?
+				.query(&CliContext::global().gitlab)
+				.with_context(|| format!("Failed to create group {}", &args.path))?;
+			println!("group {} ({}) created", &group.full_path, group.id);
+			Ok(ExitCode::from(0))
+		}
+		AdminGroupCmd::Delete(args) => {
+			let group = CliContext::global().get_group(&args.group)?;
+			let question = format!(
+				"Delete group {} with all its subgroups and projects?",
+				&group.full_path
+			);
+			if !args.yes && !CliContext::global().prompt.confirm(&question)? {
+				return Ok(ExitCode::from(1));
+			}
+			let endpoint = DeleteGroup::builder().group(group.id).build()?;
+			api::ignore(endpoint)
+				.query(&CliContext::global().gitlab)
+				.with_context(|| format!("Failed to delete group {}", &group.full_path))?;
+			// deletion is delayed on instances with delayed deletion enabled
+			println!("group {} scheduled for deletion", &group.full_path);
+			Ok(ExitCode::from(0))
+		}
+		AdminGroupCmd::Transfer(args) => {
+			let group = CliContext::global().get_group(&args.group)?;
+			let parent = args
+				.parent
+				.as_ref()
+				.map(|parent| CliContext::global().get_group(parent))
+				.transpose()?;
+			let endpoint = TransferGroup::builder()
+				.group(group.id)
+				.parent(parent.as_ref().map(|parent| parent.id))
+				.build()?;
+			let moved: Group = endpoint
+				.query(&CliContext::global().gitlab)
+				.with_context(|| format!("Failed to transfer group {}", &group.full_path))?;
+			println!("group {} moved to {}", &group.full_path, &moved.full_path);
+			Ok(ExitCode::from(0))
+		}
+	}
+}
+
+pub fn cmd(args: &args::Admin) -> Result<ExitCode> {
+	// the health probes don't need an admin token
+	if !matches!(args.cmd, AdminCmd::Health(_)) {
+		CliContext::global().require_admin()?;
+	}
+	match &args.cmd {
+		AdminCmd::Hooks(args) => hooks(args),
+		AdminCmd::License(_) => {
+			// self-managed instances without license answer null
+			let license: Option<License> = license::License::build()
+				.query(&CliContext::global().gitlab)
+				.context("Failed to get the license")?;
+			CliContext::global().print_license(license.as_ref())
+		}
+		AdminCmd::Stats(_) => {
+			let stats: ApplicationStatistics = statistics::ApplicationStatistics::build()
+				.query(&CliContext::global().gitlab)
+				.context("Failed to get the instance statistics")?;
+			CliContext::global().print_statistics(&stats)
+		}
+		AdminCmd::User(args) => user(args),
+		AdminCmd::Group(args) => group(args),
+		AdminCmd::Health(_) => {
+			let version: Result<Version> = version::Version::build()
+				.query(&CliContext::global().gitlab)
+				.context("Failed to get the version");
+			let probes = [
+				("liveness", CliContext::global().get_health("liveness")),
+				(
+					"readiness",
+					CliContext::global().get_health("readiness?all=1"),
+				),
+			];
+			CliContext::global().print_health(&version, &probes)?;
+			let healthy = version.is_ok()
+				&& probes
+					.iter()
+					.all(|(_, probe)| probe.as_ref().is_ok_and(HealthStatus::is_healthy));
+			Ok(ExitCode::from(if healthy { 0 } else { 1 }))
+		}
+	}
+}
@@ -0,0 +1,64 @@
+use gil::{
+	api::request::Request,
+	args::{self, KeyValue},
+	context::CliContext,
+};
+
+use anyhow::{bail, Context, Result};
+use gitlab::api::{self, Query};
+use reqwest::Method;
+use std::{
+	fs,
+	io::{self, Read, Write},
+	process::ExitCode,
+};
+
+/// Read a request body given as @file, or @- for the standard input
+fn read_body(spec: &str) -> Result<Vec<u8>> {
+	let Some(path) = spec.strip_prefix('@') else {
+		bail!("Expecting @file or @- for the body, not {}", spec);
+	};
+	if path == "-" {
+		let mut body = Vec::new();
+		io::stdin()
+			.read_to_end(&mut body)
+			.context("Can't read the body from the standard input")?;
+		Ok(body)
+	} else {
+		fs::read(path).with_context(|| format!("Can't read the body from {}", path))
+	}
+}
+
+pub fn cmd(args: &args::Api) -> Result<ExitCode> {
+	let method = args.method.to_uppercase();
+	let method = Method::from_bytes(method.as_bytes())
+		.with_context(|| format!("Invalid http method {}", &args.method))?;
+	// accept the paths copied from the documentation
+	let path = args.path.trim_start_matches('/');
+	let path = path.strip_prefix("api/v4/").unwrap_or(path);
+	let body = args.raw_body.as_deref().map(read_body).transpose()?;
+
+	let endpoint = Request::builder()
+		.method(method.clone())
+		.path(path.to_owned())
+		.params(
+			args.field
+				.iter()
+				.map(|KeyValue(key, value)| (key.to_owned(), value.to_owned()))
+				.collect(),
+		)
+		.body(body)
+		.build()?;
+	let rsp = api::raw(endpoint)
+		.query(&CliContext::global().gitlab)
+		.with_context(|| format!("Failed to {} {}", method, path))?;
+
+	// pretty print the json responses, and the others as is
+	match serde_json::from_slice::<serde_json::Value>(&rsp) {
+		Ok(json) => println!("{}", serde_json::to_string_pretty(&json)?),
+		Err(_) => io::stdout()
+			.write_all(&rsp)
+			.context("Failed to print the response")?,
+	}
+	Ok(ExitCode::from(0))
+}
@@ -1,43 +1,360 @@
-use crate::{
+use gil::{
 	archive::Archive,
-	args::{self, ArchiveCmd},
+	args::{self, ArchiveCmd, ArchiveLockCmd, ColorChoice, LockFormat},
+	color::StyledStr,
 	context::CliContext,
-	lockfile::LockFile,
+	fmt::{Colorizer, Stream},
+	lockfile::{LockEntry, LockFile},
 };
 
-use anyhow::{Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use bytesize::ByteSize;
+use chrono::{SecondsFormat, Utc};
 use flate2::read::GzDecoder;
 use gitlab::api::{self, Query};
 use serde::Deserialize;
+use serde_json::json;
+use sha2::{Digest, Sha256};
 use std::{
+	borrow::Cow,
 	collections::BTreeMap,
-	fs::{create_dir, create_dir_all, remove_dir_all, File},
+	fs::{
+		self, create_dir, create_dir_all, read_dir, remove_dir, remove_dir_all, remove_file, File,
+	},
 	io,
 	ops::Deref,
-	path::PathBuf,
+	path::{Component, Path, PathBuf},
 	process::ExitCode,
 };
 
-fn get_or_create_dir(dir: &str, keep: bool, update: bool, verbose: bool) -> Result<PathBuf> {
+/// Level of detail of the extraction messages
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Verbosity {
+	/// one summary line per project
+	Quiet,
+	/// the extracted projects and the problems
+	Normal,
+	/// every extracted entry
+	Verbose,
+}
+
+/// Print the extraction messages that are relevant to the verbosity level
+struct Output {
+	verbosity: Verbosity,
+	color: ColorChoice,
+}
+
+impl Output {
+	fn new(quiet: bool) -> Self {
+		let verbosity = if quiet {
+			Verbosity::Quiet
+		} else if CliContext::global().verbose {
+			Verbosity::Verbose
+		} else {
+			Verbosity::Normal
+		};
+		Self {
+			verbosity,
+			color: CliContext::global().color,
+		}
+	}
+
+	/// Print a message on stdout if the verbosity is at least the given level
+	fn print(&self, level: Verbosity, msg: StyledStr) -> Result<()> {
+		self.print_to(Stream::Stdout, level, msg)
+	}
+
+	/// Print a message on stderr if the verbosity is at least the given level
+	fn eprint(&self, level: Verbosity, msg: StyledStr) -> Result<()> {
+		self.print_to(Stream::Stderr, level, msg)
+	}
+
+	fn print_to(&self, stream: Stream, level: Verbosity, mut msg: StyledStr) -> Result<()> {
+		if self.verbosity >= level {
+			msg.none("\n");
+			Colorizer::new(stream, self.color)
+				.with_content(msg)
+				.print()
+				.context("Failed to print")?;
+		}
+		Ok(())
+	}
+
+	/// Print an extraction error unless in quiet mode
+	fn error(&self, text: String) -> Result<()> {
+		let mut msg = StyledStr::new();
+		msg.error(text);
+		self.eprint(Verbosity::Normal, msg)
+	}
+
+	/// Print an extracted entry in verbose mode
+	fn entry(&self, path: &Path, size: Option<u64>) -> Result<()> {
+		let mut msg = StyledStr::new();
+		msg.none(format!("  {}", path.to_string_lossy()));
+		if let Some(size) = size {
+			msg.hint(format!(" ({})", ByteSize(size)));
+		}
+		self.print(Verbosity::Verbose, msg)
+	}
+}
+
+/// What was extracted from an archive
+#[derive(Debug, Default)]
+struct ExtractStats {
+	files: usize,
+	dirs: usize,
+	bytes: u64,
+	/// entries that couldn't be extracted or were ignored
+	errors: usize,
+}
+
+/// Format the version of a project as its ref (if any) and commit
+fn msg_version(msg: &mut StyledStr, project: &str, ref_: Option<&str>, commit: &str) {
+	msg.literal(project);
+	msg.none(" ");
+	if let Some(ref_) = ref_ {
+		msg.none(format!("{} ", ref_));
+	}
+	msg.hint(format!("({})", commit.get(..8).unwrap_or(commit)));
+}
+
+/// Returns true if a version is a full commit sha (sha1 or sha256) rather than a tag
+fn is_commit_sha(version: &str) -> bool {
+	matches!(version.len(), 40 | 64) && version.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+fn get_or_create_dir(dir: &str, keep: bool, update: bool, out: &Output) -> Result<PathBuf> {
 	let path = PathBuf::from(dir);
 	// remove destination dir if requested
 	if !keep && !update && path.exists() {
 		remove_dir_all(&path).with_context(|| format!("Can't remove dir {}", dir))?;
-		if verbose {
-			println!("{} removed", &dir)
-		}
+		let mut msg = StyledStr::new();
+		msg.none(format!("{} removed", dir));
+		out.print(Verbosity::Verbose, msg)?;
 	}
 	// create destination dir if necessary
 	if !path.exists() {
 		create_dir_all(&path).with_context(|| format!("Can't create dir {}", dir))?;
-		if verbose {
-			println!("Creating dir {}", &dir);
-		}
+		let mut msg = StyledStr::new();
+		msg.none(format!("Creating dir {}", dir));
+		out.print(Verbosity::Verbose, msg)?;
 	}
 	Ok(path)
 }
 
+/// Strip leading components from an archive entry path and check that the remaining relative
+/// path stays inside the extraction directory. Returns None for an empty path
+fn sanitize_path(path: &Path, strip: usize) -> Result<Option<PathBuf>> {
+	let mut sanitized = PathBuf::new();
+	let components = path.components().filter(|c| *c != Component::CurDir);
+	for component in components.skip(strip) {
+		match component {
+			Component::Normal(name) => sanitized.push(name),
+			Component::CurDir => (),
+			Component::RootDir | Component::Prefix(_) => {
+				bail!("Absolute path {:?} refused", path)
+			}
+			Component::ParentDir => bail!("Path {:?} outside of the archive refused", path),
+		}
+	}
+	Ok(Some(sanitized).filter(|path| !path.as_os_str().is_empty()))
+}
+
+/// Extract a gzipped tar archive to a directory, stripping leading path components
+fn extract(targz: &[u8], dir: &Path, strip: usize, out: &Output) -> Result<ExtractStats> {
+	// chain gzip reader and arquive reader
+	let tar = GzDecoder::new(targz);
+	let mut arquive = tar::Archive::new(tar);
+	let mut stats = ExtractStats::default();
+
+	// for each entry in the arquive
+	for entry in arquive.entries()? {
+		let mut entry = match entry {
+			Ok(entry) => entry,
+			Err(err) => {
+				stats.errors += 1;
+				out.error(format!("  Can't get archive entry: {:?}", &err))?;
+				continue;
+			}
+		};
+
+		// strip leading path components and refuse to write outside of the directory
+		let entry_path = match sanitize_path(&entry.path()?, strip) {
+			Ok(Some(entry_path)) => dir.join(entry_path),
+			// don't do anything if empty path
+			Ok(None) => continue,
+			Err(err) => {
+				stats.errors += 1;
+				out.error(format!("  {}", err))?;
+				continue;
+			}
+		};
+
+		// get the entry type
+		let file_type = entry.header().entry_type();
+		match file_type {
+			// if it's a directory, create it if doesn't exist
+			tar::EntryType::Directory => {
+				if !entry_path.exists() {
+					match create_dir(&entry_path) {
+						Ok(()) => {
+							stats.dirs += 1;
+							out.entry(&entry_path, None)?;
+						}
+						Err(err) => {
+							stats.errors += 1;
+							out.error(format!(
+								"  Can't create dir {}: {:?}",
+								&entry_path.to_string_lossy(),
+								&err
+							))?;
+							continue;
+						}
+					}
+				}
+			}
+
+			// if it's a file, extract it to local filesystem
+			tar::EntryType::Regular => {
+				let mut file = match File::create(&entry_path) {
+					Ok(file) => file,
+					Err(err) => {
+						stats.errors += 1;
+						out.error(format!(
+							"  Can't create file {}: {:?}",
+							&entry_path.to_string_lossy(),
+							&err
+						))?;
+						continue;
+					}
+				};
+				match io::copy(&mut entry, &mut file) {
+					Ok(size) => {
+						stats.files += 1;
+						stats.bytes += size;
+						out.entry(&entry_path, Some(size))?;
+					}
+					Err(err) => {
+						stats.errors += 1;
+						out.error(format!(
+							"  Can't extract {}: {:?}",
+							&entry_path.to_string_lossy(),
+							&err
+						))?;
+						continue;
+					}
+				}
+			}
+			// metadata of the archive (git archive stores the commit in a pax global header)
+			tar::EntryType::XGlobalHeader | tar::EntryType::XHeader => continue,
+			// links are not extracted, so they count as errors that --strict reports
+			tar::EntryType::Symlink | tar::EntryType::Link => {
+				stats.errors += 1;
+				out.error(format!(
+					"  {} (link) not supported",
+					&entry_path.to_string_lossy()
+				))?;
+				continue;
+			}
+			_ => {
+				stats.errors += 1;
+				out.error(format!(
+					"  {} ({:?}) ignored",
+					&entry_path.to_string_lossy(),
+					&file_type
+				))?;
+				continue;
+			}
+		}
+	}
+	Ok(stats)
+}
+
+/// Move the entries of a directory into another one, merging the directories found on both sides
+/// and replacing the files, so that the entries already there (from other projects) are kept
+fn merge_dir(from: &Path, to: &Path) -> Result<()> {
+	for entry in read_dir(from).with_context(|| format!("Can't read dir {:?}", from))? {
+		let entry = entry.with_context(|| format!("Can't read dir {:?}", from))?;
+		let (src, dest) = (entry.path(), to.join(entry.file_name()));
+		if src.is_dir() && dest.is_dir() {
+			merge_dir(&src, &dest)?;
+			continue;
+		}
+		if dest.is_dir() {
+			remove_dir_all(&dest).with_context(|| format!("Can't remove dir {:?}", &dest))?;
+		} else if dest.exists() {
+			remove_file(&dest).with_context(|| format!("Can't remove file {:?}", &dest))?;
+		}
+		fs::rename(&src, &dest).with_context(|| format!("Can't move {:?} to {:?}", &src, &dest))?;
+	}
+	remove_dir(from).with_context(|| format!("Can't remove dir {:?}", from))
+}
+
+/// Move an extracted archive from its temporary directory to its destination: in rename mode the
+/// directory replaces any previous extraction of the project, otherwise its entries are merged
+/// into the destination directory
+fn move_in_place(tmp_dir: &Path, prj_dir: &Path, rename: bool) -> Result<()> {
+	if !rename {
+		return merge_dir(tmp_dir, tmp_dir.parent().unwrap_or(tmp_dir));
+	}
+	if prj_dir.is_dir() {
+		remove_dir_all(prj_dir).with_context(|| format!("Can't remove dir {:?}", prj_dir))?;
+	} else if prj_dir.exists() {
+		remove_file(prj_dir).with_context(|| format!("Can't remove file {:?}", prj_dir))?;
+	}
+	fs::rename(tmp_dir, prj_dir)
+		.with_context(|| format!("Can't move {:?} to {:?}", tmp_dir, prj_dir))
+}
+
+/// Returns the name of the only entry of a directory if it is a directory
+fn single_dir(dir: &Path) -> Result<Option<String>> {
+	let entries = read_dir(dir)
+		.with_context(|| format!("Can't read dir {:?}", dir))?
+		.collect::<io::Result<Vec<_>>>()
+		.with_context(|| format!("Can't read dir {:?}", dir))?;
+	Ok(match entries.as_slice() {
+		[entry] if entry.path().is_dir() => Some(entry.file_name().to_string_lossy().into_owned()),
+		_ => None,
+	})
+}
+
+/// Returns the last component of a project path, which is the name of its extraction directory
+fn project_name(project: &str) -> &str {
+	match project.rfind('/') {
+		Some(i) if (i + 1) < project.len() => &project[i + 1..],
+		_ => project,
+	}
+}
+
+/// Compute a sha256 checksum of the files of a directory, their relative paths included
+fn content_hash(dir: &Path) -> Result<String> {
+	let mut hasher = Sha256::new();
+	let mut dirs = vec![dir.to_path_buf()];
+	let mut files = Vec::new();
+	while let Some(path) = dirs.pop() {
+		for entry in read_dir(&path).with_context(|| format!("Can't read dir {:?}", &path))? {
+			let path = entry
+				.with_context(|| format!("Can't read dir {:?}", &path))?
+				.path();
+			if path.is_dir() {
+				dirs.push(path);
+			} else {
+				files.push(path);
+			}
+		}
+	}
+	// visit the files in a stable order
+	files.sort();
+	for path in files {
+		let relative = path.strip_prefix(dir).unwrap_or(&path);
+		hasher.update(relative.to_string_lossy().as_bytes());
+		hasher.update([0]);
+		let mut file = File::open(&path).with_context(|| format!("Can't read {:?}", &path))?;
+		io::copy(&mut file, &mut hasher).with_context(|| format!("Can't read {:?}", &path))?;
+	}
+	Ok(hex::encode(hasher.finalize()))
+}
+
 /// Configuration for batch mode (extract sub command)
 #[derive(Deserialize)]
 pub struct BatchConfig(BTreeMap<String, String>);
@@ -69,30 +386,216 @@ impl Deref for BatchConfig {
 	}
 }
 
+/// Extract the archive of a project at a tag or a full commit sha and returns its lock entry, or
+/// None when the extraction was skipped (already extracted)
+fn extract_project(
+	args: &args::ArchiveExtract,
+	project: &str,
+	version: &str,
+	dest_dir: &Path,
+	lock: &BTreeMap<String, LockEntry>,
+	out: &Output,
+) -> Result<Option<(LockEntry, usize)>> {
+	// rename mode is like -s 1 (we remove the first path component) + replace by the project name
+	let strip = if args.rename { 1 } else { args.strip };
+	// check if a dir with the name of the project already exists
+	// this works reliably only in rename mode (-r)
+	let prj_dir = dest_dir.join(project_name(project));
+	let is_extracted = prj_dir.exists();
+
+	// don't overwrite if we were asked to keep.
+	if args.keep && is_extracted {
+		let mut msg = StyledStr::new();
+		msg.literal(project);
+		msg.none(" already extracted");
+		out.print(Verbosity::Quiet, msg)?;
+		// if no entry in lockfile in update mode, there is no garantee that we
+		// have an extraction of the right version
+		if args.update && !lock.contains_key(project) {
+			let mut msg = StyledStr::new();
+			msg.warning(
+				"We couldn't find any entry in the lockfile. Remove or run without -k to overwrite.",
+			);
+			out.eprint(Verbosity::Normal, msg)?;
+		}
+		return Ok(None);
+	}
+
+	// a commit sha is taken as is, otherwise resolve the tag to its commit
+	let (tag, target) = if is_commit_sha(version) {
+		(None, version.to_lowercase())
+	} else {
+		let prj = CliContext::global().get_project(Some(project))?;
+		let tag = CliContext::global().get_tag(Some(version), &prj)?;
+		let target = tag.commit.id.value().to_owned();
+		(Some(tag.name), target)
+	};
+	let tag = tag.as_deref();
+	// get locked_commit or tag commit
+	let mut found = false;
+	let mut commit = match lock.get(project) {
+		Some(entry) => {
+			found = true;
+			&entry.commit
+		}
+		None => &target,
+	};
+
+	if args.update && is_extracted {
+		// skip if extracted and locked commit match
+		if found && *commit == target {
+			let mut msg = StyledStr::new();
+			msg_version(&mut msg, project, tag, commit);
+			msg.none(" already extracted");
+			out.print(Verbosity::Quiet, msg)?;
+			return Ok(None);
+		} else {
+			// issue a warning when version mismatch before overwriting
+			if *commit != target {
+				let mut msg = StyledStr::new();
+				msg.warning(format!(
+					"Extracted commit {} and {} commit {} mismatch",
+					&commit[..8],
+					tag.unwrap_or("requested"),
+					&target[..8]
+				));
+				out.eprint(Verbosity::Normal, msg)?;
+			}
+			// the project dir is replaced once the new version is extracted
+			commit = &target;
+		}
+	}
+
+	// extract to a temporary sibling directory first, so that an interrupted extraction
+	// never leaves a partial project directory behind
+	let tmp_dir = dest_dir.join(format!(".{}.partial", project_name(project)));
+	if tmp_dir.exists() {
+		remove_dir_all(&tmp_dir).with_context(|| format!("Can't remove dir {:?}", &tmp_dir))?;
+	}
+	create_dir_all(&tmp_dir).with_context(|| format!("Can't create dir {:?}", &tmp_dir))?;
+
+	let endpoint = Archive::builder().project(project).sha(commit).build()?;
+
+	// NOTE: api::raw returns a vec<u8>. It would be
+	// more memory efficient to return the rewest::Response to read
+	// from a stream instead
+	let res = api::raw(endpoint)
+		.query(&CliContext::global().gitlab)
+		.context("Failed to download the archive")
+		.and_then(|targz| {
+			let mut msg = StyledStr::new();
+			msg.none("Extracting ");
+			msg_version(&mut msg, project, tag, commit);
+			out.print(Verbosity::Normal, msg)?;
+			let stats = extract(&targz, &tmp_dir, strip, out)?;
+			// without stripping, the archive has its own top directory (project-ref-sha)
+			let dir = if args.rename {
+				Some(project_name(project).to_owned())
+			} else if strip == 0 {
+				single_dir(&tmp_dir)?
+			} else {
+				None
+			};
+			Ok((hex::encode(Sha256::digest(&targz)), stats, dir))
+		})
+		.and_then(|res| move_in_place(&tmp_dir, &prj_dir, args.rename).map(|_| res));
+	if res.is_err() {
+		let _ = remove_dir_all(&tmp_dir);
+	}
+	let (sha256, stats, dir) = res.with_context(|| format!("Failed to extract {}", &project))?;
+	if out.verbosity == Verbosity::Quiet {
+		let mut msg = StyledStr::new();
+		msg_version(&mut msg, project, tag, commit);
+		msg.none(format!(
+			": {} file(s), {} dir(s), {}",
+			stats.files,
+			stats.dirs,
+			ByteSize(stats.bytes)
+		));
+		if stats.errors > 0 {
+			msg.none(", ");
+			msg.error(format!("{} error(s)", stats.errors));
+		}
+		out.print(Verbosity::Quiet, msg)?;
+	}
+	// the extracted files can only be found back in their own directory
+	let content = dir
+		.as_ref()
+		.map(|dir| content_hash(&dest_dir.join(dir)))
+		.transpose()?;
+
+	let entry = LockEntry {
+		commit: commit.to_owned(),
+		tag: tag.map(ToOwned::to_owned),
+		sha256: Some(sha256),
+		content,
+		dir,
+	};
+	Ok(Some((entry, stats.errors)))
+}
+
+/// Summary of the archives that failed to extract
+fn failure_summary(failures: &[(&String, anyhow::Error)], total: usize) -> StyledStr {
+	let mut msg = StyledStr::new();
+	msg.error(format!(
+		"{} of {} archive(s) failed to extract:",
+		failures.len(),
+		total
+	));
+	for (project, err) in failures {
+		msg.none("\n- ");
+		msg.literal(*project);
+		msg.none(format!(": {:#}", err));
+	}
+	msg
+}
+
+/// Print the failures if any, and return the exit code of the extraction
+fn report_failures(
+	failures: &[(&String, anyhow::Error)],
+	total: usize,
+	out: &Output,
+) -> Result<ExitCode> {
+	if failures.is_empty() {
+		return Ok(ExitCode::from(0));
+	}
+	out.eprint(Verbosity::Quiet, failure_summary(failures, total))?;
+	Ok(ExitCode::from(1))
+}
+
 /// Command implementaton
 pub fn cmd(args: &args::Archive) -> Result<ExitCode> {
 	match &args.cmd {
 		ArchiveCmd::Extract(args) => {
-			// rename mode is like -s 1 (we remove the first path component) + replace by the project name
-			let strip = if args.rename { 1 } else { args.strip };
 			// determine the list of project/tag to extract
 			let batch = if let Some(config) = &args.batch {
+				if args.commit.is_some() {
+					bail!(
+						"--commit can't be used in batch mode, put the commit sha in {}",
+						config
+					);
+				}
 				// in batch mode, we read from a file
 				BatchConfig::from_file(config)?
 			} else {
 				// in command line we extract only 1 project given from command line arguments
 				let project = CliContext::global().get_project(args.project.as_ref())?;
-				let ref_ = CliContext::global().check_ref(args.ref_.as_deref(), &project)?;
-				BatchConfig::singleton(project.path_with_namespace, ref_)
+				let version = if let Some(sha) = &args.commit {
+					if args.ref_.is_some() {
+						bail!("Give either a reference or a commit, not both");
+					}
+					// the commit is looked up to get its full sha
+					let commit = CliContext::global().get_commit(sha, &project)?;
+					commit.id.value().to_owned()
+				} else {
+					CliContext::global().check_ref(args.ref_.as_deref(), &project)?
+				};
+				BatchConfig::singleton(project.path_with_namespace, version)
 			};
 
+			let out = Output::new(args.quiet);
 			// create the dest directory
-			let dest_dir = get_or_create_dir(
-				&args.dir,
-				args.keep,
-				args.update,
-				CliContext::global().verbose,
-			)?;
+			let dest_dir = get_or_create_dir(&args.dir, args.keep, args.update, &out)?;
 			// open lock file (update mode)
 			let lock_name = if let Some(batch) = &args.batch {
 				batch
@@ -102,186 +605,392 @@ pub fn cmd(args: &args::Archive) -> Result<ExitCode> {
 			let mut lock = LockFile::open(lock_name)?;
 
 			// extract all rchives to specified directory
+			let mut failures = Vec::new();
 			for (project, tag) in batch.iter() {
-				// check if a dir with the name of the project already exists
-				// this works reliably only in rename mode (-r)
-				let i = match project.rfind('/') {
-					Some(i) if (i + 1) < project.len() => i + 1,
-					_ => 0,
-				};
-				let prj_dir = dest_dir.join(&project[i..]);
-				let is_extracted = prj_dir.exists();
-
-				// don't overwrite if we were asked to keep.
-				if args.keep && is_extracted {
-					println!("{} already extracted", &project);
-					// if no entry in lockfile in update mode, there is no garantee that we
-					// have an extraction of the right version
-					if args.update && !lock.contains_key(project) {
-						eprintln!("We couldn't find any entry in the lockfile\n.Remove or run without -k to overwrite.",)
-					}
-					continue;
-				}
-
-				let prj = CliContext::global().get_project(Some(project.as_str()))?;
-				let tag = CliContext::global().get_tag(Some(tag), &prj)?;
-				// get locked_commit or tag commit
-				let mut found = false;
-				let mut commit = match lock.get(project) {
-					Some(commit) => {
-						found = true;
-						commit
-					}
-					None => tag.commit.id.value(),
-				};
-
-				if args.update && is_extracted {
-					// skip if extracted and locked commit match
-					if found && commit == tag.commit.id.value() {
-						println!(
-							"{} {} ({}) already extracted",
-							project,
-							tag.name,
-							&commit[..8]
-						);
-						continue;
-					} else {
-						// issue a warning when version mismatch before overwriting
-						if commit != tag.commit.id.value() {
-							eprintln!(
-								"Extracted commit {} and {} commit {} mismatch",
-								&commit[..8],
-								&tag.name,
-								&tag.commit.short_id.value()
-							);
+				match extract_project(args, project, tag, &dest_dir, &lock, &out) {
+					Ok(Some((entry, errors))) => {
+						lock.insert(project.to_owned(), entry);
+						// the extraction is kept, but the missing entries are a failure
+						if args.strict && errors > 0 {
+							let err = anyhow!("{} entries couldn't be extracted", errors);
+							failures.push((project, err));
 						}
-						// remove project dir before update
-						remove_dir_all(&prj_dir)
-							.with_context(|| format!("Can't remove dir {:?}", &prj_dir))?;
-						commit = tag.commit.id.value();
 					}
+					Ok(None) => (),
+					// in strict mode, go on with the other projects and fail at the end
+					Err(err) if args.strict => failures.push((project, err)),
+					Err(err) => return Err(err),
 				}
+			}
+			lock.save(args.update)?;
+			report_failures(&failures, batch.len(), &out)
+		}
 
-				// create the top level dir when it is to be renamed after the project
-				if args.rename {
-					create_dir_all(&prj_dir)
-						.with_context(|| format!("Can't create dir {:?}", &prj_dir))?;
-				}
+		ArchiveCmd::Lock(args) => match &args.cmd {
+			ArchiveLockCmd::Export(args) => lock_export(args),
+		},
 
-				let endpoint = Archive::builder()
-					.project(project.as_str())
-					.sha(commit)
-					.build()?;
-
-				// NOTE: api::raw returns a vec<u8>. It would be
-				// more memory efficient to return the rewest::Response to read
-				// from a stream instead
-				let targz = api::raw(endpoint).query(&CliContext::global().gitlab)?;
-
-				println!("Extracting {} {} ({})", &project, &tag.name, &commit[..8]);
-				// chain gzip reader and arquive reader. turn vec<u8> to a slice
-				// to be able to io::Read from it
-				let tar = GzDecoder::new(targz.as_slice());
-				let mut arquive = tar::Archive::new(tar);
-
-				// for each entry in the arquive
-				for entry in arquive.entries()? {
-					let mut entry = match entry {
-						Ok(entry) => entry,
-						Err(err) => {
-							eprintln!("	 Can't get {} archive entry: {:?}", &project, &err);
-							continue;
-						}
-					};
+		ArchiveCmd::Verify(args) => verify(args),
+	}
+}
 
-					// strip leading path components if necessary
-					let entry_path: PathBuf = entry.path()?.components().skip(strip).collect();
-					// don't do anything if empty path
-					if entry_path.to_str().filter(|s| s.is_empty()).is_some() {
-						continue;
-					}
+/// Check that the archives of the lock file are extracted and, on demand, unchanged
+fn verify(args: &args::ArchiveVerify) -> Result<ExitCode> {
+	let host = &CliContext::global().repo.host;
+	let entries = LockFile::read(args.batch.as_ref().unwrap_or(host))?;
+	let dest_dir = Path::new(&args.dir);
+	let out = Output::new(false);
+	let mut drifts = 0;
+	for (project, entry) in &entries {
+		let mut msg = StyledStr::new();
+		msg_version(&mut msg, project, entry.tag.as_deref(), &entry.commit);
+		msg.none(" ");
+		// lock files written before the directory was recorded only knew the rename mode (-r)
+		let prj_dir = match &entry.dir {
+			Some(dir) => Some(dest_dir.join(dir)),
+			None => Some(dest_dir.join(project_name(project))).filter(|dir| dir.is_dir()),
+		};
+		let Some(prj_dir) = prj_dir else {
+			msg.warning("not verifiable (extracted without a directory of its own, use -r)");
+			out.print(Verbosity::Normal, msg)?;
+			continue;
+		};
+		if !prj_dir.is_dir() {
+			drifts += 1;
+			msg.error("missing");
+			out.eprint(Verbosity::Quiet, msg)?;
+			continue;
+		}
+		match &entry.content {
+			_ if !args.content => msg.good("ok"),
+			Some(content) if *content == content_hash(&prj_dir)? => msg.good("ok"),
+			Some(_) => {
+				drifts += 1;
+				msg.error("modified");
+				out.eprint(Verbosity::Quiet, msg)?;
+				continue;
+			}
+			None => msg.warning("not verifiable (no content checksum in the lock file)"),
+		}
+		out.print(Verbosity::Normal, msg)?;
+	}
+	if drifts == 0 {
+		return Ok(ExitCode::from(0));
+	}
+	let mut msg = StyledStr::new();
+	msg.error(format!(
+		"{} of {} archive(s) drifted from the lock file",
+		drifts,
+		entries.len()
+	));
+	out.eprint(Verbosity::Quiet, msg)?;
+	Ok(ExitCode::from(1))
+}
 
-					// append project dir in rename mode otherwise append destination dir
-					let entry_path = if args.rename {
-						prj_dir.join(entry_path)
-					} else {
-						dest_dir.join(entry_path)
-					};
-
-					// get the entry type
-					let file_type = entry.header().entry_type();
-					match file_type {
-						// if it's a directory, create it if doesn't exist
-						tar::EntryType::Directory => {
-							if !entry_path.exists() {
-								match create_dir(&entry_path) {
-									Ok(()) => {
-										if CliContext::global().verbose {
-											println!("	{}", &entry_path.to_string_lossy());
-										}
-									}
-									Err(err) => {
-										eprintln!(
-											"  Can't create dir {}: {:?}",
-											&entry_path.to_string_lossy(),
-											&err
-										);
-										continue;
-									}
-								}
-							}
-						}
+/// Quote a csv field if necessary
+fn csv_field(field: &str) -> Cow<'_, str> {
+	if field.contains([',', '"', '\n', '\r']) {
+		format!("\"{}\"", field.replace('"', "\"\"")).into()
+	} else {
+		field.into()
+	}
+}
 
-						// if it's a file, extract it to local filesystem
-						tar::EntryType::Regular => {
-							let mut file = match File::create(&entry_path) {
-								Ok(file) => file,
-								Err(err) => {
-									eprintln!(
-										"  Can't create file {}: {:?}",
-										&entry_path.to_string_lossy(),
-										&err
-									);
-									continue;
-								}
-							};
-							match io::copy(&mut entry, &mut file) {
-								Ok(size) => {
-									if CliContext::global().verbose {
-										println!(
-											"  {} ({})",
-											&entry_path.to_string_lossy(),
-											ByteSize(size)
-										);
-									}
-								}
-								Err(err) => {
-									eprintln!(
-										"  Can't extract {}: {:?}",
-										&entry_path.to_string_lossy(),
-										&err
-									);
-									continue;
-								}
-							}
-						}
-						// TODO: support other types (links)
-						_ => {
-							eprintln!(
-								"  {} ({:?}) ignored",
-								&entry_path.to_string_lossy(),
-								&file_type
-							);
-							continue;
-						}
+/// Print the lock file of a batch or of the host as a dependency manifest
+fn lock_export(args: &args::ArchiveLockExport) -> Result<ExitCode> {
+	let host = &CliContext::global().repo.host;
+	let entries = LockFile::read(args.batch.as_ref().unwrap_or(host))?;
+	match args.format {
+		LockFormat::Csv => {
+			println!("project,tag,commit,sha256");
+			for (project, entry) in &entries {
+				println!(
+					"{},{},{},{}",
+					csv_field(project),
+					csv_field(entry.tag.as_deref().unwrap_or_default()),
+					csv_field(&entry.commit),
+					csv_field(entry.sha256.as_deref().unwrap_or_default())
+				);
+			}
+		}
+		LockFormat::CycloneDx => {
+			let components: Vec<_> = entries
+				.iter()
+				.map(|(project, entry)| {
+					let (group, name) = project.rsplit_once('/').unwrap_or(("", project));
+					let url = format!("https://{}/{}", host, project);
+					let mut component = json!({
+						"type": "library",
+						"bom-ref": format!("{}@{}", &url, &entry.commit),
+						"name": name,
+						"version": entry.tag.as_ref().unwrap_or(&entry.commit),
+						"purl": format!(
+							"pkg:generic/{}@{}?vcs_url=git%2Bhttps://{}/{}.git%40{}",
+							project, &entry.commit, host, project, &entry.commit
+						),
+						"externalReferences": [{ "type": "vcs", "url": url }],
+						"properties": [{ "name": "gil:commit", "value": &entry.commit }],
+					});
+					if !group.is_empty() {
+						component["group"] = group.into();
 					}
-				}
+					if let Some(sha256) = &entry.sha256 {
+						component["hashes"] = json!([{ "alg": "SHA-256", "content": sha256 }]);
+					}
+					component
+				})
+				.collect();
+			let bom = json!({
+				"bomFormat": "CycloneDX",
+				"specVersion": "1.5",
+				"version": 1,
+				"metadata": {
+					"timestamp": Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+					"tools": [{ "name": "gil", "version": env!("CARGO_PKG_VERSION") }],
+				},
+				"components": components,
+			});
+			println!("{}", serde_json::to_string_pretty(&bom)?);
+		}
+	}
+	Ok(ExitCode::from(0))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		content_hash, csv_field, extract, failure_summary, is_commit_sha, move_in_place,
+		project_name, report_failures, sanitize_path, single_dir, Output, Verbosity,
+	};
+	use anyhow::anyhow;
+	use flate2::{write::GzEncoder, Compression};
+	use gil::args::ColorChoice;
+	use std::{
+		fs,
+		path::{Path, PathBuf},
+		process::ExitCode,
+	};
 
-				*lock.entry(project.to_owned()).or_default() = commit.to_owned();
+	/// Build a gzipped tar archive from paths (directories end with /) and contents
+	fn targz(entries: &[(&str, &str)]) -> Vec<u8> {
+		let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::fast()));
+		for (path, content) in entries {
+			let mut header = tar::Header::new_gnu();
+			if path.ends_with('/') {
+				header.set_entry_type(tar::EntryType::Directory);
 			}
-			lock.save(args.update)?;
+			header.set_size(content.len() as u64);
+			builder
+				.append_data(&mut header, path, content.as_bytes())
+				.unwrap();
+		}
+		builder.into_inner().unwrap().finish().unwrap()
+	}
 
-			Ok(ExitCode::from(0))
+	fn sanitize(path: &str, strip: usize) -> Option<PathBuf> {
+		sanitize_path(Path::new(path), strip).unwrap()
+	}
+
+	#[test]
+	fn strip_components() {
+		assert_eq!(
+			sanitize("prj-v1/src/main.rs", 0),
+			Some("prj-v1/src/main.rs".into())
+		);
+		assert_eq!(
+			sanitize("prj-v1/src/main.rs", 1),
+			Some("src/main.rs".into())
+		);
+		assert_eq!(sanitize("./prj-v1/./src/", 1), Some("src".into()));
+		assert_eq!(sanitize("prj-v1/", 1), None);
+		assert_eq!(sanitize("prj-v1/src", 3), None);
+	}
+
+	#[test]
+	fn refuse_absolute_paths() {
+		assert!(sanitize_path(Path::new("/etc/passwd"), 0).is_err());
+		assert!(sanitize_path(Path::new("/prj-v1/etc/passwd"), 0).is_err());
+	}
+
+	#[test]
+	fn refuse_parent_dirs() {
+		assert!(sanitize_path(Path::new("../passwd"), 0).is_err());
+		assert!(sanitize_path(Path::new("prj-v1/../../passwd"), 1).is_err());
+		assert!(sanitize_path(Path::new("prj-v1/src/../../../passwd"), 1).is_err());
+	}
+
+	#[test]
+	fn quote_csv_fields() {
+		assert_eq!(csv_field("group/project"), "group/project");
+		assert_eq!(csv_field("a,b"), "\"a,b\"");
+		assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+	}
+
+	#[test]
+	fn detect_commit_shas() {
+		assert!(is_commit_sha("2dc6aa325a317eda67812f05600bdf0fcdc70ab0"));
+		assert!(is_commit_sha("2DC6AA325A317EDA67812F05600BDF0FCDC70AB0"));
+		// short shas can't be told apart from tags
+		assert!(!is_commit_sha("2dc6aa32"));
+		assert!(!is_commit_sha("v1.2.0"));
+		assert!(!is_commit_sha("2dc6aa325a317eda67812f05600bdf0fcdc70abz"));
+	}
+
+	#[test]
+	fn project_names() {
+		assert_eq!(project_name("group/sub/project"), "project");
+		assert_eq!(project_name("project"), "project");
+		assert_eq!(project_name("group/"), "group/");
+	}
+
+	#[test]
+	fn detect_content_changes() {
+		let dir = std::env::temp_dir().join(format!("gil-content-{}", std::process::id()));
+		fs::create_dir_all(dir.join("src")).unwrap();
+		fs::write(dir.join("src/main.rs"), "fn main() {}").unwrap();
+		fs::write(dir.join("README.md"), "# project").unwrap();
+		let hash = content_hash(&dir).unwrap();
+		assert_eq!(content_hash(&dir).unwrap(), hash);
+		fs::write(dir.join("src/main.rs"), "fn main() { }").unwrap();
+		let modified = content_hash(&dir).unwrap();
+		assert_ne!(modified, hash);
+		// moving content to another file is a change as well
+		fs::rename(dir.join("src/main.rs"), dir.join("src/lib.rs")).unwrap();
+		assert_ne!(content_hash(&dir).unwrap(), modified);
+		fs::remove_dir_all(&dir).unwrap();
+	}
+
+	#[test]
+	fn count_extracted_entries() {
+		let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::fast()));
+		let mut header = tar::Header::new_gnu();
+		header.set_entry_type(tar::EntryType::Directory);
+		header.set_size(0);
+		builder
+			.append_data(&mut header, "prj-v1/src/", &[][..])
+			.unwrap();
+		for (path, content) in [
+			("prj-v1/src/main.rs", "fn main() {}"),
+			("prj-v1/README.md", "#"),
+		] {
+			let mut header = tar::Header::new_gnu();
+			header.set_size(content.len() as u64);
+			builder
+				.append_data(&mut header, path, content.as_bytes())
+				.unwrap();
 		}
+		let mut header = tar::Header::new_gnu();
+		header.set_entry_type(tar::EntryType::Symlink);
+		header.set_size(0);
+		builder
+			.append_link(&mut header, "prj-v1/main.rs", "src/main.rs")
+			.unwrap();
+		let targz = builder.into_inner().unwrap().finish().unwrap();
+
+		let dir = std::env::temp_dir().join(format!("gil-extract-{}", std::process::id()));
+		fs::create_dir_all(&dir).unwrap();
+		let out = Output {
+			verbosity: Verbosity::Quiet,
+			color: ColorChoice::Never,
+		};
+		let stats = extract(&targz, &dir, 1, &out).unwrap();
+		assert_eq!((stats.files, stats.dirs, stats.bytes), (2, 1, 13));
+		// links are not supported yet
+		assert_eq!(stats.errors, 1);
+		assert_eq!(
+			fs::read_to_string(dir.join("src/main.rs")).unwrap(),
+			"fn main() {}"
+		);
+		fs::remove_dir_all(&dir).unwrap();
+	}
+
+	#[test]
+	fn strict_failures() {
+		let out = Output {
+			verbosity: Verbosity::Quiet,
+			color: ColorChoice::Never,
+		};
+		assert_eq!(report_failures(&[], 2, &out).unwrap(), ExitCode::from(0));
+
+		let (first, second) = ("group/first".to_owned(), "group/second".to_owned());
+		let failures = [
+			(&first, anyhow!("1 entries couldn't be extracted")),
+			(&second, anyhow!("Failed to download the archive")),
+		];
+		assert_eq!(
+			failure_summary(&failures, 3).to_string(),
+			"2 of 3 archive(s) failed to extract:\n\
+			 - group/first: 1 entries couldn't be extracted\n\
+			 - group/second: Failed to download the archive"
+		);
+		assert_eq!(
+			report_failures(&failures, 3, &out).unwrap(),
+			ExitCode::from(1)
+		);
+	}
+
+	#[test]
+	fn merge_stripped_archives() {
+		let out = Output {
+			verbosity: Verbosity::Quiet,
+			color: ColorChoice::Never,
+		};
+		let dest = std::env::temp_dir().join(format!("gil-merge-{}", std::process::id()));
+		let archives = [
+			targz(&[
+				("first-v1/", ""),
+				("first-v1/src/", ""),
+				("first-v1/src/main.rs", "fn main() {}"),
+				("first-v1/README.md", "# first"),
+			]),
+			targz(&[
+				("second-v1/", ""),
+				("second-v1/src/", ""),
+				("second-v1/src/lib.rs", "pub fn f() {}"),
+				("second-v1/README.md", "# second"),
+			]),
+		];
+		for (i, archive) in archives.iter().enumerate() {
+			let tmp_dir = dest.join(format!(".prj{}.partial", i));
+			fs::create_dir_all(&tmp_dir).unwrap();
+			extract(archive, &tmp_dir, 1, &out).unwrap();
+			move_in_place(&tmp_dir, &dest.join("unused"), false).unwrap();
+		}
+		// the entries of the first archive are kept, the common files replaced
+		assert!(dest.join("src/main.rs").is_file());
+		assert!(dest.join("src/lib.rs").is_file());
+		assert_eq!(
+			fs::read_to_string(dest.join("README.md")).unwrap(),
+			"# second"
+		);
+		assert!(!dest.join(".prj0.partial").exists());
+		fs::remove_dir_all(&dest).unwrap();
+	}
+
+	#[test]
+	fn extract_git_archive() {
+		let out = Output {
+			verbosity: Verbosity::Quiet,
+			color: ColorChoice::Never,
+		};
+		// made with git archive, which starts with a pax global header holding the commit
+		let targz = include_bytes!("../../tests/fixtures/archive.tar.gz");
+		let dir = std::env::temp_dir().join(format!("gil-git-archive-{}", std::process::id()));
+		fs::create_dir_all(&dir).unwrap();
+		let stats = extract(targz, &dir, 0, &out).unwrap();
+		assert_eq!((stats.files, stats.dirs), (2, 2));
+		// the header is skipped, only the symlink is an error
+		assert_eq!(stats.errors, 1);
+		assert_eq!(
+			fs::read_to_string(dir.join("project-v1.0.0-0123abcd/src/main.rs")).unwrap(),
+			"fn main() {}\n"
+		);
+		assert!(!dir.join("pax_global_header").exists());
+		// the top directory is recorded in the lock file to verify it later
+		assert_eq!(
+			single_dir(&dir).unwrap().as_deref(),
+			Some("project-v1.0.0-0123abcd")
+		);
+		fs::remove_dir_all(&dir).unwrap();
 	}
 }
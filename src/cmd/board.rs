@@ -0,0 +1,186 @@
+use gil::{
+	api::projects::boards::ProjectBoards,
+	args::{self, BoardCmd},
+	context::CliContext,
+	types::{Board, BoardList, Issue, Project},
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use gitlab::api::{
+	self,
+	projects::issues::{EditIssue, IssueStateEvent},
+	Pagination, Query,
+};
+use std::process::ExitCode;
+
+/// Where to move an issue on a board
+enum Target<'a> {
+	/// A list of the board
+	List(&'a BoardList),
+	/// The open list: the issue is reopened and removed from the label lists
+	Open,
+	/// The closed list: the issue is closed
+	Closed,
+}
+
+/// Returns the board matching a name or an id, or the first board of the project
+fn get_board(project: &Project, spec: Option<&str>) -> Result<Board> {
+	let endpoint = ProjectBoards::builder()
+		.project(project.id.value())
+		.build()?;
+	let boards: Vec<Board> = api::paged(endpoint, Pagination::All)
+		.query(&CliContext::global().gitlab)
+		.with_context(|| {
+			format!(
+				"Failed to list the boards of {}",
+				&project.path_with_namespace
+			)
+		})?;
+	let board = match spec {
+		Some(spec) => boards
+			.into_iter()
+			.find(|board| board.name.eq_ignore_ascii_case(spec) || board.id.to_string() == spec),
+		None => boards.into_iter().next(),
+	};
+	board.ok_or_else(|| {
+		anyhow!(
+			"Board {} not found in {}",
+			spec.unwrap_or_default(),
+			&project.path_with_namespace
+		)
+	})
+}
+
+/// Returns the target of a move from a list name
+fn get_target<'a>(board: &'a Board, name: &str) -> Result<Target<'a>> {
+	if let Some(list) = board
+		.lists
+		.iter()
+		.find(|list| list.name().eq_ignore_ascii_case(name))
+	{
+		Ok(Target::List(list))
+	} else if name.eq_ignore_ascii_case("open") {
+		Ok(Target::Open)
+	} else if name.eq_ignore_ascii_case("closed") {
+		Ok(Target::Closed)
+	} else {
+		let names: Vec<&str> = board.lists.iter().map(BoardList::name).collect();
+		bail!(
+			"List {} not found in board {} (lists are open, {}, closed)",
+			name,
+			&board.name,
+			names.join(", ")
+		)
+	}
+}
+
+/// Move an issue to a list by updating the label, assignee, milestone or state matching the list.
+/// Returns false if the issue was already in the list
+fn move_issue(issue: &Issue, project: &Project, board: &Board, target: &Target) -> Result<bool> {
+	let label_lists = board.lists.iter().filter_map(|list| list.label.as_ref());
+	let assignee_lists = board.lists.iter().filter_map(|list| list.assignee.as_ref());
+	let closed = issue.state == "closed";
+
+	let mut builder = EditIssue::builder();
+	builder.project(project.id.value()).issue(issue.iid);
+	match target {
+		Target::Closed => {
+			if closed {
+				return Ok(false);
+			}
+			builder.state_event(IssueStateEvent::Close);
+		}
+		Target::Open => {
+			let labels: Vec<&str> = label_lists
+				.filter(|label| issue.labels.contains(&label.name))
+				.map(|label| label.name.as_str())
+				.collect();
+			if labels.is_empty() && !closed {
+				return Ok(false);
+			}
+			for label in labels {
+				builder.remove_label(label);
+			}
+		}
+		Target::List(list) => match list.list_type.as_str() {
+			"label" => {
+				let target = list
+					.label
+					.as_ref()
+					.ok_or_else(|| anyhow!("List without label"))?;
+				if issue.labels.contains(&target.name) && !closed {
+					return Ok(false);
+				}
+				for label in label_lists {
+					if label.id != target.id && issue.labels.contains(&label.name) {
+						builder.remove_label(label.name.as_str());
+					}
+				}
+				builder.add_label(target.name.as_str());
+			}
+			"assignee" => {
+				let target = list
+					.assignee
+					.as_ref()
+					.ok_or_else(|| anyhow!("List without assignee"))?;
+				let assigned = |id| issue.assignees.iter().any(|user| user.id.value() == id);
+				if assigned(target.id.value()) && !closed {
+					return Ok(false);
+				}
+				// keep the assignees that don't have a list on the board
+				let others: Vec<u64> = assignee_lists.map(|user| user.id.value()).collect();
+				let assignees = issue
+					.assignees
+					.iter()
+					.map(|user| user.id.value())
+					.filter(|id| !others.contains(id))
+					.chain([target.id.value()]);
+				builder.assignee_ids(assignees);
+			}
+			"milestone" => {
+				let target = list
+					.milestone
+					.as_ref()
+					.ok_or_else(|| anyhow!("List without milestone"))?;
+				let current = issue.milestone.as_ref().map(|milestone| milestone.id);
+				if current == Some(target.id) && !closed {
+					return Ok(false);
+				}
+				builder.milestone_id(target.id);
+			}
+			list_type => bail!("Moving issues to {} lists is not supported", list_type),
+		},
+	}
+	if closed && !matches!(target, Target::Closed) {
+		builder.state_event(IssueStateEvent::Reopen);
+	}
+
+	api::ignore(builder.build()?)
+		.query(&CliContext::global().gitlab)
+		.with_context(|| format!("Failed to update issue #{}", issue.iid))?;
+	Ok(true)
+}
+
+pub fn cmd(args: &args::Board) -> Result<ExitCode> {
+	let project = CliContext::global().get_project(args.project.as_ref())?;
+	match &args.cmd {
+		BoardCmd::Move(args) => {
+			let board = get_board(&project, args.board.as_deref())?;
+			let target = get_target(&board, &args.to)?;
+			let issue = CliContext::global().get_issue(args.iid, &project)?;
+			if move_issue(&issue, &project, &board, &target)? {
+				println!(
+					"Moved issue #{} ({}) to {} in board {}",
+					issue.iid, &issue.title, &args.to, &board.name
+				);
+			} else {
+				println!(
+					"Issue #{} ({}) is already in {} in board {}",
+					issue.iid, &issue.title, &args.to, &board.name
+				);
+			}
+		}
+	}
+
+	Ok(ExitCode::from(0))
+}
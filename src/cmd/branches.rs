@@ -1,16 +1,20 @@
-use crate::{
-	args::{self, BranchesCmd},
+use crate::cmd::protections;
+use gil::{
+	args::{self, AccessSpec, BranchesCmd},
 	context::CliContext,
-	types,
+	types::{self, Project},
 };
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use gitlab::api::{
 	self,
-	projects::protected_branches::{ProtectBranch, ProtectedBranches, UnprotectBranch},
+	common::{ProtectedAccessLevel, ProtectedAccessLevelWithAccess},
+	projects::protected_branches::{
+		ProtectBranch, ProtectedAccess, ProtectedBranches, UnprotectBranch,
+	},
 	Query,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::process::ExitCode;
 
 #[derive(Deserialize)]
@@ -18,37 +22,188 @@ struct Tag {
 	name: String,
 }
 
-pub fn cmd(args: &args::Branches) -> Result<ExitCode> {
-	match &args.cmd {
-		BranchesCmd::Unprotect(args) => {
-			let project = CliContext::global().get_project(args.project.as_ref())?;
-			let branch = CliContext::global().get_branchexp(args.branch.as_ref())?;
+/// Unprotect access can't be given to nobody
+fn with_access(
+	access: ProtectedAccess<ProtectedAccessLevel>,
+) -> Result<ProtectedAccess<ProtectedAccessLevelWithAccess>> {
+	Ok(match access {
+		ProtectedAccess::Level(ProtectedAccessLevel::Developer) => {
+			ProtectedAccess::Level(ProtectedAccessLevelWithAccess::Developer)
+		}
+		ProtectedAccess::Level(ProtectedAccessLevel::Maintainer) => {
+			ProtectedAccess::Level(ProtectedAccessLevelWithAccess::Maintainer)
+		}
+		ProtectedAccess::Level(ProtectedAccessLevel::Admin) => {
+			ProtectedAccess::Level(ProtectedAccessLevelWithAccess::Admin)
+		}
+		ProtectedAccess::User(id) => ProtectedAccess::User(id),
+		ProtectedAccess::Group(id) => ProtectedAccess::Group(id),
+		_ => bail!("Unprotect access can't be no-access"),
+	})
+}
 
-			let endpoint = ProtectedBranches::builder()
-				.project(project.path_with_namespace.to_owned())
-				.build()?;
-			let tags: Vec<types::ProtectedRepoBranch> =
-				endpoint.query(&CliContext::global().gitlab)?;
+/// Protection rule of a branch expression as given on the command line or in a rules file
+#[derive(Deserialize, Serialize, Clone)]
+pub struct BranchRule {
+	/// branch expression
+	pub name: String,
+	/// remove the protection instead
+	#[serde(default, skip_serializing_if = "std::ops::Not::not")]
+	pub unprotect: bool,
+	#[serde(default, skip_serializing_if = "std::ops::Not::not")]
+	pub force_push: bool,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub push_access: Option<AccessSpec>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub merge_access: Option<AccessSpec>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub unprotect_access: Option<AccessSpec>,
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub allowed_to_push: Vec<AccessSpec>,
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub allowed_to_merge: Vec<AccessSpec>,
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub allowed_to_unprotect: Vec<AccessSpec>,
+	#[serde(default, skip_serializing_if = "std::ops::Not::not")]
+	pub code_owner_approval: bool,
+}
 
-			if !tags.iter().any(|b| &b.name == branch) {
-				println!(
-					"branch '{}' protection not found on project {}",
-					&branch, &project.path_with_namespace
-				);
-			} else {
-				let endpoint = UnprotectBranch::builder()
-					.project(project.path_with_namespace.to_owned())
-					.name(branch)
-					.build()?;
-				api::ignore(endpoint).query(&CliContext::global().gitlab)?;
-				println!(
-					"branch '{}' protection has been removed on project {}",
+/// Remove the protection of a branch expression on a project
+pub fn unprotect(project: &Project, branch: &str) -> Result<()> {
+	let endpoint = ProtectedBranches::builder()
+		.project(project.path_with_namespace.to_owned())
+		.build()?;
+	let tags: Vec<types::ProtectedRepoBranch> = endpoint.query(&CliContext::global().gitlab)?;
+
+	if !tags.iter().any(|b| b.name == branch) {
+		println!(
+			"branch '{}' protection not found on project {}",
+			&branch, &project.path_with_namespace
+		);
+	} else {
+		let endpoint = UnprotectBranch::builder()
+			.project(project.path_with_namespace.to_owned())
+			.name(branch)
+			.build()?;
+		api::ignore(endpoint).query(&CliContext::global().gitlab)?;
+		println!(
+			"branch '{}' protection has been removed on project {}",
+			&branch, &project.path_with_namespace
+		);
+	}
+	Ok(())
+}
+
+/// Protect a branch expression on a project, replacing any existing protection. The existing
+/// protection is restored if the new one is rejected
+pub fn protect(project: &Project, rule: &BranchRule) -> Result<()> {
+	let branch = rule.name.as_str();
+	let endpoint = ProtectedBranches::builder()
+		.project(project.path_with_namespace.to_owned())
+		.build()?;
+	let tags: Vec<types::ProtectedRepoBranch> = endpoint.query(&CliContext::global().gitlab)?;
+
+	// unprotect if found, keeping the rule to restore
+	let previous = match tags.into_iter().find(|b| b.name == branch) {
+		Some(previous) => Some(
+			protections::branch_rule(previous)
+				.with_context(|| format!("Can't replace the protection of branch '{}'", branch))?,
+		),
+		None => None,
+	};
+	if previous.is_some() {
+		let endpoint = UnprotectBranch::builder()
+			.project(project.path_with_namespace.to_owned())
+			.name(branch)
+			.build()?;
+		api::ignore(endpoint).query(&CliContext::global().gitlab)?;
+	}
+	// an protect again (parameters may have changed)
+	let tag = match create_protection(project, rule) {
+		Ok(tag) => tag,
+		Err(err) => {
+			if let Some(previous) = &previous {
+				if let Err(restore) = create_protection(project, previous) {
+					return Err(err.context(format!(
+						"the previous protection couldn't be restored: {:#}",
+						restore
+					)));
+				}
+				eprintln!(
+					"branch '{}' previous protection has been restored on project {}",
 					&branch, &project.path_with_namespace
 				);
 			}
+			return Err(err);
+		}
+	};
+	println!(
+		"branch '{}' is protected on project {}",
+		tag.name, &project.path_with_namespace
+	);
+	Ok(())
+}
+
+/// Create the protection of a branch expression on a project
+fn create_protection(project: &Project, rule: &BranchRule) -> Result<Tag> {
+	let branch = rule.name.as_str();
+	let mut builder = ProtectBranch::builder();
+	builder
+		.project(project.path_with_namespace.to_owned())
+		.name(branch)
+		.allow_force_push(rule.force_push);
+	if rule.code_owner_approval {
+		builder.code_owner_approval_required(true);
+	}
+	// a role sets the access level, users and groups are added to the allowed list
+	let context = CliContext::global();
+	if let Some(access) = &rule.push_access {
+		match context.get_protected_access(access)? {
+			ProtectedAccess::Level(level) => builder.push_access_level(level),
+			access => builder.allowed_to_push(access),
+		};
+	}
+	if let Some(access) = &rule.merge_access {
+		match context.get_protected_access(access)? {
+			ProtectedAccess::Level(level) => builder.merge_access_level(level),
+			access => builder.allowed_to_merge(access),
+		};
+	}
+	if let Some(access) = &rule.unprotect_access {
+		match with_access(context.get_protected_access(access)?)? {
+			ProtectedAccess::Level(level) => builder.unprotect_access_level(level.into()),
+			access => builder.allowed_to_unprotect(access),
+		};
+	}
+	for access in &rule.allowed_to_push {
+		builder.allowed_to_push(context.get_protected_access(access)?);
+	}
+	for access in &rule.allowed_to_merge {
+		builder.allowed_to_merge(context.get_protected_access(access)?);
+	}
+	for access in &rule.allowed_to_unprotect {
+		builder.allowed_to_unprotect(with_access(context.get_protected_access(access)?)?);
+	}
+	let endpoint = builder.build()?;
+	endpoint
+		.query(&CliContext::global().gitlab)
+		.with_context(|| {
+			format!(
+				"Failed to protect branch '{}' on project {}",
+				&branch, &project.path_with_namespace
+			)
+		})
+}
+
+pub fn cmd(args: &args::Branches) -> Result<ExitCode> {
+	match &args.cmd {
+		BranchesCmd::Unprotect(args) => {
+			let project = CliContext::global().get_project(args.project.as_ref())?;
+			let branch = CliContext::global().get_branchexp(args.branch.as_ref())?;
+			unprotect(&project, branch)?;
 
 			if CliContext::global().open {
-				let _ = open::that(format!("{}/-/settings/repository", project.web_url));
+				CliContext::global().open_url(format!("{}/-/settings/repository", project.web_url));
 			}
 
 			Ok(ExitCode::from(0))
@@ -57,42 +212,22 @@ pub fn cmd(args: &args::Branches) -> Result<ExitCode> {
 		BranchesCmd::Protect(args) => {
 			let project = CliContext::global().get_project(args.project.as_ref())?;
 			let branch = CliContext::global().get_branchexp(args.branch.as_ref())?;
-
-			let endpoint = ProtectedBranches::builder()
-				.project(project.path_with_namespace.to_owned())
-				.build()?;
-			let tags: Vec<types::ProtectedRepoBranch> =
-				endpoint.query(&CliContext::global().gitlab)?;
-
-			// unprotect if found
-			if tags.iter().any(|b| &b.name == branch) {
-				let endpoint = UnprotectBranch::builder()
-					.project(project.path_with_namespace.to_owned())
-					.name(branch)
-					.build()?;
-				api::ignore(endpoint).query(&CliContext::global().gitlab)?;
-			}
-			// an protect again (parameters may have changed)
-			let endpoint = ProtectBranch::builder()
-				.project(project.path_with_namespace.to_owned())
-				.name(branch)
-				.allow_force_push(args.force_push)
-				.build()?;
-			let tag: Tag = endpoint
-				.query(&CliContext::global().gitlab)
-				.with_context(|| {
-					format!(
-						"Failed to protect branch '{}' on project {}",
-						&branch, &project.path_with_namespace
-					)
-				})?;
-			println!(
-				"branch '{}' is protected on project {}",
-				tag.name, &project.path_with_namespace
-			);
+			let rule = BranchRule {
+				name: branch.to_owned(),
+				unprotect: false,
+				force_push: args.force_push,
+				push_access: args.push_access.clone(),
+				merge_access: args.merge_access.clone(),
+				unprotect_access: args.unprotect_access.clone(),
+				allowed_to_push: args.allowed_to_push.clone(),
+				allowed_to_merge: args.allowed_to_merge.clone(),
+				allowed_to_unprotect: args.allowed_to_unprotect.clone(),
+				code_owner_approval: args.code_owner_approval,
+			};
+			protect(&project, &rule)?;
 
 			if CliContext::global().open {
-				let _ = open::that(format!("{}/-/settings/repository", project.web_url));
+				CliContext::global().open_url(format!("{}/-/settings/repository", project.web_url));
 			}
 
 			Ok(ExitCode::from(0))
@@ -0,0 +1,142 @@
+use gil::{
+	api::groups::GroupProjects,
+	args,
+	config::CloneProtocol,
+	context::CliContext,
+	types::{Group, Project},
+	utils::glob_match,
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use gitlab::api::{self, Pagination, Query};
+use std::{
+	env,
+	path::{Path, PathBuf},
+	process::{Command, ExitCode},
+};
+
+/// Split a group specification (my-group/sub/api-*) in a group path and a project pattern
+fn split_spec(spec: &str) -> (&str, Option<&str>) {
+	let spec = spec.trim_end_matches('/');
+	match spec.rsplit_once('/') {
+		Some((group, pattern)) if pattern.contains(['*', '?']) => (group, Some(pattern)),
+		_ => (spec, None),
+	}
+}
+
+/// List the projects of a group (and its subgroups) whose path relative to the group matches
+/// the pattern
+pub fn group_projects(spec: &str) -> Result<(Group, Vec<Project>)> {
+	let (group, pattern) = split_spec(spec);
+	let group = CliContext::global().get_group(group)?;
+	let endpoint = GroupProjects::builder().group(group.id).build()?;
+	let projects: Vec<Project> = api::paged(endpoint, Pagination::All)
+		.query(&CliContext::global().gitlab)
+		.with_context(|| format!("Failed to list projects of group {}", &group.full_path))?;
+	let prefix = format!("{}/", &group.full_path);
+	let projects = projects
+		.into_iter()
+		.filter(|project| {
+			pattern.is_none_or(|pattern| {
+				let path = project.path_with_namespace.strip_prefix(&prefix);
+				glob_match(pattern, path.unwrap_or(&project.path_with_namespace))
+			})
+		})
+		.collect();
+	Ok((group, projects))
+}
+
+/// The url to clone a project with the protocol configured for the host
+pub fn clone_url(project: &Project) -> Result<&str> {
+	let context = CliContext::global();
+	let protocol = context
+		.config
+		.hosts
+		.get(&context.repo.host)
+		.map(|host| host.clone_protocol)
+		.unwrap_or_default();
+	match protocol {
+		CloneProtocol::Ssh => project.ssh_url_to_repo.as_deref(),
+		CloneProtocol::Https => project.http_url_to_repo.as_deref(),
+	}
+	.ok_or_else(|| anyhow!("No clone url for {}", &project.path_with_namespace))
+}
+
+/// Run a git command, in a directory if given
+pub fn git(dir: Option<&Path>, args: &[&str]) -> Result<()> {
+	let mut cmd = Command::new("git");
+	if let Some(dir) = dir {
+		cmd.arg("-C").arg(dir);
+	}
+	let status = cmd
+		.args(args)
+		.status()
+		.with_context(|| "Failed to run git")?;
+	if !status.success() {
+		bail!("git {} failed ({})", args.join(" "), status);
+	}
+	Ok(())
+}
+
+/// Run a git command in a directory and return its trimmed output
+pub fn git_output(dir: &Path, args: &[&str]) -> Result<String> {
+	let output = Command::new("git")
+		.arg("-C")
+		.arg(dir)
+		.args(args)
+		.output()
+		.with_context(|| "Failed to run git")?;
+	if !output.status.success() {
+		bail!(
+			"git {} failed: {}",
+			args.join(" "),
+			String::from_utf8_lossy(&output.stderr).trim()
+		);
+	}
+	Ok(String::from_utf8_lossy(&output.stdout).trim().to_owned())
+}
+
+/// Directory where to create the namespace tree
+pub fn root_dir(dir: Option<&PathBuf>) -> Result<PathBuf> {
+	Ok(match dir {
+		Some(dir) => dir.to_owned(),
+		None => env::current_dir()?,
+	})
+}
+
+pub fn cmd(args: &args::CloneGroup) -> Result<ExitCode> {
+	let (group, projects) = group_projects(&args.group)?;
+	if projects.is_empty() {
+		println!("No project found in group {}", &group.full_path);
+		return Ok(ExitCode::from(0));
+	}
+
+	let root = root_dir(args.dir.as_ref())?;
+	let (mut cloned, mut updated, mut skipped, mut failed) = (0, 0, 0, 0);
+	for project in &projects {
+		let dest = root.join(&project.path_with_namespace);
+		let res = if dest.exists() {
+			if args.update {
+				println!("Fetching {}", &project.path_with_namespace);
+				git(Some(&dest), &["fetch", "--all", "--prune"]).map(|_| updated += 1)
+			} else {
+				skipped += 1;
+				Ok(())
+			}
+		} else {
+			println!("Cloning {}", &project.path_with_namespace);
+			clone_url(project)
+				.and_then(|url| git(None, &["clone", url, &dest.to_string_lossy()]))
+				.map(|_| cloned += 1)
+		};
+		if let Err(e) = res {
+			eprintln!("{:#}", e);
+			failed += 1;
+		}
+	}
+	println!(
+		"{} cloned, {} updated, {} skipped, {} failed",
+		cloned, updated, skipped, failed
+	);
+	Ok(ExitCode::from(if failed == 0 { 0 } else { 1 }))
+}
@@ -0,0 +1,54 @@
+use gil::{
+	args::{self, CommitCmd},
+	context::CliContext,
+	types,
+};
+
+use anyhow::{Context, Result};
+use gitlab::api::{self, projects::repository::commits, Pagination, Query};
+use std::process::ExitCode;
+
+pub fn cmd(args: &args::Commit) -> Result<ExitCode> {
+	let project = CliContext::global().get_project(args.project.as_ref())?;
+	match &args.cmd {
+		CommitCmd::List(args) => {
+			let ref_ = CliContext::global().check_ref(args.ref_.as_deref(), &project)?;
+			let endpoint = commits::Commits::builder()
+				.project(project.path_with_namespace.as_str())
+				.ref_name(&ref_)
+				.build()?;
+			let commits: Vec<types::Commit> = api::paged(endpoint, Pagination::Limit(args.limit))
+				.query(&CliContext::global().gitlab)
+				.with_context(|| {
+					format!(
+						"Failed to list commits of {} @ {}",
+						&project.path_with_namespace, &ref_
+					)
+				})?;
+			let signatures = if args.signatures {
+				let shas = commits.iter().map(|commit| &commit.id);
+				Some(CliContext::global().get_signatures(shas, &project)?)
+			} else {
+				None
+			};
+			CliContext::global().print_commits(&commits, &ref_, &project, signatures.as_ref())?;
+			if CliContext::global().open {
+				CliContext::global().open_url(format!("{}/-/commits/{}", &project.web_url, &ref_));
+			}
+			Ok(ExitCode::from(0))
+		}
+
+		CommitCmd::Verify(args) => {
+			let commit = CliContext::global().get_commit(&args.sha, &project)?;
+			let signature =
+				CliContext::global().get_commit_signature(commit.id.value(), &project)?;
+			CliContext::global().print_commit_signature(&commit, signature.as_ref())?;
+			if CliContext::global().open {
+				CliContext::global().open_url(&commit.web_url);
+			}
+			// allow scripts to reject unverified commits
+			let verified = signature.is_some_and(|signature| signature.verified());
+			Ok(ExitCode::from(if verified { 0 } else { 1 }))
+		}
+	}
+}
@@ -0,0 +1,107 @@
+use super::clone::group_projects;
+use gil::{
+	args,
+	context::CliContext,
+	types::{Pipeline, Project, StatusState},
+};
+
+use anyhow::{bail, Context, Result};
+use gitlab::api::{self, projects::pipelines, Pagination, Query};
+use std::{
+	panic,
+	process::ExitCode,
+	sync::atomic::{AtomicUsize, Ordering},
+	thread,
+};
+
+/// Number of projects queried at the same time
+const WORKERS: usize = 8;
+
+/// Returns the projects of a group, or the projects listed in the configuration of the host
+fn get_projects(group: Option<&String>) -> Result<Vec<Project>> {
+	if let Some(group) = group {
+		return Ok(group_projects(group)?.1);
+	}
+	let context = CliContext::global();
+	let names = context
+		.config
+		.hosts
+		.get(&context.repo.host)
+		.map(|host| host.dashboard.as_slice())
+		.unwrap_or_default();
+	if names.is_empty() {
+		bail!(
+			"No dashboard projects configured for {}, give a group instead",
+			&context.repo.host
+		);
+	}
+	names
+		.iter()
+		.map(|name| context.get_project(Some(name)))
+		.collect()
+}
+
+/// Returns the latest pipeline of the default branch of a project
+fn latest_pipeline(project: &Project) -> Result<Option<Pipeline>> {
+	// empty repositories don't have a default branch
+	let Some(branch) = &project.default_branch else {
+		return Ok(None);
+	};
+	let endpoint = pipelines::Pipelines::builder()
+		.project(project.path_with_namespace.to_owned())
+		.ref_(branch.as_str())
+		.build()?;
+	let pipelines: Vec<Pipeline> = api::paged(endpoint, Pagination::Limit(1))
+		.query(&CliContext::global().gitlab)
+		.with_context(|| {
+			format!(
+				"Failed to list pipelines for {} @ {}",
+				&project.name_with_namespace, branch
+			)
+		})?;
+	Ok(pipelines.into_iter().next())
+}
+
+pub fn cmd(args: &args::Dashboard) -> Result<ExitCode> {
+	let projects = get_projects(args.group.as_ref())?;
+	if projects.is_empty() {
+		println!("No project to show");
+		return Ok(ExitCode::from(0));
+	}
+
+	// query the projects from a few threads, each one taking the next project not yet queried
+	let next = AtomicUsize::new(0);
+	let mut pipelines: Vec<_> = thread::scope(|scope| {
+		let workers: Vec<_> = (0..WORKERS.min(projects.len()))
+			.map(|_| {
+				scope.spawn(|| {
+					let mut pipelines = Vec::new();
+					loop {
+						let i = next.fetch_add(1, Ordering::Relaxed);
+						let Some(project) = projects.get(i) else {
+							break pipelines;
+						};
+						pipelines.push((i, latest_pipeline(project)));
+					}
+				})
+			})
+			.collect();
+		workers
+			.into_iter()
+			.flat_map(|worker| worker.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+			.collect()
+	});
+	pipelines.sort_by_key(|(i, _)| *i);
+
+	let board: Vec<_> = projects
+		.iter()
+		.zip(pipelines.into_iter().map(|(_, pipeline)| pipeline))
+		.collect();
+	CliContext::global().print_dashboard(&board)?;
+	let failed = board.iter().any(|(_, pipeline)| match pipeline {
+		Ok(Some(pipeline)) => pipeline.status == StatusState::Failed,
+		Ok(None) => false,
+		Err(_) => true,
+	});
+	Ok(ExitCode::from(if failed { 1 } else { 0 }))
+}
@@ -0,0 +1,136 @@
+use gil::{
+	api::projects::{deployments::Deployments, environments::StopEnvironment},
+	args::{self, EnvCmd},
+	context::CliContext,
+	types::{Deployment, Environment, Job, Project},
+};
+
+use anyhow::{anyhow, Context, Result};
+use gitlab::api::{
+	self,
+	projects::{environments::Environments, jobs::RetryJob},
+	Pagination, Query,
+};
+use std::{process::ExitCode, slice};
+
+/// Get an environment by its name
+fn get_environment(name: &str, project: &Project) -> Result<Environment> {
+	let endpoint = Environments::builder()
+		.project(project.id.value())
+		.name(name)
+		.build()?;
+	let environments: Vec<Environment> = endpoint
+		.query(&CliContext::global().gitlab)
+		.with_context(|| format!("Failed to get environment {}", name))?;
+	environments
+		.into_iter()
+		.find(|environment| environment.name == name)
+		.ok_or_else(|| {
+			anyhow!(
+				"Environment {} not found in project {}",
+				name,
+				&project.path_with_namespace
+			)
+		})
+}
+
+pub fn cmd(args: &args::Env) -> Result<ExitCode> {
+	let project = CliContext::global().get_project(args.project.as_ref())?;
+	match &args.cmd {
+		EnvCmd::List(_) => {
+			let endpoint = Environments::builder()
+				.project(project.id.value())
+				.build()?;
+			let environments: Vec<Environment> = api::paged(endpoint, Pagination::All)
+				.query(&CliContext::global().gitlab)
+				.with_context(|| {
+					format!(
+						"Failed to list environments of {}",
+						&project.name_with_namespace
+					)
+				})?;
+			CliContext::global().print_environments(&environments, &project)?;
+			if CliContext::global().open {
+				CliContext::global().open_url(format!("{}/-/environments", project.web_url));
+			}
+		}
+
+		EnvCmd::Stop(args) => {
+			let environment = get_environment(&args.name, &project)?;
+			let endpoint = StopEnvironment::builder()
+				.project(project.id.value())
+				.environment(environment.id)
+				.build()?;
+			api::ignore(endpoint)
+				.query(&CliContext::global().gitlab)
+				.with_context(|| format!("Failed to stop environment {}", &args.name))?;
+			println!(
+				"environment {} of project {} is stopping",
+				&args.name, &project.path_with_namespace
+			);
+		}
+
+		EnvCmd::Rollback(args) => {
+			let environment = get_environment(&args.name, &project)?;
+			let endpoint = Deployments::builder()
+				.project(project.id.value())
+				.environment(Some(environment.name.as_str()))
+				.status(Some("success"))
+				.build()?;
+			// the latest successful deployment is the one currently deployed
+			let pagination = if args.to.is_some() {
+				Pagination::All
+			} else {
+				Pagination::Limit(2)
+			};
+			let deployments: Vec<Deployment> = api::paged(endpoint, pagination)
+				.query(&CliContext::global().gitlab)
+				.with_context(|| format!("Failed to list deployments of {}", &args.name))?;
+			let deployment = if let Some(id) = args.to {
+				deployments
+					.into_iter()
+					.find(|deployment| deployment.id == id)
+					.ok_or_else(|| {
+						anyhow!(
+							"No successful deployment {} found for environment {}",
+							id,
+							&args.name
+						)
+					})?
+			} else {
+				deployments.into_iter().nth(1).ok_or_else(|| {
+					anyhow!("No previous deployment found for environment {}", &args.name)
+				})?
+			};
+			let job = deployment.deployable.as_ref().ok_or_else(|| {
+				anyhow!("Deployment {} has no job to run again", deployment.id)
+			})?;
+
+			let question = format!(
+				"Roll back {} to deployment {} ({} @ {}) by running job {} ({}) again?",
+				&args.name,
+				deployment.id,
+				&deployment.ref_,
+				&deployment.sha.value()[..8],
+				job.id,
+				&job.name
+			);
+			if !args.yes && !CliContext::global().prompt.confirm(&question)? {
+				return Ok(ExitCode::from(1));
+			}
+			let endpoint = RetryJob::builder()
+				.project(project.id.value())
+				.job(job.id.value())
+				.build()?;
+			let job: Job = endpoint
+				.query(&CliContext::global().gitlab)
+				.with_context(|| format!("Failed to run job {} again", job.id))?;
+			CliContext::global().print_jobs(slice::from_ref(&job))?;
+			if CliContext::global().open {
+				CliContext::global().open_url(job.web_url);
+			}
+		}
+	}
+
+	Ok(ExitCode::from(0))
+}
@@ -0,0 +1,60 @@
+use gil::{
+	api::projects::error_tracking::{
+		detailed_error::SentryDetailedErrorVariables, errors::SentryErrorsVariables,
+		SentryDetailedError, SentryErrors,
+	},
+	args::{self, ErrorsCmd},
+	context::CliContext,
+};
+
+use anyhow::{anyhow, Context, Result};
+use graphql_client::GraphQLQuery;
+use std::process::ExitCode;
+
+pub fn cmd(args: &args::Errors) -> Result<ExitCode> {
+	let project = CliContext::global().get_project(args.project.as_ref())?;
+	// the REST API only exposes the error tracking settings, errors are queried with GraphQL
+	match &args.cmd {
+		ErrorsCmd::List(args) => {
+			let query = SentryErrors::build_query(SentryErrorsVariables {
+				full_path: project.path_with_namespace.clone(),
+				first: args.limit,
+				search_term: args.search.clone(),
+				sort: args.sort.clone(),
+			});
+			let errors = CliContext::global()
+				.gitlab
+				.graphql::<SentryErrors>(&query)
+				.with_context(|| {
+					format!("Failed to list errors of {}", &project.name_with_namespace)
+				})?
+				.errors()
+				.ok_or_else(|| {
+					anyhow!(
+						"Error tracking is not enabled for {}",
+						&project.name_with_namespace
+					)
+				})?;
+			CliContext::global().print_errors(&errors, &project)
+		}
+		ErrorsCmd::Show(args) => {
+			let query = SentryDetailedError::build_query(SentryDetailedErrorVariables::new(
+				&project.path_with_namespace,
+				args.id,
+			));
+			let error = CliContext::global()
+				.gitlab
+				.graphql::<SentryDetailedError>(&query)
+				.with_context(|| format!("Failed to get error {}", args.id))?
+				.error()
+				.ok_or_else(|| {
+					anyhow!(
+						"Error {} not found in {}",
+						args.id,
+						&project.name_with_namespace
+					)
+				})?;
+			CliContext::global().print_error(&error)
+		}
+	}
+}
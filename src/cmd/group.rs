@@ -0,0 +1,70 @@
+use crate::cmd::protections::Rules;
+use gil::{
+	api::groups::GroupProjects,
+	args::{self, GroupCmd},
+	context::CliContext,
+	types::Project,
+	utils::glob_match,
+};
+
+use anyhow::{Context, Result};
+use gitlab::api::{self, Pagination, Query};
+use std::process::ExitCode;
+
+pub fn cmd(args: &args::Group) -> Result<ExitCode> {
+	match &args.cmd {
+		GroupCmd::Apply(args) => {
+			let rules = Rules::from_file(&args.rules)?;
+
+			let group = CliContext::global().get_group(&args.group)?;
+			let endpoint = GroupProjects::builder().group(group.id).build()?;
+			let projects: Vec<Project> = api::paged(endpoint, Pagination::All)
+				.query(&CliContext::global().gitlab)
+				.with_context(|| format!("Failed to list projects of group {}", &group.name))?;
+			let projects: Vec<Project> = projects
+				.into_iter()
+				// archived projects are read-only
+				.filter(|project| !project.archived)
+				.filter(|project| {
+					args.match_.as_ref().is_none_or(|pattern| {
+						glob_match(pattern, &project.path_with_namespace)
+							|| glob_match(pattern, &project.name)
+					})
+				})
+				.collect();
+			if projects.is_empty() {
+				println!("No project found in group {}", &group.full_path);
+				return Ok(ExitCode::from(0));
+			}
+
+			for project in &projects {
+				println!("- {}", &project.path_with_namespace);
+			}
+			let question = format!(
+				"Apply {} branch rule(s) and {} tag rule(s) to {} project(s)?",
+				rules.branches.len(),
+				rules.tags.len(),
+				projects.len()
+			);
+			if !args.yes && !CliContext::global().prompt.confirm(&question)? {
+				return Ok(ExitCode::from(1));
+			}
+			let mut failed = 0;
+			for project in &projects {
+				if let Err(e) = rules.apply(project) {
+					eprintln!("{:#}", e);
+					failed += 1;
+				}
+			}
+			println!(
+				"Rules applied to {} project(s), {} failed",
+				projects.len() - failed,
+				failed
+			);
+			if CliContext::global().open {
+				CliContext::global().open_url(&group.web_url);
+			}
+			Ok(ExitCode::from(if failed == 0 { 0 } else { 1 }))
+		}
+	}
+}
@@ -0,0 +1,83 @@
+use gil::{
+	api::projects::hooks::{ProjectHookEvents, TestProjectHook},
+	args::{self, HookCmd},
+	context::CliContext,
+	types::{HookDelivery, ProjectHook},
+};
+
+use anyhow::{bail, Context, Result};
+use gitlab::api::{self, projects::hooks::Hooks, Pagination, Query};
+use std::process::ExitCode;
+
+/// Events that can be sent to test a webhook
+const TEST_EVENTS: [&str; 12] = [
+	"push",
+	"tag_push",
+	"merge_requests",
+	"issues",
+	"confidential_issues",
+	"note",
+	"job",
+	"pipeline",
+	"wiki_page",
+	"releases",
+	"emoji",
+	"resource_access_token",
+];
+
+pub fn cmd(args: &args::Hook) -> Result<ExitCode> {
+	let project = CliContext::global().get_project(args.project.as_ref())?;
+	match &args.cmd {
+		HookCmd::List(_) => {
+			let endpoint = Hooks::builder().project(project.id.value()).build()?;
+			let hooks: Vec<ProjectHook> = api::paged(endpoint, Pagination::All)
+				.query(&CliContext::global().gitlab)
+				.with_context(|| {
+					format!(
+						"Failed to list webhooks of {}",
+						&project.name_with_namespace
+					)
+				})?;
+			CliContext::global().print_project_hooks(&hooks, &project)?;
+		}
+
+		HookCmd::Deliveries(args) => {
+			let endpoint = ProjectHookEvents::builder()
+				.project(project.id.value())
+				.hook_id(args.id)
+				.status(args.status.as_deref())
+				.build()?;
+			let deliveries: Vec<HookDelivery> = api::paged(endpoint, Pagination::Limit(args.limit))
+				.query(&CliContext::global().gitlab)
+				.with_context(|| format!("Failed to list the deliveries of webhook {}", args.id))?;
+			CliContext::global().print_hook_deliveries(&deliveries, args.id)?;
+		}
+
+		HookCmd::Test(args) => {
+			let event = args.event.strip_suffix("_events").unwrap_or(&args.event);
+			if !TEST_EVENTS.contains(&event) {
+				bail!(
+					"Invalid event {}, use one of {}",
+					&args.event,
+					TEST_EVENTS.join(", ")
+				);
+			}
+			let trigger = format!("{}_events", event);
+			let endpoint = TestProjectHook::builder()
+				.project(project.id.value())
+				.hook_id(args.id)
+				.trigger(&trigger)
+				.build()?;
+			api::ignore(endpoint)
+				.query(&CliContext::global().gitlab)
+				.with_context(|| format!("Failed to test webhook {}", args.id))?;
+			println!("test {} event sent to webhook {}", event, args.id);
+		}
+	}
+
+	if CliContext::global().open {
+		CliContext::global().open_url(format!("{}/-/hooks", project.web_url));
+	}
+
+	Ok(ExitCode::from(0))
+}
@@ -0,0 +1,124 @@
+use crate::cmd::todo::resolve_todos;
+use gil::{
+	api::{
+		issues::{AssignedIssues, IssuesOrderBy},
+		projects::issues::{
+			AddIssueSpentTime, SetIssueTimeEstimate, SubscribeIssue, UnsubscribeIssue,
+		},
+	},
+	args::{self, IssueCmd, IssueSort},
+	context::CliContext,
+	types::{Issue, TimeStats},
+};
+
+use anyhow::{Context, Result};
+use gitlab::api::{
+	self,
+	projects::issues::{EditIssue, IssueStateEvent},
+	Pagination, Query,
+};
+use std::process::ExitCode;
+
+/// List the opened issues assigned to the current user across all projects
+fn mine(args: &args::IssueMine) -> Result<ExitCode> {
+	let user = CliContext::global().get_current_user()?;
+	let endpoint = AssignedIssues::builder()
+		.order_by(args.sort.map(|sort| match sort {
+			IssueSort::Due => IssuesOrderBy::DueDate,
+			IssueSort::Priority => IssuesOrderBy::Priority,
+		}))
+		.build()?;
+	let issues: Vec<Issue> = api::paged(endpoint, Pagination::All)
+		.query(&CliContext::global().gitlab)
+		.context("Failed to list issues")?;
+	CliContext::global().print_user_issues(&issues, &user)
+}
+
+pub fn cmd(args: &args::Issue) -> Result<ExitCode> {
+	// listing issues across projects doesn't need a project, so it's fetched on demand
+	let get_project = || CliContext::global().get_project(args.project.as_ref());
+	match &args.cmd {
+		IssueCmd::Mine(args) => return mine(args),
+		IssueCmd::Subscribe(args) => {
+			let project = get_project()?;
+			let issue = CliContext::global().get_issue(args.iid, &project)?;
+			let endpoint = SubscribeIssue::builder()
+				.project(project.id.value())
+				.issue(issue.iid)
+				.build()?;
+			let subscribed = CliContext::global()
+				.query_toggle(endpoint)
+				.with_context(|| format!("Failed to subscribe to issue #{}", issue.iid))?;
+			if subscribed {
+				println!("Subscribed to issue #{} ({})", issue.iid, &issue.title);
+			} else {
+				println!("Already subscribed to issue #{} ({})", issue.iid, &issue.title);
+			}
+		}
+		IssueCmd::Unsubscribe(args) => {
+			let project = get_project()?;
+			let issue = CliContext::global().get_issue(args.iid, &project)?;
+			let endpoint = UnsubscribeIssue::builder()
+				.project(project.id.value())
+				.issue(issue.iid)
+				.build()?;
+			let unsubscribed = CliContext::global()
+				.query_toggle(endpoint)
+				.with_context(|| format!("Failed to unsubscribe from issue #{}", issue.iid))?;
+			if unsubscribed {
+				println!("Unsubscribed from issue #{} ({})", issue.iid, &issue.title);
+			} else {
+				println!("Not subscribed to issue #{} ({})", issue.iid, &issue.title);
+			}
+		}
+		IssueCmd::Close(args) => {
+			let project = get_project()?;
+			let issue = CliContext::global().get_issue(args.iid, &project)?;
+			let endpoint = EditIssue::builder()
+				.project(project.id.value())
+				.issue(issue.iid)
+				.state_event(IssueStateEvent::Close)
+				.build()?;
+			api::ignore(endpoint)
+				.query(&CliContext::global().gitlab)
+				.with_context(|| format!("Failed to close issue #{}", issue.iid))?;
+			println!("Issue #{} ({}) closed", issue.iid, &issue.title);
+			if args.resolve_todo {
+				resolve_todos(&project, "Issue", issue.iid)?;
+			}
+		}
+		IssueCmd::Spend(args) => {
+			let project = get_project()?;
+			let issue = CliContext::global().get_issue(args.iid, &project)?;
+			let endpoint = AddIssueSpentTime::builder()
+				.project(project.id.value())
+				.issue(issue.iid)
+				.duration(&args.time.0)
+				.summary(args.summary.as_deref())
+				.build()?;
+			let stats: TimeStats = endpoint
+				.query(&CliContext::global().gitlab)
+				.with_context(|| format!("Failed to add spent time to issue #{}", issue.iid))?;
+			let reference = format!("#{}", issue.iid);
+			CliContext::global().print_time_stats(&reference, &issue.title, &stats)?;
+		}
+		IssueCmd::Estimate(args) => {
+			let project = get_project()?;
+			let issue = CliContext::global().get_issue(args.iid, &project)?;
+			let endpoint = SetIssueTimeEstimate::builder()
+				.project(project.id.value())
+				.issue(issue.iid)
+				.duration(&args.time.0)
+				.build()?;
+			let stats: TimeStats = endpoint
+				.query(&CliContext::global().gitlab)
+				.with_context(|| {
+					format!("Failed to set the time estimate of issue #{}", issue.iid)
+				})?;
+			let reference = format!("#{}", issue.iid);
+			CliContext::global().print_time_stats(&reference, &issue.title, &stats)?;
+		}
+	}
+
+	Ok(ExitCode::from(0))
+}
@@ -0,0 +1,117 @@
+use gil::{
+	api::groups::GroupIterations,
+	args::{self, IterationCmd, OwnedNameOrId},
+	context::CliContext,
+	types::{Group, Issue, Iteration},
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use gitlab::api::{
+	self,
+	groups::issues::{IssueIteration, Issues},
+	projects::issues::notes::CreateIssueNote,
+	Pagination, Query,
+};
+use std::process::ExitCode;
+
+/// Returns the group given on the command line or the group of the project
+fn get_group(group: Option<&OwnedNameOrId>, project: Option<&OwnedNameOrId>) -> Result<Group> {
+	if let Some(group) = group {
+		CliContext::global().get_group(group)
+	} else {
+		let project = CliContext::global().get_project(project)?;
+		let (namespace, _) = project
+			.path_with_namespace
+			.rsplit_once('/')
+			.ok_or_else(|| anyhow!("Can't find the group of {}", &project.path_with_namespace))?;
+		CliContext::global().get_group(namespace)
+	}
+}
+
+/// List the iterations of a group and its parents in a given state
+fn get_iterations(group: &Group, state: &str) -> Result<Vec<Iteration>> {
+	let endpoint = GroupIterations::builder()
+		.group(group.id)
+		.state(Some(state))
+		.include_ancestors(true)
+		.build()?;
+	api::paged(endpoint, Pagination::All)
+		.query(&CliContext::global().gitlab)
+		.with_context(|| format!("Failed to list the iterations of {}", &group.full_path))
+}
+
+/// Returns the iteration with the given id, or the current iteration of the group
+fn get_iteration(group: &Group, id: Option<u64>) -> Result<Iteration> {
+	let iteration = match id {
+		Some(id) => get_iterations(group, "all")?
+			.into_iter()
+			.find(|iteration| iteration.id == id),
+		None => get_iterations(group, "current")?.into_iter().next(),
+	};
+	iteration.ok_or_else(|| match id {
+		Some(id) => anyhow!("Iteration {} not found in {}", id, &group.full_path),
+		None => anyhow!("No current iteration in {}", &group.full_path),
+	})
+}
+
+pub fn cmd(args: &args::Iteration) -> Result<ExitCode> {
+	let (group, project) = (args.group.as_ref(), args.project.as_ref());
+	match &args.cmd {
+		IterationCmd::List(args) => {
+			let group = get_group(group, project)?;
+			let iterations = get_iterations(&group, &args.state)?;
+			CliContext::global().print_iterations(&iterations, &group)
+		}
+		IterationCmd::Show(args) => {
+			let group = get_group(group, project)?;
+			let iteration = get_iteration(&group, args.id)?;
+			let endpoint = Issues::builder()
+				.group(group.id)
+				.iteration(IssueIteration::Id(iteration.id))
+				.build()?;
+			let issues: Vec<Issue> = api::paged(endpoint, Pagination::All)
+				.query(&CliContext::global().gitlab)
+				.with_context(|| {
+					format!(
+						"Failed to list the issues of iteration {}",
+						iteration.name()
+					)
+				})?;
+			CliContext::global().print_iteration(&iteration, &issues)
+		}
+		IterationCmd::Assign(args) => {
+			if args.iids.is_empty() {
+				bail!("No issue to assign");
+			}
+			let group = get_group(group, project)?;
+			let project = CliContext::global().get_project(project)?;
+			let iteration = get_iteration(&group, args.iteration)?;
+			// the REST API can't set the iteration of an issue but a quick action can
+			let body = format!("/iteration *iteration:{}", iteration.id);
+			for iid in &args.iids {
+				let issue = CliContext::global().get_issue(*iid, &project)?;
+				let endpoint = CreateIssueNote::builder()
+					.project(project.id.value())
+					.issue(issue.iid)
+					.body(body.as_str())
+					.build()?;
+				api::ignore(endpoint)
+					.query(&CliContext::global().gitlab)
+					.with_context(|| {
+						format!(
+							"Failed to assign issue #{} to iteration {}",
+							issue.iid,
+							iteration.name()
+						)
+					})?;
+				println!(
+					"Issue #{} ({}) assigned to iteration {}",
+					issue.iid,
+					&issue.title,
+					iteration.name()
+				);
+			}
+			Ok(ExitCode::from(0))
+		}
+	}
+}
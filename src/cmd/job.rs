@@ -0,0 +1,71 @@
+use gil::{
+	api::projects::jobs::KeepJobArtifacts,
+	args::{self, JobCmd},
+	context::CliContext,
+	types::Job,
+};
+
+use anyhow::{bail, Context, Result};
+use gitlab::api::{
+	projects::jobs::{JobVariableAttribute, PlayJob},
+	Query,
+};
+use std::{process::ExitCode, slice};
+
+pub fn cmd(args: &args::Job) -> Result<ExitCode> {
+	let project = CliContext::global().get_project(args.project.as_ref())?;
+
+	match &args.cmd {
+		JobCmd::Play(args) => {
+			let id = match (args.id, &args.name) {
+				(Some(id), None) => id,
+				(None, Some(name)) => {
+					let ref_ = CliContext::global().check_ref(args.ref_.as_deref(), &project)?;
+					CliContext::global()
+						.get_manual_job(name, args.pipeline, &project, &ref_)?
+						.id
+						.value()
+				}
+				(Some(_), Some(_)) => bail!("Give either a job id or a job name, not both"),
+				(None, None) => bail!("Give the id or the name (--name) of the job to play"),
+			};
+			let variables = args
+				.var
+				.iter()
+				.map(|var| {
+					JobVariableAttribute::builder()
+						.key(var.0.as_str())
+						.value(var.1.as_str())
+						.build()
+				})
+				.collect::<Result<Vec<_>, _>>()?;
+			let endpoint = PlayJob::builder()
+				.project(project.id.value())
+				.job(id)
+				.job_variables_attributes(variables.into_iter())
+				.build()?;
+			let job: Job = endpoint
+				.query(&CliContext::global().gitlab)
+				.with_context(|| format!("Failed to play job {}", id))?;
+			CliContext::global().print_jobs(slice::from_ref(&job))?;
+			if CliContext::global().open {
+				CliContext::global().open_url(job.web_url);
+			}
+		}
+		JobCmd::KeepArtifacts(args) => {
+			let endpoint = KeepJobArtifacts::builder()
+				.project(project.id.value())
+				.job(args.id)
+				.build()?;
+			let job: Job = endpoint
+				.query(&CliContext::global().gitlab)
+				.with_context(|| format!("Failed to keep the artifacts of job {}", args.id))?;
+			println!("Artifacts of job {} ({}) will be kept", job.id, &job.name);
+			if CliContext::global().open {
+				CliContext::global().open_url(job.web_url);
+			}
+		}
+	}
+
+	Ok(ExitCode::from(0))
+}
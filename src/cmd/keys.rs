@@ -1,25 +1,129 @@
-use crate::{
-	api::users::keys::{AddKey, DeleteKey, ListKeys},
+use gil::{
+	api::users::keys::{AddKey, DeleteKey, GetUserKey, ListKeys},
 	args::{self, KeyIdType, KeysCmd},
 	context::CliContext,
 	types::SshKey,
 };
 
-use anyhow::{Context, Result};
-use gitlab::api::{self, Query};
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{NaiveTime, Utc};
+use gitlab::api::{self, Pagination, Query};
 use ssh_key::{HashAlg, PublicKey};
-use std::{fs::read_to_string, process::ExitCode};
+use std::{
+	fs::read_to_string,
+	panic,
+	process::{Command, ExitCode},
+	slice, thread,
+};
+
+/// Read the candidate public keys from a file, the ssh-agent or GitHub
+fn read_keys(args: &args::AddKey) -> Result<Vec<PublicKey>> {
+	let sources = [args.key.is_some(), args.from_agent, args.from_github.is_some()];
+	if sources.into_iter().filter(|given| *given).count() > 1 {
+		bail!("Give only one of a key path, --from-agent or --from-github")
+	}
+	let keys = if args.from_agent {
+		let output = Command::new("ssh-add")
+			.arg("-L")
+			.output()
+			.context("Failed to run ssh-add")?;
+		if !output.status.success() {
+			// no identities is reported on stdout, no agent on stderr
+			let reason = String::from_utf8_lossy(&output.stdout)
+				+ String::from_utf8_lossy(&output.stderr);
+			bail!("Unable to list the ssh-agent identities: {}", reason.trim());
+		}
+		String::from_utf8(output.stdout)?
+	} else if let Some(user) = &args.from_github {
+		let url = format!("https://github.com/{}.keys", user);
+		reqwest::blocking::get(&url)
+			.and_then(|rsp| rsp.error_for_status())
+			.and_then(|rsp| rsp.text())
+			.with_context(|| format!("Failed to get the keys from {}", &url))?
+	} else if let Some(path) = &args.key {
+		read_to_string(path).with_context(|| format!("Can't read ssh key {}", path))?
+	} else {
+		bail!("Give a key path, --from-agent or --from-github")
+	};
+	keys.lines()
+		.filter(|line| !line.trim().is_empty())
+		.map(|line| PublicKey::from_openssh(line).map_err(Into::into))
+		.collect()
+}
+
+/// Choose a key interactively if there are several candidates
+fn pick_key(mut keys: Vec<PublicKey>) -> Result<PublicKey> {
+	if keys.len() <= 1 {
+		return keys.pop().ok_or_else(|| anyhow!("No ssh key found"));
+	}
+	let items: Vec<_> = keys
+		.iter()
+		.map(|key| {
+			format!(
+				"{} {} {}",
+				key.algorithm().as_str(),
+				key.fingerprint(HashAlg::Sha256),
+				key.comment()
+			)
+		})
+		.collect();
+	let index = CliContext::global().prompt.select("Key to add:", &items)?;
+	Ok(keys.swap_remove(index))
+}
+
+/// Maximum number of keys queried at the same time
+const MAX_CONCURRENT_QUERIES: usize = 8;
+
+/// Get the keys of a user one by one to have all their attributes, querying them concurrently
+/// by batches
+fn get_keys_details(username: &str, keys: &[SshKey]) -> Result<Vec<SshKey>> {
+	let mut details = Vec::with_capacity(keys.len());
+	for batch in keys.chunks(MAX_CONCURRENT_QUERIES) {
+		let batch: Vec<SshKey> = thread::scope(|scope| {
+			let queries: Vec<_> = batch
+				.iter()
+				.map(|key| {
+					scope.spawn(move || -> Result<SshKey> {
+						GetUserKey::builder()
+							.user(username)
+							.key_id(key.id.value())
+							.build()?
+							.query(&CliContext::global().gitlab)
+							.with_context(|| {
+								format!("Failed to get key {} of {}", &key.title, username)
+							})
+					})
+				})
+				.collect();
+			queries
+				.into_iter()
+				.map(|query| query.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+				.collect::<Result<_>>()
+		})?;
+		details.extend(batch);
+	}
+	Ok(details)
+}
 
 pub fn cmd(args: &args::Keys) -> Result<ExitCode> {
 	match &args.cmd {
 		KeysCmd::Add(args) => {
-			// read ssh key on disk
-			let key = read_to_string(&args.key)?;
-			let ssh_key = PublicKey::from_openssh(&key)?;
+			if args.usage_type.is_some() {
+				CliContext::global().require_version((15, 7), "The usage type of ssh keys")?;
+			}
+
+			// read ssh key on disk, from the agent or from github
+			let ssh_key = pick_key(read_keys(args)?)?;
+			let key = ssh_key.to_openssh()?;
 			let fingerprint = ssh_key.fingerprint(HashAlg::Sha256);
 
-			// get title from args or key
-			let title = args.title.as_deref().unwrap_or_else(|| ssh_key.comment());
+			// get title from args or key (github keys have no comment)
+			let default_title = match &args.from_github {
+				Some(user) if ssh_key.comment().is_empty() => format!("{}@github", user),
+				_ if ssh_key.comment().is_empty() => fingerprint.to_string(),
+				_ => ssh_key.comment().to_owned(),
+			};
+			let title = args.title.as_deref().unwrap_or(&default_title);
 
 			// try to delete existing key with same fingerprint on overwrite mode
 			if args.overwrite {
@@ -31,10 +135,15 @@ pub fn cmd(args: &args::Keys) -> Result<ExitCode> {
 			}
 
 			// try to add the key
-			let endpoint = AddKey::builder().key(&key).title(title).build()?;
+			let endpoint = AddKey::builder()
+				.key(&key)
+				.title(title)
+				.expires_at(args.expires_at.map(|date| date.and_time(NaiveTime::MIN).and_utc()))
+				.usage_type(args.usage_type)
+				.build()?;
 			api::ignore(endpoint)
 				.query(&CliContext::global().gitlab)
-				.with_context(|| format!("Failed to add ssh key {}", &args.key))?;
+				.with_context(|| format!("Failed to add ssh key {}", title))?;
 			println!(
 				"Key {} has been {}",
 				&title,
@@ -57,7 +166,7 @@ pub fn cmd(args: &args::Keys) -> Result<ExitCode> {
 				} else {
 					format!("https://{}/-/profile/keys", CliContext::global().repo.host)
 				};
-				let _ = open::that(url);
+				CliContext::global().open_url(url);
 			}
 
 			Ok(ExitCode::from(0))
@@ -66,20 +175,30 @@ pub fn cmd(args: &args::Keys) -> Result<ExitCode> {
 		KeysCmd::List(args) => {
 			let user = CliContext::global().get_user(args.user.as_deref())?;
 			let endpoint = ListKeys::builder().user(&user.username).build()?;
-			let keys: Vec<SshKey> = endpoint.query(&CliContext::global().gitlab)?;
+			let mut keys: Vec<SshKey> =
+				api::paged(endpoint, Pagination::All).query(&CliContext::global().gitlab)?;
+			// the listing of the keys of another user lacks the expiration date and the usage type
+			if args.user.is_some() {
+				keys = get_keys_details(&user.username, &keys)?;
+			}
 
 			if CliContext::global().open {
-				let _ = open::that(format!(
+				CliContext::global().open_url(format!(
 					"https://{}/-/profile/keys",
 					CliContext::global().repo.host
 				));
 			}
 
-			CliContext::global().print_keys(&keys, &user)
+			CliContext::global().print_keys(&keys, &user, args.detail || args.user.is_some())
 		}
 
 		KeysCmd::Delete(args) => {
 			let key = CliContext::global().get_key(&args.id)?;
+			let user = CliContext::global().get_user(None)?;
+			CliContext::global().print_keys(slice::from_ref(&key), &user, true)?;
+			if !args.yes && !CliContext::global().prompt.confirm("Delete 1 key?")? {
+				return Ok(ExitCode::from(1));
+			}
 			let endpoint = DeleteKey::builder().key_id(key.id.value()).build()?;
 			api::ignore(endpoint)
 				.query(&CliContext::global().gitlab)
@@ -91,7 +210,7 @@ pub fn cmd(args: &args::Keys) -> Result<ExitCode> {
 			}
 
 			if CliContext::global().open {
-				let _ = open::that(format!(
+				CliContext::global().open_url(format!(
 					"https://{}/-/profile/keys",
 					CliContext::global().repo.host
 				));
@@ -99,5 +218,45 @@ pub fn cmd(args: &args::Keys) -> Result<ExitCode> {
 
 			Ok(ExitCode::from(0))
 		}
+
+		KeysCmd::Prune(args) => {
+			if !args.expired && args.unused_since.is_none() {
+				bail!("Nothing to prune, use --expired and/or --unused-since");
+			}
+			let user = CliContext::global().get_user(None)?;
+			let endpoint = ListKeys::builder().user(&user.username).build()?;
+			let keys: Vec<SshKey> =
+				api::paged(endpoint, Pagination::All).query(&CliContext::global().gitlab)?;
+
+			// keys never used are considered used at creation
+			let unused_before = args.unused_since.map(|period| Utc::now() - period.0);
+			let keys: Vec<SshKey> = keys
+				.into_iter()
+				.filter(|key| {
+					(args.expired && key.expired())
+						|| unused_before.is_some_and(|date| {
+							key.last_used_at.unwrap_or(key.created_at) < date
+						})
+				})
+				.collect();
+			if keys.is_empty() {
+				println!("No key to prune");
+				return Ok(ExitCode::from(0));
+			}
+
+			CliContext::global().print_keys(&keys, &user, true)?;
+			let question = format!("Delete {} key(s)?", keys.len());
+			if !args.yes && !CliContext::global().prompt.confirm(&question)? {
+				return Ok(ExitCode::from(1));
+			}
+			for key in keys {
+				let endpoint = DeleteKey::builder().key_id(key.id.value()).build()?;
+				api::ignore(endpoint)
+					.query(&CliContext::global().gitlab)
+					.with_context(|| format!("Failed to delete key {}", &key.title))?;
+				println!("Key {}({}) deleted", &key.title, key.id.value());
+			}
+			Ok(ExitCode::from(0))
+		}
 	}
 }
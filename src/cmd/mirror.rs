@@ -0,0 +1,119 @@
+use gil::{
+	api::projects::remote_mirrors::{
+		CreateRemoteMirror, DeleteRemoteMirror, RemoteMirrors, StartPullMirror, SyncRemoteMirror,
+	},
+	args::{self, MirrorCmd},
+	context::CliContext,
+	types::RemoteMirror,
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use gitlab::api::{self, Pagination, Query};
+use std::{env, process::ExitCode};
+use url::Url;
+
+/// Add the credentials to the mirror url
+fn mirror_url(args: &args::MirrorAdd) -> Result<String> {
+	let mut url = Url::parse(&args.url).with_context(|| format!("Invalid url {}", &args.url))?;
+	if let Some(username) = &args.username {
+		url.set_username(username)
+			.map_err(|_| anyhow!("Can't set credentials on {}", &args.url))?;
+	}
+	if let Some(var) = &args.password_env {
+		let password =
+			env::var(var).with_context(|| format!("Environment variable {} not set", var))?;
+		url.set_password(Some(&password))
+			.map_err(|_| anyhow!("Can't set credentials on {}", &args.url))?;
+	}
+	Ok(url.into())
+}
+
+pub fn cmd(args: &args::Mirror) -> Result<ExitCode> {
+	let project = CliContext::global().get_project(args.project.as_ref())?;
+	match &args.cmd {
+		MirrorCmd::List(_) => {
+			let endpoint = RemoteMirrors::builder()
+				.project(project.id.value())
+				.build()?;
+			let mirrors: Vec<RemoteMirror> = api::paged(endpoint, Pagination::All)
+				.query(&CliContext::global().gitlab)
+				.with_context(|| {
+					format!(
+						"Failed to list mirrors of {}",
+						&project.name_with_namespace
+					)
+				})?;
+			CliContext::global().print_mirrors(&mirrors, &project)?;
+		}
+
+		MirrorCmd::Add(args) => {
+			if args.password_env.is_some() && args.username.is_none() {
+				bail!("A password needs a username");
+			}
+			let url = mirror_url(args)?;
+			let endpoint = CreateRemoteMirror::builder()
+				.project(project.id.value())
+				.url(&url)
+				.enabled(Some(!args.disabled))
+				.only_protected_branches(Some(args.protected))
+				.keep_divergent_refs(Some(args.keep_divergent))
+				.build()?;
+			let mirror: RemoteMirror = endpoint
+				.query(&CliContext::global().gitlab)
+				.with_context(|| format!("Failed to add mirror {}", &args.url))?;
+			println!(
+				"mirror {}({}) has been added to project {}",
+				&mirror.url, mirror.id, &project.path_with_namespace
+			);
+		}
+
+		MirrorCmd::Delete(args) => {
+			let endpoint = DeleteRemoteMirror::builder()
+				.project(project.id.value())
+				.mirror_id(args.id)
+				.build()?;
+			api::ignore(endpoint)
+				.query(&CliContext::global().gitlab)
+				.with_context(|| format!("Failed to delete mirror {}", args.id))?;
+			println!(
+				"mirror {} has been deleted from project {}",
+				args.id, &project.path_with_namespace
+			);
+		}
+
+		MirrorCmd::Sync(args) => {
+			if let Some(id) = args.id {
+				let endpoint = SyncRemoteMirror::builder()
+					.project(project.id.value())
+					.mirror_id(id)
+					.build()?;
+				api::ignore(endpoint)
+					.query(&CliContext::global().gitlab)
+					.with_context(|| format!("Failed to update mirror {}", id))?;
+				println!("mirror {} update has been triggered", id);
+			} else {
+				let endpoint = StartPullMirror::builder()
+					.project(project.id.value())
+					.build()?;
+				api::ignore(endpoint)
+					.query(&CliContext::global().gitlab)
+					.with_context(|| {
+						format!(
+							"Failed to update the pull mirror of {}",
+							&project.path_with_namespace
+						)
+					})?;
+				println!(
+					"pull mirror update of project {} has been triggered",
+					&project.path_with_namespace
+				);
+			}
+		}
+	}
+
+	if CliContext::global().open {
+		CliContext::global().open_url(format!("{}/-/settings/repository", project.web_url));
+	}
+
+	Ok(ExitCode::from(0))
+}
@@ -0,0 +1,526 @@
+use crate::cmd::todo::resolve_todos;
+use gil::{
+	api::{
+		merge_requests::{MergeRequestsScope, OpenedMergeRequests},
+		projects::{
+			deployments::Deployments,
+			merge_requests::{
+				AddMergeRequestSpentTime, ResetMergeRequestApprovals, SetMergeRequestTimeEstimate,
+				SubscribeMergeRequest, UnsubscribeMergeRequest,
+			},
+			repository::{CherryPickCommit, Compare},
+		},
+	},
+	args::{self, MrCmd},
+	context::CliContext,
+	types::{Commit, Comparison, Deployment, Diff, Environment, MergeRequest, Project, TimeStats},
+};
+
+use anyhow::{bail, Context, Result};
+use gitlab::api::{
+	self,
+	projects::{
+		environments,
+		merge_requests::{
+			self, ApproveMergeRequest, CreateMergeRequest, EditMergeRequest, MergeMergeRequest,
+			MergeRequestCommits, MergeRequestDiffs, RebaseMergeRequest,
+		},
+		repository::branches::CreateBranch,
+	},
+	Pagination, Query,
+};
+use std::{collections::HashSet, process::ExitCode, thread, time::Duration};
+
+/// Maximum time to wait for a rebase to finish in seconds
+const REBASE_TIMEOUT: u64 = 300;
+
+/// Change the title of a merge request to toggle its draft state
+fn set_draft(mr: &MergeRequest, project: &Project, draft: bool) -> Result<ExitCode> {
+	let state = if draft { "draft" } else { "ready" };
+	if mr.draft == draft {
+		println!("Merge request !{} ({}) is already {}", mr.iid, &mr.title, state);
+		return Ok(ExitCode::from(0));
+	}
+	let title = if draft {
+		mr.draft_title()
+	} else {
+		mr.ready_title().to_owned()
+	};
+	let endpoint = EditMergeRequest::builder()
+		.project(project.id.value())
+		.merge_request(mr.iid)
+		.title(&title)
+		.build()?;
+	api::ignore(endpoint)
+		.query(&CliContext::global().gitlab)
+		.with_context(|| format!("Failed to mark merge request !{} as {}", mr.iid, state))?;
+	println!("Merge request !{} ({}) marked as {}", mr.iid, &title, state);
+	Ok(ExitCode::from(0))
+}
+
+/// Rebase a merge request and wait for the rebase to finish
+fn rebase(mr: &MergeRequest, project: &Project, skip_ci: bool) -> Result<ExitCode> {
+	let endpoint = RebaseMergeRequest::builder()
+		.project(project.id.value())
+		.merge_request(mr.iid)
+		.skip_ci(skip_ci)
+		.build()?;
+	api::ignore(endpoint)
+		.query(&CliContext::global().gitlab)
+		.with_context(|| format!("Failed to rebase merge request !{}", mr.iid))?;
+	println!(
+		"Rebasing {} onto {} for merge request !{}",
+		&mr.source_branch, &mr.target_branch, mr.iid
+	);
+
+	// the rebase is asynchronous
+	let endpoint = merge_requests::MergeRequest::builder()
+		.project(project.id.value())
+		.merge_request(mr.iid)
+		.include_rebase_in_progress(true)
+		.build()?;
+	for _ in 0..REBASE_TIMEOUT {
+		thread::sleep(Duration::from_secs(1));
+		let mr: MergeRequest = endpoint
+			.query(&CliContext::global().gitlab)
+			.with_context(|| format!("Failed to get merge request !{}", mr.iid))?;
+		if mr.rebase_in_progress == Some(true) {
+			continue;
+		}
+		if let Some(error) = mr.merge_error.as_deref().filter(|e| !e.is_empty()) {
+			eprintln!("Rebase of merge request !{} failed: {}", mr.iid, error);
+			if mr.has_conflicts {
+				eprintln!(
+					"{} has conflicts with {}, resolve them locally",
+					&mr.source_branch, &mr.target_branch
+				);
+			}
+			return Ok(ExitCode::from(1));
+		}
+		println!("Merge request !{} rebased", mr.iid);
+		return Ok(ExitCode::from(0));
+	}
+	bail!(
+		"Rebase of merge request !{} still in progress after {}s",
+		mr.iid,
+		REBASE_TIMEOUT
+	)
+}
+
+/// Files changed on both the source and the target branches of a merge request since they
+/// diverged, which are the candidates for conflicts
+fn changed_on_both(mr: &MergeRequest, project: &Project) -> Result<Vec<String>> {
+	let endpoint = MergeRequestDiffs::builder()
+		.project(project.id.value())
+		.merge_request(mr.iid)
+		.build()?;
+	let source: Vec<Diff> = api::paged(endpoint, Pagination::All)
+		.query(&CliContext::global().gitlab)
+		.with_context(|| format!("Failed to get the changes of merge request !{}", mr.iid))?;
+
+	// changes on the target branch since the merge base with the source branch
+	let endpoint = Compare::builder()
+		.project(project.id.value())
+		.from(&mr.source_branch)
+		.to(&mr.target_branch)
+		.build()?;
+	let target: Comparison = endpoint
+		.query(&CliContext::global().gitlab)
+		.with_context(|| {
+			format!(
+				"Failed to compare {} with {}",
+				&mr.source_branch, &mr.target_branch
+			)
+		})?;
+	let target: HashSet<&str> = target
+		.diffs
+		.iter()
+		.flat_map(|diff| [diff.old_path.as_str(), diff.new_path.as_str()])
+		.collect();
+
+	let mut files: Vec<String> = source
+		.into_iter()
+		.filter(|diff| {
+			target.contains(diff.old_path.as_str()) || target.contains(diff.new_path.as_str())
+		})
+		.map(|diff| diff.new_path)
+		.collect();
+	files.sort();
+	files.dedup();
+	Ok(files)
+}
+
+/// Set the reviewers of a merge request
+fn set_reviewers(mr: &MergeRequest, project: &Project, reviewers: &[u64]) -> Result<()> {
+	let mut builder = EditMergeRequest::builder();
+	builder.project(project.id.value()).merge_request(mr.iid);
+	if reviewers.is_empty() {
+		builder.without_reviewer();
+	} else {
+		builder.reviewers(reviewers.iter().copied());
+	}
+	api::ignore(builder.build()?)
+		.query(&CliContext::global().gitlab)
+		.with_context(|| format!("Failed to set the reviewers of merge request !{}", mr.iid))
+}
+
+/// Request a review from users, removing them first from the reviewers if they already are, so
+/// that gitlab asks them again
+fn request_review(mr: &MergeRequest, project: &Project, usernames: &[String]) -> Result<()> {
+	let current: Vec<u64> = mr.reviewers.iter().map(|user| user.id.value()).collect();
+	let requested: Vec<u64> = if usernames.is_empty() {
+		current.clone()
+	} else {
+		usernames
+			.iter()
+			.map(|username| Ok(CliContext::global().get_user(Some(username))?.id.value()))
+			.collect::<Result<_>>()?
+	};
+	if requested.is_empty() {
+		bail!(
+			"Merge request !{} has no reviewers, give some with -r",
+			mr.iid
+		);
+	}
+
+	if requested.iter().any(|id| current.contains(id)) {
+		let others: Vec<u64> = current
+			.iter()
+			.copied()
+			.filter(|id| !requested.contains(id))
+			.collect();
+		set_reviewers(mr, project, &others)?;
+	}
+	let mut reviewers = current;
+	for id in requested {
+		if !reviewers.contains(&id) {
+			reviewers.push(id);
+		}
+	}
+	set_reviewers(mr, project, &reviewers)
+}
+
+/// The commits to cherry-pick to port a merged merge request: the merge or squash commit, or
+/// the commits of the merge request from the oldest for fast-forward merges
+fn merged_commits(mr: &MergeRequest, project: &Project) -> Result<Vec<String>> {
+	if mr.state != "merged" {
+		bail!("Merge request !{} is {}, not merged", mr.iid, &mr.state);
+	}
+	if let Some(sha) = mr
+		.merge_commit_sha
+		.as_ref()
+		.or(mr.squash_commit_sha.as_ref())
+	{
+		return Ok(vec![sha.value().to_owned()]);
+	}
+	let endpoint = MergeRequestCommits::builder()
+		.project(project.id.value())
+		.merge_request(mr.iid)
+		.build()?;
+	let commits: Vec<Commit> = api::paged(endpoint, Pagination::All)
+		.query(&CliContext::global().gitlab)
+		.with_context(|| format!("Failed to list the commits of merge request !{}", mr.iid))?;
+	Ok(commits
+		.into_iter()
+		.rev()
+		.map(|commit| commit.id.value().to_owned())
+		.collect())
+}
+
+/// Cherry-pick a merged merge request onto a branch, or onto a new branch with a backport merge
+/// request
+fn cherry_pick(mr: &MergeRequest, project: &Project, branch: &str, create_mr: bool) -> Result<()> {
+	let shas = merged_commits(mr, project)?;
+	let target = if create_mr {
+		let name = format!("cherry-pick-{}-{}", mr.iid, &shas[shas.len() - 1][..8]);
+		let endpoint = CreateBranch::builder()
+			.project(project.id.value())
+			.branch(name.as_str())
+			.ref_(branch)
+			.build()?;
+		api::ignore(endpoint)
+			.query(&CliContext::global().gitlab)
+			.with_context(|| format!("Failed to create branch {} from {}", &name, branch))?;
+		name
+	} else {
+		branch.to_owned()
+	};
+
+	for sha in &shas {
+		let endpoint = CherryPickCommit::builder()
+			.project(project.id.value())
+			.sha(sha)
+			.branch(&target)
+			.build()?;
+		let commit: Commit = endpoint
+			.query(&CliContext::global().gitlab)
+			.with_context(|| format!("Failed to cherry-pick {} onto {}", &sha[..8], &target))?;
+		println!(
+			"Cherry-picked {} onto {} as {}",
+			&sha[..8],
+			&target,
+			&commit.short_id
+		);
+	}
+
+	if create_mr {
+		let title = format!("Backport !{} to {}: {}", mr.iid, branch, mr.ready_title());
+		let description = format!("Cherry-picked from !{}", mr.iid);
+		let endpoint = CreateMergeRequest::builder()
+			.project(project.id.value())
+			.source_branch(target.as_str())
+			.target_branch(branch)
+			.title(title.as_str())
+			.description(description.as_str())
+			.remove_source_branch(true)
+			.build()?;
+		let backport: MergeRequest = endpoint
+			.query(&CliContext::global().gitlab)
+			.with_context(|| format!("Failed to create a merge request from {}", &target))?;
+		println!(
+			"Backport merge request !{} created: {}",
+			backport.iid, &backport.web_url
+		);
+		if CliContext::global().open {
+			CliContext::global().open_url(&backport.web_url);
+		}
+	}
+	Ok(())
+}
+
+/// List the opened merge requests of the current user across all projects
+fn mine(args: &args::MrMine) -> Result<ExitCode> {
+	let user = CliContext::global().get_current_user()?;
+	let mut builder = OpenedMergeRequests::builder();
+	let role = match (args.reviewer, args.assignee) {
+		(false, false) => {
+			builder.scope(MergeRequestsScope::CreatedByMe);
+			"authored by"
+		}
+		(false, true) => {
+			builder.scope(MergeRequestsScope::AssignedToMe);
+			"assigned to"
+		}
+		(true, false) => {
+			builder
+				.scope(MergeRequestsScope::All)
+				.reviewer_id(Some(user.id.value()));
+			"awaiting a review from"
+		}
+		(true, true) => bail!("Give either --reviewer or --assignee"),
+	};
+	let mrs: Vec<MergeRequest> = api::paged(builder.build()?, Pagination::All)
+		.query(&CliContext::global().gitlab)
+		.context("Failed to list merge requests")?;
+	CliContext::global().print_user_merge_requests(&mrs, &user, role)
+}
+
+pub fn cmd(args: &args::Mr) -> Result<ExitCode> {
+	// listing merge requests across projects doesn't need a project, so it's fetched on demand
+	let get_project = || CliContext::global().get_project(args.project.as_ref());
+	match &args.cmd {
+		MrCmd::Mine(args) => return mine(args),
+		MrCmd::Env(args) => {
+			let project = get_project()?;
+			let mr = CliContext::global().get_merge_request(args.iid, &project)?;
+
+			// review apps are the environments deployed from the merge request branch
+			let mr_ref = format!("refs/merge-requests/{}/head", mr.iid);
+			let endpoint = Deployments::builder()
+				.project(project.id.value())
+				.build()?;
+			let deployments: Vec<Deployment> = api::paged(endpoint, Pagination::Limit(100))
+				.query(&CliContext::global().gitlab)
+				.with_context(|| {
+					format!(
+						"Failed to list deployments of {}",
+						&project.name_with_namespace
+					)
+				})?;
+			let mut environments: Vec<Environment> = Vec::new();
+			let deployments = deployments
+				.into_iter()
+				.filter(|d| d.ref_ == mr.source_branch || d.ref_ == mr_ref);
+			for deployment in deployments {
+				let Some(environment) = deployment.environment else {
+					continue;
+				};
+				if environments.iter().any(|e| e.id == environment.id) {
+					continue;
+				}
+				// get the environment state
+				let environment: Environment = environments::Environment::builder()
+					.project(project.id.value())
+					.environment(environment.id)
+					.build()?
+					.query(&CliContext::global().gitlab)
+					.with_context(|| format!("Failed to get environment {}", &environment.name))?;
+				environments.push(environment);
+			}
+
+			CliContext::global().print_review_apps(&environments, &mr)?;
+			if CliContext::global().open {
+				for url in environments.iter().filter_map(|e| e.external_url.as_ref()) {
+					CliContext::global().open_url(url);
+				}
+			}
+		}
+		MrCmd::Subscribe(args) => {
+			let project = get_project()?;
+			let mr = CliContext::global().get_merge_request(args.iid, &project)?;
+			let endpoint = SubscribeMergeRequest::builder()
+				.project(project.id.value())
+				.merge_request(mr.iid)
+				.build()?;
+			let subscribed = CliContext::global()
+				.query_toggle(endpoint)
+				.with_context(|| format!("Failed to subscribe to merge request !{}", mr.iid))?;
+			if subscribed {
+				println!("Subscribed to merge request !{} ({})", mr.iid, &mr.title);
+			} else {
+				println!("Already subscribed to merge request !{} ({})", mr.iid, &mr.title);
+			}
+		}
+		MrCmd::Unsubscribe(args) => {
+			let project = get_project()?;
+			let mr = CliContext::global().get_merge_request(args.iid, &project)?;
+			let endpoint = UnsubscribeMergeRequest::builder()
+				.project(project.id.value())
+				.merge_request(mr.iid)
+				.build()?;
+			let unsubscribed = CliContext::global()
+				.query_toggle(endpoint)
+				.with_context(|| format!("Failed to unsubscribe from merge request !{}", mr.iid))?;
+			if unsubscribed {
+				println!("Unsubscribed from merge request !{} ({})", mr.iid, &mr.title);
+			} else {
+				println!("Not subscribed to merge request !{} ({})", mr.iid, &mr.title);
+			}
+		}
+		MrCmd::Spend(args) => {
+			let project = get_project()?;
+			let mr = CliContext::global().get_merge_request(Some(args.iid), &project)?;
+			let endpoint = AddMergeRequestSpentTime::builder()
+				.project(project.id.value())
+				.merge_request(mr.iid)
+				.duration(&args.time.0)
+				.summary(args.summary.as_deref())
+				.build()?;
+			let stats: TimeStats = endpoint
+				.query(&CliContext::global().gitlab)
+				.with_context(|| format!("Failed to add spent time to merge request !{}", mr.iid))?;
+			let reference = format!("!{}", mr.iid);
+			CliContext::global().print_time_stats(&reference, &mr.title, &stats)?;
+		}
+		MrCmd::Estimate(args) => {
+			let project = get_project()?;
+			let mr = CliContext::global().get_merge_request(Some(args.iid), &project)?;
+			let endpoint = SetMergeRequestTimeEstimate::builder()
+				.project(project.id.value())
+				.merge_request(mr.iid)
+				.duration(&args.time.0)
+				.build()?;
+			let stats: TimeStats = endpoint
+				.query(&CliContext::global().gitlab)
+				.with_context(|| {
+					format!("Failed to set the time estimate of merge request !{}", mr.iid)
+				})?;
+			let reference = format!("!{}", mr.iid);
+			CliContext::global().print_time_stats(&reference, &mr.title, &stats)?;
+		}
+		MrCmd::Ready(args) => {
+			let project = get_project()?;
+			let mr = CliContext::global().get_merge_request(args.iid, &project)?;
+			return set_draft(&mr, &project, false);
+		}
+		MrCmd::Draft(args) => {
+			let project = get_project()?;
+			let mr = CliContext::global().get_merge_request(args.iid, &project)?;
+			return set_draft(&mr, &project, true);
+		}
+		MrCmd::Rebase(args) => {
+			let project = get_project()?;
+			let mr = CliContext::global().get_merge_request(args.iid, &project)?;
+			return rebase(&mr, &project, args.skip_ci);
+		}
+		MrCmd::Conflicts(args) => {
+			let project = get_project()?;
+			let mr = CliContext::global().get_merge_request(args.iid, &project)?;
+			let files = changed_on_both(&mr, &project)?;
+			CliContext::global().print_conflicts(&mr, &files)?;
+			if CliContext::global().open && mr.has_conflicts {
+				CliContext::global().open_url(format!("{}/conflicts", &mr.web_url));
+			}
+			return Ok(ExitCode::from(if mr.has_conflicts { 1 } else { 0 }));
+		}
+		MrCmd::CherryPick(args) => {
+			let project = get_project()?;
+			let mr = CliContext::global().get_merge_request(Some(args.iid), &project)?;
+			cherry_pick(&mr, &project, &args.branch, args.create_mr)?;
+		}
+		MrCmd::Approve(args) => {
+			let project = get_project()?;
+			let mr = CliContext::global().get_merge_request(args.iid, &project)?;
+			let endpoint = ApproveMergeRequest::builder()
+				.project(project.id.value())
+				.merge_request(mr.iid)
+				.build()?;
+			api::ignore(endpoint)
+				.query(&CliContext::global().gitlab)
+				.with_context(|| format!("Failed to approve merge request !{}", mr.iid))?;
+			println!("Merge request !{} ({}) approved", mr.iid, &mr.title);
+			if args.resolve_todo {
+				resolve_todos(&project, "MergeRequest", mr.iid)?;
+			}
+		}
+		MrCmd::Merge(args) => {
+			let project = get_project()?;
+			let mr = CliContext::global().get_merge_request(args.iid, &project)?;
+			let endpoint = MergeMergeRequest::builder()
+				.project(project.id.value())
+				.merge_request(mr.iid)
+				.squash(args.squash)
+				.should_remove_source_branch(args.remove_source_branch)
+				.build()?;
+			api::ignore(endpoint)
+				.query(&CliContext::global().gitlab)
+				.with_context(|| format!("Failed to merge merge request !{}", mr.iid))?;
+			println!("Merge request !{} ({}) merged", mr.iid, &mr.title);
+			if args.resolve_todo {
+				resolve_todos(&project, "MergeRequest", mr.iid)?;
+			}
+		}
+		MrCmd::RequestReview(args) => {
+			let project = get_project()?;
+			let mr = CliContext::global().get_merge_request(args.iid, &project)?;
+			if args.reset_approvals {
+				let endpoint = ResetMergeRequestApprovals::builder()
+					.project(project.id.value())
+					.merge_request(mr.iid)
+					.build()?;
+				api::ignore(endpoint)
+					.query(&CliContext::global().gitlab)
+					.with_context(|| {
+						format!("Failed to reset the approvals of merge request !{}", mr.iid)
+					})?;
+				println!("Approvals of merge request !{} reset", mr.iid);
+			}
+			request_review(&mr, &project, &args.reviewer)?;
+			let reviewers: Vec<String> = if args.reviewer.is_empty() {
+				mr.reviewers
+					.iter()
+					.map(|user| user.username.clone())
+					.collect()
+			} else {
+				args.reviewer.clone()
+			};
+			println!(
+				"Review of merge request !{} requested from {}",
+				mr.iid,
+				reviewers.join(", ")
+			);
+		}
+	}
+
+	Ok(ExitCode::from(0))
+}
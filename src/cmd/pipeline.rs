@@ -1,21 +1,36 @@
-use std::process::ExitCode;
+use std::{
+	fs::File,
+	io::{self, BufReader},
+	process::ExitCode,
+};
 
-use crate::{
-	args::{self, PipelineCmd},
+use gil::{
+	api::projects::jobs::DeleteJobArtifacts,
+	args::{self, PipelineCmd, ScheduleCmd},
 	context::CliContext,
-	types,
+	section::Failure,
+	trace::TraceReader,
+	types::{self, StatusState},
+	utils::{glob_match, join},
 };
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use bytesize::ByteSize;
+use chrono::{Duration, Utc};
 use gitlab::api::{
 	self,
 	projects::{
 		jobs::{self, JobScope},
+		pipeline_schedules,
 		pipelines,
+		repository::branches,
 	},
 	Pagination, Query,
 };
 
+/// Number of lines of the log of a failed job shown by --explain-failure
+const FAILURE_LINES: usize = 50;
+
 /// Command implementation
 pub fn cmd(args: &args::Pipeline) -> Result<ExitCode> {
 	match &args.cmd {
@@ -35,10 +50,16 @@ pub fn cmd(args: &args::Pipeline) -> Result<ExitCode> {
 						)
 					})?;
 
-			CliContext::global().print_pipelines(&pipelines, &project)?;
+			let signatures = if cmd_args.signatures {
+				let shas = pipelines.iter().map(|pipeline| &pipeline.sha);
+				Some(CliContext::global().get_signatures(shas, &project)?)
+			} else {
+				None
+			};
+			CliContext::global().print_pipelines(&pipelines, &project, signatures.as_ref())?;
 
 			if CliContext::global().open {
-				let _ = open::that(format!("{}/-/pipelines", &project.web_url));
+				CliContext::global().open_url(format!("{}/-/pipelines", &project.web_url));
 			}
 			Ok(ExitCode::from(0))
 		}
@@ -67,7 +88,7 @@ pub fn cmd(args: &args::Pipeline) -> Result<ExitCode> {
 			CliContext::global().print_jobs(&jobs)?;
 
 			if CliContext::global().open {
-				let _ = open::that(pipeline.web_url);
+				CliContext::global().open_url(pipeline.web_url);
 			}
 			Ok(ExitCode::from(0))
 		}
@@ -75,16 +96,56 @@ pub fn cmd(args: &args::Pipeline) -> Result<ExitCode> {
 		PipelineCmd::Status(cmd_args) => {
 			// get project from command line or context
 			let project = CliContext::global().get_project(cmd_args.project.as_ref())?;
-			// get a reference (a tag or a branch)
-			let ref_ = CliContext::global().check_ref(cmd_args.ref_.as_deref(), &project)?;
-			let pipeline = CliContext::global().get_pipeline(cmd_args.id, &project, &ref_)?;
+			let pipeline = if let Some(iid) = cmd_args.mr {
+				if cmd_args.id.is_some() || cmd_args.ref_.is_some() {
+					bail!("--mr can't be used with a pipeline id or a reference");
+				}
+				CliContext::global().get_mr_pipeline(iid, &project)?
+			} else {
+				// get a reference (a tag or a branch) and its pipeline
+				CliContext::global()
+					.get_ref_pipeline(cmd_args.ref_.as_deref(), cmd_args.id, &project)?
+					.1
+			};
+
+			// get the commit, its signature and the jobs at the same time
+			let sha = pipeline.sha.value();
+			let (signature, jobs) = join(
+				|| {
+					cmd_args.signature.then(|| {
+						join(
+							|| CliContext::global().get_commit(sha, &project),
+							|| CliContext::global().get_commit_signature(sha, &project),
+						)
+					})
+				},
+				|| CliContext::global().get_jobs(&project, pipeline.id.value()),
+			);
 
 			CliContext::global().print_pipeline(&pipeline, &project)?;
-			let jobs = CliContext::global().get_jobs(&project, pipeline.id.value())?;
+			if let Some((commit, signature)) = signature {
+				CliContext::global().print_commit_signature(&commit?, signature?.as_ref())?;
+			}
+			if cmd_args.show_vars {
+				let endpoint = pipelines::PipelineVariables::builder()
+					.project(project.path_with_namespace.as_str())
+					.pipeline(pipeline.id.value())
+					.build()?;
+				let variables: Vec<types::PipelineVariable> = endpoint
+					.query(&CliContext::global().gitlab)
+					.with_context(|| {
+						format!("Failed to get the variables of pipeline {}", pipeline.id)
+					})?;
+				CliContext::global().print_pipeline_variables(&pipeline, &variables)?;
+			}
+			let jobs = jobs?;
 			CliContext::global().print_jobs(&jobs)?;
+			if cmd_args.explain_failure && pipeline.status == StatusState::Failed {
+				explain_failure(&project, &jobs)?;
+			}
 
 			if CliContext::global().open {
-				let _ = open::that(pipeline.web_url);
+				CliContext::global().open_url(pipeline.web_url);
 			}
 			Ok(ExitCode::from(0))
 		}
@@ -92,9 +153,12 @@ pub fn cmd(args: &args::Pipeline) -> Result<ExitCode> {
 		PipelineCmd::Cancel(cmd_args) => {
 			// get project from command line or context
 			let project = CliContext::global().get_project(cmd_args.project.as_ref())?;
-			// get a reference (a tag or a branch)
-			let ref_ = CliContext::global().check_ref(cmd_args.ref_.as_deref(), &project)?;
-			let pipeline = CliContext::global().get_pipeline(cmd_args.id, &project, &ref_)?;
+			// get a reference (a tag or a branch) and its pipeline
+			let (_, pipeline) = CliContext::global().get_ref_pipeline(
+				cmd_args.ref_.as_deref(),
+				cmd_args.id,
+				&project,
+			)?;
 
 			let endpoint = pipelines::CancelPipeline::builder()
 				.project(project.path_with_namespace.to_owned())
@@ -112,7 +176,7 @@ pub fn cmd(args: &args::Pipeline) -> Result<ExitCode> {
 			CliContext::global().print_jobs(&jobs)?;
 
 			if CliContext::global().open {
-				let _ = open::that(pipeline.web_url);
+				CliContext::global().open_url(pipeline.web_url);
 			}
 			Ok(ExitCode::from(0))
 		}
@@ -120,9 +184,12 @@ pub fn cmd(args: &args::Pipeline) -> Result<ExitCode> {
 		PipelineCmd::Retry(cmd_args) => {
 			// get project from command line or context
 			let project = CliContext::global().get_project(cmd_args.project.as_ref())?;
-			// get a reference (a tag or a branch)
-			let ref_ = CliContext::global().check_ref(cmd_args.ref_.as_deref(), &project)?;
-			let pipeline = CliContext::global().get_pipeline(cmd_args.id, &project, &ref_)?;
+			// get a reference (a tag or a branch) and its pipeline
+			let (_, pipeline) = CliContext::global().get_ref_pipeline(
+				cmd_args.ref_.as_deref(),
+				cmd_args.id,
+				&project,
+			)?;
 
 			let endpoint = pipelines::RetryPipeline::builder()
 				.project(project.path_with_namespace.to_owned())
@@ -138,16 +205,45 @@ pub fn cmd(args: &args::Pipeline) -> Result<ExitCode> {
 			CliContext::global().print_jobs(&jobs)?;
 
 			if CliContext::global().open {
-				let _ = open::that(pipeline.web_url);
+				CliContext::global().open_url(pipeline.web_url);
 			}
 			Ok(ExitCode::from(0))
 		}
 
 		PipelineCmd::Log(cmd_args) => {
+			if cmd_args.strip_ansi && cmd_args.keep_ansi {
+				bail!("--strip-ansi and --keep-ansi can't be used together");
+			}
+			if cmd_args.tail.is_some() {
+				// the index of the sections are unknown in an excerpt of the log
+				if cmd_args.interactive {
+					bail!("--interactive can't be used with --tail");
+				}
+				if cmd_args
+					.expand
+					.iter()
+					.any(|spec| spec.parse::<usize>().is_ok())
+				{
+					bail!("--expand takes section names with --tail");
+				}
+			}
+			if cmd_args.interactive && cmd_args.output.is_some() && !cmd_args.tee {
+				bail!("--interactive needs --tee to print the log written to a file");
+			}
 			// get project from command line or context
 			let project = CliContext::global().get_project(cmd_args.project.as_ref())?;
-			// get a reference (a tag or a branch)
-			let ref_ = CliContext::global().check_ref(cmd_args.ref_.as_deref(), &project)?;
+			// get the latest pipeline of the merge request or a reference (a tag or a branch)
+			let (pipeline_id, ref_) = if let Some(iid) = cmd_args.mr {
+				if cmd_args.id.is_some() || cmd_args.ref_.is_some() {
+					bail!("--mr can't be used with a pipeline id or a reference");
+				}
+				let pipeline = CliContext::global().get_mr_pipeline(iid, &project)?;
+				let ref_ = pipeline.ref_.unwrap_or_default();
+				(Some(pipeline.id.value()), ref_)
+			} else {
+				let ref_ = CliContext::global().check_ref(cmd_args.ref_.as_deref(), &project)?;
+				(cmd_args.id, ref_)
+			};
 
 			let scopes = [
 				JobScope::Running,
@@ -155,24 +251,351 @@ pub fn cmd(args: &args::Pipeline) -> Result<ExitCode> {
 				JobScope::Success,
 				JobScope::Canceled,
 			];
-			let job = CliContext::global().get_job(
-				cmd_args.job_id,
-				cmd_args.id,
-				&project,
-				&ref_,
-				scopes.into_iter(),
-			)?;
+			let job = if let Some(pattern) = &cmd_args.job_name {
+				if cmd_args.job_id.is_some() {
+					bail!("--job-name can't be used with --job-id");
+				}
+				let mut jobs = CliContext::global().get_named_jobs(
+					pattern,
+					pipeline_id,
+					&project,
+					&ref_,
+					scopes.into_iter(),
+				)?;
+				if jobs.len() > 1 {
+					return print_logs(&project, &jobs, cmd_args);
+				}
+				jobs.swap_remove(0)
+			} else {
+				CliContext::global().get_job(
+					cmd_args.job_id,
+					pipeline_id,
+					&project,
+					&ref_,
+					scopes.into_iter(),
+				)?
+			};
 			let endpoint = jobs::JobTrace::builder()
-				.project(project.path_with_namespace)
+				.project(project.path_with_namespace.as_str())
 				.job(job.id.value())
 				.build()?;
 
-			let log = api::raw(endpoint).query(&CliContext::global().gitlab)?;
-			CliContext::global().print_log(&log, &job, cmd_args)?;
+			let mut log = TraceReader::new(&CliContext::global().gitlab, &endpoint)?;
+			if let Some(path) = &cmd_args.output {
+				File::create(path)
+					.and_then(|mut file| io::copy(&mut log, &mut file))
+					.with_context(|| format!("Failed to write log to {:?}", path))?;
+				if cmd_args.tee {
+					// print the log back from the file instead of fetching it twice
+					let file = File::open(path)
+						.with_context(|| format!("Failed to read log from {:?}", path))?;
+					CliContext::global().print_log(BufReader::new(file), &job, cmd_args, None)?;
+				} else {
+					println!("Log for job {} written to {:?}", job.id.value(), path);
+				}
+			} else if let Some(lines) = cmd_args.tail {
+				CliContext::global().print_log_tail(&endpoint, &job, cmd_args, lines, None)?;
+			} else {
+				CliContext::global().print_log(BufReader::new(log), &job, cmd_args, None)?;
+			}
+			if CliContext::global().open {
+				CliContext::global().open_url(job.web_url);
+			}
+			Ok(ExitCode::from(0))
+		}
+
+		PipelineCmd::Branches(cmd_args) => {
+			// get project from command line or context
+			let project = CliContext::global().get_project(cmd_args.project.as_ref())?;
+			let endpoint = branches::Branches::builder()
+				.project(project.path_with_namespace.to_owned())
+				.build()?;
+			let branches: Vec<types::RepoBranch> = api::paged(endpoint, Pagination::All)
+				.query(&CliContext::global().gitlab)
+				.with_context(|| {
+					format!("Failed to list branches of {}", &project.name_with_namespace)
+				})?;
+
+			// like gitlab, consider branches with commits during the last 3 months as active
+			let active_since = Utc::now() - Duration::days(90);
+			let mut branches: Vec<types::RepoBranch> = branches
+				.into_iter()
+				.filter(|branch| {
+					cmd_args
+						.pattern
+						.as_ref()
+						.is_none_or(|pattern| glob_match(pattern, &branch.name))
+				})
+				.filter(|branch| {
+					cmd_args.all
+						|| branch
+							.commit
+							.as_ref()
+							.and_then(|commit| commit.committed_date)
+							.is_none_or(|date| date >= active_since)
+				})
+				.collect();
+			// most recently updated branches first
+			branches.sort_by(|a, b| {
+				let date = |branch: &types::RepoBranch| {
+					branch.commit.as_ref().and_then(|commit| commit.committed_date)
+				};
+				date(b).cmp(&date(a))
+			});
+
+			let mut latest = Vec::with_capacity(branches.len());
+			for branch in branches {
+				let endpoint = pipelines::Pipelines::builder()
+					.project(project.path_with_namespace.to_owned())
+					.ref_(branch.name.as_str())
+					.build()?;
+				let pipelines: Vec<types::Pipeline> =
+					api::paged(endpoint, Pagination::Limit(1))
+						.query(&CliContext::global().gitlab)
+						.with_context(|| {
+							format!(
+								"Failed to list pipelines for {} @ {}",
+								&project.name_with_namespace, &branch.name
+							)
+						})?;
+				latest.push((branch, pipelines.into_iter().next()));
+			}
+
+			CliContext::global().print_branch_pipelines(&latest, &project)?;
+
 			if CliContext::global().open {
-				let _ = open::that(job.web_url);
+				CliContext::global().open_url(format!("{}/-/pipelines", &project.web_url));
 			}
 			Ok(ExitCode::from(0))
 		}
+
+		PipelineCmd::Schedule(cmd_args) => {
+			// get project from command line or context
+			let project = CliContext::global().get_project(cmd_args.project.as_ref())?;
+			match &cmd_args.cmd {
+				ScheduleCmd::List(_) => {
+					let endpoint = pipeline_schedules::PipelineSchedules::builder()
+						.project(project.path_with_namespace.to_owned())
+						.build()?;
+					let schedules: Vec<types::PipelineSchedule> =
+						api::paged(endpoint, Pagination::All)
+							.query(&CliContext::global().gitlab)
+							.with_context(|| {
+								format!(
+									"Failed to list pipeline schedules for {}",
+									&project.name_with_namespace
+								)
+							})?;
+					CliContext::global().print_schedules(&schedules, &project)?;
+				}
+
+				ScheduleCmd::Run(args) => {
+					let endpoint = pipeline_schedules::PlayPipelineSchedule::builder()
+						.project(project.path_with_namespace.to_owned())
+						.id(args.id)
+						.build()?;
+					api::ignore(endpoint)
+						.query(&CliContext::global().gitlab)
+						.with_context(|| format!("Failed to run pipeline schedule {}", args.id))?;
+					println!(
+						"Pipeline schedule {} has been started on project {}",
+						args.id, &project.path_with_namespace
+					);
+				}
+			}
+
+			if CliContext::global().open {
+				CliContext::global().open_url(format!("{}/-/pipeline_schedules", &project.web_url));
+			}
+			Ok(ExitCode::from(0))
+		}
+
+		PipelineCmd::Delete(cmd_args) => {
+			// get project from command line or context
+			let project = CliContext::global().get_project(cmd_args.project.as_ref())?;
+
+			let pipelines: Vec<types::Pipeline> = match (cmd_args.id, cmd_args.older_than) {
+				(Some(id), None) => {
+					vec![CliContext::global().get_pipeline(Some(id), &project, "")?]
+				}
+				(None, Some(period)) => {
+					let mut builder = pipelines::Pipelines::builder();
+					builder
+						.project(project.path_with_namespace.to_owned())
+						.updated_before(Utc::now() - period.0);
+					if let Some(ref_) = &cmd_args.ref_ {
+						builder.ref_(ref_);
+					}
+					if let Some(status) = cmd_args.status {
+						builder.status(status.into());
+					}
+					let pipelines: Vec<types::Pipeline> =
+						api::paged(builder.build()?, Pagination::All)
+							.query(&CliContext::global().gitlab)
+							.with_context(|| {
+								format!(
+									"Failed to list pipelines for {}",
+									&project.name_with_namespace
+								)
+							})?;
+					if pipelines.is_empty() {
+						println!("No pipeline to delete");
+						return Ok(ExitCode::from(0));
+					}
+					CliContext::global().print_pipelines(&pipelines, &project, None)?;
+					let question = format!("Delete {} pipeline(s)?", pipelines.len());
+					if !cmd_args.yes && !CliContext::global().prompt.confirm(&question)? {
+						return Ok(ExitCode::from(1));
+					}
+					pipelines
+				}
+				_ => bail!("Give either a pipeline id or --older-than"),
+			};
+
+			for pipeline in pipelines {
+				let endpoint = pipelines::DeletePipeline::builder()
+					.project(project.path_with_namespace.to_owned())
+					.pipeline(pipeline.id.value())
+					.build()?;
+				api::ignore(endpoint)
+					.query(&CliContext::global().gitlab)
+					.with_context(|| format!("Failed to delete pipeline {}", pipeline.id))?;
+				println!("Pipeline {} deleted", pipeline.id);
+			}
+			Ok(ExitCode::from(0))
+		}
+
+		PipelineCmd::DeleteArtifacts(cmd_args) => {
+			// get project from command line or context
+			let project = CliContext::global().get_project(cmd_args.project.as_ref())?;
+
+			let pipelines: Vec<types::Pipeline> = if let Some(period) = cmd_args.older_than {
+				let mut builder = pipelines::Pipelines::builder();
+				builder
+					.project(project.path_with_namespace.to_owned())
+					.updated_before(Utc::now() - period.0);
+				if let Some(ref_) = &cmd_args.ref_ {
+					builder.ref_(ref_);
+				}
+				api::paged(builder.build()?, Pagination::All)
+					.query(&CliContext::global().gitlab)
+					.with_context(|| {
+						format!(
+							"Failed to list pipelines for {}",
+							&project.name_with_namespace
+						)
+					})?
+			} else {
+				// get a reference (a tag or a branch)
+				let ref_ = CliContext::global().check_ref(cmd_args.ref_.as_deref(), &project)?;
+				vec![CliContext::global().get_pipeline(cmd_args.id, &project, &ref_)?]
+			};
+
+			let mut jobs = Vec::new();
+			for pipeline in &pipelines {
+				jobs.extend(
+					CliContext::global()
+						.get_jobs(&project, pipeline.id.value())?
+						.into_iter()
+						.filter(|job| job.deletable_artifacts().next().is_some()),
+				);
+			}
+			if jobs.is_empty() {
+				println!("No artifacts to delete");
+				return Ok(ExitCode::from(0));
+			}
+
+			CliContext::global().print_jobs(&jobs)?;
+			let size: u64 = jobs
+				.iter()
+				.flat_map(|job| job.deletable_artifacts())
+				.map(|artifact| artifact.size)
+				.sum();
+			let question = format!(
+				"Delete the artifacts of {} job(s) ({})?",
+				jobs.len(),
+				ByteSize(size)
+			);
+			if !cmd_args.yes && !CliContext::global().prompt.confirm(&question)? {
+				return Ok(ExitCode::from(1));
+			}
+			for job in jobs {
+				let endpoint = DeleteJobArtifacts::builder()
+					.project(project.id.value())
+					.job(job.id.value())
+					.build()?;
+				api::ignore(endpoint)
+					.query(&CliContext::global().gitlab)
+					.with_context(|| format!("Failed to delete the artifacts of job {}", job.id))?;
+				println!("Artifacts of job {} ({}) deleted", job.id, &job.name);
+			}
+			Ok(ExitCode::from(0))
+		}
+	}
+}
+
+/// Print the end of the log of the first job that failed the pipeline
+fn explain_failure(project: &types::Project, jobs: &[types::Job]) -> Result<()> {
+	// the jobs include the retried ones: only consider the last attempt of each job
+	let failed = jobs
+		.iter()
+		.filter(|job| job.status == StatusState::Failed && !job.allow_failure)
+		.filter(|job| {
+			!jobs
+				.iter()
+				.any(|other| other.name == job.name && other.id.value() > job.id.value())
+		})
+		.min_by_key(|job| (job.finished_at, job.id.value()));
+	let Some(job) = failed else {
+		return Ok(());
+	};
+	let endpoint = jobs::JobTrace::builder()
+		.project(project.path_with_namespace.as_str())
+		.job(job.id.value())
+		.build()?;
+	let log = TraceReader::new(&CliContext::global().gitlab, &endpoint)?;
+	let failure = Failure::from_log(BufReader::new(log), FAILURE_LINES)
+		.with_context(|| format!("Failed to read the log of job {}", job.id))?;
+	CliContext::global().print_failure(job, &failure)?;
+	Ok(())
+}
+
+/// Print the logs of several jobs one after the other, each line prefixed with the job name
+fn print_logs(
+	project: &types::Project,
+	jobs: &[types::Job],
+	args: &args::PipelineLog,
+) -> Result<ExitCode> {
+	if args.interactive || args.output.is_some() {
+		bail!(
+			"--interactive and --output need a single job but {} jobs match {}",
+			jobs.len(),
+			args.job_name.as_deref().unwrap_or_default()
+		);
+	}
+	let width = jobs
+		.iter()
+		.map(|job| job.name.chars().count())
+		.max()
+		.unwrap_or(0);
+	for (i, job) in jobs.iter().enumerate() {
+		if i > 0 {
+			println!();
+		}
+		let prefix = format!("{:width$} | ", job.name, width = width);
+		let endpoint = jobs::JobTrace::builder()
+			.project(project.path_with_namespace.as_str())
+			.job(job.id.value())
+			.build()?;
+		if let Some(lines) = args.tail {
+			CliContext::global().print_log_tail(&endpoint, job, args, lines, Some(&prefix))?;
+		} else {
+			let log = TraceReader::new(&CliContext::global().gitlab, &endpoint)?;
+			CliContext::global().print_log(BufReader::new(log), job, args, Some(&prefix))?;
+		}
+		if CliContext::global().open {
+			CliContext::global().open_url(&job.web_url);
+		}
 	}
+	Ok(ExitCode::from(0))
 }
@@ -0,0 +1,26 @@
+use gil::{args, context::CliContext};
+
+use anyhow::{Context, Result};
+use std::process::{Command, ExitCode};
+
+/// Command implementation: run the external gil-<name> executable with the resolved
+/// host, project and token exported in its environment
+pub fn cmd(args: &args::Plugin) -> Result<ExitCode> {
+	let mut command = Command::new(&args.path);
+	command
+		.args(&args.args)
+		.env("GIL_HOST", &CliContext::global().repo.host)
+		.env("GIL_TOKEN", CliContext::global().token.value())
+		.env("GIL_TOKEN_TYPE", CliContext::global().token.as_str());
+	if let Some(project) = &CliContext::global().repo.name {
+		command.env("GIL_PROJECT", project);
+	}
+	if CliContext::global().verbose {
+		println!("Running {:?}", &args.path);
+	}
+
+	let status = command
+		.status()
+		.with_context(|| format!("Failed to run {} ({:?})", &args.name, &args.path))?;
+	Ok(ExitCode::from(status.code().unwrap_or(1) as u8))
+}
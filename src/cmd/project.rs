@@ -1,15 +1,146 @@
-use crate::{
-	args::{self, ProjectCmd},
+use crate::cmd::protections::Rules;
+use gil::{
+	api::{
+		groups::GroupProjects,
+		projects::{EditProjectSettings, StarProject, UnstarProject},
+	},
+	args::{self, OwnedNameOrId, ProjectCmd},
 	context::CliContext,
+	types::{
+		project::{CiSettings, GeneralSettings, MergeSettings},
+		Project, ProjectSettings,
+	},
+	utils::glob_match,
 };
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use gitlab::api::{
 	self,
-	projects::{ArchiveProject, UnarchiveProject},
-	Query,
+	projects::{self, ArchiveProject, UnarchiveProject},
+	Pagination, Query,
 };
-use std::process::ExitCode;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{fs::File, io, process::ExitCode};
+
+/// Settings of a project as code, grouped as on the settings pages
+#[derive(Deserialize, Serialize)]
+struct Settings {
+	#[serde(default)]
+	general: GeneralSettings,
+	#[serde(default)]
+	merge: MergeSettings,
+	#[serde(default)]
+	ci: CiSettings,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	protections: Option<Rules>,
+}
+
+/// Returns the settings of a project
+fn project_settings(project: &Project) -> Result<ProjectSettings> {
+	let endpoint = projects::Project::builder()
+		.project(project.id.value())
+		.build()?;
+	endpoint
+		.query(&CliContext::global().gitlab)
+		.with_context(|| format!("failed to get settings of {}", project.name))
+}
+
+/// Returns the settings that differ from the current ones as (name, current, wanted)
+fn changed_settings(
+	current: &ProjectSettings,
+	wanted: &ProjectSettings,
+) -> Result<Vec<(String, Value, Value)>> {
+	let current = serde_json::to_value(current)?;
+	let Value::Object(wanted) = serde_json::to_value(wanted)? else {
+		return Ok(Vec::new());
+	};
+	Ok(wanted
+		.into_iter()
+		.filter_map(|(name, value)| {
+			let current = current.get(&name).cloned().unwrap_or(Value::Null);
+			(current != value).then_some((name, current, value))
+		})
+		.collect())
+}
+
+/// Archive or unarchive a project
+fn set_archived(project: &Project, archived: bool) -> Result<()> {
+	if archived {
+		let endpoint = ArchiveProject::builder()
+			.project(project.id.value())
+			.build()?;
+		api::ignore(endpoint)
+			.query(&CliContext::global().gitlab)
+			.with_context(|| format!("failed to archive project {}", project.name))
+	} else {
+		let endpoint = UnarchiveProject::builder()
+			.project(project.id.value())
+			.build()?;
+		api::ignore(endpoint)
+			.query(&CliContext::global().gitlab)
+			.with_context(|| format!("failed to unarchive project {}", project.name))
+	}
+}
+
+/// Archive or unarchive all the projects of a group matching a pattern
+fn bulk_archive(
+	group: &OwnedNameOrId,
+	pattern: Option<&str>,
+	yes: bool,
+	archived: bool,
+) -> Result<ExitCode> {
+	let action = if archived { "archive" } else { "unarchive" };
+	let group = CliContext::global().get_group(group)?;
+	let endpoint = GroupProjects::builder().group(group.id).build()?;
+	let projects: Vec<Project> = api::paged(endpoint, Pagination::All)
+		.query(&CliContext::global().gitlab)
+		.with_context(|| format!("failed to list projects of group {}", &group.name))?;
+	let projects: Vec<Project> = projects
+		.into_iter()
+		// skip the projects already in the wanted state
+		.filter(|project| project.archived != archived)
+		.filter(|project| {
+			pattern.is_none_or(|pattern| {
+				glob_match(pattern, &project.path_with_namespace)
+					|| glob_match(pattern, &project.name)
+					|| project
+						.path_with_namespace
+						.rsplit_once('/')
+						.is_some_and(|(_, path)| glob_match(pattern, path))
+			})
+		})
+		.collect();
+	if projects.is_empty() {
+		println!("No project to {} in group {}", action, &group.full_path);
+		return Ok(ExitCode::from(0));
+	}
+
+	for project in &projects {
+		println!("- {}", &project.path_with_namespace);
+	}
+	let verb = if archived { "Archive" } else { "Unarchive" };
+	let question = format!("{} {} project(s)?", verb, projects.len());
+	if !yes && !CliContext::global().prompt.confirm(&question)? {
+		return Ok(ExitCode::from(1));
+	}
+	let mut failed = 0;
+	for project in &projects {
+		if let Err(e) = set_archived(project, archived) {
+			eprintln!("{:#}", e);
+			failed += 1;
+		} else {
+			println!("project {}({}) has been {}d", &project.name, project.id, action);
+		}
+	}
+	println!(
+		"{} project(s) {}d, {} failed",
+		projects.len() - failed,
+		action,
+		failed
+	);
+	Ok(ExitCode::from(if failed == 0 { 0 } else { 1 }))
+}
 
 pub fn cmd(args: &args::Project) -> Result<ExitCode> {
 	match &args.cmd {
@@ -20,37 +151,233 @@ pub fn cmd(args: &args::Project) -> Result<ExitCode> {
 
 			CliContext::global().print_project(&project, &ref_)?;
 			if CliContext::global().open {
-				let _ = open::that(format!("{}/-/tree/{}", &project.web_url, &ref_));
+				CliContext::global().open_url(format!("{}/-/tree/{}", &project.web_url, &ref_));
 			}
 			Ok(ExitCode::from(0))
 		}
-		ProjectCmd::Archive(_args) => {
+		ProjectCmd::Archive(aargs) => {
+			if let Some(group) = &aargs.group {
+				return bulk_archive(group, aargs.match_.as_deref(), aargs.yes, true);
+			} else if aargs.match_.is_some() {
+				bail!("--match can only be used with --group");
+			}
 			let project = CliContext::global().get_project(args.project.as_ref())?;
-			let endpoint = ArchiveProject::builder()
-				.project(project.id.value())
-				.build()?;
-			api::ignore(endpoint)
-				.query(&CliContext::global().gitlab)
-				.with_context(|| format!("failed to archive project {}", project.name))?;
+			set_archived(&project, true)?;
 			println!(
 				"project {}({}) has been archived",
 				&project.name, project.id
 			);
 			Ok(ExitCode::from(0))
 		}
-		ProjectCmd::Unarchive(_args) => {
+		ProjectCmd::Unarchive(uargs) => {
+			if let Some(group) = &uargs.group {
+				return bulk_archive(group, uargs.match_.as_deref(), uargs.yes, false);
+			} else if uargs.match_.is_some() {
+				bail!("--match can only be used with --group");
+			}
 			let project = CliContext::global().get_project(args.project.as_ref())?;
-			let endpoint = UnarchiveProject::builder()
-				.project(project.id.value())
-				.build()?;
-			api::ignore(endpoint)
-				.query(&CliContext::global().gitlab)
-				.with_context(|| format!("failed to unarchive project {}", project.name))?;
+			set_archived(&project, false)?;
 			println!(
 				"project {}({}) has been unarchived",
 				&project.name, project.id
 			);
 			Ok(ExitCode::from(0))
 		}
+		ProjectCmd::Usage(uargs) => {
+			let projects: Vec<Project> = if let Some(group) = &uargs.group {
+				let group = CliContext::global().get_group(group)?;
+				let endpoint = GroupProjects::builder()
+					.group(group.id)
+					.statistics(true)
+					.build()?;
+				let mut projects: Vec<Project> = api::paged(endpoint, Pagination::All)
+					.query(&CliContext::global().gitlab)
+					.with_context(|| format!("failed to list projects of group {}", &group.name))?;
+				projects.sort_by_key(|project| {
+					std::cmp::Reverse(project.statistics.as_ref().map(|s| s.storage_size))
+				});
+				projects
+			} else {
+				let project = CliContext::global().get_project(args.project.as_ref())?;
+				let endpoint = projects::Project::builder()
+					.project(project.id.value())
+					.statistics(true)
+					.build()?;
+				let project: Project = endpoint
+					.query(&CliContext::global().gitlab)
+					.with_context(|| format!("failed to get statistics of {}", project.name))?;
+				vec![project]
+			};
+			CliContext::global().print_usage(&projects)?;
+			Ok(ExitCode::from(0))
+		}
+		ProjectCmd::Star(_) => {
+			let project = CliContext::global().get_project(args.project.as_ref())?;
+			let endpoint = StarProject::builder()
+				.project(project.id.value())
+				.build()?;
+			let starred = CliContext::global()
+				.query_toggle(endpoint)
+				.with_context(|| format!("failed to star project {}", &project.name))?;
+			if starred {
+				println!("project {}({}) has been starred", &project.name, project.id);
+			} else {
+				println!("project {}({}) is already starred", &project.name, project.id);
+			}
+			Ok(ExitCode::from(0))
+		}
+		ProjectCmd::Unstar(_) => {
+			let project = CliContext::global().get_project(args.project.as_ref())?;
+			let endpoint = UnstarProject::builder()
+				.project(project.id.value())
+				.build()?;
+			let unstarred = CliContext::global()
+				.query_toggle(endpoint)
+				.with_context(|| format!("failed to unstar project {}", &project.name))?;
+			if unstarred {
+				println!("project {}({}) has been unstarred", &project.name, project.id);
+			} else {
+				println!("project {}({}) is not starred", &project.name, project.id);
+			}
+			Ok(ExitCode::from(0))
+		}
+		ProjectCmd::ExportSettings(_) => {
+			let project = CliContext::global().get_project(args.project.as_ref())?;
+			let current = project_settings(&project)?;
+			let settings = Settings {
+				general: current.general,
+				merge: current.merge,
+				ci: current.ci,
+				protections: Some(Rules::from_project(&project)?),
+			};
+			serde_yaml::to_writer(io::stdout(), &settings)?;
+			Ok(ExitCode::from(0))
+		}
+		ProjectCmd::ApplySettings(sargs) => {
+			let file = File::open(&sargs.file)
+				.with_context(|| format!("Can't open settings file {:?}", &sargs.file))?;
+			let settings: Settings = serde_yaml::from_reader(file)
+				.with_context(|| format!("Can't read settings file {:?}", &sargs.file))?;
+			let project = CliContext::global().get_project(args.project.as_ref())?;
+			let wanted = ProjectSettings {
+				general: settings.general,
+				merge: settings.merge,
+				ci: settings.ci,
+			};
+			let changes = changed_settings(&project_settings(&project)?, &wanted)?;
+			// the protections missing from the rules are removed
+			let protections = match &settings.protections {
+				Some(rules) => Some(rules.changes(&project)?),
+				None => None,
+			}
+			.filter(|rules| !rules.is_empty());
+			if changes.is_empty() && protections.is_none() {
+				println!(
+					"project {}({}) settings are up to date",
+					&project.name, project.id
+				);
+				return Ok(ExitCode::from(0));
+			}
+
+			if !sargs.yes {
+				for (name, current, wanted) in &changes {
+					println!("- {}: {} -> {}", name, current, wanted);
+				}
+				if let Some(protections) = &protections {
+					protections.print_changes();
+				}
+				let question = format!(
+					"Change {} setting(s){} of {}?",
+					changes.len(),
+					if protections.is_some() {
+						" and apply the protection rules"
+					} else {
+						""
+					},
+					&project.path_with_namespace
+				);
+				if !CliContext::global().prompt.confirm(&question)? {
+					return Ok(ExitCode::from(1));
+				}
+			}
+
+			if !changes.is_empty() {
+				let endpoint = EditProjectSettings::builder()
+					.project(project.id.value())
+					.settings(&wanted)
+					.build()?;
+				api::ignore(endpoint)
+					.query(&CliContext::global().gitlab)
+					.with_context(|| format!("failed to change settings of {}", project.name))?;
+				println!(
+					"project {}({}) settings have been changed",
+					&project.name, project.id
+				);
+			}
+			if let Some(protections) = &protections {
+				protections.apply(&project)?;
+			}
+			if CliContext::global().open {
+				CliContext::global().open_url(format!("{}/edit", project.web_url));
+			}
+			Ok(ExitCode::from(0))
+		}
+		ProjectCmd::Starred(sargs) => {
+			let endpoint = projects::Projects::builder().starred(true).build()?;
+			let projects: Vec<Project> = api::paged(endpoint, Pagination::All)
+				.query(&CliContext::global().gitlab)
+				.context("failed to list starred projects")?;
+			let projects: Vec<Project> = projects
+				.into_iter()
+				.filter(|project| {
+					sargs
+						.match_
+						.as_deref()
+						.is_none_or(|pattern| glob_match(pattern, &project.path_with_namespace))
+				})
+				.collect();
+			CliContext::global().print_starred(&projects)?;
+			if CliContext::global().open {
+				CliContext::global().open_url(format!(
+					"https://{}/dashboard/projects/starred",
+					CliContext::global().repo.host
+				));
+			}
+			Ok(ExitCode::from(0))
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{changed_settings, Settings};
+	use gil::types::ProjectSettings;
+	use serde_json::json;
+
+	#[test]
+	fn settings_changes() {
+		let current: ProjectSettings = serde_json::from_value(json!({
+			"id": 1,
+			"visibility": "private",
+			"merge_method": "merge",
+			"build_timeout": 3600,
+			"ci_config_path": null,
+		}))
+		.unwrap();
+		assert_eq!(current.general.visibility.as_deref(), Some("private"));
+		assert_eq!(current.ci.build_timeout, Some(3600));
+
+		let settings: Settings =
+			serde_yaml::from_str("merge:\n  merge_method: ff\nci:\n  build_timeout: 3600\n")
+				.unwrap();
+		let wanted = ProjectSettings {
+			general: settings.general,
+			merge: settings.merge,
+			ci: settings.ci,
+		};
+		assert_eq!(
+			changed_settings(&current, &wanted).unwrap(),
+			[("merge_method".to_owned(), json!("merge"), json!("ff"))]
+		);
 	}
 }
@@ -0,0 +1,398 @@
+use crate::cmd::{
+	branches::{self, BranchRule},
+	tags::{self, TagRule},
+};
+use gil::{
+	args::{self, AccessSpec, OwnedNameOrId, ProtectionsCmd},
+	context::CliContext,
+	types::{repository::PRBAccessLevel, Project, ProtectedRepoBranch, ProtectedTag},
+};
+
+use anyhow::{bail, Context, Result};
+use gitlab::api::{
+	self,
+	common::ProtectedAccessLevel,
+	projects::{
+		protected_branches::{ProtectedAccess, ProtectedBranches},
+		protected_tags::ProtectedTags,
+	},
+	Pagination, Query,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, fs::File, io, path::Path, process::ExitCode};
+
+/// Declarative protection rules applied to projects
+#[derive(Deserialize, Serialize, Default)]
+pub struct Rules {
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub branches: Vec<BranchRule>,
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub tags: Vec<TagRule>,
+}
+
+/// Returns the access spec of an access level of a protected branch or tag. Deploy keys can't
+/// be given in the rules
+fn access_spec(access: &PRBAccessLevel) -> Result<AccessSpec> {
+	Ok(match (access.user_id, access.group_id, access.access_level) {
+		_ if access.deploy_key_id.is_some() => bail!(
+			"Deploy key access ({}) can't be expressed in the rules",
+			&access.access_level_description
+		),
+		(Some(id), _, _) => AccessSpec::User(OwnedNameOrId::Id(id)),
+		(_, Some(id), _) => AccessSpec::Group(OwnedNameOrId::Id(id)),
+		(_, _, Some(level)) => level.to_string().parse()?,
+		_ => bail!("Unknown access {}", &access.access_level_description),
+	})
+}
+
+/// Split access levels into the role and the users and groups also allowed
+fn split_access(accesses: &[PRBAccessLevel]) -> Result<(Option<AccessSpec>, Vec<AccessSpec>)> {
+	let mut role = None;
+	let mut allowed = Vec::new();
+	for access in accesses {
+		match access_spec(access)? {
+			spec @ AccessSpec::Role(_) if role.is_none() => role = Some(spec),
+			spec => allowed.push(spec),
+		}
+	}
+	Ok((role, allowed))
+}
+
+/// Returns the accesses given by a role and the users and groups also allowed. The role is
+/// maintainer when none is given, like on Gitlab
+fn accesses(
+	role: Option<&AccessSpec>,
+	allowed: &[AccessSpec],
+) -> Result<Vec<ProtectedAccess<ProtectedAccessLevel>>> {
+	let maintainer = AccessSpec::Role(ProtectedAccessLevel::Maintainer);
+	let role = match role {
+		Some(role) => Some(role),
+		None if allowed.iter().any(|a| matches!(a, AccessSpec::Role(_))) => None,
+		None => Some(&maintainer),
+	};
+	role.into_iter()
+		.chain(allowed)
+		.map(|access| CliContext::global().get_protected_access(access))
+		.collect()
+}
+
+/// A role and the users and groups also allowed
+type Accesses<'a> = (&'a Option<AccessSpec>, &'a [AccessSpec]);
+
+/// Returns true if two roles and lists of users and groups give the same accesses
+fn same_accesses((role, allowed): Accesses, (other_role, other_allowed): Accesses) -> Result<bool> {
+	let given = accesses(role.as_ref(), allowed)?;
+	let other = accesses(other_role.as_ref(), other_allowed)?;
+	Ok(given.len() == other.len() && given.iter().all(|access| other.contains(access)))
+}
+
+/// Returns true if a branch rule gives the same protection as the current one
+fn same_branch_rule(rule: &BranchRule, current: &BranchRule) -> Result<bool> {
+	if rule.force_push != current.force_push
+		|| rule.code_owner_approval != current.code_owner_approval
+	{
+		return Ok(false);
+	}
+	let pairs: [(Accesses, Accesses); 3] = [
+		(
+			(&rule.push_access, &rule.allowed_to_push),
+			(&current.push_access, &current.allowed_to_push),
+		),
+		(
+			(&rule.merge_access, &rule.allowed_to_merge),
+			(&current.merge_access, &current.allowed_to_merge),
+		),
+		(
+			(&rule.unprotect_access, &rule.allowed_to_unprotect),
+			(&current.unprotect_access, &current.allowed_to_unprotect),
+		),
+	];
+	for (accesses, current) in pairs {
+		if !same_accesses(accesses, current)? {
+			return Ok(false);
+		}
+	}
+	Ok(true)
+}
+
+/// Returns true if a tag rule gives the same protection as the current one
+fn same_tag_rule(rule: &TagRule, current: &TagRule) -> Result<bool> {
+	same_accesses(
+		(&None, &rule.create_access),
+		(&None, &current.create_access),
+	)
+}
+
+/// Returns the rule of a protected branch
+pub fn branch_rule(branch: ProtectedRepoBranch) -> Result<BranchRule> {
+	let (push_access, allowed_to_push) = split_access(&branch.push_access_levels)?;
+	let (merge_access, allowed_to_merge) = split_access(&branch.merge_access_levels)?;
+	let (unprotect_access, allowed_to_unprotect) = split_access(&branch.unprotect_access_levels)?;
+	Ok(BranchRule {
+		name: branch.name,
+		unprotect: false,
+		force_push: branch.allow_force_push,
+		push_access,
+		merge_access,
+		unprotect_access,
+		allowed_to_push,
+		allowed_to_merge,
+		allowed_to_unprotect,
+		code_owner_approval: branch.code_owner_approval_required.unwrap_or(false),
+	})
+}
+
+impl Rules {
+	/// Read the rules from a yaml file
+	pub fn from_file(path: &Path) -> Result<Self> {
+		let file = File::open(path).with_context(|| format!("Can't open rules file {:?}", path))?;
+		serde_yaml::from_reader(file).with_context(|| format!("Can't read rules file {:?}", path))
+	}
+
+	/// Returns the rules of the protected branches and tags of a project
+	pub fn from_project(project: &Project) -> Result<Self> {
+		let endpoint = ProtectedBranches::builder()
+			.project(project.id.value())
+			.build()?;
+		let protected_branches: Vec<ProtectedRepoBranch> = api::paged(endpoint, Pagination::All)
+			.query(&CliContext::global().gitlab)
+			.with_context(|| {
+				format!(
+					"Failed to list protected branches of {}",
+					&project.path_with_namespace
+				)
+			})?;
+		let endpoint = ProtectedTags::builder()
+			.project(project.id.value())
+			.build()?;
+		let protected_tags: Vec<ProtectedTag> = api::paged(endpoint, Pagination::All)
+			.query(&CliContext::global().gitlab)
+			.with_context(|| {
+				format!(
+					"Failed to list protected tags of {}",
+					&project.path_with_namespace
+				)
+			})?;
+
+		let branches = protected_branches
+			.into_iter()
+			.map(branch_rule)
+			.collect::<Result<_>>()?;
+		let mut tags = Vec::new();
+		for tag in protected_tags {
+			tags.push(TagRule {
+				create_access: tag
+					.create_access_levels
+					.iter()
+					.map(access_spec)
+					.collect::<Result<_>>()?,
+				name: tag.name,
+				unprotect: false,
+			});
+		}
+		Ok(Self { branches, tags })
+	}
+
+	/// Returns true if there is no rule
+	pub fn is_empty(&self) -> bool {
+		self.branches.is_empty() && self.tags.is_empty()
+	}
+
+	/// Returns the rules changing the current protections: the protections missing or
+	/// different, and the removals of existing protections
+	fn changed(&self, current: &Self) -> Result<Self> {
+		let mut changed = Self::default();
+		for rule in &self.branches {
+			let protection = current.branches.iter().find(|b| b.name == rule.name);
+			let unchanged = match protection {
+				Some(protection) => !rule.unprotect && same_branch_rule(rule, protection)?,
+				None => rule.unprotect,
+			};
+			if !unchanged {
+				changed.branches.push(rule.clone());
+			}
+		}
+		for rule in &self.tags {
+			let protection = current.tags.iter().find(|t| t.name == rule.name);
+			let unchanged = match protection {
+				Some(protection) => !rule.unprotect && same_tag_rule(rule, protection)?,
+				None => rule.unprotect,
+			};
+			if !unchanged {
+				changed.tags.push(rule.clone());
+			}
+		}
+		Ok(changed)
+	}
+
+	/// Returns the rules converging a project to these rules: the changed protections and the
+	/// removals of the protections missing from the rules
+	pub fn changes(&self, project: &Project) -> Result<Self> {
+		let current = Self::from_project(project)?;
+		let mut changes = self.changed(&current)?;
+		let removals = self.removals(current);
+		changes.branches.extend(removals.branches);
+		changes.tags.extend(removals.tags);
+		Ok(changes)
+	}
+
+	/// Returns the rules removing the current protections that are missing from the rules
+	fn removals(&self, current: Self) -> Self {
+		let branches: HashSet<&str> = self
+			.branches
+			.iter()
+			.map(|rule| rule.name.as_str())
+			.collect();
+		let tags: HashSet<&str> = self.tags.iter().map(|rule| rule.name.as_str()).collect();
+		Self {
+			branches: current
+				.branches
+				.into_iter()
+				.filter(|rule| !branches.contains(rule.name.as_str()))
+				.map(|rule| BranchRule {
+					unprotect: true,
+					..rule
+				})
+				.collect(),
+			tags: current
+				.tags
+				.into_iter()
+				.filter(|rule| !tags.contains(rule.name.as_str()))
+				.map(|rule| TagRule {
+					unprotect: true,
+					..rule
+				})
+				.collect(),
+		}
+	}
+
+	/// Print the protections changed by the rules
+	pub fn print_changes(&self) {
+		for rule in &self.branches {
+			if rule.unprotect {
+				println!("- branch '{}' protection will be removed", &rule.name);
+			} else {
+				println!("- branch '{}' will be protected", &rule.name);
+			}
+		}
+		for rule in &self.tags {
+			if rule.unprotect {
+				println!("- tag '{}' protection will be removed", &rule.name);
+			} else {
+				println!("- tag '{}' will be protected", &rule.name);
+			}
+		}
+	}
+
+	/// Apply the rules to a project, leaving alone the protections already matching the rules
+	pub fn apply(&self, project: &Project) -> Result<()> {
+		let changed = self.changed(&Self::from_project(project)?)?;
+		if changed.is_empty() {
+			println!(
+				"protections of project {} are up to date",
+				&project.path_with_namespace
+			);
+		}
+		for rule in &changed.branches {
+			if rule.unprotect {
+				branches::unprotect(project, &rule.name)?;
+			} else {
+				branches::protect(project, rule)?;
+			}
+		}
+		for rule in &changed.tags {
+			if rule.unprotect {
+				tags::unprotect(project, &rule.name)?;
+			} else {
+				tags::protect(project, rule)?;
+			}
+		}
+		Ok(())
+	}
+}
+
+pub fn cmd(args: &args::Protections) -> Result<ExitCode> {
+	match &args.cmd {
+		ProtectionsCmd::Export(args) => {
+			let project = CliContext::global().get_project(args.project.as_ref())?;
+			let rules = Rules::from_project(&project)?;
+			serde_yaml::to_writer(io::stdout(), &rules)?;
+			Ok(ExitCode::from(0))
+		}
+
+		ProtectionsCmd::Apply(args) => {
+			let rules = Rules::from_file(&args.rules)?;
+			let project = CliContext::global().get_project(args.project.as_ref())?;
+
+			// the protections missing from the rules are removed
+			let changes = rules.changes(&project)?;
+			if changes.is_empty() {
+				println!(
+					"protections of project {} are up to date",
+					&project.path_with_namespace
+				);
+				return Ok(ExitCode::from(0));
+			}
+			if !args.yes {
+				changes.print_changes();
+				let question = format!(
+					"Change {} branch protection(s) and {} tag protection(s) of {}?",
+					changes.branches.len(),
+					changes.tags.len(),
+					&project.path_with_namespace
+				);
+				if !CliContext::global().prompt.confirm(&question)? {
+					return Ok(ExitCode::from(1));
+				}
+			}
+
+			changes.apply(&project)?;
+
+			if CliContext::global().open {
+				CliContext::global().open_url(format!("{}/-/settings/repository", project.web_url));
+			}
+			Ok(ExitCode::from(0))
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::split_access;
+	use gil::types::repository::PRBAccessLevel;
+
+	#[test]
+	fn access_levels() {
+		let accesses: Vec<PRBAccessLevel> = serde_json::from_str(
+			r#"[
+				{"access_level": 30, "access_level_description": "Developers + Maintainers"},
+				{"access_level": 40, "access_level_description": "bot", "user_id": 12},
+				{"access_level": 30, "access_level_description": "team", "group_id": 7}
+			]"#,
+		)
+		.unwrap();
+		let (role, allowed) = split_access(&accesses).unwrap();
+		assert_eq!(role.unwrap().to_string(), "developer");
+		assert_eq!(
+			allowed.iter().map(ToString::to_string).collect::<Vec<_>>(),
+			["user:12", "group:7"]
+		);
+		assert_eq!(
+			serde_yaml::to_string(&allowed).unwrap(),
+			"- user:12\n- group:7\n"
+		);
+	}
+
+	#[test]
+	fn deploy_key_access() {
+		let accesses: Vec<PRBAccessLevel> = serde_json::from_str(
+			r#"[{"access_level": 40, "access_level_description": "ci key", "deploy_key_id": 3}]"#,
+		)
+		.unwrap();
+		let err = split_access(&accesses).err().unwrap();
+		assert_eq!(
+			err.to_string(),
+			"Deploy key access (ci key) can't be expressed in the rules"
+		);
+	}
+}
@@ -0,0 +1,247 @@
+use gil::{
+	api::projects::repository::Compare,
+	args::{self, ReleaseCmd},
+	context::CliContext,
+	types::{Commit, Comparison, MergeRequest, Project},
+};
+
+use anyhow::{bail, Context, Result};
+use gitlab::api::{
+	self,
+	projects::{
+		merge_requests::{MergeRequestCommits, MergeRequestState, MergeRequests},
+		releases::CreateRelease,
+	},
+	Pagination, Query,
+};
+use std::{collections::HashMap, fmt::Write, process::ExitCode};
+
+/// Sections of the release notes with the conventional commit types they gather
+const SECTIONS: [(&str, &[&str]); 4] = [
+	("Features", &["feat", "feature"]),
+	("Bug fixes", &["fix", "bugfix"]),
+	("Performance", &["perf"]),
+	("Documentation", &["docs", "doc"]),
+];
+
+/// Section of the changes of any other type
+const OTHER_SECTION: &str = "Other changes";
+
+/// Returns the section of a change from its conventional commit type (`type(scope)!: title`)
+/// and the title without the type, or the other section and the unchanged title
+fn classify(title: &str) -> (usize, String) {
+	let Some((prefix, description)) = title.split_once(':') else {
+		return (SECTIONS.len(), title.to_owned());
+	};
+	let prefix = prefix.strip_suffix('!').unwrap_or(prefix);
+	let (kind, scope) = match prefix.split_once('(') {
+		Some((kind, scope)) => match scope.strip_suffix(')') {
+			Some(scope) => (kind, Some(scope)),
+			None => return (SECTIONS.len(), title.to_owned()),
+		},
+		None => (prefix, None),
+	};
+	match SECTIONS
+		.iter()
+		.position(|(_, kinds)| kinds.iter().any(|k| k.eq_ignore_ascii_case(kind)))
+	{
+		Some(i) => match scope {
+			Some(scope) => (i, format!("{}: {}", scope, description.trim())),
+			None => (i, description.trim().to_owned()),
+		},
+		None => (SECTIONS.len(), title.to_owned()),
+	}
+}
+
+/// Returns the merge requests merged since a ref
+fn merged_since(project: &Project, from: &str) -> Result<Vec<MergeRequest>> {
+	let commit = CliContext::global().get_commit(from, project)?;
+	let endpoint = MergeRequests::builder()
+		.project(project.id.value())
+		.state(MergeRequestState::Merged)
+		.updated_after(commit.committed_date)
+		.build()?;
+	api::paged(endpoint, Pagination::All)
+		.query(&CliContext::global().gitlab)
+		.with_context(|| {
+			format!(
+				"Failed to list merge requests of {}",
+				&project.path_with_namespace
+			)
+		})
+}
+
+/// Returns the changes between two refs as (position in the comparison, title, reference) with
+/// a merge request reference for the merged merge requests, and a commit reference for the
+/// commits that don't belong to any
+fn changes(
+	project: &Project,
+	comparison: &Comparison,
+	args: &args::ReleaseNotes,
+) -> Result<Vec<(usize, String, String)>> {
+	let position: HashMap<&str, usize> = comparison
+		.commits
+		.iter()
+		.enumerate()
+		.map(|(i, commit)| (commit.id.value().as_str(), i))
+		.collect();
+	// merge commits are never listed
+	let mut listed: Vec<bool> = comparison
+		.commits
+		.iter()
+		.map(|commit| commit.parent_ids.len() > 1)
+		.collect();
+	let mut changes = Vec::new();
+
+	if !args.commits {
+		for mr in merged_since(project, &args.from)? {
+			let shas = [&mr.merge_commit_sha, &mr.squash_commit_sha, &mr.sha];
+			let Some(&i) = shas
+				.into_iter()
+				.flatten()
+				.find_map(|sha| position.get(sha.value().as_str()))
+			else {
+				continue;
+			};
+			for sha in shas.into_iter().flatten() {
+				if let Some(&i) = position.get(sha.value().as_str()) {
+					listed[i] = true;
+				}
+			}
+			// the commits of the source branch are in the range unless squashed
+			if mr.squash_commit_sha.is_none() {
+				let endpoint = MergeRequestCommits::builder()
+					.project(project.id.value())
+					.merge_request(mr.iid)
+					.build()?;
+				let commits: Vec<Commit> = api::paged(endpoint, Pagination::All)
+					.query(&CliContext::global().gitlab)
+					.with_context(|| {
+						format!("Failed to list the commits of merge request !{}", mr.iid)
+					})?;
+				for commit in commits {
+					if let Some(&i) = position.get(commit.id.value().as_str()) {
+						listed[i] = true;
+					}
+				}
+			}
+			changes.push((i, mr.title, format!("!{}", mr.iid)));
+		}
+	}
+
+	for (i, commit) in comparison.commits.iter().enumerate() {
+		if !listed[i] {
+			changes.push((i, commit.title.to_owned(), commit.short_id.to_owned()));
+		}
+	}
+	changes.sort_by_key(|(i, _, _)| *i);
+	Ok(changes)
+}
+
+/// Returns the changes as markdown lists under a heading per section
+fn release_notes(changes: Vec<(usize, String, String)>) -> String {
+	let mut sections = vec![Vec::new(); SECTIONS.len() + 1];
+	for (_, title, reference) in changes {
+		let (section, title) = classify(&title);
+		sections[section].push(format!("- {} ({})", title, reference));
+	}
+	let mut notes = String::new();
+	for (i, lines) in sections.iter().enumerate() {
+		if lines.is_empty() {
+			continue;
+		}
+		if !notes.is_empty() {
+			notes.push('\n');
+		}
+		let heading = SECTIONS
+			.get(i)
+			.map_or(OTHER_SECTION, |(heading, _)| heading);
+		let _ = writeln!(notes, "### {}\n", heading);
+		for line in lines {
+			let _ = writeln!(notes, "{}", line);
+		}
+	}
+	notes
+}
+
+pub fn cmd(args: &args::Release) -> Result<ExitCode> {
+	match &args.cmd {
+		ReleaseCmd::Notes(args) => {
+			let project = CliContext::global().get_project(args.project.as_ref())?;
+			let endpoint = Compare::builder()
+				.project(project.id.value())
+				.from(&args.from)
+				.to(&args.to)
+				.build()?;
+			let comparison: Comparison = endpoint
+				.query(&CliContext::global().gitlab)
+				.with_context(|| format!("Failed to compare {} with {}", &args.from, &args.to))?;
+			let Some(last) = comparison.commits.last() else {
+				bail!("No changes between {} and {}", &args.from, &args.to);
+			};
+			let last = last.id.value().to_owned();
+			let notes = release_notes(changes(&project, &comparison, args)?);
+			print!("{}", &notes);
+
+			if let Some(tag) = &args.release {
+				let endpoint = CreateRelease::builder()
+					.project(project.id.value())
+					.tag_name(tag.as_str())
+					.name(tag.as_str())
+					.description(notes.as_str())
+					.ref_sha(last.as_str())
+					.build()?;
+				api::ignore(endpoint)
+					.query(&CliContext::global().gitlab)
+					.with_context(|| format!("Failed to create release {}", tag))?;
+				eprintln!(
+					"Release {} created on {}",
+					tag, &project.path_with_namespace
+				);
+				if CliContext::global().open {
+					CliContext::global()
+						.open_url(format!("{}/-/releases/{}", project.web_url, tag));
+				}
+			}
+			Ok(ExitCode::from(0))
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{classify, release_notes};
+
+	#[test]
+	fn conventional_types() {
+		assert_eq!(classify("feat: add notes"), (0, "add notes".to_owned()));
+		assert_eq!(classify("Fix(api)!: a bug"), (1, "api: a bug".to_owned()));
+		assert_eq!(classify("docs: README"), (3, "README".to_owned()));
+		for title in [
+			"Update: something",
+			"feat(api: nope",
+			"no type",
+			"chore: deps",
+		] {
+			assert_eq!(classify(title), (4, title.to_owned()));
+		}
+	}
+
+	#[test]
+	fn grouped_markdown() {
+		let changes = [
+			(0, "chore: deps", "abc1234"),
+			(1, "fix: crash", "!2"),
+			(2, "feat: notes", "!3"),
+		]
+		.map(|(i, title, reference)| (i, title.to_owned(), reference.to_owned()));
+		assert_eq!(
+			release_notes(changes.into()),
+			concat!(
+				"### Features\n\n- notes (!3)\n\n",
+				"### Bug fixes\n\n- crash (!2)\n\n",
+				"### Other changes\n\n- chore: deps (abc1234)\n",
+			)
+		);
+	}
+}
@@ -0,0 +1,200 @@
+use gil::{
+	api::projects::secure_files::{
+		CreateSecureFile, DeleteSecureFile, DownloadSecureFile, SecureFiles,
+	},
+	args::{self, SecureFileCmd},
+	context::CliContext,
+	trace::TraceReader,
+	types::{Project, SecureFile},
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use gitlab::api::{self, Pagination, Query};
+use sha2::{Digest, Sha256};
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
+use std::{
+	fs::{self, File, OpenOptions},
+	io::{self, Read, Write},
+	path::{Path, PathBuf},
+	process::ExitCode,
+	slice,
+};
+
+/// Maximum size of a secure file accepted by Gitlab
+const MAX_SIZE: u64 = 5 * 1024 * 1024;
+
+/// Create a file only readable by the user, failing if it exists unless `force` is set
+fn create(path: &Path, force: bool) -> Result<File> {
+	let mut options = OpenOptions::new();
+	options.write(true);
+	if force {
+		options.create(true).truncate(true);
+	} else {
+		options.create_new(true);
+	}
+	#[cfg(unix)]
+	options.mode(0o600);
+	options.open(path).map_err(|err| match err.kind() {
+		io::ErrorKind::AlreadyExists => {
+			anyhow!("{:?} already exists, use --force to overwrite it", path)
+		}
+		_ => anyhow!(err).context(format!("Can't create {:?}", path)),
+	})
+}
+
+/// Returns the secure files of a project
+fn secure_files(project: &Project) -> Result<Vec<SecureFile>> {
+	let endpoint = SecureFiles::builder().project(project.id.value()).build()?;
+	api::paged(endpoint, Pagination::All)
+		.query(&CliContext::global().gitlab)
+		.with_context(|| {
+			format!(
+				"Failed to list secure files of {}",
+				&project.name_with_namespace
+			)
+		})
+}
+
+/// Returns the secure file of a project with the given name or id
+fn get_secure_file(project: &Project, file: &str) -> Result<SecureFile> {
+	let mut files = secure_files(project)?;
+	let id = file.parse::<u64>().ok();
+	match files
+		.iter()
+		.position(|secure_file| secure_file.name == file)
+		.or_else(|| {
+			files
+				.iter()
+				.position(|secure_file| Some(secure_file.id) == id)
+		}) {
+		Some(i) => Ok(files.swap_remove(i)),
+		None => bail!(
+			"Secure file {} not found in {}",
+			file,
+			&project.path_with_namespace
+		),
+	}
+}
+
+pub fn cmd(args: &args::SecureFile) -> Result<ExitCode> {
+	match &args.cmd {
+		SecureFileCmd::List(args) => {
+			let project = CliContext::global().get_project(args.project.as_ref())?;
+			let files = secure_files(&project)?;
+			CliContext::global().print_secure_files(&files, &project)?;
+			if CliContext::global().open {
+				CliContext::global().open_url(format!("{}/-/ci/secure_files", project.web_url));
+			}
+			Ok(ExitCode::from(0))
+		}
+
+		SecureFileCmd::Upload(args) => {
+			let name = match &args.name {
+				Some(name) => name.to_owned(),
+				None => args
+					.file
+					.file_name()
+					.map(|name| name.to_string_lossy().into_owned())
+					.ok_or_else(|| anyhow!("Give a name to {:?}", &args.file))?,
+			};
+			// the endpoint sends the content in a single request, which is fine given the size
+			// limit of the secure files
+			let size = fs::metadata(&args.file)
+				.with_context(|| format!("Can't read {:?}", &args.file))?
+				.len();
+			if size > MAX_SIZE {
+				bail!(
+					"{:?} is too large ({} bytes), secure files are limited to 5MB",
+					&args.file,
+					size
+				);
+			}
+			let content =
+				fs::read(&args.file).with_context(|| format!("Can't read {:?}", &args.file))?;
+			let project = CliContext::global().get_project(args.project.as_ref())?;
+			let endpoint = CreateSecureFile::builder()
+				.project(project.id.value())
+				.name(&name)
+				.content(&content)
+				.build()?;
+			let file: SecureFile = endpoint
+				.query(&CliContext::global().gitlab)
+				.with_context(|| format!("Failed to upload {}", &name))?;
+			println!(
+				"Secure file {} ({}) uploaded to {}",
+				&file.name, file.id, &project.path_with_namespace
+			);
+			Ok(ExitCode::from(0))
+		}
+
+		SecureFileCmd::Download(args) => {
+			let project = CliContext::global().get_project(args.project.as_ref())?;
+			let file = get_secure_file(&project, &args.file)?;
+			let path = args
+				.output
+				.clone()
+				.unwrap_or_else(|| PathBuf::from(&file.name));
+			let endpoint = DownloadSecureFile::builder()
+				.project(project.id.value())
+				.file_id(file.id)
+				.build()?;
+			let mut content = TraceReader::new(&CliContext::global().gitlab, &endpoint)?;
+			let mut output = create(&path, args.force)?;
+			// check the content while writing it
+			let mut hasher = Sha256::new();
+			let mut buf = [0; 1 << 16];
+			let res = loop {
+				match content.read(&mut buf) {
+					Ok(0) => break Ok(()),
+					Ok(len) => {
+						hasher.update(&buf[..len]);
+						if let Err(err) = output.write_all(&buf[..len]) {
+							break Err(err);
+						}
+					}
+					Err(err) if err.kind() == io::ErrorKind::Interrupted => (),
+					Err(err) => break Err(err),
+				}
+			};
+			if let Err(err) = res {
+				let _ = fs::remove_file(&path);
+				return Err(err)
+					.with_context(|| format!("Failed to download {} to {:?}", &file.name, &path));
+			}
+			if file.checksum_algorithm == "sha256"
+				&& hex::encode(hasher.finalize()) != file.checksum
+			{
+				let _ = fs::remove_file(&path);
+				bail!("Checksum mismatch for {}, download removed", &file.name);
+			}
+			println!("Secure file {} written to {:?}", &file.name, &path);
+			Ok(ExitCode::from(0))
+		}
+
+		SecureFileCmd::Delete(args) => {
+			let project = CliContext::global().get_project(args.project.as_ref())?;
+			let file = get_secure_file(&project, &args.file)?;
+			CliContext::global().print_secure_files(slice::from_ref(&file), &project)?;
+			if !args.yes
+				&& !CliContext::global()
+					.prompt
+					.confirm("Delete 1 secure file?")?
+			{
+				return Ok(ExitCode::from(1));
+			}
+			let endpoint = DeleteSecureFile::builder()
+				.project(project.id.value())
+				.file_id(file.id)
+				.build()?;
+			api::ignore(endpoint)
+				.query(&CliContext::global().gitlab)
+				.with_context(|| format!("Failed to delete secure file {}", &file.name))?;
+			println!(
+				"Secure file {} deleted from {}",
+				&file.name, &project.path_with_namespace
+			);
+			Ok(ExitCode::from(0))
+		}
+	}
+}
@@ -0,0 +1,229 @@
+use gil::args;
+
+use anyhow::{anyhow, bail, Context, Result};
+use flate2::read::GzDecoder;
+use reqwest::blocking::Client;
+use semver::Version;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::{
+	env::{
+		self,
+		consts::{ARCH, OS},
+	},
+	fs::{self, File},
+	io,
+	path::Path,
+	process::ExitCode,
+};
+
+/// A release asset on GitHub
+#[derive(Deserialize)]
+struct GithubAsset {
+	name: String,
+	browser_download_url: String,
+}
+
+/// A release on GitHub
+#[derive(Deserialize)]
+struct GithubRelease {
+	tag_name: String,
+	assets: Vec<GithubAsset>,
+}
+
+/// A release link on GitLab
+#[derive(Deserialize)]
+struct GitlabLink {
+	name: String,
+	url: String,
+	direct_asset_url: Option<String>,
+}
+
+/// The assets of a GitLab release
+#[derive(Deserialize)]
+struct GitlabAssets {
+	links: Vec<GitlabLink>,
+}
+
+/// A release on GitLab
+#[derive(Deserialize)]
+struct GitlabRelease {
+	tag_name: String,
+	assets: GitlabAssets,
+}
+
+/// A release with its version and its assets (name, download url)
+struct Release {
+	version: Version,
+	assets: Vec<(String, String)>,
+}
+
+impl Release {
+	/// Get the latest release from GitHub or from a GitLab instance
+	fn latest(client: &Client, args: &args::SelfUpdate) -> Result<Self> {
+		let (tag, assets) = if let Some(host) = &args.gitlab {
+			let url = format!(
+				"https://{}/api/v4/projects/{}/releases/permalink/latest",
+				host,
+				args.project.replace('/', "%2F")
+			);
+			let release: GitlabRelease = client
+				.get(&url)
+				.send()
+				.and_then(|rsp| rsp.error_for_status())
+				.and_then(|rsp| rsp.json())
+				.with_context(|| format!("Failed to get the latest release from {}", &url))?;
+			let assets = release
+				.assets
+				.links
+				.into_iter()
+				.map(|link| (link.name, link.direct_asset_url.unwrap_or(link.url)))
+				.collect();
+			(release.tag_name, assets)
+		} else {
+			let url = format!(
+				"https://api.github.com/repos/{}/releases/latest",
+				&args.project
+			);
+			let release: GithubRelease = client
+				.get(&url)
+				.send()
+				.and_then(|rsp| rsp.error_for_status())
+				.and_then(|rsp| rsp.json())
+				.with_context(|| format!("Failed to get the latest release from {}", &url))?;
+			let assets = release
+				.assets
+				.into_iter()
+				.map(|asset| (asset.name, asset.browser_download_url))
+				.collect();
+			(release.tag_name, assets)
+		};
+		let version = Version::parse(tag.trim_start_matches('v'))
+			.with_context(|| format!("Release tag {} is not a version", &tag))?;
+		Ok(Self { version, assets })
+	}
+
+	/// Get the download url of an asset
+	fn asset_url(&self, name: &str) -> Result<&str> {
+		self.assets
+			.iter()
+			.find_map(|(n, url)| (n == name).then_some(url.as_str()))
+			.ok_or_else(|| anyhow!("Asset {} not found in release {}", name, self.version))
+	}
+}
+
+/// Download an url to memory
+fn download(client: &Client, url: &str) -> Result<Vec<u8>> {
+	let bytes = client
+		.get(url)
+		.send()
+		.and_then(|rsp| rsp.error_for_status())
+		.and_then(|rsp| rsp.bytes())
+		.with_context(|| format!("Failed to download {}", url))?;
+	Ok(bytes.to_vec())
+}
+
+/// Extract the binary from the release tarball to the given path
+fn extract_binary(targz: &[u8], bin_name: &str, dest: &Path) -> Result<()> {
+	let mut archive = tar::Archive::new(GzDecoder::new(targz));
+	for entry in archive.entries()? {
+		let mut entry = entry?;
+		let is_binary = entry.header().entry_type() == tar::EntryType::Regular
+			&& entry.path()?.file_name().and_then(|name| name.to_str()) == Some(bin_name);
+		if is_binary {
+			let mut file =
+				File::create(dest).with_context(|| format!("Can't create file {:?}", dest))?;
+			io::copy(&mut entry, &mut file)
+				.with_context(|| format!("Can't extract {} to {:?}", bin_name, dest))?;
+			return Ok(());
+		}
+	}
+	bail!("{} not found in the release archive", bin_name)
+}
+
+/// Replace the running executable by the new one
+fn replace_exe(new: &Path, exe: &Path) -> Result<()> {
+	#[cfg(unix)]
+	{
+		use std::os::unix::fs::PermissionsExt;
+		fs::set_permissions(new, fs::Permissions::from_mode(0o755))?;
+	}
+	// a running executable can't be overwritten on windows but it can be renamed
+	#[cfg(windows)]
+	{
+		let old = exe.with_extension("old.exe");
+		let _ = fs::remove_file(&old);
+		fs::rename(exe, &old).with_context(|| format!("Can't rename {:?}", exe))?;
+	}
+	fs::rename(new, exe).with_context(|| format!("Can't replace {:?}", exe))
+}
+
+/// Command implementation
+pub fn cmd(args: &args::SelfUpdate, verbose: bool) -> Result<ExitCode> {
+	let client = Client::builder()
+		.user_agent(concat!(
+			env!("CARGO_PKG_NAME"),
+			"/",
+			env!("CARGO_PKG_VERSION")
+		))
+		.build()?;
+	let current = Version::parse(env!("CARGO_PKG_VERSION"))?;
+	let release = Release::latest(&client, args)?;
+
+	let newer = release.version > current;
+	if args.check && newer {
+		println!(
+			"{} {} is available (installed {})",
+			env!("CARGO_PKG_NAME"),
+			release.version,
+			current
+		);
+		return Ok(ExitCode::from(0));
+	}
+	// --force reinstalls the installed release, but never makes --check report an update
+	if !newer && (args.check || !args.force) {
+		println!("{} {} is up to date", env!("CARGO_PKG_NAME"), current);
+		return Ok(ExitCode::from(0));
+	}
+
+	// release assets are named after the platform and come with a sha256 checksum file
+	let asset = format!("{}-{}-{}.tar.gz", env!("CARGO_PKG_NAME"), ARCH, OS);
+	let checksum = format!("{}.sha256", &asset);
+	let targz = download(&client, release.asset_url(&asset)?)?;
+	let expected = String::from_utf8(download(&client, release.asset_url(&checksum)?)?)?;
+	let expected = expected
+		.split_whitespace()
+		.next()
+		.ok_or_else(|| anyhow!("Empty checksum file {}", &checksum))?;
+	let actual = hex::encode(Sha256::digest(&targz));
+	if !actual.eq_ignore_ascii_case(expected) {
+		bail!(
+			"Checksum mismatch for {}: expected {} got {}",
+			&asset,
+			expected,
+			&actual
+		);
+	}
+	if verbose {
+		println!("Checksum of {} verified ({})", &asset, &actual);
+	}
+
+	// extract next to the current executable to be able to rename it atomically
+	let exe = env::current_exe()?.canonicalize()?;
+	let bin_name = format!("{}{}", env!("CARGO_PKG_NAME"), env::consts::EXE_SUFFIX);
+	let new = exe.with_file_name(format!(".{}.new", &bin_name));
+	let res = extract_binary(&targz, &bin_name, &new).and_then(|_| replace_exe(&new, &exe));
+	if res.is_err() {
+		let _ = fs::remove_file(&new);
+	}
+	res?;
+
+	println!(
+		"{} updated from {} to {} ({:?})",
+		env!("CARGO_PKG_NAME"),
+		current,
+		release.version,
+		&exe
+	);
+	Ok(ExitCode::from(0))
+}
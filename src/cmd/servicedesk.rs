@@ -0,0 +1,57 @@
+use gil::{
+	args::{self, ServiceDeskCmd, ServiceDeskSettingsCmd},
+	context::CliContext,
+	types::{Issue, Project},
+};
+
+use anyhow::{Context, Result};
+use gitlab::api::{
+	self,
+	issues::{IssueState, ProjectIssues},
+	projects::EditProject,
+	Pagination, Query,
+};
+use std::process::ExitCode;
+
+/// The user authoring the issues created by email
+static SUPPORT_BOT: &str = "support-bot";
+
+pub fn cmd(args: &args::ServiceDesk) -> Result<ExitCode> {
+	let project = CliContext::global().get_project(args.project.as_ref())?;
+	match &args.cmd {
+		ServiceDeskCmd::Issues(args) => {
+			let mut builder = ProjectIssues::builder();
+			builder.project(project.id.value()).author(SUPPORT_BOT);
+			if !args.all {
+				builder.state(IssueState::Opened);
+			}
+			let issues: Vec<Issue> = api::paged(builder.build()?, Pagination::All)
+				.query(&CliContext::global().gitlab)
+				.with_context(|| {
+					format!(
+						"Failed to list Service Desk issues of {}",
+						&project.name_with_namespace
+					)
+				})?;
+			CliContext::global().print_service_desk_issues(&issues, &project)
+		}
+		ServiceDeskCmd::Settings(args) => match &args.cmd {
+			ServiceDeskSettingsCmd::Get(_) => CliContext::global().print_service_desk(&project),
+			ServiceDeskSettingsCmd::Set(args) => {
+				let endpoint = EditProject::builder()
+					.project(project.id.value())
+					.service_desk_enabled(args.enabled)
+					.build()?;
+				let updated: Project = endpoint
+					.query(&CliContext::global().gitlab)
+					.with_context(|| {
+						format!(
+							"Failed to change the Service Desk of {}",
+							&project.name_with_namespace
+						)
+					})?;
+				CliContext::global().print_service_desk(&updated)
+			}
+		},
+	}
+}
@@ -0,0 +1,47 @@
+use gil::{args, context::CliContext, types::MergeRequest};
+
+use anyhow::{Context, Result};
+use gitlab::api::{projects::merge_requests, Query};
+use std::process::ExitCode;
+
+pub fn cmd(args: &args::Status) -> Result<ExitCode> {
+	let context = CliContext::global();
+	let project = context.get_project(args.project.as_ref())?;
+	let ref_ = context.get_ref(args.ref_.as_deref(), &project)?;
+	context.print_project(&project, &ref_)?;
+
+	// merge requests only exist for branches
+	if context.find_tag(&ref_, &project)?.is_none() {
+		let mrs: Vec<MergeRequest> = merge_requests::MergeRequests::builder()
+			.project(project.id.value())
+			.source_branch(ref_.as_str())
+			.state(merge_requests::MergeRequestState::Opened)
+			.build()?
+			.query(&context.gitlab)
+			.with_context(|| {
+				format!(
+					"Failed to list merge requests of {}",
+					&project.path_with_namespace
+				)
+			})?;
+		if let Some(mr) = mrs.first() {
+			context.print_merge_request(mr)?;
+		} else {
+			println!("No opened merge request for {}", &ref_);
+		}
+	}
+
+	match context.get_pipeline(None, &project, &ref_) {
+		Ok(pipeline) => {
+			context.print_pipeline(&pipeline, &project)?;
+			let jobs = context.get_jobs(&project, pipeline.id.value())?;
+			context.print_jobs(&jobs)?;
+		}
+		Err(_) => println!("No pipeline for {}", &ref_),
+	}
+
+	if context.open {
+		CliContext::global().open_url(format!("{}/-/tree/{}", &project.web_url, &ref_));
+	}
+	Ok(ExitCode::from(0))
+}
@@ -0,0 +1,66 @@
+use super::clone::{clone_url, git, git_output, group_projects, root_dir};
+use gil::args;
+
+use anyhow::{anyhow, Result};
+use std::{path::Path, process::ExitCode};
+
+/// Number of commits the current branch is ahead and behind its upstream
+fn ahead_behind(dir: &Path) -> Result<(u32, u32)> {
+	let counts = git_output(dir, &["rev-list", "--left-right", "--count", "HEAD...@{upstream}"])?;
+	let (ahead, behind) = counts
+		.split_once(char::is_whitespace)
+		.ok_or_else(|| anyhow!("Unexpected rev-list output: {}", &counts))?;
+	Ok((ahead.trim().parse()?, behind.trim().parse()?))
+}
+
+pub fn cmd(args: &args::Sync) -> Result<ExitCode> {
+	let (group, projects) = group_projects(&args.group)?;
+	if projects.is_empty() {
+		println!("No project found in group {}", &group.full_path);
+		return Ok(ExitCode::from(0));
+	}
+
+	let root = root_dir(args.dir.as_ref())?;
+	let (mut cloned, mut pulled, mut uptodate, mut failed) = (0, 0, 0, 0);
+	for project in &projects {
+		let path = &project.path_with_namespace;
+		let dest = root.join(path);
+		let res = if dest.exists() {
+			git(Some(&dest), &["fetch", "--all", "--prune", "--quiet"])
+				.and_then(|_| ahead_behind(&dest))
+				.and_then(|(ahead, behind)| {
+					if behind == 0 {
+						if ahead == 0 {
+							uptodate += 1;
+						} else {
+							println!("{}: {} ahead", path, ahead);
+						}
+						return Ok(());
+					}
+					println!("{}: {} ahead, {} behind", path, ahead, behind);
+					if args.dry_run {
+						return Ok(());
+					}
+					// never create merge commits in the local clones
+					git(Some(&dest), &["pull", "--ff-only", "--quiet"]).map(|_| pulled += 1)
+				})
+		} else if args.dry_run {
+			println!("{}: not cloned", path);
+			Ok(())
+		} else {
+			println!("Cloning {}", path);
+			clone_url(project)
+				.and_then(|url| git(None, &["clone", url, &dest.to_string_lossy()]))
+				.map(|_| cloned += 1)
+		};
+		if let Err(e) = res {
+			eprintln!("{}: {:#}", path, e);
+			failed += 1;
+		}
+	}
+	println!(
+		"{} cloned, {} pulled, {} up to date, {} failed",
+		cloned, pulled, uptodate, failed
+	);
+	Ok(ExitCode::from(if failed == 0 { 0 } else { 1 }))
+}
@@ -1,53 +1,217 @@
-use crate::{
-	args::{self, TagsCmd},
+use gil::{
+	args::{self, AccessSpec, TagsCmd},
 	context::CliContext,
-	types,
+	types::{self, Project},
 };
 
 use anyhow::{Context, Result};
 use gitlab::api::{
 	self,
-	projects::protected_tags::{ProtectTag, ProtectedTags, UnprotectTag},
+	projects::{
+		protected_tags::{ProtectTag, ProtectedAccess, ProtectedTags, UnprotectTag},
+		releases::CreateRelease,
+		repository::tags::CreateTag,
+	},
 	Query,
 };
-use serde::Deserialize;
-use std::process::ExitCode;
+use serde::{Deserialize, Serialize};
+use std::{fs, process::ExitCode};
 
 #[derive(Deserialize)]
 struct Tag {
 	name: String,
 }
 
+/// Protection rule of a tag expression as given on the command line or in a rules file
+#[derive(Deserialize, Serialize, Clone)]
+pub struct TagRule {
+	/// tag expression
+	pub name: String,
+	/// remove the protection instead
+	#[serde(default, skip_serializing_if = "std::ops::Not::not")]
+	pub unprotect: bool,
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub create_access: Vec<AccessSpec>,
+}
+
+/// Remove the protection of a tag expression on a project
+pub fn unprotect(project: &Project, tag: &str) -> Result<()> {
+	let endpoint = ProtectedTags::builder()
+		.project(project.path_with_namespace.to_owned())
+		.build()?;
+	let tags: Vec<types::ProtectedTag> = endpoint.query(&CliContext::global().gitlab)?;
+
+	if !tags.iter().any(|t| t.name == tag) {
+		println!(
+			"tag '{}' protection not found on project {}",
+			&tag, &project.path_with_namespace
+		);
+	} else {
+		let endpoint = UnprotectTag::builder()
+			.project(project.path_with_namespace.to_owned())
+			.name(tag)
+			.build()?;
+		api::ignore(endpoint).query(&CliContext::global().gitlab)?;
+		println!(
+			"tag '{}' protection has been removed on project {}",
+			&tag, &project.path_with_namespace
+		);
+	}
+	Ok(())
+}
+
+/// Protect a tag expression on a project, updating the access levels of an existing protection
+pub fn protect(project: &Project, rule: &TagRule) -> Result<()> {
+	let tag = rule.name.as_str();
+	let endpoint = ProtectedTags::builder()
+		.project(project.path_with_namespace.to_owned())
+		.build()?;
+	let tags: Vec<types::ProtectedTag> = endpoint.query(&CliContext::global().gitlab)?;
+
+	let protected = tags.iter().any(|t| t.name == tag);
+	if protected && rule.create_access.is_empty() {
+		println!(
+			"tag '{}' protection already added on project {}",
+			&tag, &project.path_with_namespace
+		);
+	} else {
+		// unprotect first to update the access levels
+		if protected {
+			let endpoint = UnprotectTag::builder()
+				.project(project.path_with_namespace.to_owned())
+				.name(tag)
+				.build()?;
+			api::ignore(endpoint).query(&CliContext::global().gitlab)?;
+		}
+		let mut builder = ProtectTag::builder();
+		builder
+			.project(project.path_with_namespace.to_owned())
+			.name(tag);
+		// a role sets the access level, users and groups are added to the allowed list
+		for access in &rule.create_access {
+			match CliContext::global().get_protected_access(access)? {
+				ProtectedAccess::Level(level) => builder.create_access_level(level),
+				access => builder.allowed_to_create(access),
+			};
+		}
+		let endpoint = builder.build()?;
+		let tag: Tag = endpoint
+			.query(&CliContext::global().gitlab)
+			.with_context(|| {
+				format!(
+					"Failed to protect tag '{}' on project {}",
+					&tag, &project.path_with_namespace
+				)
+			})?;
+		println!(
+			"tag '{}' is protected on project {}",
+			tag.name, &project.path_with_namespace
+		);
+	}
+	Ok(())
+}
+
+/// Returns the section of a changelog whose heading mentions the tag (with or without its v
+/// prefix), or the whole changelog if there is none
+fn changelog_section<'a>(changelog: &'a str, tag: &str) -> &'a str {
+	let version = tag.strip_prefix('v').unwrap_or(tag);
+	// offset of the section content and level of its heading
+	let mut section: Option<(usize, usize)> = None;
+	let mut offset = 0;
+	for line in changelog.split_inclusive('\n') {
+		let level = line.chars().take_while(|&c| c == '#').count();
+		if level > 0 {
+			match section {
+				Some((start, section_level)) if level <= section_level => {
+					return changelog[start..offset].trim();
+				}
+				None if line
+					.split(|c: char| !(c.is_ascii_alphanumeric() || "._-+".contains(c)))
+					.any(|word| word.strip_prefix('v').unwrap_or(word) == version) =>
+				{
+					section = Some((offset + line.len(), level));
+				}
+				_ => (),
+			}
+		}
+		offset += line.len();
+	}
+	match section {
+		Some((start, _)) => changelog[start..].trim(),
+		None => changelog.trim(),
+	}
+}
+
 pub fn cmd(args: &args::Tags) -> Result<ExitCode> {
 	match &args.cmd {
-		TagsCmd::Unprotect(args) => {
+		TagsCmd::Create(args) => {
 			let project = CliContext::global().get_project(args.project.as_ref())?;
-			let tag = CliContext::global().get_tagexp(Some(&args.tag))?;
-
-			let endpoint = ProtectedTags::builder()
-				.project(project.path_with_namespace.to_owned())
+			let notes = match &args.notes_file {
+				Some(path) => {
+					let changelog = fs::read_to_string(path)
+						.with_context(|| format!("Can't read {:?}", path))?;
+					Some(changelog_section(&changelog, &args.tag).to_owned())
+				}
+				None => None,
+			};
+			let ref_ = CliContext::global().get_branchexp(args.ref_.as_ref())?;
+			let endpoint = CreateTag::builder()
+				.project(project.id.value())
+				.tag_name(args.tag.as_str())
+				.ref_(ref_.as_str())
+				.message(args.message.as_deref().unwrap_or(&args.tag))
 				.build()?;
-			let tags: Vec<types::ProtectedTag> = endpoint.query(&CliContext::global().gitlab)?;
+			let tag: types::Tag = endpoint
+				.query(&CliContext::global().gitlab)
+				.with_context(|| {
+					format!(
+						"Failed to create tag '{}' on project {}",
+						&args.tag, &project.path_with_namespace
+					)
+				})?;
+			println!(
+				"tag '{}' created on {} ({}) of project {}",
+				&tag.name,
+				ref_,
+				tag.commit.short_id.value(),
+				&project.path_with_namespace
+			);
 
-			if !tags.iter().any(|t| &t.name == tag) {
-				println!(
-					"tag '{}' protection not found on project {}",
-					&tag, &project.path_with_namespace
-				);
-			} else {
-				let endpoint = UnprotectTag::builder()
-					.project(project.path_with_namespace.to_owned())
-					.name(tag)
-					.build()?;
-				api::ignore(endpoint).query(&CliContext::global().gitlab)?;
+			let release = args.release || notes.is_some();
+			if release {
+				let mut builder = CreateRelease::builder();
+				builder
+					.project(project.id.value())
+					.tag_name(tag.name.as_str())
+					.name(tag.name.as_str());
+				if let Some(notes) = &notes {
+					builder.description(notes.as_str());
+				}
+				api::ignore(builder.build()?)
+					.query(&CliContext::global().gitlab)
+					.with_context(|| format!("Failed to create release {}", &tag.name))?;
 				println!(
-					"tag '{}' protection has been removed on project {}",
-					&tag, &project.path_with_namespace
+					"release '{}' created on project {}",
+					&tag.name, &project.path_with_namespace
 				);
 			}
 
 			if CliContext::global().open {
-				let _ = open::that(format!("{}/-/settings/repository", project.web_url));
+				let page = if release { "releases" } else { "tags" };
+				CliContext::global()
+					.open_url(format!("{}/-/{}/{}", project.web_url, page, &tag.name));
+			}
+
+			Ok(ExitCode::from(0))
+		}
+
+		TagsCmd::Unprotect(args) => {
+			let project = CliContext::global().get_project(args.project.as_ref())?;
+			let tag = CliContext::global().get_tagexp(Some(&args.tag))?;
+			unprotect(&project, tag)?;
+
+			if CliContext::global().open {
+				CliContext::global().open_url(format!("{}/-/settings/repository", project.web_url));
 			}
 
 			Ok(ExitCode::from(0))
@@ -56,41 +220,58 @@ pub fn cmd(args: &args::Tags) -> Result<ExitCode> {
 		TagsCmd::Protect(args) => {
 			let project = CliContext::global().get_project(args.project.as_ref())?;
 			let tag = CliContext::global().get_tagexp(Some(&args.tag))?;
+			let rule = TagRule {
+				name: tag.to_owned(),
+				unprotect: false,
+				create_access: args.create_access.clone(),
+			};
+			protect(&project, &rule)?;
+
+			if CliContext::global().open {
+				CliContext::global().open_url(format!("{}/-/settings/repository", project.web_url));
+			}
 
+			Ok(ExitCode::from(0))
+		}
+
+		TagsCmd::Protections(args) => {
+			let project = CliContext::global().get_project(args.project.as_ref())?;
 			let endpoint = ProtectedTags::builder()
 				.project(project.path_with_namespace.to_owned())
 				.build()?;
 			let tags: Vec<types::ProtectedTag> = endpoint.query(&CliContext::global().gitlab)?;
 
-			if tags.iter().any(|t| &t.name == tag) {
-				println!(
-					"tag '{}' protection already added on project {}",
-					&tag, &project.path_with_namespace
-				);
-			} else {
-				let endpoint = ProtectTag::builder()
-					.project(project.path_with_namespace.to_owned())
-					.name(tag)
-					.build()?;
-				let tag: Tag = endpoint
-					.query(&CliContext::global().gitlab)
-					.with_context(|| {
-						format!(
-							"Failed to protect tag '{}' on project {}",
-							&tag, &project.path_with_namespace
-						)
-					})?;
-				println!(
-					"tag '{}' is protected on project {}",
-					tag.name, &project.path_with_namespace
-				);
-			}
-
 			if CliContext::global().open {
-				let _ = open::that(format!("{}/-/settings/repository", project.web_url));
+				CliContext::global().open_url(format!("{}/-/settings/repository", project.web_url));
 			}
 
-			Ok(ExitCode::from(0))
+			CliContext::global().print_protected_tags(&tags, &project)
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::changelog_section;
+
+	#[test]
+	fn changelog_sections() {
+		let changelog = concat!(
+			"# Changelog\n",
+			"\n",
+			"## [1.3.0] - 2026-10-01\n",
+			"\n",
+			"### Added\n",
+			"- tags create\n",
+			"\n",
+			"## v1.2.0\n",
+			"- release notes\n",
+		);
+		assert_eq!(
+			changelog_section(changelog, "v1.3.0"),
+			"### Added\n- tags create"
+		);
+		assert_eq!(changelog_section(changelog, "1.2.0"), "- release notes");
+		assert_eq!(changelog_section(changelog, "v1.2"), changelog.trim());
+	}
+}
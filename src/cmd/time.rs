@@ -0,0 +1,92 @@
+use gil::{
+	args::{self, TimeCmd},
+	context::CliContext,
+	types::{Issue, MergeRequest, MilestoneTime, TimeStats},
+};
+
+use anyhow::{Context, Result};
+use gitlab::api::{self, issues::ProjectIssues, projects::merge_requests, Pagination, Query};
+use std::process::ExitCode;
+
+/// Add the time tracked on an issue or a merge request to its milestone entry
+fn add_time<'a>(
+	report: &'a mut Vec<MilestoneTime>,
+	milestone: Option<&str>,
+	stats: &TimeStats,
+) -> &'a mut MilestoneTime {
+	let index = match report
+		.iter()
+		.position(|entry| entry.milestone.as_deref() == milestone)
+	{
+		Some(index) => index,
+		None => {
+			report.push(MilestoneTime {
+				milestone: milestone.map(str::to_owned),
+				..Default::default()
+			});
+			report.len() - 1
+		}
+	};
+	let entry = &mut report[index];
+	entry.time_stats.time_estimate += stats.time_estimate;
+	entry.time_stats.total_time_spent += stats.total_time_spent;
+	entry
+}
+
+pub fn cmd(args: &args::Time) -> Result<ExitCode> {
+	let project = CliContext::global().get_project(args.project.as_ref())?;
+	match &args.cmd {
+		TimeCmd::Report(args) => {
+			// the issues endpoint only filters milestones by timebox, so filter by title here
+			let endpoint = ProjectIssues::builder()
+				.project(project.id.value())
+				.build()?;
+			let issues: Vec<Issue> = api::paged(endpoint, Pagination::All)
+				.query(&CliContext::global().gitlab)
+				.with_context(|| {
+					format!("Failed to list issues of {}", &project.name_with_namespace)
+				})?;
+			let issues = issues.into_iter().filter(|issue| {
+				args.milestone.as_ref().is_none_or(|title| {
+					issue.milestone.as_ref().is_some_and(|m| &m.title == title)
+				})
+			});
+
+			let mut builder = merge_requests::MergeRequests::builder();
+			builder.project(project.id.value());
+			if let Some(milestone) = &args.milestone {
+				builder.milestone(milestone);
+			}
+			let mrs: Vec<MergeRequest> = api::paged(builder.build()?, Pagination::All)
+				.query(&CliContext::global().gitlab)
+				.with_context(|| {
+					format!(
+						"Failed to list merge requests of {}",
+						&project.name_with_namespace
+					)
+				})?;
+
+			// only count what has some tracked time
+			let tracked =
+				|stats: &TimeStats| stats.time_estimate != 0 || stats.total_time_spent != 0;
+			let mut report = Vec::new();
+			for issue in issues.filter(|issue| tracked(&issue.time_stats)) {
+				let milestone = issue.milestone.as_ref().map(|m| m.title.as_str());
+				add_time(&mut report, milestone, &issue.time_stats).issues += 1;
+			}
+			for mr in mrs.iter().filter(|mr| tracked(&mr.time_stats)) {
+				let milestone = mr.milestone.as_ref().map(|m| m.title.as_str());
+				add_time(&mut report, milestone, &mr.time_stats).merge_requests += 1;
+			}
+			// milestones by title, the time without milestone last
+			report.sort_by(|a, b| match (&a.milestone, &b.milestone) {
+				(Some(a), Some(b)) => a.cmp(b),
+				(a, b) => b.is_some().cmp(&a.is_some()),
+			});
+
+			CliContext::global().print_time_report(&report, &project)?;
+		}
+	}
+
+	Ok(ExitCode::from(0))
+}
@@ -0,0 +1,84 @@
+use gil::{
+	api::todos::{MarkTodoDone, PendingTodos},
+	args::{self, TodoCmd},
+	context::CliContext,
+	types::{Project, Todo},
+};
+
+use anyhow::{bail, Context, Result};
+use gitlab::api::{self, Pagination, Query};
+use std::process::ExitCode;
+
+/// Returns the pending todos of the current user (on a project)
+fn pending_todos(project: Option<&Project>) -> Result<Vec<Todo>> {
+	let endpoint = PendingTodos::builder()
+		.project_id(project.map(|project| project.id.value()))
+		.build()?;
+	api::paged(endpoint, Pagination::All)
+		.query(&CliContext::global().gitlab)
+		.context("Failed to list todos")
+}
+
+/// Mark a todo as done
+fn mark_done(id: u64) -> Result<()> {
+	let endpoint = MarkTodoDone::builder().todo_id(id).build()?;
+	api::ignore(endpoint)
+		.query(&CliContext::global().gitlab)
+		.with_context(|| format!("Failed to mark todo {} as done", id))
+}
+
+/// Mark the pending todos about an issue or a merge request (`target_type`) of a project as done
+pub fn resolve_todos(project: &Project, target_type: &str, iid: u64) -> Result<()> {
+	let todos: Vec<Todo> = pending_todos(Some(project))?
+		.into_iter()
+		.filter(|todo| {
+			todo.target_type == target_type
+				&& todo.target.as_ref().and_then(|target| target.iid) == Some(iid)
+		})
+		.collect();
+	for todo in &todos {
+		mark_done(todo.id)?;
+	}
+	println!("{} todo(s) marked as done", todos.len());
+	Ok(())
+}
+
+pub fn cmd(args: &args::Todo) -> Result<ExitCode> {
+	match &args.cmd {
+		TodoCmd::List(args) => {
+			let project = args
+				.project
+				.as_ref()
+				.map(|project| CliContext::global().get_project(Some(project)))
+				.transpose()?;
+			let todos = pending_todos(project.as_ref())?;
+			CliContext::global().print_todos(&todos, project.as_ref())
+		}
+		TodoCmd::Done(args) => {
+			match (args.id, &args.project) {
+				(Some(id), None) => {
+					mark_done(id)?;
+					println!("Todo {} marked as done", id);
+				}
+				(None, Some(project)) => {
+					let project = CliContext::global().get_project(Some(project))?;
+					let todos = pending_todos(Some(&project))?;
+					CliContext::global().print_todos(&todos, Some(&project))?;
+					if todos.is_empty() {
+						return Ok(ExitCode::from(0));
+					}
+					let question = format!("Mark {} todo(s) as done?", todos.len());
+					if !args.yes && !CliContext::global().prompt.confirm(&question)? {
+						return Ok(ExitCode::from(1));
+					}
+					for todo in &todos {
+						mark_done(todo.id)?;
+					}
+					println!("{} todo(s) marked as done", todos.len());
+				}
+				_ => bail!("Give either a todo id or --project"),
+			}
+			Ok(ExitCode::from(0))
+		}
+	}
+}
@@ -1,23 +1,43 @@
 use std::process::ExitCode;
 
-use crate::{
+use gil::{
 	api::{
 		personal_access_tokens::{
-			PersonalAccessTokenState, PersonalAccessTokens, RevokePersonalAccessToken,
-			RotatePersonalAccessToken,
+			CurrentPersonalAccessToken, PersonalAccessTokenState, PersonalAccessTokens,
+			RevokePersonalAccessToken, RotatePersonalAccessToken,
 		},
 		users::personal_access_tokens::CreatePersonalAccessToken,
 	},
 	args,
-	args::TokenCmd,
+	args::{TokenCmd, TokenOutput},
 	context::CliContext,
 	types::token::PersonalAccessToken,
+	utils::glob_match,
 };
 
 use anyhow::{bail, Context, Result};
-use gitlab::api::{self, Query};
+use chrono::{Duration, Utc};
+use gitlab::api::{self, Pagination, Query};
+
+/// Variable name used for env and dotenv outputs
+static TOKEN_VAR: &str = "GITLAB_TOKEN";
+
+/// Print the secret of a newly created or rotated token
+fn print_secret(token: &PersonalAccessToken, output: TokenOutput) -> Result<()> {
+	let Some(secret) = &token.token else {
+		bail!("Token not found in response");
+	};
+	match output {
+		TokenOutput::Raw => println!("{}", secret),
+		TokenOutput::Env => println!("export {}={}", TOKEN_VAR, secret),
+		TokenOutput::Dotenv => println!("{}={}", TOKEN_VAR, secret),
+		TokenOutput::Json => println!("{}", serde_json::to_string_pretty(token)?),
+	}
+	Ok(())
+}
 
 pub fn cmd(args: &args::Token) -> Result<ExitCode> {
+	let mut exit_code = ExitCode::from(0);
 	match &args.cmd {
 		TokenCmd::Create(args) => {
 			// try to revoke a token with the the same name
@@ -43,20 +63,58 @@ pub fn cmd(args: &args::Token) -> Result<ExitCode> {
 				.scopes(args.scopes.iter())
 				.build()?;
 			let token: PersonalAccessToken = endpoint.query(&CliContext::global().gitlab)?;
-			if let Some(token) = token.token {
-				println!("{}", token);
-			} else {
-				bail!("Token not found in response");
-			}
+			print_secret(&token, args.output)?;
+		}
+
+		TokenCmd::Info(_) => {
+			let token: PersonalAccessToken = CurrentPersonalAccessToken
+				.query(&CliContext::global().gitlab)
+				.context(
+					"Failed to get the current token (only personal access tokens are supported)",
+				)?;
+			let user = CliContext::global().get_user(None)?;
+			CliContext::global().print_tokens(&[token], &user)?;
 		}
 
 		TokenCmd::Revoke(args) => {
-			let token = CliContext::global().get_token(args.name.as_ref())?;
-			let endpoint = RevokePersonalAccessToken::builder()
-				.token_id(token.id)
-				.build()?;
-			api::ignore(endpoint).query(&CliContext::global().gitlab)?;
-			println!("token {}({}) has been revoked", args.name, token.id);
+			let (tokens, user) = match (&args.name, &args.match_) {
+				(Some(name), None) => (
+					vec![CliContext::global().get_token(name)?],
+					CliContext::global().get_user(None)?,
+				),
+				(None, Some(pattern)) => {
+					let user = CliContext::global().get_user(args.username.as_deref())?;
+					let endpoint = PersonalAccessTokens::builder()
+						.user_id(user.id.value())
+						.state(Some(PersonalAccessTokenState::Active))
+						.build()?;
+					let mut tokens: Vec<PersonalAccessToken> =
+						api::paged(endpoint, Pagination::All)
+							.query(&CliContext::global().gitlab)?;
+					tokens.retain(|token| glob_match(pattern, &token.name));
+					if tokens.is_empty() {
+						bail!("No active token matching {}", pattern);
+					}
+					(tokens, user)
+				}
+				_ => bail!("Give either a token name or a --match pattern"),
+			};
+			// show what will be revoked as an id or a name can designate another token than the
+			// one intended
+			CliContext::global().print_tokens(&tokens, &user)?;
+			let question = format!("Revoke {} token(s)?", tokens.len());
+			if !args.yes && !CliContext::global().prompt.confirm(&question)? {
+				return Ok(ExitCode::from(1));
+			}
+			for token in tokens {
+				let endpoint = RevokePersonalAccessToken::builder()
+					.token_id(token.id)
+					.build()?;
+				api::ignore(endpoint)
+					.query(&CliContext::global().gitlab)
+					.with_context(|| format!("Failed to revoke token {}", &token.name))?;
+				println!("token {}({}) has been revoked", &token.name, token.id);
+			}
 		}
 
 		TokenCmd::List(args) => {
@@ -72,34 +130,46 @@ pub fn cmd(args: &args::Token) -> Result<ExitCode> {
 				builder.state(Some(PersonalAccessTokenState::Active));
 			}
 			let endpoint = builder.build()?;
-			let tokens: Vec<PersonalAccessToken> = endpoint.query(&CliContext::global().gitlab)?;
-			if tokens.is_empty() {
+			let mut tokens: Vec<PersonalAccessToken> =
+				api::paged(endpoint, Pagination::All).query(&CliContext::global().gitlab)?;
+			if let Some(days) = args.expiring {
+				// no token expiring is a success (useful in cron jobs)
+				let limit = Utc::now().date_naive() + Duration::days(days.into());
+				tokens.retain(|token| token.expires_at.is_some_and(|date| date <= limit));
+				if !tokens.is_empty() {
+					exit_code = ExitCode::from(1);
+				}
+			} else if tokens.is_empty() {
 				bail!("No token found matching criterias");
 			}
-			CliContext::global().print_tokens(&tokens, &user)?;
+			// the token page is still opened when no token is expiring
+			if !tokens.is_empty() {
+				CliContext::global().print_tokens(&tokens, &user)?;
+			}
 		}
 
 		TokenCmd::Rotate(args) => {
+			CliContext::global().require_version((16, 0), "Token rotation")?;
+			if args.expires_at.is_some() {
+				CliContext::global()
+					.require_version((16, 6), "The expiration date of a rotated token")?;
+			}
 			let token = CliContext::global().get_token(args.name.as_ref())?;
 			let endpoint = RotatePersonalAccessToken::builder()
 				.token_id(token.id)
 				.expires_at(args.expires_at)
 				.build()?;
 			let token: PersonalAccessToken = endpoint.query(&CliContext::global().gitlab)?;
-			if let Some(token) = token.token {
-				println!("{}", token);
-			} else {
-				bail!("Token not found in response");
-			}
+			print_secret(&token, args.output)?;
 		}
 	}
 
 	if CliContext::global().open {
-		let _ = open::that(format!(
+		CliContext::global().open_url(format!(
 			"https://{}/-/profile/personal_access_tokens",
 			CliContext::global().repo.host
 		));
 	}
 
-	Ok(ExitCode::from(0))
+	Ok(exit_code)
 }
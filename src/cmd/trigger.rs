@@ -0,0 +1,107 @@
+use gil::{
+	api::projects::triggers::{
+		CreatePipelineTrigger, DeletePipelineTrigger, PipelineTriggers, TriggerPipeline,
+	},
+	args::{self, TriggerCmd},
+	context::CliContext,
+	types::{self, PipelineTrigger},
+};
+
+use anyhow::{anyhow, Context, Result};
+use gitlab::api::{self, Pagination, Query};
+use std::{env, process::ExitCode};
+
+pub fn cmd(args: &args::Trigger) -> Result<ExitCode> {
+	let project = CliContext::global().get_project(args.project.as_ref())?;
+	let list_triggers = || -> Result<Vec<PipelineTrigger>> {
+		let endpoint = PipelineTriggers::builder()
+			.project(project.id.value())
+			.build()?;
+		api::paged(endpoint, Pagination::All)
+			.query(&CliContext::global().gitlab)
+			.with_context(|| {
+				format!(
+					"Failed to list triggers of {}",
+					&project.name_with_namespace
+				)
+			})
+	};
+
+	match &args.cmd {
+		TriggerCmd::List(_) => {
+			let triggers = list_triggers()?;
+			CliContext::global().print_triggers(&triggers, &project)?;
+			if CliContext::global().open {
+				CliContext::global().open_url(format!("{}/-/settings/ci_cd", project.web_url));
+			}
+		}
+
+		TriggerCmd::Create(args) => {
+			let endpoint = CreatePipelineTrigger::builder()
+				.project(project.id.value())
+				.description(&args.description)
+				.build()?;
+			let trigger: PipelineTrigger = endpoint
+				.query(&CliContext::global().gitlab)
+				.with_context(|| format!("Failed to create trigger {}", &args.description))?;
+			println!("{}", trigger.token);
+		}
+
+		TriggerCmd::Delete(args) => {
+			let endpoint = DeletePipelineTrigger::builder()
+				.project(project.id.value())
+				.trigger_id(args.id)
+				.build()?;
+			api::ignore(endpoint)
+				.query(&CliContext::global().gitlab)
+				.with_context(|| format!("Failed to delete trigger {}", args.id))?;
+			println!(
+				"trigger {} has been deleted from project {}",
+				args.id, &project.path_with_namespace
+			);
+		}
+
+		TriggerCmd::Run(args) => {
+			let token = if let Some(id) = args.id {
+				list_triggers()?
+					.into_iter()
+					.find(|trigger| trigger.id == id)
+					.map(|trigger| trigger.token)
+					.ok_or_else(|| anyhow!("Trigger {} not found", id))?
+			} else {
+				env::var(&args.token_env).with_context(|| {
+					format!(
+						"Give a trigger id or set the token in {}",
+						&args.token_env
+					)
+				})?
+			};
+			let ref_ = CliContext::global().check_ref(args.ref_.as_deref(), &project)?;
+			let variables: Vec<(String, String)> = args
+				.var
+				.iter()
+				.map(|var| (var.0.to_owned(), var.1.to_owned()))
+				.collect();
+			let endpoint = TriggerPipeline::builder()
+				.project(project.id.value())
+				.token(&token)
+				.ref_(&ref_)
+				.variables(&variables)
+				.build()?;
+			let pipeline: types::Pipeline = endpoint
+				.query(&CliContext::global().gitlab)
+				.with_context(|| {
+					format!(
+						"Failed to trigger a pipeline for {} @ {}",
+						&project.path_with_namespace, &ref_
+					)
+				})?;
+			CliContext::global().print_pipeline(&pipeline, &project)?;
+			if CliContext::global().open {
+				CliContext::global().open_url(pipeline.web_url);
+			}
+		}
+	}
+
+	Ok(ExitCode::from(0))
+}
@@ -1,7 +1,18 @@
-use crate::{api::user::User, args, context::CliContext, types};
+use gil::{
+	api::{
+		user::User,
+		users::{MembershipSource, UserEvents, UserMemberships},
+	},
+	args,
+	context::CliContext,
+	types::{
+		self,
+		user::{Event, Membership, UserProfile},
+	},
+};
 
-use anyhow::Result;
-use gitlab::api::Query;
+use anyhow::{Context, Result};
+use gitlab::api::{self, projects::Projects, users, Pagination, Query};
 use std::process::ExitCode;
 
 pub fn cmd(args: &args::Users) -> Result<ExitCode> {
@@ -25,5 +36,49 @@ pub fn cmd(args: &args::Users) -> Result<ExitCode> {
 				})
 				.unwrap_or(ExitCode::from(1)))
 		}
+		args::UserCmd::Info(args) => {
+			let profile: UserProfile = if let Some(username) = &args.username {
+				let user = CliContext::global().get_user(Some(username))?;
+				let endpoint = users::User::builder().user(user.id.value()).build()?;
+				endpoint.query(&CliContext::global().gitlab)?
+			} else {
+				User::build().query(&CliContext::global().gitlab)?
+			};
+			if CliContext::global().open {
+				CliContext::global().open_url(&profile.web_url);
+			}
+			CliContext::global().print_user_profile(&profile)
+		}
+		args::UserCmd::Activity(args) => {
+			let user = CliContext::global().get_user(args.username.as_deref())?;
+			let endpoint = UserEvents::builder().user_id(user.id.value()).build()?;
+			let events: Vec<Event> = api::paged(endpoint, Pagination::Limit(args.limit))
+				.query(&CliContext::global().gitlab)
+				.with_context(|| format!("Failed to get the events of {}", &user.username))?;
+			CliContext::global().print_events(&events, &user)
+		}
+		args::UserCmd::Projects(args) => {
+			if let Some(username) = &args.username {
+				// only admins can list the memberships of another user
+				let user = CliContext::global().get_user(Some(username))?;
+				let endpoint = UserMemberships::builder()
+					.user_id(user.id.value())
+					.source_type(Some(MembershipSource::Project))
+					.build()?;
+				let memberships: Vec<Membership> = api::paged(endpoint, Pagination::All)
+					.query(&CliContext::global().gitlab)
+					.with_context(|| {
+						format!("Failed to get the memberships of {}", &user.username)
+					})?;
+				CliContext::global().print_memberships(&memberships, &user)
+			} else {
+				let user = CliContext::global().get_current_user()?;
+				let endpoint = Projects::builder().membership(true).build()?;
+				let projects: Vec<types::Project> = api::paged(endpoint, Pagination::All)
+					.query(&CliContext::global().gitlab)
+					.context("Failed to get the projects of current user")?;
+				CliContext::global().print_user_projects(&projects, &user)
+			}
+		}
 	}
 }
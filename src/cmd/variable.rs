@@ -0,0 +1,318 @@
+use gil::{
+	api::projects::variables::ProjectVariables,
+	args::{self, VariableCmd},
+	context::CliContext,
+	types::{variable::VariableType, Project, Variable},
+};
+
+use anyhow::{bail, Context, Result};
+use gitlab::api::{
+	self,
+	projects::variables::{CreateProjectVariable, ProjectVariableFilter, UpdateProjectVariable},
+	Pagination, Query,
+};
+use std::{
+	borrow::Cow,
+	collections::HashMap,
+	fs,
+	io::{self, Read},
+	process::ExitCode,
+};
+
+/// Returns the variables of a project in an environment scope
+fn project_variables(project: &Project, environment: &str) -> Result<Vec<Variable>> {
+	let endpoint = ProjectVariables::builder()
+		.project(project.id.value())
+		.build()?;
+	let variables: Vec<Variable> = api::paged(endpoint, Pagination::All)
+		.query(&CliContext::global().gitlab)
+		.with_context(|| {
+			format!(
+				"Failed to list variables of {}",
+				&project.name_with_namespace
+			)
+		})?;
+	Ok(variables
+		.into_iter()
+		.filter(|variable| variable.environment_scope == environment)
+		.collect())
+}
+
+/// Quote a value for a dotenv file if necessary
+fn dotenv_value(value: &str) -> Cow<'_, str> {
+	if !value.is_empty()
+		&& value
+			.chars()
+			.all(|c| c.is_ascii_alphanumeric() || "_-./:@+,=%".contains(c))
+	{
+		return value.into();
+	}
+	let mut quoted = String::with_capacity(value.len() + 2);
+	quoted.push('"');
+	for c in value.chars() {
+		match c {
+			'\n' => quoted.push_str("\\n"),
+			'\r' => quoted.push_str("\\r"),
+			'\t' => quoted.push_str("\\t"),
+			'"' | '\\' | '$' => {
+				quoted.push('\\');
+				quoted.push(c);
+			}
+			c => quoted.push(c),
+		}
+	}
+	quoted.push('"');
+	quoted.into()
+}
+
+/// Append a double quoted string to `value`, interpreting its escape sequences. Returns what
+/// follows the closing quote, or None if the string continues on the next line
+fn unquote<'a>(quoted: &'a str, value: &mut String) -> Option<&'a str> {
+	let mut chars = quoted.char_indices();
+	while let Some((i, c)) = chars.next() {
+		match c {
+			'"' => return Some(&quoted[i + 1..]),
+			'\\' => match chars.next() {
+				Some((_, 'n')) => value.push('\n'),
+				Some((_, 'r')) => value.push('\r'),
+				Some((_, 't')) => value.push('\t'),
+				Some((_, c @ ('"' | '\\' | '$'))) => value.push(c),
+				Some((_, c)) => {
+					value.push('\\');
+					value.push(c);
+				}
+				None => value.push('\\'),
+			},
+			c => value.push(c),
+		}
+	}
+	None
+}
+
+/// Parse the `KEY=VALUE` lines of a dotenv file, skipping blank lines and comments. Values can
+/// be single quoted (literal) or double quoted (with escape sequences, on several lines)
+fn parse_dotenv(content: &str) -> Result<Vec<(String, String)>> {
+	let mut variables = Vec::new();
+	let mut lines = content.lines().enumerate();
+	while let Some((i, line)) = lines.next() {
+		let line = line.trim();
+		if line.is_empty() || line.starts_with('#') {
+			continue;
+		}
+		let line = line.strip_prefix("export ").unwrap_or(line);
+		let Some((key, value)) = line.split_once('=') else {
+			bail!("Line {}: KEY=VALUE expected", i + 1);
+		};
+		let key = key.trim();
+		if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+			bail!("Line {}: invalid variable name {:?}", i + 1, key);
+		}
+		let value = value.trim_start();
+		let value = if let Some(quoted) = value.strip_prefix('"') {
+			let mut value = String::new();
+			let mut quoted = quoted;
+			let rest = loop {
+				if let Some(rest) = unquote(quoted, &mut value) {
+					break rest;
+				}
+				let Some((_, line)) = lines.next() else {
+					bail!("Line {}: unterminated quote", i + 1);
+				};
+				value.push('\n');
+				quoted = line;
+			};
+			let rest = rest.trim();
+			if !rest.is_empty() && !rest.starts_with('#') {
+				bail!("Line {}: unexpected {:?} after the value", i + 1, rest);
+			}
+			value
+		} else if let Some(quoted) = value.strip_prefix('\'') {
+			match quoted.split_once('\'') {
+				Some((value, _)) => value.to_owned(),
+				None => bail!("Line {}: unterminated quote", i + 1),
+			}
+		} else {
+			// a comment can follow an unquoted value
+			let value = value.split(" #").next().unwrap_or_default();
+			value.trim_end().to_owned()
+		};
+		variables.push((key.to_owned(), value));
+	}
+	Ok(variables)
+}
+
+pub fn cmd(args: &args::Variable) -> Result<ExitCode> {
+	match &args.cmd {
+		VariableCmd::Export(args) => {
+			let project = CliContext::global().get_project(args.project.as_ref())?;
+			for variable in project_variables(&project, &args.environment)? {
+				if variable.variable_type == VariableType::File {
+					eprintln!("{} skipped (file variable)", &variable.key);
+					continue;
+				}
+				let Some(value) = &variable.value else {
+					eprintln!("{} skipped (hidden variable)", &variable.key);
+					continue;
+				};
+				println!("{}={}", &variable.key, dotenv_value(value));
+			}
+			Ok(ExitCode::from(0))
+		}
+
+		VariableCmd::Import(args) => {
+			let content = if args.file == "-" {
+				let mut content = String::new();
+				io::stdin().read_to_string(&mut content).map(|_| content)
+			} else {
+				fs::read_to_string(&args.file)
+			}
+			.with_context(|| format!("Can't read {}", &args.file))?;
+			let variables =
+				parse_dotenv(&content).with_context(|| format!("Can't parse {}", &args.file))?;
+
+			let project = CliContext::global().get_project(args.project.as_ref())?;
+			let existing = project_variables(&project, &args.environment)?;
+			let existing: HashMap<_, _> = existing
+				.iter()
+				.map(|variable| (variable.key.as_str(), variable))
+				.collect();
+
+			// confirm the update of the variables whose value differs (or is hidden)
+			let updates: Vec<_> = variables
+				.iter()
+				.filter(|(key, value)| {
+					existing
+						.get(key.as_str())
+						.is_some_and(|variable| variable.value.as_ref() != Some(value))
+				})
+				.map(|(key, _)| key.as_str())
+				.collect();
+			if !updates.is_empty() && !args.yes {
+				println!("Variables to update: {}", updates.join(", "));
+				let question = format!(
+					"Update {} variable(s) of {}?",
+					updates.len(),
+					&project.path_with_namespace
+				);
+				if !CliContext::global().prompt.confirm(&question)? {
+					return Ok(ExitCode::from(1));
+				}
+			}
+
+			let (mut created, mut updated) = (0, 0);
+			for (key, value) in &variables {
+				match existing.get(key.as_str()) {
+					Some(variable) if variable.value.as_ref() == Some(value) => (),
+					Some(_) => {
+						let filter = ProjectVariableFilter::builder()
+							.environment_scope(args.environment.as_str())
+							.build()?;
+						let endpoint = UpdateProjectVariable::builder()
+							.project(project.id.value())
+							.key(key.as_str())
+							.value(value.as_str())
+							.filter(filter)
+							.build()?;
+						api::ignore(endpoint)
+							.query(&CliContext::global().gitlab)
+							.with_context(|| format!("Failed to update variable {}", key))?;
+						updated += 1;
+					}
+					None => {
+						let endpoint = CreateProjectVariable::builder()
+							.project(project.id.value())
+							.key(key.as_str())
+							.value(value.as_str())
+							.protected(args.protected)
+							.masked(args.masked)
+							.environment_scope(args.environment.as_str())
+							.build()?;
+						api::ignore(endpoint)
+							.query(&CliContext::global().gitlab)
+							.with_context(|| format!("Failed to create variable {}", key))?;
+						created += 1;
+					}
+				}
+			}
+			println!(
+				"{} variable(s) created, {} updated and {} unchanged in {}",
+				created,
+				updated,
+				variables.len() - created - updated,
+				&project.path_with_namespace
+			);
+			Ok(ExitCode::from(0))
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{dotenv_value, parse_dotenv};
+
+	#[test]
+	fn quote_values() {
+		assert_eq!(
+			dotenv_value("https://example.com/a"),
+			"https://example.com/a"
+		);
+		assert_eq!(dotenv_value(""), "\"\"");
+		assert_eq!(dotenv_value("a b"), "\"a b\"");
+		assert_eq!(
+			dotenv_value("say \"$HOME\"\nbye\\"),
+			"\"say \\\"\\$HOME\\\"\\nbye\\\\\""
+		);
+	}
+
+	#[test]
+	fn parse_lines() {
+		let content = concat!(
+			"# comment\n",
+			"\n",
+			"PLAIN=value # comment\n",
+			"export EXPORTED = spaced\n",
+			"SINGLE='$literal \\n'\n",
+			"DOUBLE=\"line1\\nline2 \\\"quoted\\\"\" # comment\n",
+			"MULTI=\"first\n",
+			"second\"\n",
+			"EMPTY=\n",
+		);
+		assert_eq!(
+			parse_dotenv(content).unwrap(),
+			[
+				("PLAIN", "value"),
+				("EXPORTED", "spaced"),
+				("SINGLE", "$literal \\n"),
+				("DOUBLE", "line1\nline2 \"quoted\""),
+				("MULTI", "first\nsecond"),
+				("EMPTY", ""),
+			]
+			.map(|(key, value)| (key.to_owned(), value.to_owned()))
+		);
+	}
+
+	#[test]
+	fn roundtrip() {
+		for value in ["simple", "", "with space", "a\"b'c$d\\e\nf\tg\r"] {
+			let line = format!("KEY={}", dotenv_value(value));
+			assert_eq!(
+				parse_dotenv(&line).unwrap(),
+				[("KEY".to_owned(), value.to_owned())]
+			);
+		}
+	}
+
+	#[test]
+	fn reject_invalid_lines() {
+		for content in [
+			"NOVALUE",
+			"=value",
+			"BAD-NAME=1",
+			"Q=\"open",
+			"Q='open",
+			"Q=\"a\" b",
+		] {
+			assert!(parse_dotenv(content).is_err(), "{:?}", content);
+		}
+	}
+}
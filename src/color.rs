@@ -1,4 +1,4 @@
-/// Borowed from clap builder/styled_str.rs
+//! Borowed from clap builder/styled_str.rs
 
 /// Terminal-styling container
 #[derive(Clone, Default, Debug, PartialEq, Eq)]
@@ -31,74 +31,102 @@ impl StyledStr {
     }
 
     #[allow(dead_code)]
-    pub(crate) fn header(&mut self, msg: impl Into<String>) {
+    pub fn header(&mut self, msg: impl Into<String>) {
         self.stylize_(Some(Style::Header), msg.into());
     }
 
     #[allow(dead_code)]
-    pub(crate) fn literal(&mut self, msg: impl Into<String>) {
+    pub fn literal(&mut self, msg: impl Into<String>) {
         self.stylize_(Some(Style::Literal), msg.into());
     }
 
     #[allow(dead_code)]
-    pub(crate) fn placeholder(&mut self, msg: impl Into<String>) {
+    pub fn placeholder(&mut self, msg: impl Into<String>) {
         self.stylize_(Some(Style::Placeholder), msg.into());
     }
 
     #[allow(dead_code)]
-    pub(crate) fn good(&mut self, msg: impl Into<String>) {
+    pub fn good(&mut self, msg: impl Into<String>) {
         self.stylize_(Some(Style::Good), msg.into());
     }
 
     #[allow(dead_code)]
-    pub(crate) fn warning(&mut self, msg: impl Into<String>) {
+    pub fn warning(&mut self, msg: impl Into<String>) {
         self.stylize_(Some(Style::Warning), msg.into());
     }
 
     #[allow(dead_code)]
-    pub(crate) fn error(&mut self, msg: impl Into<String>) {
+    pub fn error(&mut self, msg: impl Into<String>) {
         self.stylize_(Some(Style::Error), msg.into());
     }
 
     #[allow(dead_code)]
-    pub(crate) fn hint(&mut self, msg: impl Into<String>) {
+    pub fn hint(&mut self, msg: impl Into<String>) {
         self.stylize_(Some(Style::Hint), msg.into());
     }
 
-    pub(crate) fn none(&mut self, msg: impl Into<String>) {
+    pub fn none(&mut self, msg: impl Into<String>) {
         self.stylize_(None, msg.into());
     }
 
     #[allow(dead_code)]
-    pub(crate) fn stylize(&mut self, style: impl Into<Option<Style>>, msg: impl Into<String>) {
+    pub fn stylize(&mut self, style: impl Into<Option<Style>>, msg: impl Into<String>) {
         self.stylize_(style.into(), msg.into());
     }
 
+    /// Returns true if there is nothing to display
+    pub fn is_empty(&self) -> bool {
+        self.iter().all(|(_, content)| content.is_empty())
+    }
+
     #[allow(dead_code)]
-    pub(crate) fn trim(&mut self) {
+    pub fn trim(&mut self) {
         self.trim_start();
         self.trim_end();
     }
 
     #[allow(dead_code)]
-    pub(crate) fn trim_start(&mut self) {
+    pub fn trim_start(&mut self) {
         if let Some((_, item)) = self.iter_mut().next() {
             *item = item.trim_start().to_owned();
         }
     }
 
     #[cfg(feature = "color")]
-    pub(crate) fn trim_end(&mut self) {
+    pub fn trim_end(&mut self) {
         if let Some((_, item)) = self.pieces.last_mut() {
             *item = item.trim_end().to_owned();
         }
     }
 
     #[cfg(not(feature = "color"))]
-    pub(crate) fn trim_end(&mut self) {
+    pub fn trim_end(&mut self) {
         self.pieces = self.pieces.trim_end().to_owned();
     }
 
+    /// Move the urls (hints starting with ` (http`) that would overflow a line of the given width
+    /// to the beginning of the next line
+    #[cfg(feature = "color")]
+    pub fn wrap_urls(&mut self, width: usize) {
+        let mut line_len = 0;
+        for (style, content) in &mut self.pieces {
+            if *style == Some(Style::Hint)
+                && content.starts_with(" (http")
+                && line_len + content.chars().count() > width
+            {
+                content.replace_range(..1, "\n  ");
+            }
+            line_len = match content.rfind('\n') {
+                Some(i) => content[i + 1..].chars().count(),
+                None => line_len + content.chars().count(),
+            };
+        }
+    }
+
+    /// Urls can't be told apart without styles
+    #[cfg(not(feature = "color"))]
+    pub fn wrap_urls(&mut self, _width: usize) {}
+
     #[cfg(feature = "color")]
     fn stylize_(&mut self, style: Option<Style>, msg: String) {
         if !msg.is_empty() {
@@ -112,22 +140,22 @@ impl StyledStr {
     }
 
     #[cfg(feature = "color")]
-    pub(crate) fn iter(&self) -> impl Iterator<Item = (Option<Style>, &str)> {
+    pub fn iter(&self) -> impl Iterator<Item = (Option<Style>, &str)> {
         self.pieces.iter().map(|(s, c)| (*s, c.as_str()))
     }
 
     #[cfg(not(feature = "color"))]
-    pub(crate) fn iter(&self) -> impl Iterator<Item = (Option<Style>, &str)> {
+    pub fn iter(&self) -> impl Iterator<Item = (Option<Style>, &str)> {
         [(None, self.pieces.as_str())].into_iter()
     }
 
     #[cfg(feature = "color")]
-    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = (Option<Style>, &mut String)> {
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Option<Style>, &mut String)> {
         self.pieces.iter_mut().map(|(s, c)| (*s, c))
     }
 
     #[cfg(not(feature = "color"))]
-    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = (Option<Style>, &mut String)> {
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Option<Style>, &mut String)> {
         [(None, &mut self.pieces)].into_iter()
     }
 
@@ -144,7 +172,7 @@ impl StyledStr {
     }
 
     #[allow(dead_code)]
-    pub(crate) fn extend(
+    pub fn extend(
         &mut self,
         other: impl IntoIterator<Item = (impl Into<Option<Style>>, impl Into<String>)>,
     ) {
@@ -154,7 +182,7 @@ impl StyledStr {
     }
 
     #[cfg(feature = "color")]
-    pub(crate) fn write_colored(&self, buffer: &mut termcolor::Buffer) -> std::io::Result<()> {
+    pub fn write_colored(&self, buffer: &mut termcolor::Buffer) -> std::io::Result<()> {
         use std::io::Write;
         use termcolor::WriteColor;
 
@@ -281,7 +309,7 @@ impl std::fmt::Display for AnsiDisplay<'_> {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
-pub(crate) enum Style {
+pub enum Style {
     Header,
     Literal,
     Placeholder,
@@ -1,23 +1,115 @@
-use crate::{args::Opts, oidc::login};
+use crate::{
+	args::{DateFormat, Opts},
+	connection::HttpOptions,
+	oidc::login,
+};
 
 use anyhow::{anyhow, Context, Result};
 use directories::ProjectDirs;
+use reqwest::Certificate;
 use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
 use std::{
 	collections::HashMap,
 	env,
 	ffi::OsStr,
-	fs::{create_dir_all, File},
+	fs::{self, create_dir_all, read_to_string, File},
 	ops::Deref,
-	path::PathBuf,
+	path::{Path, PathBuf},
+	time::Duration,
 };
 
-static ORG: &str = "ITSufficient";
+pub static ORG: &str = "ITSufficient";
+
+/// Supported configuration file extensions by order of preference
+static EXTENSIONS: [&str; 2] = ["yaml", "toml"];
+
+/// Returns the first configuration file with a supported extension found in a directory
+fn find_config(dir: &Path, name: &str) -> Option<PathBuf> {
+	EXTENSIONS
+		.iter()
+		.map(|ext| dir.join(format!("{}.{}", name, ext)))
+		.find(|path| path.exists())
+}
+
+/// Replace the ${VAR} references of a string by the value of the environment variables. `$${`
+/// is kept as a literal `${`
+fn interpolate_env(s: &str) -> Result<String> {
+	let mut res = String::with_capacity(s.len());
+	let mut rest = s;
+	while let Some(start) = rest.find("${") {
+		// escaped reference
+		if rest[..start].ends_with('$') {
+			res.push_str(&rest[..start - 1]);
+			res.push_str("${");
+			rest = &rest[start + 2..];
+			continue;
+		}
+		let len = rest[start..]
+			.find('}')
+			.ok_or_else(|| anyhow!("Unterminated variable in {}", s))?;
+		let name = &rest[start + 2..start + len];
+		let value =
+			env::var(name).with_context(|| format!("Environment variable {} is not set", name))?;
+		res.push_str(&rest[..start]);
+		res.push_str(&value);
+		rest = &rest[start + len + 1..];
+	}
+	res.push_str(rest);
+	Ok(res)
+}
+
+/// Read a secret from a file, without the trailing newline
+fn read_secret(path: &str) -> Result<String> {
+	let path = interpolate_env(path.trim())?;
+	let secret = read_to_string(&path).with_context(|| format!("Can't read secret {}", &path))?;
+	Ok(secret.trim_end_matches(['\r', '\n']).to_owned())
+}
+
+/// Substitute the ${VAR} references in strings and the `!file path` values (YAML tag or string
+/// prefix) by the content of the file
+fn interpolate(value: &mut Value) -> Result<()> {
+	match value {
+		Value::String(s) => {
+			*s = match s.strip_prefix("!file ") {
+				Some(path) => read_secret(path)?,
+				None => interpolate_env(s)?,
+			}
+		}
+		Value::Tagged(tagged) if tagged.tag == "file" => {
+			let path = tagged
+				.value
+				.as_str()
+				.ok_or_else(|| anyhow!("!file expects a path"))?;
+			*value = Value::String(read_secret(path)?);
+		}
+		Value::Tagged(tagged) => interpolate(&mut tagged.value)?,
+		Value::Sequence(values) => {
+			for value in values {
+				interpolate(value)?;
+			}
+		}
+		Value::Mapping(mapping) => {
+			for (_, value) in mapping.iter_mut() {
+				interpolate(value)?;
+			}
+		}
+		Value::Null | Value::Bool(_) | Value::Number(_) => {}
+	}
+	Ok(())
+}
 
 /// Root configuration file
 #[derive(Deserialize)]
 pub struct Config {
 	pub hosts: HashMap<String, HostConfig>,
+	/// language of the relative dates (2 letters code)
+	pub language: Option<String>,
+	/// show dates as relative or absolute
+	#[serde(default)]
+	pub dates: DateFormat,
+	/// command opening the links instead of the default browser (url appended)
+	pub browser: Option<String>,
 	#[serde(skip)]
 	pub path: PathBuf,
 }
@@ -29,6 +121,49 @@ pub struct HostConfig {
 	pub ca: Option<String>,
 	/// auth type
 	pub auth: AuthType,
+	/// protocol used to clone repositories: ssh (default) or https
+	#[serde(rename = "clone-protocol", default)]
+	pub clone_protocol: CloneProtocol,
+	/// projects shown by the dashboard command
+	#[serde(default)]
+	pub dashboard: Vec<String>,
+	/// maximum number of seconds to wait for the answer of a request
+	pub timeout: Option<u64>,
+	/// don't verify the tls certificate of the host (test instances only)
+	#[serde(rename = "insecure-skip-verify", default)]
+	pub insecure_skip_verify: bool,
+}
+
+impl HostConfig {
+	/// The settings of the http connections to the host, the command line taking precedence
+	pub fn http_options(&self, opts: &Opts) -> Result<HttpOptions> {
+		let ca = self
+			.ca
+			.as_ref()
+			.map(|ca| {
+				fs::read(ca)
+					.with_context(|| format!("Error reading {}", ca))
+					.and_then(|pem| {
+						Certificate::from_pem(&pem)
+							.with_context(|| format!("Reading certificate {}", ca))
+					})
+			})
+			.transpose()?;
+		Ok(HttpOptions {
+			timeout: opts.timeout.or(self.timeout).map(Duration::from_secs),
+			insecure: self.insecure_skip_verify,
+			ca,
+		})
+	}
+}
+
+/// Protocol used to clone repositories
+#[derive(Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CloneProtocol {
+	#[default]
+	Ssh,
+	Https,
 }
 
 /// Authentication type supported
@@ -41,6 +176,31 @@ pub enum AuthType {
 	OAuth2(OAuth2),
 }
 
+/// Token used to authenticate to the Gitlab API
+pub enum ApiToken {
+	/// personal, project or group access token
+	Private(String),
+	/// oauth2 access token
+	OAuth2(String),
+}
+
+impl ApiToken {
+	/// The secret value of the token
+	pub fn value(&self) -> &str {
+		match self {
+			Self::Private(token) | Self::OAuth2(token) => token,
+		}
+	}
+
+	/// The token type
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::Private(_) => "private",
+			Self::OAuth2(_) => "oauth2",
+		}
+	}
+}
+
 /// Oidc configuration part
 #[derive(Deserialize)]
 pub struct OAuth2 {
@@ -57,8 +217,10 @@ impl Config {
 	/// Initialiser from an optional file path.
 	/// If no path is given, it will try to find one from
 	/// - GIL_CONFIG environment variable
-	/// - HOME directory: ~/.config/gil/config.yaml
-	/// - Current directory: .gil_config.yaml
+	/// - HOME directory: ~/.config/gil/config.yaml or config.toml
+	/// - Current directory: .gil_config.yaml or .gil_config.toml
+	///
+	/// The format is detected from the extension: TOML for .toml, YAML otherwise
 	pub fn from_file<T>(path: Option<&T>, verbose: bool) -> Result<Self>
 	where
 		T: AsRef<OsStr>,
@@ -75,13 +237,11 @@ impl Config {
 				.filter(|path| path.exists())
 				// then test from project dir
 				.or_else(|| {
-					ProjectDirs::from("me", ORG, env!("CARGO_BIN_NAME"))
-						.map(|dir| dir.config_dir().join("config.yaml"))
-						.filter(|path| path.exists())
+					ProjectDirs::from("me", ORG, env!("CARGO_PKG_NAME"))
+						.and_then(|dir| find_config(dir.config_dir(), "config"))
 				})
 				// then test in current directory
-				.or_else(|| Some(PathBuf::from(".gil_config.yaml")))
-				.filter(|path| path.exists())
+				.or_else(|| find_config(Path::new(""), ".gil_config"))
 				// finally return an error if nothing worked
 				.ok_or_else(|| anyhow!("Unable to find a suitable configuration file"))?
 		};
@@ -89,11 +249,20 @@ impl Config {
 		if verbose {
 			println!("Reading configuration from {:?}", &config_path);
 		}
-		// open configuration file
-		let file =
-			File::open(&config_path).with_context(|| format!("Can't open {:?}", &config_path))?;
-		// deserialize configuration
-		let mut config: Self = serde_yaml::from_reader(file)
+		// parse configuration according to its extension
+		let mut value: Value = if config_path.extension() == Some(OsStr::new("toml")) {
+			let content = read_to_string(&config_path)
+				.with_context(|| format!("Can't open {:?}", &config_path))?;
+			toml::from_str(&content).with_context(|| format!("Can't read {:?}", &config_path))?
+		} else {
+			let file = File::open(&config_path)
+				.with_context(|| format!("Can't open {:?}", &config_path))?;
+			serde_yaml::from_reader(file)
+				.with_context(|| format!("Can't read {:?}", &config_path))?
+		};
+		// substitute the secrets before deserializing the configuration
+		interpolate(&mut value).with_context(|| format!("Can't read {:?}", &config_path))?;
+		let mut config: Self = serde_yaml::from_value(value)
 			.with_context(|| format!("Can't read {:?}", &config_path))?;
 
 		// save the path for reference
@@ -102,6 +271,27 @@ impl Config {
 	}
 }
 
+impl Config {
+	/// The host to use outside of a git repository: GIL_HOST environment variable or the only
+	/// configured host
+	pub fn default_host(&self) -> Option<String> {
+		env::var("GIL_HOST")
+			.ok()
+			.or_else(|| match self.hosts.len() {
+				1 => self.hosts.keys().next().cloned(),
+				_ => None,
+			})
+	}
+
+	/// The command opening the links: GIL_BROWSER environment variable or the configured browser
+	pub fn browser(&self) -> Option<String> {
+		env::var("GIL_BROWSER")
+			.ok()
+			.or_else(|| self.browser.clone())
+			.filter(|browser| !browser.trim().is_empty())
+	}
+}
+
 /// OAuth2 login token
 #[derive(Deserialize, Serialize)]
 pub struct OAuth2Token(String);
@@ -114,7 +304,7 @@ impl OAuth2Token {
 
 	/// Try silently read the cache file
 	pub fn from_cache(host: &str) -> Option<Self> {
-		ProjectDirs::from("me", ORG, env!("CARGO_BIN_NAME"))
+		ProjectDirs::from("me", ORG, env!("CARGO_PKG_NAME"))
 			.map(|dir| dir.cache_dir().join(host))
 			.and_then(|path| {
 				File::open(path)
@@ -126,16 +316,17 @@ impl OAuth2Token {
 	/// Try to login
 	pub fn from_login(
 		host: &str,
-		ca: &Option<String>,
+		http: &HttpOptions,
 		config: &OAuth2,
+		browser: Option<&str>,
 		opts: &Opts,
 	) -> Result<Self> {
-		login(host, ca, config, opts)
+		login(host, http, config, browser, opts)
 	}
 
 	/// Try to save the cache information to file
 	pub fn save(&self, host: &str) -> Result<()> {
-		ProjectDirs::from("me", ORG, env!("CARGO_BIN_NAME"))
+		ProjectDirs::from("me", ORG, env!("CARGO_PKG_NAME"))
 			.ok_or_else(|| anyhow!("Unable to find a suitable cache file path for oidc login"))
 			.map(|dir| dir.cache_dir().join(host))
 			.and_then(|path| {
@@ -167,3 +358,52 @@ impl Deref for OAuth2Token {
 		&self.0
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::{interpolate_env, HostConfig};
+	use crate::args::Opts;
+	use argh::FromArgs;
+	use std::time::Duration;
+
+	#[test]
+	fn host_http_options() {
+		let host: HostConfig =
+			serde_yaml::from_str("auth: xxx\ntimeout: 10\ninsecure-skip-verify: true\n").unwrap();
+		let opts = Opts::from_args(&["gil"], &["status"]).unwrap();
+		let http = host.http_options(&opts).unwrap();
+		assert!(http.insecure);
+		assert_eq!(http.timeout, Some(Duration::from_secs(10)));
+		assert!(http.ca.is_none());
+
+		// the command line takes precedence
+		let opts = Opts::from_args(&["gil"], &["--timeout", "2", "status"]).unwrap();
+		let http = host.http_options(&opts).unwrap();
+		assert_eq!(http.timeout, Some(Duration::from_secs(2)));
+
+		// the certificate is verified by default
+		let host: HostConfig = serde_yaml::from_str("auth: xxx\n").unwrap();
+		assert!(!host.http_options(&opts).unwrap().insecure);
+	}
+
+	#[test]
+	fn env_references() {
+		std::env::set_var("GIL_TEST_TOKEN", "secret");
+		assert_eq!(interpolate_env("${GIL_TEST_TOKEN}").unwrap(), "secret");
+		assert_eq!(
+			interpolate_env("a-${GIL_TEST_TOKEN}-b").unwrap(),
+			"a-secret-b"
+		);
+		// escaped references are kept as is, without the escaping $
+		assert_eq!(
+			interpolate_env("$${GIL_TEST_TOKEN}").unwrap(),
+			"${GIL_TEST_TOKEN}"
+		);
+		assert_eq!(
+			interpolate_env("$$${GIL_TEST_TOKEN}").unwrap(),
+			"$${GIL_TEST_TOKEN}"
+		);
+		assert!(interpolate_env("${GIL_TEST_UNSET}").is_err());
+		assert!(interpolate_env("${GIL_TEST_TOKEN").is_err());
+	}
+}
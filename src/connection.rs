@@ -0,0 +1,147 @@
+//! A Gitlab API client sending the requests with a reqwest client configured per host.
+//!
+//! The client of the gitlab crate builds its own reqwest client and only lets choose between a
+//! verified or an insecure connection, so there is no way to give it a timeout or a root
+//! certificate. This one implements the same `RestClient` and `Client` traits, so the endpoints
+//! and the [`crate::client::GitlabClient`] wrapper are used unchanged, plus the GraphQL queries
+
+use crate::config::ApiToken;
+
+use bytes::Bytes;
+use gitlab::{
+	api::{self, users::CurrentUser, ApiError, Query},
+	AuthError, GitlabError, RestError,
+};
+use graphql_client::{GraphQLQuery, QueryBody, Response as GraphQLResponse};
+use http::{
+	header::{HeaderName, HeaderValue, AUTHORIZATION},
+	request::Builder,
+	Response,
+};
+use reqwest::{
+	blocking::{Client, ClientBuilder},
+	Certificate,
+};
+use serde::Deserialize;
+use std::{fmt::Debug, time::Duration};
+use url::Url;
+
+/// Settings of the http connections to a host
+#[derive(Debug, Clone, Default)]
+pub struct HttpOptions {
+	/// maximum duration of a request, from the connection to the end of the response
+	pub timeout: Option<Duration>,
+	/// accept any certificate, even self-signed or expired ones
+	pub insecure: bool,
+	/// additional root certificate
+	pub ca: Option<Certificate>,
+}
+
+impl HttpOptions {
+	/// Returns a client builder with the settings applied
+	pub fn client_builder(&self) -> ClientBuilder {
+		let mut builder = Client::builder().danger_accept_invalid_certs(self.insecure);
+		if let Some(ca) = &self.ca {
+			builder = builder.add_root_certificate(ca.clone());
+		}
+		match self.timeout {
+			Some(timeout) => builder.timeout(timeout),
+			None => builder,
+		}
+	}
+}
+
+/// A connection to the REST and GraphQL APIs of a Gitlab instance
+pub struct Connection {
+	client: Client,
+	rest_url: Url,
+	graphql_url: Url,
+	/// authentication header sent with every request
+	auth: (HeaderName, HeaderValue),
+}
+
+impl Connection {
+	/// Initializer connecting to the instance at a base url (`https://gitlab.com` for instance).
+	/// The token is checked by getting the current user
+	pub fn new(url: &Url, token: &ApiToken, options: &HttpOptions) -> Result<Self, GitlabError> {
+		let (name, value) = match token {
+			ApiToken::Private(token) => {
+				(HeaderName::from_static("private-token"), token.to_owned())
+			}
+			ApiToken::OAuth2(token) => (AUTHORIZATION, format!("Bearer {}", token)),
+		};
+		let mut value = HeaderValue::from_str(&value).map_err(AuthError::from)?;
+		value.set_sensitive(true);
+		let gitlab = Self {
+			client: options.client_builder().build()?,
+			rest_url: url.join("api/v4/")?,
+			graphql_url: url.join("api/graphql")?,
+			auth: (name, value),
+		};
+		api::ignore(CurrentUser::builder().build().unwrap()).query(&gitlab)?;
+		Ok(gitlab)
+	}
+
+	/// Send a GraphQL query
+	pub fn graphql<Q>(
+		&self,
+		query: &QueryBody<Q::Variables>,
+	) -> Result<Q::ResponseData, GitlabError>
+	where
+		Q: GraphQLQuery,
+		Q::Variables: Debug,
+		for<'d> Q::ResponseData: Deserialize<'d>,
+	{
+		let rsp = self
+			.client
+			.post(self.graphql_url.clone())
+			.header(&self.auth.0, &self.auth.1)
+			.json(query)
+			.send()?;
+		let status = rsp.status();
+		if status.is_server_error() {
+			return Err(GitlabError::Http { status });
+		}
+		let rsp: GraphQLResponse<Q::ResponseData> =
+			serde_json::from_reader(rsp).map_err(|source| GitlabError::DataType {
+				source,
+				typename: std::any::type_name::<Q::ResponseData>(),
+			})?;
+		if let Some(message) = rsp.errors {
+			return Err(GitlabError::GraphQL { message });
+		}
+		rsp.data.ok_or(GitlabError::NoResponse {})
+	}
+}
+
+impl api::RestClient for Connection {
+	type Error = RestError;
+
+	fn rest_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<Self::Error>> {
+		Ok(self.rest_url.join(endpoint)?)
+	}
+}
+
+impl api::Client for Connection {
+	fn rest(
+		&self,
+		mut request: Builder,
+		body: Vec<u8>,
+	) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+		let call = || -> Result<_, RestError> {
+			if let Some(headers) = request.headers_mut() {
+				headers.insert(&self.auth.0, self.auth.1.clone());
+			}
+			let rsp = self.client.execute(request.body(body)?.try_into()?)?;
+
+			let mut http_rsp = Response::builder()
+				.status(rsp.status())
+				.version(rsp.version());
+			if let Some(headers) = http_rsp.headers_mut() {
+				headers.extend(rsp.headers().clone());
+			}
+			Ok(http_rsp.body(rsp.bytes()?)?)
+		};
+		call().map_err(ApiError::client)
+	}
+}
@@ -3,35 +3,79 @@ use crate::{
 		keys::GetKey,
 		personal_access_tokens::{PersonalAccessTokenState, PersonalAccessTokens},
 		users::keys::ListKeys,
+		version,
 	},
-	args::{ColorChoice, KeyIdType, Opts, PipelineLog, SubCommand},
+	args::{
+		AccessSpec, ColorChoice, DateFormat, DurationFormat, KeyIdType, Opts, OwnedNameOrId,
+		PipelineLog, SubCommand,
+	},
+	client::GitlabClient,
 	color::{Style, StyledStr},
-	config::{AuthType, Config, OAuth2Token},
-	fmt::{Colorizer, Stream},
+	config::{ApiToken, AuthType, Config, OAuth2Token},
+	connection::{Connection, HttpOptions},
+	fmt::{ansi_support, ellipsize, term_width, Colorizer, Stream},
 	git::GitProject,
+	prompt::Prompt,
+	section::{Failure, Section, SectionType, Sections},
+	trace::{TraceReader, CHUNK_SIZE},
 	types::{
-		Job, PersonalAccessToken, Pipeline, Project, RepoBranch, SshKey, StatusState, Tag, User,
+		user::{Event, Membership, UserProfile},
+		variable::VariableType,
+		ApplicationStatistics, Commit, CommitSignature, DetailedError, Environment, Group,
+		HealthStatus, HookDelivery, Issue, Iteration, Job, License, MergeRequest, MilestoneTime,
+		ObjectId, PersonalAccessToken, Pipeline, PipelineSchedule, PipelineTrigger,
+		PipelineVariable, Project, ProjectHook, ProtectedTag, RemoteMirror, RepoBranch, SecureFile,
+		SentryError, Signatures, SshKey, StatusState, SystemHook, Tag, TimeStats, Todo, User,
+		Version,
+	},
+	utils::{
+		edit_distance, format_duration, format_latency, format_time_tracking, glob_match,
+		is_headless, join, open_browser, take_from_vec,
 	},
-	utils::{format_duration, take_from_vec},
 };
 
 use anyhow::{anyhow, bail, Context, Result};
-use chrono::{NaiveTime, Utc};
-use gitlab::{
-	api::{
-		common::NameOrId,
-		projects::{
-			self,
-			jobs::JobScope,
-			pipelines,
-			repository::{branches, tags},
-		},
-		users::{CurrentUser, Users},
-		Query,
+use bytesize::ByteSize;
+use chrono::{DateTime, Duration, NaiveTime, SecondsFormat, Utc};
+use gitlab::api::{
+	self,
+	common::{NameOrId, ProtectedAccessLevel},
+	groups,
+	projects::{
+		self, issues,
+		jobs::JobScope,
+		merge_requests::{self, pipelines::MergeRequestPipelines},
+		pipelines,
+		protected_branches::ProtectedAccess,
+		repository::{branches, commits, tags},
 	},
-	Gitlab,
+	users::{CurrentUser, Users},
+	ApiError, Endpoint, Pagination, Query,
+};
+use std::{
+	borrow::Cow,
+	collections::{BTreeMap, HashSet, VecDeque},
+	convert::Into,
+	fmt::Display,
+	io::{BufRead, Read},
+	process::ExitCode,
+	str::FromStr,
+	sync::OnceLock,
+	time::Duration as StdDuration,
 };
-use std::{convert::Into, fmt::Display, process::ExitCode, str::FromStr, sync::OnceLock};
+use timeago::{languages::IsolangLanguage, BoxedLanguage};
+use url::Url;
+
+/// Relative dates formatter in the given language (ISO 639-1 code)
+fn timeago_formatter(lang: Option<&str>) -> Result<timeago::Formatter<BoxedLanguage>> {
+	let language = match lang {
+		Some(code) => IsolangLanguage::from_639_1(code)
+			.and_then(timeago::from_isolang)
+			.ok_or_else(|| anyhow!("Unsupported language {} for the dates", code))?,
+		None => Box::new(timeago::English),
+	};
+	Ok(timeago::Formatter::with_language(language))
+}
 
 fn status_style(status: StatusState) -> Option<Style> {
 	Some(match status {
@@ -47,72 +91,6 @@ fn status_style(status: StatusState) -> Option<Style> {
 	})
 }
 
-/// Marker for section start and end
-#[derive(Debug, PartialEq, Clone)]
-enum SectionType {
-	Start,
-	End,
-}
-
-impl FromStr for SectionType {
-	type Err = anyhow::Error;
-
-	fn from_str(s: &str) -> Result<Self, Self::Err> {
-		if s == "section_start" {
-			Ok(SectionType::Start)
-		} else if s == "section_end" {
-			Ok(SectionType::End)
-		} else {
-			Err(anyhow!("Section delimiter not found"))
-		}
-	}
-}
-
-/// Parsing result of a log section
-#[derive(Debug, Clone)]
-struct Section {
-	type_: SectionType,
-	timestamp: i64,
-	name: String,
-	collapsed: bool,
-}
-
-impl FromStr for Section {
-	type Err = anyhow::Error;
-
-	/// dumb parser for <https://docs.gitlab.com/ee/ci/jobs/#expand-and-collapse-job-log-sections>
-	fn from_str(s: &str) -> Result<Self, Self::Err> {
-		if s.starts_with("section_start:") || s.starts_with("section_end:") {
-			// section_type:id:name[flags]
-			let info: [&str; 3] = s
-				// remove leading \r
-				.trim()
-				.splitn(3, ':')
-				.collect::<Vec<&str>>()
-				.try_into()
-				.unwrap();
-			// string to enum
-			let type_ = SectionType::from_str(info[0])?;
-			let name = info[2];
-			// try to separate name and flags
-			let name_flags = name
-				.find('[')
-				.and_then(|i| name.find(']').map(|j| (&name[..i], &name[i + 1..j])));
-			Ok(Self {
-				type_,
-				timestamp: info[1].parse()?,
-				name: name_flags
-					.map(|(n, _)| n.to_owned())
-					.unwrap_or_else(|| name.to_owned()),
-				collapsed: name_flags
-					.map(|(_, flags)| flags == "collapsed=true")
-					.unwrap_or(false),
-			})
-		} else {
-			Err(anyhow!("Section delimiter not found"))
-		}
-	}
-}
 /// Type of current line in the log printer
 enum LogState {
 	Text,
@@ -122,35 +100,89 @@ enum LogState {
 /// Structure to drive the section headers parser
 struct LogContext {
 	pub state: LogState,
-	pub sections: Vec<Section>,
+	pub sections: Sections,
+	/// the sections started so far, in order
+	pub started: Vec<Section>,
+	/// names of the sections expanded with --expand
+	pub expanded: HashSet<String>,
+	/// name of the only section to show (picked interactively)
+	pub only: Option<String>,
 }
 
 impl Default for LogContext {
 	fn default() -> Self {
 		Self {
 			state: LogState::Text,
-			sections: Vec::default(),
+			sections: Sections::default(),
+			started: Vec::new(),
+			expanded: HashSet::new(),
+			only: None,
 		}
 	}
 }
 
 impl LogContext {
+	/// Returns true if a section is selected by the section filter or expanded
+	fn is_selected(&self, section: &Section, args: &PipelineLog) -> bool {
+		(!args.only_headers && section.name.contains(&args.section))
+			|| self.expanded.contains(&section.name)
+	}
+
 	/// Decide if we show the current line int the log printer
 	fn show_line(&self, args: &PipelineLog) -> bool {
+		// a section picked interactively is shown alone
+		if let Some(name) = &self.only {
+			return self.sections.iter().any(|section| &section.name == name);
+		}
 		// show line if we have no filter
 		args.all
 			// if we are outside of any sections (the first log lines)
 			|| ((args.only_headers || args.headers) && self.sections.is_empty())
-			// if we are inside a non collapsed section or a collapsed one which id contains the given string
-			|| (!args.only_headers
-				&& self
+			// if we are inside a non collapsed section or a collapsed one which id contains the
+			// given string or is expanded
+			|| (self
 				.sections
 				.iter()
-				.all(|section| !section.collapsed || section.name.contains(&args.section))
+				.all(|section| !section.collapsed || self.is_selected(section, args))
 				&& self
 				.sections
 				.iter()
-				.any(|section| section.name.contains(&args.section)))
+				.any(|section| self.is_selected(section, args)))
+	}
+
+	/// Decide if we show the section headers, given if we show the lines of the section
+	fn show_header(&self, args: &PipelineLog, show_line: bool) -> bool {
+		match &self.only {
+			Some(_) => show_line,
+			None => args.all || args.headers || args.only_headers,
+		}
+	}
+}
+
+/// Color mode of the command line (never when built without color support)
+#[cfg(feature = "color")]
+fn color_choice(opts: &Opts) -> ColorChoice {
+	opts.color
+}
+
+/// Color mode of the command line (never when built without color support)
+#[cfg(not(feature = "color"))]
+fn color_choice(_opts: &Opts) -> ColorChoice {
+	ColorChoice::Never
+}
+
+/// Formatter of the dates given by the command line or the configuration (None for absolute
+/// dates). The command line takes precedence over the configuration
+fn dates_formatter(
+	opts: &Opts,
+	config: &Config,
+) -> Result<Option<timeago::Formatter<BoxedLanguage>>> {
+	match opts.dates.unwrap_or(config.dates) {
+		DateFormat::Relative => {
+			let lang = opts.lang.as_ref().or(config.language.as_ref());
+			Ok(Some(timeago_formatter(lang.map(String::as_str))?))
+		}
+		DateFormat::Absolute => Ok(None),
 	}
 }
 
@@ -168,16 +200,34 @@ pub struct CliContext {
 	pub no_cache: bool,
 	/// open links automatically
 	pub open: bool,
+	/// print the links instead of opening them
+	pub print_url: bool,
+	/// questions asked on the terminal
+	pub prompt: Prompt,
 	/// show urls
 	pub url: bool,
 	/// color mode
 	pub color: ColorChoice,
+	/// width of the terminal to fit the output in (None when not truncating)
+	pub width: Option<usize>,
+	/// formatter of the relative dates (None for absolute dates)
+	pub timeago: Option<timeago::Formatter<BoxedLanguage>>,
+	/// how durations are shown
+	pub duration: DurationFormat,
+	/// base url of the instance
+	pub base_url: Url,
+	/// settings of the http connections to the instance
+	pub http: HttpOptions,
 	/// the gitlab connexion
-	pub gitlab: Gitlab,
+	pub gitlab: GitlabClient,
+	/// the token used by the gitlab connexion
+	pub token: ApiToken,
 	/// the configuration file
 	pub config: Config,
 	/// information about the current git repo
 	pub repo: GitProject,
+	/// version of the instance, queried once when a feature depends on it
+	version: OnceLock<Option<Version>>,
 }
 
 impl CliContext {
@@ -190,8 +240,11 @@ impl CliContext {
 		// read yaml config
 		let config = Config::from_file(opts.config.as_ref(), opts.verbose)?;
 
-		// get information from git
-		let repo = GitProject::from_currentdir()?;
+		// get information from git or only the host outside of a git repository
+		let repo = match GitProject::from_currentdir() {
+			Ok(repo) => repo,
+			Err(e) => GitProject::from_host(&config.default_host().ok_or(e)?),
+		};
 
 		// get the auth configuration for the remote host
 		let host_config = config.hosts.get(&repo.host).ok_or_else(|| {
@@ -202,50 +255,172 @@ impl CliContext {
 			)
 		})?;
 
-		let gitlab = match &host_config.auth {
-			AuthType::OAuth2(oauth2) => {
-				// try to get the token from cache
-				if let Some(token) = OAuth2Token::from_cache(&repo.host) {
-					// check if we can login with that
-					if let Ok(gitlab) = Gitlab::with_oauth2(&repo.host, token) {
-						Ok(gitlab)
-					// otherwise try renew the token
+		let color = color_choice(&opts);
+		let http = host_config.http_options(&opts)?;
+		let url = Url::parse(&format!("https://{}", &repo.host))
+			.with_context(|| format!("Invalid host {}", &repo.host))?;
+
+		let (token, gitlab) = if opts.offline {
+			// reuse the known token as is (for external commands) without trying to login
+			let token = match &host_config.auth {
+				AuthType::OAuth2(_) => ApiToken::OAuth2(
+					OAuth2Token::from_cache(&repo.host)
+						.map(Into::into)
+						.unwrap_or_default(),
+				),
+				AuthType::Token(token) => ApiToken::Private(token.to_owned()),
+			};
+			let gitlab = GitlabClient::offline(&repo.host, color)
+				.token(&token)
+				.sudo(opts.sudo.clone())
+				.timing(opts.timing);
+			(token, gitlab)
+		} else {
+			if http.insecure {
+				let mut msg = StyledStr::new();
+				msg.error("WARNING:");
+				msg.warning(format!(
+					" the certificate of {} is not verified (insecure-skip-verify), the token and \
+					 the data can be intercepted by anyone on the network\n",
+					&repo.host
+				));
+				let _ = Colorizer::new(Stream::Stderr, color)
+					.with_content(msg)
+					.print();
+			}
+			let (token, gitlab) = match &host_config.auth {
+				AuthType::OAuth2(oauth2) => {
+					// try to get the token from cache and check if we can login with that
+					let cached = OAuth2Token::from_cache(&repo.host).and_then(|token| {
+						let token = ApiToken::OAuth2(token.into());
+						Connection::new(&url, &token, &http)
+							.ok()
+							.map(|gitlab| (token, gitlab))
+					});
+					if let Some((token, gitlab)) = cached {
+						(token, Ok(gitlab))
+					// otherwise try to login (or renew the token) following the oauth2 flow
 					} else {
 						println!("Trying to log in through https://{}", &repo.host);
-						let token =
-							OAuth2Token::from_login(&repo.host, &host_config.ca, oauth2, &opts)?;
-						Gitlab::with_oauth2(&repo.host, token)
+						let token = OAuth2Token::from_login(
+							&repo.host,
+							&http,
+							oauth2,
+							config.browser().as_deref(),
+							&opts,
+						)?;
+						let token = ApiToken::OAuth2(token.into());
+						let gitlab = Connection::new(&url, &token, &http);
+						(token, gitlab)
 					}
-				// otherwise try to login following the oauth2 flow
-				} else {
-					println!("Trying to log in through https://{}", &repo.host);
-					let token = crate::oidc::login(&repo.host, &host_config.ca, oauth2, &opts)?;
-					Gitlab::with_oauth2(&repo.host, token)
 				}
-			}
 
-			AuthType::Token(token) => Gitlab::new(&repo.host, token),
-		}
-		.with_context(|| format!("Can't connect to {}", &repo.host))?;
+				AuthType::Token(token) => {
+					let token = ApiToken::Private(token.to_owned());
+					let gitlab = Connection::new(&url, &token, &http);
+					(token, gitlab)
+				}
+			};
+			let gitlab = gitlab.with_context(|| format!("Can't connect to {}", &repo.host))?;
+			// wrap the client to handle rate limits and cache responses
+			let gitlab = GitlabClient::new(gitlab, &repo.host, color)
+				.token(&token)
+				.sudo(opts.sudo.clone())
+				.reuse_lookups(!opts.no_cache_api)
+				.throttle(!opts.no_throttle)
+				.timing(opts.timing);
+			(token, gitlab)
+		};
+
+		Self::from_parts(opts, config, repo, url, http, gitlab, token)
+	}
 
-		#[cfg(feature = "color")]
-		let color = opts.color;
-		#[cfg(not(feature = "color"))]
-		let color = ColorChoice::Never;
+	/// Initializer targeting the API at an arbitrary base url (`http://127.0.0.1:8080` for
+	/// instance) with a private token, instead of the host of the repository. Responses are not
+	/// cached. Used to run the commands against a fake Gitlab
+	pub fn with_url(
+		mut opts: Opts,
+		config: Config,
+		repo: GitProject,
+		url: &str,
+		token: &str,
+	) -> Result<Self> {
+		let url = Url::parse(url).with_context(|| format!("Invalid url {}", url))?;
+		let host = match (url.host_str(), url.port()) {
+			(Some(host), Some(port)) => format!("{}:{}", host, port),
+			(Some(host), None) => host.to_owned(),
+			_ => bail!("Missing host in {}", url),
+		};
+
+		let color = color_choice(&opts);
+		let http = HttpOptions {
+			timeout: opts.timeout.map(StdDuration::from_secs),
+			..Default::default()
+		};
+		let token = ApiToken::Private(token.to_owned());
+		let gitlab = Connection::new(&url, &token, &http)
+			.with_context(|| format!("Can't connect to {}", url))?;
+		let gitlab = GitlabClient::new(gitlab, &host, color)
+			.without_cache()
+			.sudo(opts.sudo.clone())
+			.throttle(!opts.no_throttle)
+			.timing(opts.timing);
+		opts.no_cache = true;
+
+		Self::from_parts(opts, config, repo, url, http, gitlab, token)
+	}
 
+	/// Initializer from the options and the connection to the instance, shared by the other
+	/// initializers
+	fn from_parts(
+		opts: Opts,
+		config: Config,
+		repo: GitProject,
+		base_url: Url,
+		http: HttpOptions,
+		gitlab: GitlabClient,
+		token: ApiToken,
+	) -> Result<Self> {
+		let color = color_choice(&opts);
+		let timeago = dates_formatter(&opts, &config)?;
 		Ok(Self {
 			cmd: opts.cmd,
 			verbose: opts.verbose,
 			no_cache: opts.no_cache,
-			open: opts.open,
+			open: opts.open || opts.print_url,
+			print_url: opts.print_url,
+			prompt: Prompt::new(!opts.non_interactive),
 			url: opts.url,
 			color,
+			width: if opts.no_truncate { None } else { term_width() },
+			timeago,
+			duration: opts.duration,
+			base_url,
+			http,
 			gitlab,
+			token,
 			config,
 			repo,
+			version: OnceLock::new(),
 		})
 	}
 
+	/// Open a link with the browser of `$GIL_BROWSER` or of the configuration (the default browser
+	/// of the system otherwise). The link is printed instead with --print-url, when there is no
+	/// display to show the default browser on or when no browser can be launched
+	pub fn open_url(&self, url: impl AsRef<str>) {
+		let url = url.as_ref();
+		let browser = self.config.browser();
+		if !self.print_url && (browser.is_some() || !is_headless()) {
+			match open_browser(url, browser.as_deref()) {
+				Ok(()) => return,
+				Err(err) if self.verbose => eprintln!("Can't open a browser: {}", err),
+				Err(_) => (),
+			}
+		}
+		println!("{}", url);
+	}
+
 	/// Get a project (which can be the one provided or a default one)
 	pub fn get_project<'a, T>(&'a self, default: Option<T>) -> Result<Project>
 	where
@@ -256,11 +431,29 @@ impl CliContext {
 				.map(Into::into)
 				.or(self.repo.name.as_deref().map(Into::into));
 		if let Some(id) = &id {
-			projects::Project::builder()
+			let project = projects::Project::builder()
 				.project(id.to_owned())
 				.build()?
-				.query(&self.gitlab)
-				.with_context(|| format!("Can't find a project named {}", id))
+				.query(&self.gitlab.lookup());
+			// suggest the closest names to recover from typos
+			if let (Err(ApiError::Gitlab { msg }), NameOrId::Name(name)) = (&project, id) {
+				if msg.starts_with("404") {
+					let similar = self.similar_projects(name).unwrap_or_default();
+					if !similar.is_empty() {
+						return project.with_context(|| {
+							format!(
+								"Can't find a project named {}, did you mean {}?",
+								name,
+								similar.join(" or ")
+							)
+						});
+					}
+				}
+			}
+			project.with_context(|| match id {
+				NameOrId::Name(name) => format!("Can't find a project named {}", name),
+				NameOrId::Id(id) => format!("Can't find a project with id {}", id),
+			})
 		} else {
 			Err(anyhow!(
 				"Can't find a project name. Specify one manually on the command line"
@@ -268,6 +461,43 @@ impl CliContext {
 		}
 	}
 
+	/// Returns the paths of the projects the user is a member of with a name close to the given
+	/// one, the closest first
+	fn similar_projects(&self, name: &str) -> Result<Vec<String>> {
+		// compare whole paths, or only the project names when no namespace is given
+		let full = name.contains('/');
+		let project_name = name.rsplit('/').next().unwrap_or(name);
+		// search with the start of the name so that typos further in the name still match
+		let search: String = project_name.chars().take(3).collect();
+		let endpoint = projects::Projects::builder()
+			.search(search)
+			.membership(true)
+			.simple(true)
+			.build()?;
+		let projects: Vec<Project> =
+			api::paged(endpoint, Pagination::Limit(100)).query(&self.gitlab)?;
+		let max_distance = (name.chars().count() / 3).max(2);
+		let mut similar: Vec<(usize, String)> = projects
+			.into_iter()
+			.map(|project| {
+				let candidate = if full {
+					project.path_with_namespace.as_str()
+				} else {
+					project
+						.path_with_namespace
+						.rsplit('/')
+						.next()
+						.unwrap_or_default()
+				};
+				let distance = edit_distance(&name.to_lowercase(), &candidate.to_lowercase());
+				(distance, project.path_with_namespace)
+			})
+			.filter(|(distance, _)| *distance <= max_distance)
+			.collect();
+		similar.sort();
+		Ok(similar.into_iter().take(3).map(|(_, path)| path).collect())
+	}
+
 	/// Get a tag (which can be the one provided or a default one) for the given project
 	pub fn get_tag(&self, default: Option<&str>, project: &Project) -> Result<Tag> {
 		let tag = default.map(AsRef::as_ref).or(self.repo.tag.as_deref());
@@ -276,7 +506,7 @@ impl CliContext {
 				.project(project.path_with_namespace.as_str())
 				.tag_name(tag)
 				.build()?
-				.query(&self.gitlab)
+				.query(&self.gitlab.lookup())
 				.with_context(|| {
 					format!(
 						"Can't find a tag {} for project {}",
@@ -291,6 +521,24 @@ impl CliContext {
 		}
 	}
 
+	/// Returns a tag of a project or None if there is no such tag
+	pub fn find_tag(&self, name: &str, project: &Project) -> Result<Option<Tag>> {
+		let endpoint = tags::Tag::builder()
+			.project(project.path_with_namespace.as_str())
+			.tag_name(name)
+			.build()?;
+		match endpoint.query(&self.gitlab.lookup()) {
+			Ok(tag) => Ok(Some(tag)),
+			Err(ApiError::Gitlab { msg }) if msg.starts_with("404") => Ok(None),
+			Err(e) => Err(e).with_context(|| {
+				format!(
+					"Failed to get tag {} of {}",
+					name, &project.path_with_namespace
+				)
+			}),
+		}
+	}
+
 	/// Get a branch (which can be the one provided or a default one) for the given project
 	pub fn get_branch(&self, default: Option<&str>, project: &Project) -> Result<RepoBranch> {
 		let branch = default.or(Some(self.repo.branch.as_str()));
@@ -299,7 +547,7 @@ impl CliContext {
 				.project(project.path_with_namespace.as_str())
 				.branch(branch)
 				.build()?
-				.query(&self.gitlab)
+				.query(&self.gitlab.lookup())
 				.with_context(|| {
 					format!(
 						"Can't find a branch {} for project {}",
@@ -331,22 +579,24 @@ impl CliContext {
 	/// Get a reference (which can be the one provided or a default one) for the given project
 	/// checking that it is tag or a branch name
 	pub fn get_ref(&self, ref_: Option<&str>, project: &Project) -> Result<String> {
-		// get a reference (a tag or a branch)
-		self.get_tag(ref_, project)
-			.map(|tag| tag.name)
-				.or_else(|_| {
-					self
-						// get branch from the context
-						.get_branch(None::<&str>, project)
-						.map(|branch| branch.name)
-				})
-				.with_context(|| {
-					anyhow!("Failed to find a suitable reference for project {} to build the pipeline upon.", &project.name_with_namespace)
-				})
+		// get a reference (a tag or a branch, from the context for the branch), both at once
+		let (tag, branch) = join(
+			|| self.get_tag(ref_, project),
+			|| self.get_branch(None::<&str>, project),
+		);
+		tag.map(|tag| tag.name)
+			.or_else(|_| branch.map(|branch| branch.name))
+			.with_context(|| {
+				anyhow!("Failed to find a suitable reference for project {} to build the pipeline upon.", &project.name_with_namespace)
+			})
 	}
 
-	/// Get a reference but returns an Err if the given reference has diverged
+	/// Get a reference but returns an Err if the given reference has diverged.
+	/// Merge request refs (refs/merge-requests/<iid>/head) are returned as is
 	pub fn check_ref(&self, ref_: Option<&str>, project: &Project) -> Result<String> {
+		if let Some(r) = ref_.filter(|r| is_mr_ref(r)) {
+			return Ok(r.to_owned());
+		}
 		let ref2_ = self.get_ref(ref_, project)?;
 		// check that ref didn't change
 		if let Some(r) = ref_ {
@@ -403,16 +653,174 @@ impl CliContext {
 		}
 	}
 
-	/// Returns the job with the provived id (default) or the first job of the last pipeline for the a given
-	/// project and tag or raises an error
-	pub fn get_job<I>(
+	/// Get a reference (see check_ref) and a pipeline built on it: the one given or the latest.
+	/// A given pipeline is queried at the same time as the reference
+	pub fn get_ref_pipeline(
+		&self,
+		ref_: Option<&str>,
+		id: Option<u64>,
+		project: &Project,
+	) -> Result<(String, Pipeline)> {
+		if id.is_some() {
+			let (ref_, pipeline) = join(
+				|| self.check_ref(ref_, project),
+				|| self.get_pipeline(id, project, ""),
+			);
+			Ok((ref_?, pipeline?))
+		} else {
+			let ref_ = self.check_ref(ref_, project)?;
+			let pipeline = self.get_pipeline(None, project, &ref_)?;
+			Ok((ref_, pipeline))
+		}
+	}
+
+	/// Returns the merge request with the given iid or the opened merge request of the current
+	/// branch
+	pub fn get_merge_request(&self, iid: Option<u64>, project: &Project) -> Result<MergeRequest> {
+		if let Some(iid) = iid {
+			merge_requests::MergeRequest::builder()
+				.project(project.path_with_namespace.as_str())
+				.merge_request(iid)
+				.build()?
+				.query(&self.gitlab)
+				.with_context(|| {
+					format!(
+						"Failed to get merge request !{} of {}",
+						iid, &project.path_with_namespace
+					)
+				})
+		} else {
+			let branch = self.repo.branch.as_str();
+			let mrs: Vec<MergeRequest> = merge_requests::MergeRequests::builder()
+				.project(project.path_with_namespace.as_str())
+				.source_branch(branch)
+				.state(merge_requests::MergeRequestState::Opened)
+				.build()?
+				.query(&self.gitlab)
+				.with_context(|| {
+					format!(
+						"Failed to list merge requests of {}",
+						&project.path_with_namespace
+					)
+				})?;
+			mrs.into_iter().next().ok_or_else(|| {
+				anyhow!(
+					"No opened merge request found for branch {} of {}",
+					branch,
+					&project.path_with_namespace
+				)
+			})
+		}
+	}
+
+	/// Query an endpoint without response, returns false when gitlab answers not modified (the
+	/// resource is already in the requested state)
+	pub fn query_toggle<E: Endpoint>(&self, endpoint: E) -> Result<bool> {
+		match api::ignore(endpoint).query(&self.gitlab) {
+			Ok(()) => Ok(true),
+			Err(ApiError::GitlabService { status, .. }) if status.as_u16() == 304 => Ok(false),
+			Err(e) => Err(e.into()),
+		}
+	}
+
+	/// Returns a commit of a project
+	pub fn get_commit(&self, sha: &str, project: &Project) -> Result<Commit> {
+		commits::Commit::builder()
+			.project(project.path_with_namespace.as_str())
+			.commit(sha)
+			.build()?
+			.query(&self.gitlab)
+			.with_context(|| {
+				format!(
+					"Failed to get commit {} of {}",
+					sha, &project.path_with_namespace
+				)
+			})
+	}
+
+	/// Returns the signature of a commit or None if the commit is not signed
+	pub fn get_commit_signature(
+		&self,
+		sha: &str,
+		project: &Project,
+	) -> Result<Option<CommitSignature>> {
+		let endpoint = commits::Signature::builder()
+			.project(project.path_with_namespace.as_str())
+			.commit(sha)
+			.build()?;
+		match endpoint.query(&self.gitlab) {
+			Ok(signature) => Ok(Some(signature)),
+			// unsigned commits have no signature resource
+			Err(ApiError::Gitlab { msg }) if msg.starts_with("404") => Ok(None),
+			Err(e) => Err(e).with_context(|| {
+				format!(
+					"Failed to get the signature of commit {} of {}",
+					sha, &project.path_with_namespace
+				)
+			}),
+		}
+	}
+
+	/// Returns the signatures of a list of commits
+	pub fn get_signatures<'a, I>(&self, shas: I, project: &Project) -> Result<Signatures>
+	where
+		I: IntoIterator<Item = &'a ObjectId>,
+	{
+		let mut signatures = Signatures::new();
+		for sha in shas {
+			if !signatures.contains_key(sha) {
+				let signature = self.get_commit_signature(sha.value(), project)?;
+				signatures.insert(sha.clone(), signature);
+			}
+		}
+		Ok(signatures)
+	}
+
+	/// Returns the issue with the given iid
+	pub fn get_issue(&self, iid: u64, project: &Project) -> Result<Issue> {
+		issues::Issue::builder()
+			.project(project.path_with_namespace.as_str())
+			.issue(iid)
+			.build()?
+			.query(&self.gitlab)
+			.with_context(|| {
+				format!(
+					"Failed to get issue #{} of {}",
+					iid, &project.path_with_namespace
+				)
+			})
+	}
+
+	/// Returns the latest pipeline of a merge request
+	pub fn get_mr_pipeline(&self, iid: u64, project: &Project) -> Result<Pipeline> {
+		let endpoint = MergeRequestPipelines::builder()
+			.project(project.path_with_namespace.as_str())
+			.merge_request(iid)
+			.build()?;
+		let pipelines: Vec<Pipeline> = endpoint.query(&self.gitlab).with_context(|| {
+			format!(
+				"Failed to list pipelines of merge request !{} of {}",
+				iid, &project.path_with_namespace
+			)
+		})?;
+		pipelines.into_iter().next().ok_or_else(|| {
+			anyhow!(
+				"No pipeline found for merge request !{} of {}",
+				iid,
+				&project.path_with_namespace
+			)
+		})
+	}
+
+	/// Returns the pipeline with the provided id or the last pipeline of a ref, and its jobs in
+	/// the given states
+	fn get_pipeline_jobs<I>(
 		&self,
-		default: Option<u64>,
 		pipeline_id: Option<u64>,
 		project: &Project,
 		ref_: &str,
 		scopes: I,
-	) -> Result<Job>
+	) -> Result<(Pipeline, Vec<Job>)>
 	where
 		I: Iterator<Item = JobScope>,
 	{
@@ -423,12 +831,31 @@ impl CliContext {
 			.include_retried(true)
 			.scopes(scopes)
 			.build()?;
-		let jobs: Vec<Job> = endpoint.query(&self.gitlab).with_context(|| {
-			format!(
-				"Failed to list jobs for the pipeline {} {} @ {}",
-				pipeline.id, &project.path_with_namespace, ref_
-			)
-		})?;
+		let jobs: Vec<Job> = api::paged(endpoint, Pagination::All)
+			.query(&self.gitlab)
+			.with_context(|| {
+				format!(
+					"Failed to list jobs for the pipeline {} {} @ {}",
+					pipeline.id, &project.path_with_namespace, ref_
+				)
+			})?;
+		Ok((pipeline, jobs))
+	}
+
+	/// Returns the job with the provived id (default) or the first job of the last pipeline for the a given
+	/// project and tag or raises an error
+	pub fn get_job<I>(
+		&self,
+		default: Option<u64>,
+		pipeline_id: Option<u64>,
+		project: &Project,
+		ref_: &str,
+		scopes: I,
+	) -> Result<Job>
+	where
+		I: Iterator<Item = JobScope>,
+	{
+		let (pipeline, jobs) = self.get_pipeline_jobs(pipeline_id, project, ref_, scopes)?;
 
 		// try to get the index of a suitable job
 		let i = if let Some(job_id) = default {
@@ -490,6 +917,76 @@ impl CliContext {
 		Ok(take_from_vec(jobs, i).unwrap())
 	}
 
+	/// Returns the jobs with a log of the provided pipeline or of the last pipeline of a ref,
+	/// which names match a glob pattern (the jobs of a matrix for instance). Only the latest
+	/// attempt of the retried jobs is kept
+	pub fn get_named_jobs<I>(
+		&self,
+		pattern: &str,
+		pipeline_id: Option<u64>,
+		project: &Project,
+		ref_: &str,
+		scopes: I,
+	) -> Result<Vec<Job>>
+	where
+		I: Iterator<Item = JobScope>,
+	{
+		let (pipeline, jobs) = self.get_pipeline_jobs(pipeline_id, project, ref_, scopes)?;
+		let mut named: Vec<&Job> = Vec::new();
+		for job in jobs.iter().filter(|job| glob_match(pattern, &job.name)) {
+			match named.iter_mut().find(|latest| latest.name == job.name) {
+				Some(latest) if latest.id.value() < job.id.value() => *latest = job,
+				Some(_) => (),
+				None => named.push(job),
+			}
+		}
+		named.retain(|job| has_log(*job));
+		if named.is_empty() {
+			bail!(
+				"No job matching {} with a log in pipeline {} ({} @ {})",
+				pattern,
+				pipeline.id.value(),
+				&project.path_with_namespace,
+				ref_
+			);
+		}
+		named.sort_by_key(|job| job.id.value());
+		let named: Vec<Job> = named.into_iter().cloned().collect();
+
+		self.print_pipeline(&pipeline, project)?;
+		self.print_jobs(&jobs)?;
+		Ok(named)
+	}
+
+	/// Returns the manual job with the provided name of the provided pipeline or of the last
+	/// pipeline of a ref. The latest attempt is returned if the job was already played
+	pub fn get_manual_job(
+		&self,
+		name: &str,
+		pipeline_id: Option<u64>,
+		project: &Project,
+		ref_: &str,
+	) -> Result<Job> {
+		let (pipeline, jobs) = self.get_pipeline_jobs(
+			pipeline_id,
+			project,
+			ref_,
+			std::iter::once(JobScope::Manual),
+		)?;
+		jobs.into_iter()
+			.filter(|job| job.name == name && job.status == StatusState::Manual)
+			.max_by_key(|job| job.id.value())
+			.ok_or_else(|| {
+				anyhow!(
+					"No manual job named {} in pipeline {} ({} @ {})",
+					name,
+					pipeline.id.value(),
+					&project.path_with_namespace,
+					ref_
+				)
+			})
+	}
+
 	/// Get a list of Job(s) for a given project's pipeline id
 	pub fn get_jobs(&self, project: &Project, pipeline: u64) -> Result<Vec<Job>> {
 		let endpoint = pipelines::PipelineJobs::builder()
@@ -497,12 +994,14 @@ impl CliContext {
 			.pipeline(pipeline)
 			.include_retried(true)
 			.build()?;
-		let jobs: Vec<_> = endpoint.query(&self.gitlab).with_context(|| {
-			format!(
-				"Failed to jobs list for the pipeline {} of the project {}",
-				pipeline, &project.path_with_namespace
-			)
-		})?;
+		let jobs: Vec<_> = api::paged(endpoint, Pagination::All)
+			.query(&self.gitlab)
+			.with_context(|| {
+				format!(
+					"Failed to jobs list for the pipeline {} of the project {}",
+					pipeline, &project.path_with_namespace
+				)
+			})?;
 		Ok(jobs)
 	}
 
@@ -510,11 +1009,65 @@ impl CliContext {
 	pub fn get_current_user(&self) -> Result<User> {
 		let endpoint = CurrentUser::builder().build()?;
 		let user = endpoint
-			.query(&self.gitlab)
+			.query(&self.gitlab.lookup())
 			.with_context(|| "Failed to get current user information")?;
 		Ok(user)
 	}
 
+	/// Fail unless the current user is an administrator of the instance
+	pub fn require_admin(&self) -> Result<()> {
+		let user = self.get_current_user()?;
+		if user.is_admin != Some(true) {
+			bail!("{} is not an administrator of {}", &user.username, &self.repo.host);
+		}
+		Ok(())
+	}
+
+	/// Returns the version of the instance, queried once per invocation and only when needed.
+	/// None when it can't be known (offline or version hidden to the user)
+	pub fn gitlab_version(&self) -> Option<&Version> {
+		self.version
+			.get_or_init(|| version::Version::build().query(&self.gitlab.lookup()).ok())
+			.as_ref()
+	}
+
+	/// Fail if the instance is older than the release introducing a feature. The feature is
+	/// let through when the version is unknown, leaving the API answer
+	pub fn require_version(&self, (major, minor): (u32, u32), feature: &str) -> Result<()> {
+		if let Some(version) = self.gitlab_version() {
+			if version
+				.release()
+				.is_some_and(|release| release < (major, minor))
+			{
+				bail!(
+					"{} requires GitLab >= {}.{} ({} runs {})",
+					feature,
+					major,
+					minor,
+					&self.repo.host,
+					&version.version
+				);
+			}
+		}
+		Ok(())
+	}
+
+	/// Query a health probe of the instance (`liveness` for instance). Probes are not part of the
+	/// API and are only accessible from the IP addresses of the monitoring whitelist
+	pub fn get_health(&self, probe: &str) -> Result<HealthStatus> {
+		let url = self.base_url.join(&format!("-/{}", probe))?;
+		if self.gitlab.is_offline() {
+			bail!("{} can't be queried offline", &url);
+		}
+		self.http
+			.client_builder()
+			.build()
+			.and_then(|client| client.get(url.clone()).send())
+			.and_then(|rsp| rsp.error_for_status())
+			.and_then(|rsp| rsp.json())
+			.with_context(|| format!("Failed to query {}", &url))
+	}
+
 	/// Get user with name or current user
 	pub fn get_user(&self, username: Option<&str>) -> Result<User> {
 		if let Some(username) = username {
@@ -522,13 +1075,16 @@ impl CliContext {
 			let users: Vec<User> = endpoint
 				.query(&self.gitlab)
 				.with_context(|| format!("Failed to get user {} information", username))?;
-			if users.len() > 1 {
-				bail!("More than one user matching {}", username);
-			}
-			users
-				.into_iter()
-				.nth(0)
-				.ok_or_else(|| anyhow!("Fail to get a user"))
+			let index = if users.len() > 1 {
+				let items: Vec<_> = users.iter().map(|user| &user.web_url).collect();
+				self.prompt.select(
+					&format!("More than one user matching {}:", username),
+					&items,
+				)?
+			} else {
+				0
+			};
+			take_from_vec(users, index).ok_or_else(|| anyhow!("Fail to get a user"))
 		} else {
 			self.get_current_user()
 		}
@@ -549,7 +1105,8 @@ impl CliContext {
 					.user_id(user.id.value())
 					.state(Some(PersonalAccessTokenState::Active))
 					.build()?;
-				let tokens: Vec<PersonalAccessToken> = endpoint.query(&self.gitlab)?;
+				let tokens: Vec<PersonalAccessToken> =
+					api::paged(endpoint, Pagination::All).query(&self.gitlab)?;
 				tokens.into_iter().filter(|e| e.id == *id).collect()
 			}
 			NameOrId::Name(name) => {
@@ -563,35 +1120,40 @@ impl CliContext {
 			}
 		};
 
-		if tokens.len() > 1 {
-			bail!(
-				"More than one token matching {}: revoke by id instead of name.",
-				name
-			);
-		}
-		tokens
-			.into_iter()
-			.nth(0)
-			.ok_or_else(|| anyhow!("Token {} not found", name))
+		let index = if tokens.len() > 1 {
+			let items: Vec<_> = tokens
+				.iter()
+				.map(|token| format!("{} ({})", &token.name, token.id))
+				.collect();
+			self.prompt
+				.select(&format!("More than one token matching {}:", name), &items)?
+		} else {
+			0
+		};
+		take_from_vec(tokens, index).ok_or_else(|| anyhow!("Token {} not found", name))
 	}
 
 	/// Get a key by its name of id
 	pub fn get_key(&self, id: &KeyIdType) -> Result<SshKey> {
 		let user = self.get_current_user()?;
 		let endpoint = ListKeys::builder().user(&user.username).build()?;
-		let keys: Vec<SshKey> = endpoint.query(&self.gitlab)?;
+		let keys: Vec<SshKey> = api::paged(endpoint, Pagination::All).query(&self.gitlab)?;
 
 		let key = match id {
 			KeyIdType::Id(id) => keys.into_iter().filter(|k| k.id.value() == *id).nth(0),
 			KeyIdType::Name(name) => {
 				let mkeys: Vec<SshKey> = keys.into_iter().filter(|k| k.title == *name).collect();
-				if mkeys.len() > 1 {
-					bail!(
-						"More than one key matching {}: Delete by id instead of name.",
-						name
-					);
-				}
-				mkeys.into_iter().nth(0)
+				let index = if mkeys.len() > 1 {
+					let items: Vec<_> = mkeys
+						.iter()
+						.map(|key| format!("{} ({}) {}", &key.title, key.id.value(), &key.key))
+						.collect();
+					self.prompt
+						.select(&format!("More than one key matching {}:", name), &items)?
+				} else {
+					0
+				};
+				take_from_vec(mkeys, index)
 			}
 			KeyIdType::FingerPrint(fingerprint) => {
 				let f = &fingerprint.to_string();
@@ -602,40 +1164,186 @@ impl CliContext {
 		key.ok_or_else(|| anyhow!("Key {} not found", id))
 	}
 
-	/// Print a StyledStr with Colorize
-	pub fn print_msg(&self, msg: StyledStr) -> Result<ExitCode> {
-		Colorizer::new(Stream::Stdout, self.color)
-			.with_content(msg)
-			.print()
-			.with_context(|| "Failed to print")
-			.map(|_| ExitCode::from(0))
+	/// Get a group by its path or id
+	pub fn get_group<'a, T>(&self, group: T) -> Result<Group>
+	where
+		T: Into<NameOrId<'a>> + Display,
+	{
+		let name = group.to_string();
+		let endpoint = groups::Group::builder().group(group).build()?;
+		endpoint
+			.query(&self.gitlab)
+			.with_context(|| format!("Can't find a group named {}", name))
 	}
 
-	/// Print section headers
-	fn print_section(&self, title: &str, section: &Section, show_line: bool) -> Result<ExitCode> {
+	/// Resolve users and groups of an access specification to their ids
+	pub fn get_protected_access(
+		&self,
+		access: &AccessSpec,
+	) -> Result<ProtectedAccess<ProtectedAccessLevel>> {
+		Ok(match access {
+			AccessSpec::Role(level) => ProtectedAccess::Level(*level),
+			AccessSpec::User(OwnedNameOrId::Id(id)) => ProtectedAccess::User(*id),
+			AccessSpec::User(OwnedNameOrId::Name(name)) => {
+				ProtectedAccess::User(self.get_user(Some(name))?.id.value())
+			}
+			AccessSpec::Group(OwnedNameOrId::Id(id)) => ProtectedAccess::Group(*id),
+			AccessSpec::Group(group) => ProtectedAccess::Group(self.get_group(group)?.id),
+		})
+	}
+
+	/// Print a StyledStr with Colorize
+	pub fn print_msg(&self, mut msg: StyledStr) -> Result<ExitCode> {
+		if let Some(width) = self.width {
+			msg.wrap_urls(width);
+		}
+		Self::print_msg_color(msg, self.color)
+	}
+
+	/// Format a past date relative to now, or as an ISO 8601 timestamp
+	pub fn ago(&self, date: DateTime<Utc>) -> String {
+		match &self.timeago {
+			Some(formatter) => formatter.convert_chrono(date, Utc::now()),
+			None => date.to_rfc3339_opts(SecondsFormat::Secs, true),
+		}
+	}
+
+	/// Shorten a name (project path, job name) to at most half of the terminal width
+	pub fn fit<'a>(&self, name: &'a str) -> Cow<'a, str> {
+		match self.width {
+			Some(width) => ellipsize(name, (width / 2).max(20)),
+			None => Cow::Borrowed(name),
+		}
+	}
+
+	/// Print a StyledStr with Colorize using a specific color choice
+	fn print_msg_color(msg: StyledStr, color: ColorChoice) -> Result<ExitCode> {
+		Colorizer::new(Stream::Stdout, color)
+			.with_content(msg)
+			.print()
+			.with_context(|| "Failed to print")
+			.map(|_| ExitCode::from(0))
+	}
+
+	/// Print on stderr the number of requests and the time spent by endpoint, the slowest
+	/// first, when the requests are recorded (--timing)
+	pub fn print_timings(&self) -> Result<()> {
+		let Some(timings) = self.gitlab.timings() else {
+			return Ok(());
+		};
+		// (calls, total, max) by method and endpoint
+		let mut endpoints: BTreeMap<(String, &str), (usize, StdDuration, StdDuration)> =
+			BTreeMap::new();
+		for timing in &timings {
+			let stats = endpoints
+				.entry((timing.method.to_string(), &timing.endpoint))
+				.or_default();
+			stats.0 += 1;
+			stats.1 += timing.duration;
+			stats.2 = stats.2.max(timing.duration);
+		}
+		let mut endpoints: Vec<_> = endpoints.into_iter().collect();
+		endpoints.sort_by_key(|(_, (_, total, _))| std::cmp::Reverse(*total));
+
 		let mut msg = StyledStr::new();
+		if !endpoints.is_empty() {
+			msg.hint(format!(
+				"{:>5} {:>8} {:>8}  endpoint\n",
+				"calls", "total", "max"
+			));
+			for ((method, endpoint), (calls, total, max)) in endpoints {
+				msg.none(format!(
+					"{:>5} {:>8} {:>8}  ",
+					calls,
+					format_latency(total),
+					format_latency(max)
+				));
+				msg.literal(method);
+				msg.none(" ");
+				msg.none(self.fit(endpoint));
+				msg.none("\n");
+			}
+		}
+		let total: StdDuration = timings.iter().map(|timing| timing.duration).sum();
+		msg.none(format!("{} request(s) in ", timings.len()));
+		msg.literal(format_latency(total));
+		msg.none("\n");
+		Colorizer::new(Stream::Stderr, self.color)
+			.with_content(msg)
+			.print()
+			.with_context(|| "Failed to print")
+	}
 
+	/// Format a section header, with the index of the section in the log if given
+	fn msg_section(
+		&self,
+		msg: &mut StyledStr,
+		title: &str,
+		section: &Section,
+		index: Option<usize>,
+		show_line: bool,
+	) {
 		msg.warning(format!("\n> {} [", title));
+		if let Some(index) = index {
+			msg.hint(format!("#{} ", index));
+		}
 		msg.literal(&section.name);
 		msg.warning("]");
 		msg.none(" ");
 		if show_line {
 			msg.none("\n");
 		}
-
-		self.print_msg(msg)
 	}
 
-	/// Print the log coming from Gitlab line by line filtering sections if necessary
-	fn print_log_lines(&self, log: &[u8], args: &PipelineLog) -> Result<()> {
-		use std::io::{BufRead, BufReader};
+	/// Color choice for printing a log: --strip-ansi and --keep-ansi take precedence over the color mode
+	fn log_color(&self, args: &PipelineLog) -> ColorChoice {
+		if args.strip_ansi {
+			ColorChoice::Never
+		} else if args.keep_ansi {
+			ColorChoice::Always
+		} else {
+			self.color
+		}
+	}
 
-		let colored = self.color == ColorChoice::Always
-			|| self.color == ColorChoice::Auto && atty::is(atty::Stream::Stdout);
+	/// Filter the log coming from Gitlab line by line, starting inside the given sections (or
+	/// showing only one section), and pass the formatted lines to `out`. Returns the sections
+	/// started in the log
+	fn filter_log_lines(
+		&self,
+		mut log: impl BufRead,
+		sections: Sections,
+		args: &PipelineLog,
+		only: Option<&str>,
+		mut out: impl FnMut(StyledStr) -> Result<()>,
+	) -> Result<Vec<Section>> {
+		let color = self.log_color(args);
+		// the log lines are printed with their escape sequences, which legacy Windows consoles
+		// can't interpret: strip them there
+		let colored = (color == ColorChoice::Always
+			|| color == ColorChoice::Auto && atty::is(atty::Stream::Stdout))
+			&& ansi_support(Stream::Stdout);
+		// number the headers on a terminal so that sections can be expanded by index, unless
+		// the log is incomplete
+		let numbered = args.tail.is_none() && atty::is(atty::Stream::Stdout);
 
-		let mut reader = BufReader::new(log).lines();
-		let mut state = LogContext::default();
-		while let Some(Ok(line)) = reader.next() {
+		let mut state = LogContext {
+			sections,
+			only: only.map(ToOwned::to_owned),
+			..LogContext::default()
+		};
+		let mut buf = Vec::new();
+		// read the log line by line as it is fetched, tolerating invalid utf-8
+		while log
+			.read_until(b'\n', &mut buf)
+			.context("Failed to read the log")?
+			> 0
+		{
+			let line = String::from_utf8_lossy(&buf);
+			let line = line.strip_suffix('\n').unwrap_or(&line);
+			let line = line.strip_suffix('\r').unwrap_or(line).to_owned();
+			buf.clear();
+			let mut msg = StyledStr::new();
 			// evaluate show_line for each line
 			let mut show_line = state.show_line(args);
 			for (_effect, s) in yew_ansi::get_sgr_segments(&line) {
@@ -646,9 +1354,7 @@ impl CliContext {
 						} else {
 							// when not in color mode we need to print the segment without style
 							if show_line && !colored {
-								let mut msg = StyledStr::new();
 								msg.none(s);
-								self.print_msg(msg)?;
 							}
 						}
 					}
@@ -656,32 +1362,42 @@ impl CliContext {
 						match section.type_ {
 							// start of new section
 							SectionType::Start => {
-								state.sections.push(section.clone());
+								state.started.push(section.clone());
+								// expand the section if asked by name or index
+								if args
+									.expand
+									.iter()
+									.any(|spec| section.matches(spec, state.started.len()))
+								{
+									state.expanded.insert(section.name.clone());
+								}
+								state.sections.start(section.clone());
 								// reevaluate show_line when changing section
 								show_line = state.show_line(args);
-								if args.all || args.headers || args.only_headers {
-									self.print_section(s, section, show_line)?;
+								if state.show_header(args, show_line) {
+									let index = numbered.then_some(state.started.len());
+									self.msg_section(&mut msg, s, section, index, show_line);
 								}
 								state.state = LogState::Text;
 								// line has already been printed so force to skip in colored mode
 								if colored {
 									if show_line {
-										self.print_msg("\n".into())?;
+										msg.none("\n");
 									}
 									show_line = false;
 								}
 							}
 							// end of a section
 							SectionType::End => {
-								let prev_section = state.sections.pop();
-								if args.all || args.headers || args.only_headers {
+								let shown = state.show_line(args);
+								let prev_section = state.sections.end(section);
+								if state.show_header(args, shown) {
 									if let Some(prev_section) = prev_section {
 										let f = format_duration(
 											section.timestamp - prev_section.timestamp,
+											self.duration,
 										);
-										let mut msg = StyledStr::new();
 										msg.warning(format!("< [{}]\n", f));
-										self.print_msg(msg)?;
 									}
 								}
 								// reevaluate show_line when changing section
@@ -701,84 +1417,1253 @@ impl CliContext {
 				}
 			}
 			if show_line {
-				let mut msg = StyledStr::new();
 				if colored {
 					msg.none(line);
 				}
 				msg.none("\n");
-				self.print_msg(msg)?;
 			}
+			if !msg.is_empty() {
+				out(msg)?;
+			}
+		}
+
+		Ok(state.started)
+	}
+
+	/// Returns the last `lines` formatted lines of a log starting inside the given sections
+	fn log_tail(
+		&self,
+		log: impl BufRead,
+		sections: Sections,
+		args: &PipelineLog,
+		lines: usize,
+	) -> Result<VecDeque<StyledStr>> {
+		let mut tail = VecDeque::with_capacity(lines);
+		self.filter_log_lines(log, sections, args, None, |msg| {
+			if tail.len() == lines {
+				tail.pop_front();
+			}
+			if lines > 0 {
+				tail.push_back(msg);
+			}
+			Ok(())
+		})?;
+		Ok(tail)
+	}
+
+	/// Returns a printer of the formatted lines of a log, prefixing them if asked (to tell the
+	/// logs of several jobs apart)
+	fn log_printer<'a>(
+		&self,
+		args: &PipelineLog,
+		prefix: Option<&'a str>,
+	) -> impl FnMut(StyledStr) -> Result<()> + 'a {
+		let color = self.log_color(args);
+		let mut line_start = true;
+		move |msg| {
+			let msg = match prefix {
+				Some(prefix) => prefix_lines(msg, prefix, &mut line_start),
+				None => msg,
+			};
+			Self::print_msg_color(msg, color).map(|_| ())
+		}
+	}
+
+	/// Print the log coming from Gitlab line by line filtering sections if necessary. Returns
+	/// the sections started in the log (none with --tail)
+	fn print_log_lines(
+		&self,
+		log: impl BufRead,
+		args: &PipelineLog,
+		prefix: Option<&str>,
+	) -> Result<Vec<Section>> {
+		let mut print = self.log_printer(args, prefix);
+		match args.tail {
+			Some(lines) => {
+				for msg in self.log_tail(log, Sections::default(), args, lines)? {
+					print(msg)?;
+				}
+				Ok(Vec::new())
+			}
+			None => self.filter_log_lines(log, Sections::default(), args, None, print),
+		}
+	}
+
+	/// Ask for the sections of a log to show, by name or index, until the answer is empty
+	fn expand_sections(&self, log: &[u8], sections: &[Section], args: &PipelineLog) -> Result<()> {
+		if sections.is_empty() {
+			return Ok(());
+		}
+		let color = self.log_color(args);
+		let mut msg = StyledStr::new();
+		msg.none("\nCollapsed sections:");
+		for (i, section) in sections.iter().enumerate() {
+			if section.collapsed {
+				msg.none(" ");
+				msg.hint(format!("#{} ", i + 1));
+				msg.literal(&section.name);
+			}
+		}
+		msg.none("\n");
+		Self::print_msg_color(msg, color)?;
+		loop {
+			let answer = self
+				.prompt
+				.input("Section to show (name or index, empty to quit):")?;
+			if answer.is_empty() {
+				return Ok(());
+			}
+			match sections
+				.iter()
+				.enumerate()
+				.find(|(i, section)| section.matches(&answer, i + 1))
+			{
+				Some((_, section)) => {
+					self.filter_log_lines(
+						log,
+						Sections::default(),
+						args,
+						Some(&section.name),
+						|msg| Self::print_msg_color(msg, color).map(|_| ()),
+					)?;
+				}
+				None => eprintln!("No section {} in the log", answer),
+			}
+		}
+	}
+
+	/// Print job's log header
+	fn print_log_header(&self, job: &Job, args: &PipelineLog) -> Result<()> {
+		let mut msg = StyledStr::new();
+		msg.none("Log for job ");
+		msg.literal(job.id.to_string());
+		msg.none(" - ");
+		msg.stylize(status_style(job.status), format!("{:?}", job.status));
+		if self.url {
+			msg.hint(format!(" ({})", job.web_url));
+		}
+		msg.none("\n\n");
+		Self::print_msg_color(msg, self.log_color(args))?;
+		Ok(())
+	}
+
+	/// Print job's log header and its log with its lines prefixed if asked, then the sections
+	/// asked with --interactive
+	pub fn print_log(
+		&self,
+		mut log: impl BufRead,
+		job: &Job,
+		args: &PipelineLog,
+		prefix: Option<&str>,
+	) -> Result<()> {
+		self.print_log_header(job, args)?;
+		if !args.interactive {
+			return self.print_log_lines(log, args, prefix).map(|_| ());
+		}
+		// keep the log to print the sections afterwards without fetching it again
+		let mut bytes = Vec::new();
+		log.read_to_end(&mut bytes)
+			.context("Failed to read the log")?;
+		let sections = self.print_log_lines(&bytes[..], args, prefix)?;
+		self.expand_sections(&bytes, &sections, args)
+	}
+
+	/// Print job's log header and the last lines of its log, prefixed if asked. Only the end of
+	/// the trace is fetched, doubling its size until it holds enough lines once filtered
+	pub fn print_log_tail<E: Endpoint>(
+		&self,
+		endpoint: &E,
+		job: &Job,
+		args: &PipelineLog,
+		lines: usize,
+		prefix: Option<&str>,
+	) -> Result<()> {
+		let size = TraceReader::new(&self.gitlab, endpoint)?
+			.size()
+			.context("Failed to get the log size")?;
+		// the fetched end of the log, starting at offset (the whole log if ranges are unsupported)
+		let mut excerpt = Vec::new();
+		let mut offset = size;
+		let mut length = CHUNK_SIZE;
+		let tail = loop {
+			let start = offset.map_or(0, |offset| offset.saturating_sub(length));
+			let mut bytes = Vec::new();
+			TraceReader::new(&self.gitlab, endpoint)?
+				.from_offset(start)
+				.take(offset.map_or(u64::MAX, |offset| offset - start))
+				.read_to_end(&mut bytes)
+				.context("Failed to read the log")?;
+			bytes.extend_from_slice(&excerpt);
+			excerpt = bytes;
+			offset = Some(start);
+			// skip the first line which may be incomplete, unless the log is complete
+			let log = match excerpt.iter().position(|&b| b == b'\n') {
+				Some(i) if start > 0 => &excerpt[i + 1..],
+				_ => &excerpt[..],
+			};
+			let sections = Sections::enclosing(log).context("Failed to read the log")?;
+			let tail = self.log_tail(log, sections, args, lines)?;
+			if tail.len() >= lines || start == 0 {
+				break tail;
+			}
+			length *= 2;
+		};
+
+		self.print_log_header(job, args)?;
+		let mut print = self.log_printer(args, prefix);
+		for msg in tail {
+			print(msg)?;
+		}
+		Ok(())
+	}
+
+	/// Print pipeline header
+	pub fn msg_pipeline(
+		&self,
+		msg: &mut StyledStr,
+		pipeline: &Pipeline,
+		project: &Project,
+		signatures: Option<&Signatures>,
+	) {
+		msg.none("Pipeline ");
+		msg.literal(pipeline.id.to_string());
+		msg.none(format!(
+			" ({} @ {} = {}",
+			project.name_with_namespace.as_str(),
+			&pipeline.ref_.as_ref().unwrap_or(&"??".to_owned()),
+			&pipeline.sha.value()[..8]
+		));
+		if let Some(signature) = signatures.and_then(|signatures| signatures.get(&pipeline.sha)) {
+			msg.none(" ");
+			msg_signature(msg, signature.as_ref());
+		}
+		msg.none(")");
+		if let Some(created_at) = pipeline.created_at {
+			msg.none(" [");
+			msg.literal(self.ago(created_at));
+			msg.none("]");
+		}
+		msg.none(" - ");
+		msg.stylize(
+			status_style(pipeline.status),
+			format!("{:?}", pipeline.status),
+		);
+		if self.url {
+			msg.hint(format!(" ({})", pipeline.web_url));
+		}
+		msg.none("\n");
+	}
+
+	pub fn print_pipeline(&self, pipeline: &Pipeline, project: &Project) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		self.msg_pipeline(&mut msg, pipeline, project, None);
+		self.print_msg(msg)
+	}
+
+	/// Print what triggered a pipeline and the variables it was run with
+	pub fn print_pipeline_variables(
+		&self,
+		pipeline: &Pipeline,
+		variables: &[PipelineVariable],
+	) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		msg.none("Triggered by ");
+		msg.literal(pipeline.source.as_deref().unwrap_or("unknown source"));
+		if variables.is_empty() {
+			msg.none(" without variables\n");
+		} else {
+			msg.none(" with variables\n");
+			for variable in variables {
+				msg.none("- ");
+				msg.literal(&variable.key);
+				match variable.variable_type {
+					VariableType::EnvVar => {
+						msg.none("=");
+						msg.none(self.fit(&variable.value.replace('\n', "\\n")));
+					}
+					VariableType::File => {
+						let size = ByteSize(variable.value.len() as u64);
+						msg.hint(format!(" (file of {})", size));
+					}
+				}
+				msg.none("\n");
+			}
+		}
+		self.print_msg(msg)
+	}
+
+	/// Print pipelines list, annotating the commits with their signature state if given
+	pub fn print_pipelines(
+		&self,
+		pipelines: &[Pipeline],
+		project: &Project,
+		signatures: Option<&Signatures>,
+	) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		if pipelines.is_empty() {
+			msg.none("No pipelines found for ");
+			msg.literal(project.name_with_namespace.as_str());
+		} else {
+			msg.none("Pipelines for ");
+			msg.literal(project.name_with_namespace.as_str());
+			msg.none("\n");
+			for pipeline in pipelines.iter() {
+				msg.none("- ");
+				self.msg_pipeline(&mut msg, pipeline, project, signatures);
+			}
+		}
+		// msg.none("\n");
+		self.print_msg(msg)
+	}
+
+	/// Print the latest pipeline of branches
+	pub fn print_branch_pipelines(
+		&self,
+		branches: &[(RepoBranch, Option<Pipeline>)],
+		project: &Project,
+	) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		if branches.is_empty() {
+			msg.none("No branches found for ");
+			msg.literal(project.name_with_namespace.as_str());
+		} else {
+			msg.none("Latest pipelines for ");
+			msg.literal(project.name_with_namespace.as_str());
+			msg.none("\n");
+			let width = branches.iter().map(|(b, _)| b.name.len()).max().unwrap_or(0);
+			for (branch, pipeline) in branches {
+				msg.none("- ");
+				msg.literal(format!("{:width$}", branch.name, width = width));
+				msg.none(" ");
+				if let Some(pipeline) = pipeline {
+					msg.stylize(
+						status_style(pipeline.status),
+						format!("{:10}", format!("{:?}", pipeline.status)),
+					);
+					msg.none(format!(" {}", pipeline.id));
+					if let Some(created_at) = pipeline.created_at {
+						let ago = self.ago(created_at);
+						msg.none(" [");
+						msg.literal(ago);
+						msg.none("]");
+					}
+					if self.url {
+						msg.hint(format!(" ({})", pipeline.web_url));
+					}
+				} else {
+					msg.hint("no pipeline");
+				}
+				msg.none("\n");
+			}
+		}
+		self.print_msg(msg)
+	}
+
+	/// Print the latest pipeline of the default branch of projects
+	pub fn print_dashboard(
+		&self,
+		projects: &[(&Project, Result<Option<Pipeline>>)],
+	) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		let paths: Vec<_> = projects
+			.iter()
+			.map(|(project, _)| self.fit(&project.path_with_namespace))
+			.collect();
+		let width = paths.iter().map(|path| path.chars().count()).max().unwrap_or(0);
+		for ((_, pipeline), path) in projects.iter().zip(paths.iter()) {
+			msg.none("- ");
+			msg.literal(format!("{:width$}", path, width = width));
+			msg.none(" ");
+			match pipeline {
+				Ok(Some(pipeline)) => {
+					msg.stylize(
+						status_style(pipeline.status),
+						format!("{:10}", format!("{:?}", pipeline.status)),
+					);
+					msg.none(format!(" {}", pipeline.id));
+					if let Some(ref_) = &pipeline.ref_ {
+						msg.none(format!(" @ {}", ref_));
+					}
+					if let Some(created_at) = pipeline.created_at {
+						let ago = self.ago(created_at);
+						msg.none(" [");
+						msg.literal(ago);
+						msg.none("]");
+					}
+					if self.url {
+						msg.hint(format!(" ({})", pipeline.web_url));
+					}
+				}
+				Ok(None) => msg.hint("no pipeline"),
+				Err(e) => msg.error(format!("{:#}", e)),
+			}
+			msg.none("\n");
+		}
+		self.print_msg(msg)
+	}
+
+	/// Print the pipeline schedules with their next run and last pipeline
+	pub fn print_schedules(
+		&self,
+		schedules: &[PipelineSchedule],
+		project: &Project,
+	) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		if schedules.is_empty() {
+			msg.none("No pipeline schedules found for ");
+			msg.literal(project.name_with_namespace.as_str());
+		} else {
+			msg.none("Pipeline schedules for ");
+			msg.literal(project.name_with_namespace.as_str());
+			msg.none("\n");
+			for schedule in schedules {
+				msg.none("- ");
+				msg.literal(schedule.id.to_string());
+				msg.none(format!(" {} @ {} ", &schedule.description, &schedule.ref_));
+				msg.hint(format!("({} {})", &schedule.cron, &schedule.cron_timezone));
+				if !schedule.active {
+					msg.warning(" inactive");
+				} else if let Some(next_run_at) = schedule.next_run_at {
+					let mut formatter = timeago::Formatter::new();
+					formatter.ago("");
+					let next = formatter.convert_chrono(Utc::now(), next_run_at);
+					msg.none(" next run in ");
+					msg.literal(next.trim_end());
+					msg.none(format!(" ({})", next_run_at.format("%Y-%m-%d %H:%M UTC")));
+				}
+				if let Some(pipeline) = &schedule.last_pipeline {
+					msg.none(" - last ");
+					msg.stylize(
+						status_style(pipeline.status),
+						format!("{:?}", pipeline.status),
+					);
+					if self.url {
+						msg.hint(format!(" ({})", pipeline.web_url));
+					}
+				}
+				msg.none("\n");
+			}
+		}
+		self.print_msg(msg)
+	}
+
+	/// Print the storage used by projects
+	pub fn print_usage(&self, projects: &[Project]) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		let mut total = 0;
+		for project in projects {
+			let stats = project.statistics.clone().unwrap_or_default();
+			total += stats.storage_size;
+			msg.none("- ");
+			msg.literal(&project.path_with_namespace);
+			msg.none(" ");
+			msg.good(ByteSize(stats.storage_size).to_string());
+			msg.hint(format!(
+				" (repository {}, lfs {}, artifacts {}, packages {}, registry {})",
+				ByteSize(stats.repository_size),
+				ByteSize(stats.lfs_objects_size),
+				ByteSize(stats.job_artifacts_size),
+				ByteSize(stats.packages_size),
+				ByteSize(stats.container_registry_size)
+			));
+			if self.url {
+				msg.hint(format!(" ({})", project.web_url));
+			}
+			msg.none("\n");
+		}
+		if projects.len() > 1 {
+			msg.none(format!("Total for {} projects: ", projects.len()));
+			msg.good(ByteSize(total).to_string());
+			msg.none("\n");
+		}
+		self.print_msg(msg)
+	}
+
+	/// Print the protected tags with their create access levels
+	pub fn print_protected_tags(&self, tags: &[ProtectedTag], project: &Project) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		if tags.is_empty() {
+			msg.none("No protected tags found for ");
+			msg.literal(project.name_with_namespace.as_str());
+		} else {
+			msg.none("Protected tags for ");
+			msg.literal(project.name_with_namespace.as_str());
+			msg.none("\n");
+			for tag in tags {
+				msg.none("- ");
+				msg.literal(&tag.name);
+				msg.none(" allowed to create: ");
+				for (i, access) in tag.create_access_levels.iter().enumerate() {
+					if i > 0 {
+						msg.none(", ");
+					}
+					msg.hint(&access.access_level_description);
+				}
+				msg.none("\n");
+			}
+		}
+		self.print_msg(msg)
+	}
+
+	/// Print the push mirrors of a project
+	pub fn print_mirrors(&self, mirrors: &[RemoteMirror], project: &Project) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		if mirrors.is_empty() {
+			msg.none("No mirrors found for ");
+			msg.literal(project.name_with_namespace.as_str());
+		} else {
+			msg.none("Mirrors for ");
+			msg.literal(project.name_with_namespace.as_str());
+			msg.none("\n");
+			for mirror in mirrors {
+				msg.none("- ");
+				msg.literal(mirror.id.to_string());
+				msg.none(format!(" {} ", &mirror.url));
+				if !mirror.enabled {
+					msg.warning("disabled");
+				} else if mirror.update_status == "failed" {
+					msg.error(&mirror.update_status);
+				} else {
+					msg.good(&mirror.update_status);
+				}
+				if let Some(last_update_at) = mirror.last_update_at {
+					msg.hint(format!(" ({})", self.ago(last_update_at)));
+				}
+				if mirror.only_protected_branches {
+					msg.hint(" [protected branches]");
+				}
+				msg.none("\n");
+				if let Some(error) = mirror.last_error.as_ref().filter(|e| !e.is_empty()) {
+					msg.none("  ");
+					msg.error(error);
+					msg.none("\n");
+				}
+			}
+		}
+		self.print_msg(msg)
+	}
+
+	/// Print the webhooks of a project
+	pub fn print_project_hooks(
+		&self,
+		hooks: &[ProjectHook],
+		project: &Project,
+	) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		if hooks.is_empty() {
+			msg.none("No webhooks found for ");
+			msg.literal(project.name_with_namespace.as_str());
+		} else {
+			msg.none("Webhooks for ");
+			msg.literal(project.name_with_namespace.as_str());
+			msg.none("\n");
+			for hook in hooks {
+				msg.none("- ");
+				msg.literal(hook.id.to_string());
+				msg.none(format!(" {} ", &hook.url));
+				if let Some(name) = hook.name.as_deref().filter(|name| !name.is_empty()) {
+					msg.hint(format!("({}) ", name));
+				}
+				msg.hint(format!("[{}]", hook.events().join(", ")));
+				if !hook.enable_ssl_verification {
+					msg.warning(" no ssl verification");
+				}
+				if let Some(status @ ("temporarily_disabled" | "disabled")) =
+					hook.alert_status.as_deref()
+				{
+					msg.none(" ");
+					msg.error(status.replace('_', " "));
+				}
+				msg.none(" - ");
+				msg.hint(format!("created {}", self.ago(hook.created_at)));
+				msg.none("\n");
+			}
+		}
+		self.print_msg(msg)
+	}
+
+	/// Print the recent deliveries of a webhook
+	pub fn print_hook_deliveries(
+		&self,
+		deliveries: &[HookDelivery],
+		hook: u64,
+	) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		if deliveries.is_empty() {
+			msg.none("No recent deliveries for webhook ");
+			msg.literal(hook.to_string());
+		} else {
+			msg.none("Recent deliveries of webhook ");
+			msg.literal(hook.to_string());
+			msg.none("\n");
+			for delivery in deliveries {
+				msg.none("- ");
+				if delivery.is_success() {
+					msg.good(&delivery.response_status);
+				} else {
+					msg.error(&delivery.response_status);
+				}
+				msg.none(" ");
+				msg.literal(&delivery.trigger);
+				msg.hint(format!(" {:.2}s", delivery.execution_duration));
+				if let Some(created_at) = delivery.created_at {
+					msg.none(" - ");
+					msg.hint(self.ago(created_at));
+				}
+				msg.none("\n");
+				// the beginning of the answer often explains a failure
+				if !delivery.is_success() {
+					if let Some(line) = delivery
+						.response_body
+						.as_deref()
+						.and_then(|body| body.lines().find(|line| !line.trim().is_empty()))
+					{
+						msg.none("  ");
+						msg.hint(self.fit(line.trim()));
+						msg.none("\n");
+					}
+				}
+			}
+		}
+		self.print_msg(msg)
+	}
+
+	/// Print the environments of a project
+	pub fn print_environments(
+		&self,
+		environments: &[Environment],
+		project: &Project,
+	) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		if environments.is_empty() {
+			msg.none("No environments found for ");
+			msg.literal(project.name_with_namespace.as_str());
+		} else {
+			msg.none("Environments for ");
+			msg.literal(project.name_with_namespace.as_str());
+			msg.none("\n");
+			for environment in environments {
+				msg_environment(&mut msg, environment);
+			}
+		}
+		self.print_msg(msg)
+	}
+
+	/// Print the review apps of a merge request
+	pub fn print_review_apps(
+		&self,
+		environments: &[Environment],
+		mr: &MergeRequest,
+	) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		if environments.is_empty() {
+			msg.none("No review apps found for merge request ");
+			msg.literal(format!("!{}", mr.iid));
+		} else {
+			msg.none("Review apps for merge request ");
+			msg.literal(format!("!{}", mr.iid));
+			msg.none(format!(" ({})\n", &mr.title));
+			for environment in environments {
+				msg_environment(&mut msg, environment);
+			}
+		}
+		self.print_msg(msg)
+	}
+
+	/// Print a list of commits, annotated with their signature state if given
+	pub fn print_commits(
+		&self,
+		commits: &[Commit],
+		ref_: &str,
+		project: &Project,
+		signatures: Option<&Signatures>,
+	) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		if commits.is_empty() {
+			msg.none("No commits found for ");
+			msg.literal(format!("{} @ {}", &project.name_with_namespace, ref_));
+		} else {
+			msg.none("Commits for ");
+			msg.literal(format!("{} @ {}", &project.name_with_namespace, ref_));
+			msg.none("\n");
+			for commit in commits {
+				msg.none("- ");
+				msg.literal(&commit.short_id);
+				msg.none(format!(" {} ", &commit.title));
+				msg.hint(format!(
+					"({}, {})",
+					&commit.author_name,
+					self.ago(commit.committed_date)
+				));
+				let signature = signatures.and_then(|signatures| signatures.get(&commit.id));
+				if let Some(signature) = signature {
+					msg.none(" - ");
+					msg_signature(&mut msg, signature.as_ref());
+				}
+				if self.url {
+					msg.hint(format!(" ({})", &commit.web_url));
+				}
+				msg.none("\n");
+			}
+		}
+		self.print_msg(msg)
+	}
+
+	/// Print the time tracking statistics of an issue or a merge request
+	pub fn print_time_stats(
+		&self,
+		reference: &str,
+		title: &str,
+		stats: &TimeStats,
+	) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		msg.literal(reference);
+		msg.none(format!(" {} ", title));
+		msg_time_stats(&mut msg, stats);
+		msg.none("\n");
+		self.print_msg(msg)
+	}
+
+	/// Print the time tracked per milestone
+	pub fn print_time_report(
+		&self,
+		report: &[MilestoneTime],
+		project: &Project,
+	) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		if report.is_empty() {
+			msg.none("No tracked time found for ");
+			msg.literal(&project.name_with_namespace);
+		} else {
+			msg.none("Tracked time for ");
+			msg.literal(&project.name_with_namespace);
+			msg.none("\n");
+			let mut total = TimeStats::default();
+			for milestone in report {
+				msg.none("- ");
+				match &milestone.milestone {
+					Some(title) => msg.literal(title),
+					None => msg.hint("no milestone"),
+				}
+				msg.none(" ");
+				msg_time_stats(&mut msg, &milestone.time_stats);
+				msg.hint(format!(
+					" ({} issue(s), {} merge request(s))",
+					milestone.issues, milestone.merge_requests
+				));
+				msg.none("\n");
+				total.time_estimate += milestone.time_stats.time_estimate;
+				total.total_time_spent += milestone.time_stats.total_time_spent;
+			}
+			if report.len() > 1 {
+				msg.none("Total ");
+				msg_time_stats(&mut msg, &total);
+				msg.none("\n");
+			}
+		}
+		self.print_msg(msg)
+	}
+
+	pub fn print_iterations(&self, iterations: &[Iteration], group: &Group) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		if iterations.is_empty() {
+			msg.none("No iterations found for ");
+			msg.literal(&group.full_path);
+		} else {
+			msg.none("Iterations of ");
+			msg.literal(&group.full_path);
+			msg.none("\n");
+			for iteration in iterations {
+				msg.none("- ");
+				msg_iteration(&mut msg, iteration, self.url);
+			}
+		}
+		self.print_msg(msg)
+	}
+
+	/// Print an iteration with its issues
+	pub fn print_iteration(&self, iteration: &Iteration, issues: &[Issue]) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		msg.none("Iteration ");
+		msg_iteration(&mut msg, iteration, self.url);
+		if let Some(description) = iteration.description.as_deref().filter(|d| !d.is_empty()) {
+			msg.hint(description);
+			msg.none("\n");
+		}
+		if issues.is_empty() {
+			msg.none("No issues");
+		}
+		for issue in issues {
+			msg_issue(&mut msg, issue, self.url);
+		}
+		self.print_msg(msg)
+	}
+
+	/// Print the Service Desk issues of a project with the email of their requester
+	pub fn print_service_desk_issues(
+		&self,
+		issues: &[Issue],
+		project: &Project,
+	) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		if issues.is_empty() {
+			msg.none("No Service Desk issues for ");
+			msg.literal(&project.name_with_namespace);
+		} else {
+			msg.none("Service Desk issues of ");
+			msg.literal(&project.name_with_namespace);
+			msg.none("\n");
+			for issue in issues {
+				msg_issue(&mut msg, issue, self.url);
+			}
+		}
+		self.print_msg(msg)
+	}
+
+	/// Print the opened issues assigned to a user with their milestone and due date
+	pub fn print_user_issues(&self, issues: &[Issue], user: &User) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		if issues.is_empty() {
+			msg.none("No opened issues assigned to ");
+			msg.literal(&user.username);
+		} else {
+			msg.none("Opened issues assigned to ");
+			msg.literal(&user.username);
+			msg.none(" :\n");
+			let today = Utc::now().date_naive();
+			for issue in issues {
+				msg.none("- ");
+				match &issue.references {
+					Some(references) => msg.literal(&references.full),
+					None => msg.literal(format!("#{}", issue.iid)),
+				}
+				msg.none(format!(" {}", &issue.title));
+				if let Some(milestone) = &issue.milestone {
+					msg.hint(format!(" [{}]", &milestone.title));
+				}
+				if let Some(due_date) = issue.due_date {
+					msg.none(" due ");
+					if due_date < today {
+						msg.error(due_date.to_string());
+					} else {
+						msg.hint(due_date.to_string());
+					}
+				}
+				if self.url {
+					msg.hint(format!(" ({})", &issue.web_url));
+				}
+				msg.none("\n");
+			}
+		}
+		self.print_msg(msg)
+	}
+
+	/// Print pending todos (of a project)
+	pub fn print_todos(&self, todos: &[Todo], project: Option<&Project>) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		if todos.is_empty() {
+			msg.none("No pending todos");
+		} else {
+			msg.none("Pending todos");
+		}
+		if let Some(project) = project {
+			msg.none(" of ");
+			msg.literal(&project.name_with_namespace);
+		}
+		if !todos.is_empty() {
+			msg.none(" :\n");
+		}
+		for todo in todos {
+			msg.none("- ");
+			msg.literal(todo.id.to_string());
+			msg.none(" ");
+			msg.warning(todo.action_name.replace('_', " "));
+			if let Some(project) = &todo.project {
+				msg.hint(format!(" {}", &project.path_with_namespace));
+			}
+			let title = todo.target.as_ref().and_then(|target| target.title.as_ref());
+			msg.none(format!(" {}", title.unwrap_or(&todo.body)));
+			msg.hint(format!(" ({})", self.ago(todo.created_at)));
+			if self.url {
+				msg.hint(format!(" ({})", &todo.target_url));
+			}
+			msg.none("\n");
+		}
+		self.print_msg(msg)
+	}
+
+	/// Print the Service Desk settings of a project
+	pub fn print_service_desk(&self, project: &Project) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		msg.none("Service Desk of ");
+		msg.literal(&project.name_with_namespace);
+		msg.none(" is ");
+		if project.service_desk_enabled {
+			msg.good("enabled");
+		} else {
+			msg.warning("disabled");
+		}
+		msg.none("\n");
+		if let Some(address) = &project.service_desk_address {
+			msg.none("Address ");
+			msg.literal(address);
+			msg.none("\n");
+		}
+		self.print_msg(msg)
+	}
+
+	pub fn print_errors(&self, errors: &[SentryError], project: &Project) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		if errors.is_empty() {
+			msg.none("No errors for ");
+			msg.literal(&project.name_with_namespace);
+		} else {
+			msg.none("Errors of ");
+			msg.literal(&project.name_with_namespace);
+			msg.none("\n");
+			for error in errors {
+				msg.none("- ");
+				msg_error(&mut msg, error, self);
+			}
+		}
+		self.print_msg(msg)
+	}
+
+	/// Print the details of an error
+	pub fn print_error(&self, error: &DetailedError) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		msg.none("Error ");
+		msg_error(&mut msg, &error.error, self);
+		if let Some(message) = error.message.as_deref().filter(|m| !m.is_empty()) {
+			msg.none(message);
+			msg.none("\n");
+		}
+		if !error.error.culprit.is_empty() {
+			msg.none("Culprit ");
+			msg.literal(&error.error.culprit);
+			msg.none("\n");
+		}
+		msg.none("First seen ");
+		msg.literal(self.ago(error.error.first_seen));
+		if let Some(version) = &error.first_release_version {
+			msg.hint(format!(" (release {})", version));
+		}
+		msg.none("\n");
+		if let Some(version) = &error.last_release_version {
+			msg.none("Last release ");
+			msg.literal(version);
+			msg.none("\n");
+		}
+		if let Some(path) = &error.gitlab_issue_path {
+			msg.none("Issue ");
+			msg.literal(path);
+			msg.none("\n");
+		}
+		self.print_msg(msg)
+	}
+
+	/// Print the signature verification state of a commit
+	pub fn print_commit_signature(
+		&self,
+		commit: &Commit,
+		signature: Option<&CommitSignature>,
+	) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		msg.none("Commit ");
+		msg.literal(&commit.short_id);
+		msg.none(format!(" {} ", &commit.title));
+		msg.hint(format!("({})", &commit.author_name));
+		msg.none("\n");
+		msg_signature(&mut msg, signature);
+		if let Some(signer) = signature.and_then(|signature| signature.signer()) {
+			msg.none(format!(" with {}", signer));
 		}
+		self.print_msg(msg)
+	}
 
-		Ok(())
+	/// Print the conflict state of a merge request and the files changed on both branches
+	/// Print a merge request with its merge status and reviewers
+	pub fn print_merge_request(&self, mr: &MergeRequest) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		msg.none("Merge request ");
+		msg_merge_request(&mut msg, mr, self.url);
+		self.print_msg(msg)
 	}
 
-	/// Print job's log header
-	pub fn print_log(&self, log: &[u8], job: &Job, args: &PipelineLog) -> Result<()> {
+	/// Print the opened merge requests related to a user (authored by, assigned to...) grouped by
+	/// project
+	pub fn print_user_merge_requests(
+		&self,
+		mrs: &[MergeRequest],
+		user: &User,
+		role: &str,
+	) -> Result<ExitCode> {
 		let mut msg = StyledStr::new();
-		msg.none("Log for job ");
-		msg.literal(job.id.to_string());
-		msg.none(" - ");
-		msg.stylize(status_style(job.status), format!("{:?}", job.status));
-		if self.url {
-			msg.hint(format!(" ({})", job.web_url));
+		if mrs.is_empty() {
+			msg.none(format!("No opened merge requests {} ", role));
+			msg.literal(&user.username);
+		} else {
+			msg.none(format!("Opened merge requests {} ", role));
+			msg.literal(&user.username);
+			msg.none(" :\n");
+			let mut projects: BTreeMap<&str, Vec<&MergeRequest>> = BTreeMap::new();
+			for mr in mrs {
+				projects
+					.entry(mr.project_path().unwrap_or_default())
+					.or_default()
+					.push(mr);
+			}
+			for (project, mrs) in projects {
+				msg.literal(project);
+				msg.none("\n");
+				for mr in mrs {
+					msg.none("- ");
+					msg_merge_request(&mut msg, mr, self.url);
+				}
+			}
 		}
-		msg.none("\n\n");
-		Colorizer::new(Stream::Stdout, self.color)
-			.with_content(msg)
-			.print()?;
-
-		self.print_log_lines(log, args)
+		self.print_msg(msg)
 	}
 
-	/// Print pipeline header
-	pub fn msg_pipeline(&self, msg: &mut StyledStr, pipeline: &Pipeline, project: &Project) {
-		msg.none("Pipeline ");
-		msg.literal(pipeline.id.to_string());
-		msg.none(format!(
-			" ({} @ {} = {})",
-			project.name_with_namespace.as_str(),
-			&pipeline.ref_.as_ref().unwrap_or(&"??".to_owned()),
-			&pipeline.sha.value()[..8]
-		));
-		if let Some(created_at) = pipeline.created_at {
-			msg.none(" [");
-			msg.literal(timeago::Formatter::new().convert_chrono(created_at, Utc::now()));
-			msg.none("]");
+	pub fn print_conflicts(&self, mr: &MergeRequest, files: &[String]) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		msg.none("Merge request ");
+		msg.literal(format!("!{}", mr.iid));
+		msg.none(format!(" ({} into {}) ", &mr.source_branch, &mr.target_branch));
+		if mr.has_conflicts {
+			msg.error("has conflicts");
+		} else {
+			msg.good("has no conflicts");
 		}
-		msg.none(" - ");
-		msg.stylize(
-			status_style(pipeline.status),
-			format!("{:?}", pipeline.status),
-		);
-		if self.url {
-			msg.hint(format!(" ({})", pipeline.web_url));
+		if let Some(status) = &mr.detailed_merge_status {
+			msg.hint(format!(" ({})", status.replace('_', " ")));
 		}
 		msg.none("\n");
+		if !files.is_empty() {
+			msg.none("Files changed on both branches since they diverged:\n");
+			for file in files {
+				msg.none("- ");
+				msg.literal(file);
+				msg.none("\n");
+			}
+		}
+		self.print_msg(msg)
 	}
 
-	pub fn print_pipeline(&self, pipeline: &Pipeline, project: &Project) -> Result<ExitCode> {
+	/// Print the pipeline trigger tokens of a project
+	pub fn print_triggers(&self, triggers: &[PipelineTrigger], project: &Project) -> Result<ExitCode> {
 		let mut msg = StyledStr::new();
-		self.msg_pipeline(&mut msg, pipeline, project);
+		if triggers.is_empty() {
+			msg.none("No triggers found for ");
+			msg.literal(project.name_with_namespace.as_str());
+		} else {
+			msg.none("Triggers for ");
+			msg.literal(project.name_with_namespace.as_str());
+			msg.none("\n");
+			for trigger in triggers {
+				msg.none("- ");
+				msg.literal(trigger.id.to_string());
+				msg.none(format!(" {} ", &trigger.description));
+				msg.hint(format!("[{}]", &trigger.token));
+				if let Some(owner) = &trigger.owner {
+					msg.none(format!(" by {}", &owner.username));
+				}
+				msg.none(" - ");
+				match trigger.last_used {
+					Some(last_used) => msg.hint(format!("used {}", self.ago(last_used))),
+					None => msg.hint("never used"),
+				}
+				msg.none("\n");
+			}
+		}
 		self.print_msg(msg)
 	}
 
-	/// Print pipelines list
-	pub fn print_pipelines(&self, pipelines: &[Pipeline], project: &Project) -> Result<ExitCode> {
+	/// Print the secure files of a project
+	pub fn print_secure_files(&self, files: &[SecureFile], project: &Project) -> Result<ExitCode> {
 		let mut msg = StyledStr::new();
-		if pipelines.is_empty() {
-			msg.none("No pipelines found for ");
+		if files.is_empty() {
+			msg.none("No secure files found for ");
 			msg.literal(project.name_with_namespace.as_str());
 		} else {
-			msg.none("Pipelines for ");
+			msg.none("Secure files for ");
 			msg.literal(project.name_with_namespace.as_str());
 			msg.none("\n");
-			for pipeline in pipelines.iter() {
+			for file in files {
 				msg.none("- ");
-				self.msg_pipeline(&mut msg, pipeline, project);
+				msg.literal(file.id.to_string());
+				msg.none(format!(" {} ", &file.name));
+				msg.hint(format!("[{}]", self.ago(file.created_at)));
+				if let Some(expires_at) = file.expires_at {
+					if expires_at < Utc::now() {
+						msg.error(format!(" expired {}", self.ago(expires_at)));
+					} else {
+						msg.none(format!(" expires {}", self.ago(expires_at)));
+					}
+				}
+				msg.none("\n");
+			}
+		}
+		self.print_msg(msg)
+	}
+
+	pub fn print_system_hooks(&self, hooks: &[SystemHook]) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		if hooks.is_empty() {
+			msg.none("No system hooks found for ");
+			msg.literal(&self.repo.host);
+		} else {
+			msg.none("System hooks for ");
+			msg.literal(&self.repo.host);
+			msg.none("\n");
+			for hook in hooks {
+				msg.none("- ");
+				msg.literal(hook.id.to_string());
+				msg.none(format!(" {} ", &hook.url));
+				if let Some(name) = hook.name.as_deref().filter(|name| !name.is_empty()) {
+					msg.hint(format!("({}) ", name));
+				}
+				let events = hook.events();
+				if events.is_empty() {
+					msg.hint("[system events]");
+				} else {
+					msg.hint(format!("[system events, {}]", events.join(", ")));
+				}
+				if !hook.enable_ssl_verification {
+					msg.warning(" no ssl verification");
+				}
+				msg.none(" - ");
+				msg.hint(format!("created {}", self.ago(hook.created_at)));
+				msg.none("\n");
+			}
+		}
+		self.print_msg(msg)
+	}
+
+	pub fn print_license(&self, license: Option<&License>) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		let Some(license) = license else {
+			msg.none("No license installed on ");
+			msg.literal(&self.repo.host);
+			return self.print_msg(msg);
+		};
+		msg.none("License ");
+		msg.literal(&license.plan);
+		if let Some(company) = &license.licensee.company {
+			msg.none(format!(" for {}", company));
+		}
+		if let Some(email) = &license.licensee.email {
+			msg.hint(format!(" <{}>", email));
+		}
+		msg.none("\nSeats ");
+		let used = format!("{}", license.active_users);
+		if license.user_limit > 0 && license.active_users > license.user_limit {
+			msg.error(used);
+		} else {
+			msg.good(used);
+		}
+		if license.user_limit > 0 {
+			msg.none(format!(" / {}", license.user_limit));
+		} else {
+			msg.none(" / unlimited");
+		}
+		msg.hint(format!(" (max {})", license.historical_max));
+		if license.overage > 0 {
+			msg.warning(format!(" {} over the limit", license.overage));
+		}
+		msg.none("\n");
+		if let Some(starts_at) = license.starts_at {
+			msg.none(format!("Starts {}\n", starts_at));
+		}
+		match license.expires_at {
+			Some(expires_at) => {
+				msg.none("Expires ");
+				if license.expired {
+					msg.error(format!("{} (expired)", expires_at));
+				} else if expires_at - Utc::now().date_naive() <= Duration::days(30) {
+					msg.warning(expires_at.to_string());
+				} else {
+					msg.good(expires_at.to_string());
+				}
+			}
+			None => msg.none("Never expires"),
+		}
+		msg.none("\n");
+		self.print_msg(msg)
+	}
+
+	pub fn print_statistics(&self, stats: &ApplicationStatistics) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		msg.none("Statistics of ");
+		msg.literal(&self.repo.host);
+		msg.none("\n");
+		for (name, count) in [
+			("projects", &stats.projects),
+			("groups", &stats.groups),
+			("users", &stats.users),
+			("active users", &stats.active_users),
+			("forks", &stats.forks),
+			("issues", &stats.issues),
+			("merge requests", &stats.merge_requests),
+			("notes", &stats.notes),
+			("snippets", &stats.snippets),
+			("ssh keys", &stats.ssh_keys),
+			("milestones", &stats.milestones),
+		] {
+			msg.none("- ");
+			msg.literal(count);
+			msg.none(format!(" {}\n", name));
+		}
+		self.print_msg(msg)
+	}
+
+	/// Print the version and the result of the health probes of the instance
+	pub fn print_health(
+		&self,
+		version: &Result<Version>,
+		probes: &[(&str, Result<HealthStatus>)],
+	) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		msg.none("GitLab ");
+		msg.literal(&self.repo.host);
+		match version {
+			Ok(version) => msg.none(format!(" {} ({})", &version.version, &version.revision)),
+			Err(e) => msg.error(format!(" unknown version: {:#}", e)),
+		}
+		msg.none("\n");
+		for (name, probe) in probes {
+			msg.none("- ");
+			msg.literal(*name);
+			msg.none(" ");
+			match probe {
+				Ok(health) => {
+					if health.is_healthy() {
+						msg.good("ok");
+					} else {
+						msg.error(&health.status);
+					}
+					msg.none("\n");
+					for (subsystem, checks) in &health.checks {
+						for check in checks {
+							msg.none(format!("  - {} ", subsystem.trim_end_matches("_check")));
+							if check.status == "ok" {
+								msg.good(&check.status);
+							} else {
+								msg.error(&check.status);
+							}
+							if let Some(message) = &check.message {
+								msg.hint(format!(" {}", message));
+							}
+							msg.none("\n");
+						}
+					}
+				}
+				Err(e) => {
+					msg.error(format!("{:#}", e));
+					msg.none("\n");
+				}
 			}
 		}
-		// msg.none("\n");
 		self.print_msg(msg)
 	}
 
@@ -789,15 +2674,17 @@ impl CliContext {
 			for job in jobs.iter().rev() {
 				msg.none("- Job ");
 				msg.literal(job.id.to_string());
-				msg.none(format!(" {} ", job.name));
+				msg.none(format!(" {} ", self.fit(&job.name)));
 				msg.hint(format!("({})", job.stage));
 				if let Some(duration) =
 					job.finished_at
 						.or_else(|| Some(Utc::now()))
 						.and_then(|end| {
-							job.started_at
-								.map(|start| format_duration((end - start).num_seconds()))
-						}) {
+							job.started_at.map(|start| {
+								format_duration((end - start).num_seconds(), self.duration)
+							})
+						})
+				{
 					msg.none(" [");
 					msg.literal(duration);
 					msg.none("]");
@@ -814,11 +2701,60 @@ impl CliContext {
 		self.print_msg(msg)
 	}
 
+	/// Print the end of the log of a failed job
+	pub fn print_failure(&self, job: &Job, failure: &Failure) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		msg.none("Job ");
+		msg.literal(job.id.to_string());
+		msg.none(format!(" {} ", self.fit(&job.name)));
+		msg.error("failed");
+		if let Some(section) = &failure.section {
+			msg.none(" in section ");
+			msg.literal(section);
+		}
+		if self.url {
+			msg.hint(format!(" ({})", job.web_url));
+		}
+		msg.none("\n");
+		for line in &failure.lines {
+			msg.none(format!("{}\n", line));
+		}
+		if let Some(error) = &failure.error {
+			msg.error(error);
+			msg.none("\n");
+		}
+		self.print_msg(msg)
+	}
+
 	// Print project header
+	/// Print the projects starred by the current user
+	pub fn print_starred(&self, projects: &[Project]) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		if projects.is_empty() {
+			msg.none("No starred projects");
+		} else {
+			msg.none("Starred projects\n");
+			for project in projects {
+				msg.none("- ");
+				msg.literal(self.fit(&project.path_with_namespace));
+				msg.hint(format!(" ({}) ", project.id));
+				msg.none(format!("{} star(s)", project.star_count));
+				if project.archived {
+					msg.warning(" archived");
+				}
+				if self.url {
+					msg.hint(format!(" ({})", &project.web_url));
+				}
+				msg.none("\n");
+			}
+		}
+		self.print_msg(msg)
+	}
+
 	pub fn print_project(&self, project: &Project, ref_: &str) -> Result<ExitCode> {
 		let mut msg = StyledStr::new();
 		msg.none("Project ");
-		msg.literal(&project.id.to_string());
+		msg.literal(project.id.to_string());
 		msg.none(" ( ");
 		msg.literal(&project.name_with_namespace);
 		msg.none(" @ ");
@@ -865,10 +2801,7 @@ impl CliContext {
 					msg.error("revoked");
 				} else {
 					msg.good("issued");
-					msg.hint(format!(
-						" ({})",
-						timeago::Formatter::new().convert_chrono(token.created_at, Utc::now(),)
-					));
+					msg.hint(format!(" ({})", self.ago(token.created_at)));
 				}
 				msg.none(", ");
 				if token.expired() {
@@ -877,10 +2810,7 @@ impl CliContext {
 						d.and_time(NaiveTime::from_hms_opt(0, 0, 0).unwrap())
 							.and_utc()
 					}) {
-						msg.hint(format!(
-							" ({})",
-							timeago::Formatter::new().convert_chrono(expires_at, Utc::now(),)
-						));
+						msg.hint(format!(" ({})", self.ago(expires_at)));
 					}
 				} else {
 					msg.good("valid");
@@ -895,7 +2825,7 @@ impl CliContext {
 	}
 
 	/// Print ssh keys
-	pub fn print_keys(&self, keys: &Vec<SshKey>, user: &User) -> Result<ExitCode> {
+	pub fn print_keys(&self, keys: &[SshKey], user: &User, detail: bool) -> Result<ExitCode> {
 		let mut msg = StyledStr::new();
 		msg.none("Key(s) for user ");
 		msg.literal(&user.username);
@@ -903,12 +2833,172 @@ impl CliContext {
 		if !keys.is_empty() {
 			for key in keys {
 				msg.none("- ");
-				msg.literal(&key.title);
+				if detail && key.expired() {
+					msg.error(&key.title);
+				} else {
+					msg.literal(&key.title);
+				}
 				msg.none(" (");
 				msg.hint(key.id.value().to_string());
 				msg.none(")");
 				msg.none("\n");
+				if detail {
+					msg.none("  ");
+					msg.hint(key.fingerprint().unwrap_or_else(|| "invalid key".to_owned()));
+					msg.none("\n  ");
+					if let Some((algorithm, bits)) = key.key_type() {
+						msg.none(format!("{} {} bits, ", algorithm, bits));
+					}
+					if let Some(usage_type) = key.usage_type {
+						msg.none(format!("{}, ", usage_type.as_str()));
+					}
+					msg.none("created");
+					msg.hint(format!(" ({})", self.ago(key.created_at)));
+					// only known for the keys of the current user
+					if let Some(last_used_at) = key.last_used_at {
+						msg.none(", last used");
+						msg.hint(format!(" ({})", self.ago(last_used_at)));
+					}
+					msg.none(", ");
+					match key.expires_at {
+						Some(expires_at) if key.expired() => {
+							msg.error("expired");
+							msg.hint(format!(" ({})", self.ago(expires_at)));
+						}
+						Some(expires_at) => {
+							msg.good("valid");
+							msg.hint(format!(" (until {})", expires_at.date_naive()));
+						}
+						None => msg.good("never expires"),
+					}
+					msg.none("\n");
+				}
+			}
+		}
+		self.print_msg(msg)
+	}
+
+	/// Print a user profile
+	pub fn print_user_profile(&self, profile: &UserProfile) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		msg.none("User ");
+		msg.literal(&profile.username);
+		msg.hint(format!("({})", profile.id.value()));
+		msg.none(format!(" - {}", &profile.name));
+		if self.url {
+			msg.hint(format!(" ({})", &profile.web_url));
+		}
+		msg.none("\n  state: ");
+		if profile.state == "active" {
+			msg.good(&profile.state);
+		} else {
+			msg.error(&profile.state);
+		}
+		if profile.is_admin == Some(true) {
+			msg.none(", ");
+			msg.warning("admin");
+		}
+		if profile.bot {
+			msg.none(", bot");
+		}
+		if let Some(two_factor) = profile.two_factor_enabled {
+			msg.none(", 2FA ");
+			if two_factor {
+				msg.good("enabled");
+			} else {
+				msg.error("disabled");
+			}
+		}
+		msg.none("\n");
+		let email = profile.email.as_ref().or(profile.public_email.as_ref());
+		for (name, value) in [
+			("email", email),
+			("organization", profile.organization.as_ref()),
+			("location", profile.location.as_ref()),
+			("bio", profile.bio.as_ref()),
+		] {
+			if let Some(value) = value.filter(|value| !value.is_empty()) {
+				msg.none(format!("  {}: ", name));
+				msg.literal(value);
+				msg.none("\n");
+			}
+		}
+		if let Some(created_at) = profile.created_at {
+			msg.none("  created: ");
+			msg.hint(self.ago(created_at));
+			msg.none("\n");
+		}
+		if let Some(last_sign_in_at) = profile.last_sign_in_at {
+			msg.none("  last sign in: ");
+			msg.hint(self.ago(last_sign_in_at));
+			msg.none("\n");
+		}
+		if let Some(last_activity_on) = profile.last_activity_on {
+			msg.none("  last activity: ");
+			msg.hint(last_activity_on.to_string());
+			msg.none("\n");
+		}
+		self.print_msg(msg)
+	}
+
+	/// Print user events
+	pub fn print_events(&self, events: &[Event], user: &User) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		msg.none("Event(s) for user ");
+		msg.literal(&user.username);
+		msg.hint(format!("({}) :\n", user.id.value()));
+		for event in events {
+			msg.none("- ");
+			msg.literal(&event.action_name);
+			if let Some(push) = &event.push_data {
+				msg.none(format!(" {} ", &push.ref_type));
+				msg.literal(push.ref_.as_deref().unwrap_or_default());
+				if let Some(title) = &push.commit_title {
+					msg.none(format!(": {}", title));
+				}
+			} else {
+				if let Some(target_type) = &event.target_type {
+					msg.none(format!(" {}", target_type));
+				}
+				if let Some(target_title) = &event.target_title {
+					msg.none(": ");
+					msg.literal(target_title);
+				}
+			}
+			msg.hint(format!(" ({})\n", self.ago(event.created_at)));
+		}
+		self.print_msg(msg)
+	}
+
+	/// Print the projects of a user
+	pub fn print_user_projects(&self, projects: &[Project], user: &User) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		msg.none("Project(s) for user ");
+		msg.literal(&user.username);
+		msg.hint(format!("({}) :\n", user.id.value()));
+		for project in projects {
+			msg.none("- ");
+			msg.literal(&project.name_with_namespace);
+			msg.hint(format!(" ({})", project.id));
+			if self.url {
+				msg.hint(format!(" {}", &project.web_url));
 			}
+			msg.none("\n");
+		}
+		self.print_msg(msg)
+	}
+
+	/// Print the project memberships of a user
+	pub fn print_memberships(&self, memberships: &[Membership], user: &User) -> Result<ExitCode> {
+		let mut msg = StyledStr::new();
+		msg.none("Project(s) for user ");
+		msg.literal(&user.username);
+		msg.hint(format!("({}) :\n", user.id.value()));
+		for membership in memberships {
+			msg.none("- ");
+			msg.literal(&membership.source_name);
+			msg.hint(format!(" ({})", membership.source_id));
+			msg.none("\n");
 		}
 		self.print_msg(msg)
 	}
@@ -949,3 +3039,167 @@ where
 		|| status == StatusState::Running
 		|| status == StatusState::Success
 }
+
+/// Prefix the lines of a message, the first one only if it starts a line. `line_start` tells
+/// if the previous message ended a line and is updated for the next one
+fn prefix_lines(msg: StyledStr, prefix: &str, line_start: &mut bool) -> StyledStr {
+	let mut prefixed = StyledStr::new();
+	for (style, text) in msg.into_iter() {
+		for part in text.split_inclusive('\n') {
+			if *line_start {
+				prefixed.hint(prefix);
+			}
+			prefixed.stylize(style, part);
+			*line_start = part.ends_with('\n');
+		}
+	}
+	prefixed
+}
+
+/// Add an environment line to a message
+fn msg_environment(msg: &mut StyledStr, environment: &Environment) {
+	msg.none("- ");
+	msg.literal(environment.id.to_string());
+	msg.none(format!(" {} ", &environment.name));
+	if environment.state == "available" {
+		msg.good(&environment.state);
+	} else {
+		msg.warning(&environment.state);
+	}
+	if let Some(url) = &environment.external_url {
+		msg.hint(format!(" ({})", url));
+	}
+	msg.none("\n");
+}
+
+/// Add a merge request line to a message, with its merge status and reviewers
+fn msg_merge_request(msg: &mut StyledStr, mr: &MergeRequest, url: bool) {
+	msg.literal(format!("!{}", mr.iid));
+	msg.none(format!(" {} ", mr.ready_title()));
+	msg.hint(format!("({} into {})", &mr.source_branch, &mr.target_branch));
+	if mr.draft {
+		msg.warning(" draft");
+	}
+	if let Some(status) = &mr.detailed_merge_status {
+		msg.none(" ");
+		match status.as_str() {
+			"mergeable" => msg.good(status),
+			"conflict" | "need_rebase" | "broken_status" => msg.error(status.replace('_', " ")),
+			_ => msg.hint(status.replace('_', " ")),
+		}
+	}
+	if !mr.reviewers.is_empty() {
+		let reviewers: Vec<&str> = mr
+			.reviewers
+			.iter()
+			.map(|user| user.username.as_str())
+			.collect();
+		msg.hint(format!(" reviewed by @{}", reviewers.join(" @")));
+	}
+	if url {
+		msg.hint(format!(" ({})", &mr.web_url));
+	}
+	msg.none("\n");
+}
+
+/// Add an issue line to a message
+fn msg_issue(msg: &mut StyledStr, issue: &Issue, url: bool) {
+	msg.none("- ");
+	msg.literal(format!("#{}", issue.iid));
+	msg.none(format!(" {} ", &issue.title));
+	if issue.state == "closed" {
+		msg.good("closed");
+	} else {
+		msg.warning("opened");
+	}
+	if let Some(email) = &issue.service_desk_reply_to {
+		msg.hint(format!(" <{}>", email));
+	}
+	if !issue.assignees.is_empty() {
+		let assignees: Vec<&str> = issue
+			.assignees
+			.iter()
+			.map(|user| user.username.as_str())
+			.collect();
+		msg.hint(format!(" @{}", assignees.join(" @")));
+	}
+	if url {
+		msg.hint(format!(" ({})", &issue.web_url));
+	}
+	msg.none("\n");
+}
+
+/// Add an error line to a message
+fn msg_error(msg: &mut StyledStr, error: &SentryError, context: &CliContext) {
+	msg.literal(&error.sentry_id);
+	msg.none(format!(" {} ", &error.title));
+	match error.status.as_str() {
+		"RESOLVED" => msg.good("resolved"),
+		"IGNORED" => msg.hint("ignored"),
+		_ => msg.error("unresolved"),
+	}
+	msg.hint(format!(
+		" {} event(s), {} user(s), last seen {}",
+		error.count,
+		error.user_count,
+		context.ago(error.last_seen)
+	));
+	if context.url {
+		msg.hint(format!(" ({})", &error.external_url));
+	}
+	msg.none("\n");
+}
+
+/// Add an iteration line to a message
+fn msg_iteration(msg: &mut StyledStr, iteration: &Iteration, url: bool) {
+	msg.literal(iteration.name());
+	msg.hint(format!(" ({}) ", iteration.id));
+	msg.none(format!("{} to {} ", iteration.start_date, iteration.due_date));
+	match iteration.state {
+		2 => msg.good(iteration.state_name()),
+		1 => msg.warning(iteration.state_name()),
+		_ => msg.hint(iteration.state_name()),
+	}
+	if url {
+		msg.hint(format!(" ({})", &iteration.web_url));
+	}
+	msg.none("\n");
+}
+
+/// Add the spent and estimated time to a message
+fn msg_time_stats(msg: &mut StyledStr, stats: &TimeStats) {
+	let spent = format_time_tracking(stats.total_time_spent);
+	if stats.time_estimate > 0 && stats.total_time_spent > stats.time_estimate {
+		msg.error(spent);
+	} else {
+		msg.good(spent);
+	}
+	msg.none(" spent / ");
+	if stats.time_estimate > 0 {
+		msg.literal(format_time_tracking(stats.time_estimate));
+		msg.none(" estimated");
+	} else {
+		msg.hint("no estimate");
+	}
+}
+
+/// Add the signature verification state of a commit to a message
+fn msg_signature(msg: &mut StyledStr, signature: Option<&CommitSignature>) {
+	match signature {
+		Some(signature) if signature.verified() => msg.good("verified"),
+		Some(signature) => msg.error(format!(
+			"unverified ({})",
+			signature.verification_status.replace('_', " ")
+		)),
+		None => msg.warning("unsigned"),
+	}
+}
+
+/// Returns true if the reference is a merge request ref (refs/merge-requests/<iid>/head|merge|train)
+fn is_mr_ref(ref_: &str) -> bool {
+	ref_.strip_prefix("refs/merge-requests/")
+		.and_then(|r| r.split_once('/'))
+		.is_some_and(|(iid, kind)| {
+			iid.parse::<u64>().is_ok() && matches!(kind, "head" | "merge" | "train")
+		})
+}
@@ -1,16 +1,17 @@
 /// Borowed from clap output/fmt.rs
 use crate::args::ColorChoice;
 use crate::color::StyledStr;
+use std::borrow::Cow;
 
 #[allow(dead_code)]
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
-pub(crate) enum Stream {
+pub enum Stream {
     Stdout,
     Stderr,
 }
 
 #[derive(Clone, Debug)]
-pub(crate) struct Colorizer {
+pub struct Colorizer {
     stream: Stream,
     #[allow(unused)]
     color_when: ColorChoice,
@@ -18,7 +19,7 @@ pub(crate) struct Colorizer {
 }
 
 impl Colorizer {
-    pub(crate) fn new(stream: Stream, color_when: ColorChoice) -> Self {
+    pub fn new(stream: Stream, color_when: ColorChoice) -> Self {
         Colorizer {
             stream,
             color_when,
@@ -26,7 +27,7 @@ impl Colorizer {
         }
     }
 
-    pub(crate) fn with_content(mut self, content: StyledStr) -> Self {
+    pub fn with_content(mut self, content: StyledStr) -> Self {
         self.content = content;
         self
     }
@@ -35,7 +36,7 @@ impl Colorizer {
 /// Printing methods.
 impl Colorizer {
     #[cfg(feature = "color")]
-    pub(crate) fn print(&self) -> std::io::Result<()> {
+    pub fn print(&self) -> std::io::Result<()> {
         use termcolor::{BufferWriter, ColorChoice as DepColorChoice};
 
         let color_when = match self.color_when {
@@ -55,7 +56,7 @@ impl Colorizer {
     }
 
     #[cfg(not(feature = "color"))]
-    pub(crate) fn print(&self) -> std::io::Result<()> {
+    pub fn print(&self) -> std::io::Result<()> {
         use std::io::Write;
 
         // [e]println can't be used here because it panics
@@ -91,3 +92,47 @@ fn is_a_tty(stream: Stream) -> bool {
 
     atty::is(stream)
 }
+
+/// Returns true if the ANSI escape sequences written to the stream are interpreted by the
+/// terminal. Windows consoles need the virtual terminal processing to be enabled, which fails on
+/// consoles older than Windows 10 where only the console API can color the output.
+#[cfg(windows)]
+pub fn ansi_support(stream: Stream) -> bool {
+    use winapi_util::console::Console;
+
+    let console = match stream {
+        Stream::Stdout => Console::stdout(),
+        Stream::Stderr => Console::stderr(),
+    };
+    match console {
+        Ok(mut console) => console.set_virtual_terminal_processing(true).is_ok(),
+        // not a console (pipe, mintty): escape sequences are passed through
+        Err(_) => true,
+    }
+}
+
+/// Returns true if the ANSI escape sequences written to the stream are interpreted by the
+/// terminal, which is always the case outside of Windows.
+#[cfg(not(windows))]
+pub fn ansi_support(_stream: Stream) -> bool {
+    true
+}
+
+/// Width of the terminal attached to stdout, None when the output is not a terminal
+pub fn term_width() -> Option<usize> {
+    terminal_size::terminal_size().map(|(width, _)| width.0 as usize)
+}
+
+/// Shorten a string to a maximum number of characters by replacing its middle with an ellipsis,
+/// keeping both ends which are the most significant parts of paths and names
+pub fn ellipsize(s: &str, max: usize) -> Cow<'_, str> {
+    let len = s.chars().count();
+    if len <= max || max < 3 {
+        return Cow::Borrowed(s);
+    }
+    let head = (max - 1) / 2;
+    let tail = max - 1 - head;
+    let start: String = s.chars().take(head).collect();
+    let end: String = s.chars().skip(len - tail).collect();
+    Cow::Owned(format!("{}…{}", start, end))
+}
@@ -18,6 +18,17 @@ pub struct GitProject {
 }
 
 impl GitProject {
+	/// A project outside of any git repository where only the host is known
+	pub fn from_host(host: &str) -> Self {
+		GitProject {
+			name: None,
+			host: host.to_owned(),
+			branch: String::new(),
+			tag: None,
+			commit: String::new(),
+		}
+	}
+
 	pub fn from_currentdir() -> Result<Self> {
 		let repo = current_dir()
 			.with_context(|| "Unable to get the current directory")
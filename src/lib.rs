@@ -0,0 +1,21 @@
+//! Gitlab API endpoints, types, configuration and execution context used by the `gil` command
+//! line tool, so that other tools can reuse them without shelling out to `gil`.
+
+pub mod api;
+pub mod archive;
+pub mod args;
+pub mod cache;
+pub mod client;
+pub mod color;
+pub mod config;
+pub mod connection;
+pub mod context;
+pub mod fmt;
+pub mod git;
+pub mod lockfile;
+pub mod oidc;
+pub mod prompt;
+pub mod section;
+pub mod trace;
+pub mod types;
+pub mod utils;
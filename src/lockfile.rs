@@ -1,46 +1,149 @@
 use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
-use std::fs::File;
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::{Seek, SeekFrom};
 use std::ops::{Deref, DerefMut};
 use std::path::Path;
 
+/// Version of a project archive extracted
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "AnyLockEntry")]
+pub struct LockEntry {
+    pub commit: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// sha256 checksum of the archive
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    /// sha256 checksum of the extracted files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// directory the archive was extracted to, relative to the destination directory (none when
+    /// the files were merged into it)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dir: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AnyLockEntry {
+    // lock files written by previous versions only hold the commit
+    Commit(String),
+    Entry {
+        commit: String,
+        #[serde(default)]
+        tag: Option<String>,
+        #[serde(default)]
+        sha256: Option<String>,
+        #[serde(default)]
+        content: Option<String>,
+        #[serde(default)]
+        dir: Option<String>,
+    },
+}
+
+impl From<AnyLockEntry> for LockEntry {
+    fn from(entry: AnyLockEntry) -> Self {
+        match entry {
+            AnyLockEntry::Commit(commit) => Self {
+                commit,
+                ..Self::default()
+            },
+            AnyLockEntry::Entry {
+                commit,
+                tag,
+                sha256,
+                content,
+                dir,
+            } => Self {
+                commit,
+                tag,
+                sha256,
+                content,
+                dir,
+            },
+        }
+    }
+}
+
 pub struct LockFile {
     name: String,
-    commits: BTreeMap<String, String>,
+    // kept open to hold the advisory lock until the LockFile is dropped
+    file: File,
+    existed: bool,
+    commits: BTreeMap<String, LockEntry>,
 }
 
 impl LockFile {
     pub fn open(name: &str) -> Result<Self> {
         // open lock file
         let lock = Path::new(&name).with_extension("lock");
-        let commits: BTreeMap<String, String> = if let Ok(file) = File::open(&lock) {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&lock)
+            .with_context(|| format!("Can't open {:?}", &lock))?;
+        // lock it exclusively so concurrent extractions wait for each other
+        match file.try_lock() {
+            Ok(()) => (),
+            Err(TryLockError::WouldBlock) => {
+                eprintln!("Waiting for {:?} to be unlocked", &lock);
+                file.lock()
+                    .with_context(|| format!("Can't lock {:?}", &lock))?;
+            }
+            Err(TryLockError::Error(err)) => {
+                return Err(err).with_context(|| format!("Can't lock {:?}", &lock))
+            }
+        }
+        let existed = file.metadata()?.len() > 0;
+        let commits: BTreeMap<String, LockEntry> = if existed {
             // deserialize lock
-            serde_yaml::from_reader(file).with_context(|| format!("Can't read {:?}", &lock))?
+            serde_yaml::from_reader(&file).with_context(|| format!("Can't read {:?}", &lock))?
         } else {
             // create empty commits list
             BTreeMap::default()
         };
         Ok(Self {
             name: name.to_owned(),
+            file,
+            existed,
             commits,
         })
     }
 
+    /// Read the entries of an existing lock file, waiting for a running extraction to finish
+    pub fn read(name: &str) -> Result<BTreeMap<String, LockEntry>> {
+        let lock = Path::new(&name).with_extension("lock");
+        let file = File::open(&lock).with_context(|| format!("Can't open {:?}", &lock))?;
+        file.lock_shared()
+            .with_context(|| format!("Can't lock {:?}", &lock))?;
+        if file.metadata()?.len() == 0 {
+            return Ok(BTreeMap::default());
+        }
+        serde_yaml::from_reader(&file).with_context(|| format!("Can't read {:?}", &lock))
+    }
+
     pub fn save(&self, update: bool) -> Result<()> {
-        // save lock file if update mode or file doesn't exists
-        let lock = Path::new(&self.name).with_extension("lock");
-        if update || !Path::new(&lock).exists() {
-            if let Ok(file) = File::create(&lock) {
-                serde_yaml::to_writer(file, &self.commits)
-                    .with_context(|| format!("Can't write {:?}", &lock))?;
-            }
+        // save lock file if update mode or file didn't exist
+        if update || !self.existed {
+            let lock = Path::new(&self.name).with_extension("lock");
+            // rewrite the file in place to keep the lock
+            let mut file = &self.file;
+            file.set_len(0)
+                .and_then(|_| file.seek(SeekFrom::Start(0)))
+                .with_context(|| format!("Can't write {:?}", &lock))?;
+            serde_yaml::to_writer(file, &self.commits)
+                .with_context(|| format!("Can't write {:?}", &lock))?;
         }
         Ok(())
     }
 }
 
 impl Deref for LockFile {
-    type Target = BTreeMap<String, String>;
+    type Target = BTreeMap<String, LockEntry>;
 
     fn deref(&self) -> &Self::Target {
         &self.commits
@@ -52,3 +155,25 @@ impl DerefMut for LockFile {
         &mut self.commits
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_previous_format() {
+        let commits: BTreeMap<String, LockEntry> = serde_yaml::from_str(
+            "group/old: 0123abcd\ngroup/new:\n  commit: 4567ef01\n  tag: 1.0.0\n  sha256: 89ab\n",
+        )
+        .unwrap();
+        assert_eq!(
+            commits["group/old"],
+            LockEntry {
+                commit: "0123abcd".to_owned(),
+                ..LockEntry::default()
+            }
+        );
+        assert_eq!(commits["group/new"].tag.as_deref(), Some("1.0.0"));
+        assert_eq!(commits["group/new"].sha256.as_deref(), Some("89ab"));
+    }
+}
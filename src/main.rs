@@ -1,44 +1,82 @@
-mod api;
-mod archive;
-mod args;
 mod cmd;
-mod color;
-mod config;
-mod context;
-mod fmt;
-mod git;
-mod lockfile;
-mod oidc;
-mod types;
-mod utils;
 
 use std::process::ExitCode;
 
-use crate::{
-	args::SubCommand,
-	cmd::{
-		archive::cmd as archive, branches::cmd as branches, keys::cmd as keys,
-		pipeline::cmd as pipeline, project::cmd as project, tags::cmd as tags, token::cmd as token,
-		users::cmd as users,
-	},
+use crate::cmd::{
+	admin::cmd as admin, api::cmd as api_request, archive::cmd as archive, board::cmd as board,
+	branches::cmd as branches, clone::cmd as clone, commit::cmd as commit,
+	dashboard::cmd as dashboard, env::cmd as env, errors::cmd as errors, group::cmd as group,
+	hook::cmd as hook, issue::cmd as issue, iteration::cmd as iteration, job::cmd as job,
+	keys::cmd as keys, mirror::cmd as mirror, mr::cmd as mr, pipeline::cmd as pipeline,
+	plugin::cmd as plugin, project::cmd as project, protections::cmd as protections,
+	release::cmd as release, secure_file::cmd as secure_file, self_update::cmd as self_update,
+	servicedesk::cmd as servicedesk, status::cmd as status, sync::cmd as sync, tags::cmd as tags,
+	time::cmd as time, todo::cmd as todo, token::cmd as token, trigger::cmd as trigger,
+	users::cmd as users, variable::cmd as variable,
+};
+use gil::{
+	args::{self, Opts, SubCommand},
 	context::{CliContext, CONTEXT},
 };
 
 use anyhow::{anyhow, Result};
 
 fn main() -> Result<ExitCode> {
-	CONTEXT
-		.set(CliContext::from_args(args::from_env())?)
-		.map_err(|_| anyhow!("Can't set global context"))?;
+	let opts: Opts = args::from_env();
+	let verbose = opts.verbose;
+
+	// self-update doesn't need a gitlab context (nor a configuration)
+	let cmd = if matches!(opts.cmd, SubCommand::SelfUpdate(_)) {
+		&opts.cmd
+	} else {
+		CONTEXT
+			.set(CliContext::from_args(opts)?)
+			.map_err(|_| anyhow!("Can't set global context"))?;
+		&CliContext::global().cmd
+	};
 
-	match &CliContext::global().cmd {
+	let exit_code = match cmd {
 		SubCommand::Tags(args) => tags(args),
+		SubCommand::Release(args) => release(args),
 		SubCommand::Pipeline(args) => pipeline(args),
 		SubCommand::Archive(args) => archive(args),
 		SubCommand::Project(args) => project(args),
 		SubCommand::Branches(args) => branches(args),
+		SubCommand::Protections(args) => protections(args),
 		SubCommand::Token(args) => token(args),
 		SubCommand::Keys(args) => keys(args),
 		SubCommand::Users(args) => users(args),
+		SubCommand::Mirror(args) => mirror(args),
+		SubCommand::Hook(args) => hook(args),
+		SubCommand::Trigger(args) => trigger(args),
+		SubCommand::Variable(args) => variable(args),
+		SubCommand::SecureFile(args) => secure_file(args),
+		SubCommand::Job(args) => job(args),
+		SubCommand::Env(args) => env(args),
+		SubCommand::Mr(args) => mr(args),
+		SubCommand::Issue(args) => issue(args),
+		SubCommand::Todo(args) => todo(args),
+		SubCommand::Board(args) => board(args),
+		SubCommand::Iteration(args) => iteration(args),
+		SubCommand::ServiceDesk(args) => servicedesk(args),
+		SubCommand::Errors(args) => errors(args),
+		SubCommand::Admin(args) => admin(args),
+		SubCommand::Time(args) => time(args),
+		SubCommand::Commit(args) => commit(args),
+		SubCommand::Group(args) => group(args),
+		SubCommand::Clone(args) => clone(args),
+		SubCommand::Sync(args) => sync(args),
+		SubCommand::Status(args) => status(args),
+		SubCommand::Dashboard(args) => dashboard(args),
+		// api is the name of the library module
+		SubCommand::Api(args) => api_request(args),
+		SubCommand::Plugin(args) => plugin(args),
+		SubCommand::SelfUpdate(args) => self_update(args, verbose),
+	};
+
+	// the timings also help understand a failed command
+	if let Some(context) = CONTEXT.get() {
+		context.print_timings()?;
 	}
+	exit_code
 }
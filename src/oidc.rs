@@ -1,6 +1,9 @@
 use crate::{
     args::Opts,
     config::{OAuth2, OAuth2Token},
+    connection::HttpOptions,
+    prompt::Prompt,
+    utils::{is_headless, open_browser},
 };
 
 use anyhow::{anyhow, bail, Context, Result};
@@ -12,14 +15,13 @@ use openidconnect::{
     AdditionalClaims, AuthenticationFlow, AuthorizationCode, ClientId, ClientSecret, CsrfToken,
     HttpRequest, HttpResponse, IssuerUrl, Nonce, OAuth2TokenResponse, RedirectUrl, Scope,
 };
-use reqwest::{blocking, Certificate};
 use serde::{Deserialize, Serialize};
 use std::{
-    fs::File,
     io::{BufRead, BufReader, Read, Write},
     net::TcpListener,
 };
 
+#[allow(dead_code)]
 #[derive(Debug, Deserialize, Serialize)]
 struct GitLabClaims {
     // Deprecated and thus optional as it might be removed in the future
@@ -29,41 +31,27 @@ struct GitLabClaims {
 impl AdditionalClaims for GitLabClaims {}
 
 struct HttpClient {
-    ca: Option<Certificate>,
+    options: HttpOptions,
 }
 
 impl HttpClient {
-    pub fn try_new(ca: &Option<String>) -> Result<Self, anyhow::Error> {
-        let ca = if let Some(ca) = ca {
-            let mut buf = Vec::new();
-            File::open(ca)
-                .with_context(|| format!("Error opening {}", ca))?
-                .read_to_end(&mut buf)
-                .with_context(|| format!("Error reading {}", ca))?;
-            Some(
-                reqwest::Certificate::from_pem(&buf)
-                    .map_err(Error::Reqwest)
-                    .with_context(|| format!("Reading certificate {}", ca))?,
-            )
-        } else {
-            None
-        };
-        Ok(HttpClient { ca })
+    pub fn new(options: &HttpOptions) -> Self {
+        HttpClient {
+            options: options.to_owned(),
+        }
     }
 
     pub fn http_client(
         self,
     ) -> impl Fn(HttpRequest) -> Result<HttpResponse, Error<reqwest::Error>> {
         move |request: HttpRequest| {
-            let mut builder = blocking::Client::builder()
+            let client = self
+                .options
+                .client_builder()
                 // Following redirects opens the client up to SSRF vulnerabilities.
-                .redirect(reqwest::redirect::Policy::none());
-            builder = if let Some(cert) = &self.ca {
-                builder.add_root_certificate(cert.to_owned())
-            } else {
-                builder
-            };
-            let client = builder.build().map_err(Error::Reqwest)?;
+                .redirect(reqwest::redirect::Policy::none())
+                .build()
+                .map_err(Error::Reqwest)?;
             let mut request_builder = client
                 .request(request.method, request.url.as_str())
                 .body(request.body);
@@ -86,87 +74,22 @@ impl HttpClient {
     }
 }
 
-// Try to login to gitlab using oidc
-// save the token to cache file and return the login information in case of success
-pub fn login(host: &str, ca: &Option<String>, config: &OAuth2, opts: &Opts) -> Result<OAuth2Token> {
-    let gitlab_client_id = ClientId::new(config.id.to_string());
-    let gitlab_client_secret = ClientSecret::new(config.secret.to_string());
-    let issuer_url =
-        IssuerUrl::new(format!("https://{}", host)).with_context(|| "Invalid issuer URL")?;
-    let http_client = HttpClient::try_new(ca)?.http_client();
-
-    // Fetch GitLab's OpenID Connect discovery document.
-    let provider_metadata = CoreProviderMetadata::discover(&issuer_url, http_client)
-        .with_context(|| "Failed to discover OpenID Provider")?;
-
-    // Set up the config for the GitLab OAuth2 process.
-    let client = CoreClient::from_provider_metadata(
-        provider_metadata,
-        gitlab_client_id,
-        Some(gitlab_client_secret),
-    )
-    // set the redirect url to where we will be listening
-    .set_redirect_uri(
-        RedirectUrl::new(format!("http://localhost:{}", config.redirect_port))
-            .with_context(|| "Invalid redirect URL")?,
-    );
-
-    // Generate the authorization URL to which we'll redirect the user.
-    let (authorize_url, csrf_state, nonce) = client
-        .authorize_url(
-            AuthenticationFlow::<CoreResponseType>::AuthorizationCode,
-            CsrfToken::new_random,
-            Nonce::new_random,
-        )
-        .add_scope(Scope::new("api".to_string()))
-        .url();
-
-    // ask the OS to open the url
-    let url = authorize_url.to_string();
-    if opts.verbose {
-        println!("redirect to {}", &url)
-    }
-    open::that(url)?;
-
-    // A very naive implementation of the redirect server.
-    let listener = TcpListener::bind(format!("127.0.0.1:{}", config.redirect_port))
-        .with_context(|| "Failed to listen to redirect url")?;
-
-    // Accept one connection
+/// Accept one connection on the redirect server, answer with a page telling the user that the
+/// login succeeded and returns the url the browser was redirected to
+fn wait_redirect(listener: &TcpListener) -> Result<Url> {
     let (mut stream, _) = listener.accept()?;
-    let code;
-    let state;
-    {
+    let redirect_url = {
         let mut reader = BufReader::new(&stream);
 
         let mut request_line = String::new();
         reader.read_line(&mut request_line)?;
 
-        let redirect_url = request_line.split_whitespace().nth(1).unwrap();
-        let url = Url::parse(&("http://localhost".to_string() + redirect_url))?;
-
-        let code_pair = url
-            .query_pairs()
-            .find(|pair| {
-                let (key, _) = pair;
-                key == "code"
-            })
-            .unwrap();
-
-        let (_, value) = code_pair;
-        code = AuthorizationCode::new(value.into_owned());
-
-        let state_pair = url
-            .query_pairs()
-            .find(|pair| {
-                let (key, _) = pair;
-                key == "state"
-            })
-            .unwrap();
-
-        let (_, value) = state_pair;
-        state = CsrfToken::new(value.into_owned());
-    }
+        let path = request_line
+            .split_whitespace()
+            .nth(1)
+            .ok_or_else(|| anyhow!("Invalid redirect request"))?;
+        Url::parse(&("http://localhost".to_string() + path))?
+    };
 
     let page = formatdoc! {"
 		<!DOCTYPE HTML>
@@ -200,7 +123,7 @@ pub fn login(host: &str, ca: &Option<String>, config: &OAuth2, opts: &Opts) -> R
 		</html>"
     ,
     version = env!("CARGO_PKG_VERSION"),
-    name = env!("CARGO_BIN_NAME") };
+    name = env!("CARGO_PKG_NAME") };
     let response = format!(
         "HTTP/1.1 200 OK\r\ncontent-length: {}\r\n\r\n{}",
         page.len(),
@@ -208,11 +131,100 @@ pub fn login(host: &str, ca: &Option<String>, config: &OAuth2, opts: &Opts) -> R
     );
     stream.write_all(response.as_bytes())?;
 
+    Ok(redirect_url)
+}
+
+/// Ask the user to visit the authorization url from a browser somewhere else and to paste back
+/// the url the browser was redirected to
+fn paste_redirect(authorize_url: &str, prompt: Prompt) -> Result<Url> {
+    println!(
+        "Open the following url in a browser to log in:\n{}",
+        authorize_url
+    );
+    let redirect_url = prompt
+        .input("Then paste the url you are redirected to (even if the page fails to load):")?;
+    Url::parse(&redirect_url).with_context(|| "Invalid redirect url")
+}
+
+/// Extract the authorization code and the state from the redirect url
+fn parse_redirect(url: &Url) -> Result<(AuthorizationCode, CsrfToken)> {
+    let param = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+            .ok_or_else(|| anyhow!("No {} found in the redirect url", name))
+    };
+    Ok((
+        AuthorizationCode::new(param("code")?),
+        CsrfToken::new(param("state")?),
+    ))
+}
+
+// Try to login to gitlab using oidc
+// save the token to cache file and return the login information in case of success
+pub fn login(
+    host: &str,
+    http: &HttpOptions,
+    config: &OAuth2,
+    browser: Option<&str>,
+    opts: &Opts,
+) -> Result<OAuth2Token> {
+    let gitlab_client_id = ClientId::new(config.id.to_string());
+    let gitlab_client_secret = ClientSecret::new(config.secret.to_string());
+    let issuer_url =
+        IssuerUrl::new(format!("https://{}", host)).with_context(|| "Invalid issuer URL")?;
+    let http_client = HttpClient::new(http).http_client();
+
+    // Fetch GitLab's OpenID Connect discovery document.
+    let provider_metadata = CoreProviderMetadata::discover(&issuer_url, http_client)
+        .with_context(|| "Failed to discover OpenID Provider")?;
+
+    // Set up the config for the GitLab OAuth2 process.
+    let client = CoreClient::from_provider_metadata(
+        provider_metadata,
+        gitlab_client_id,
+        Some(gitlab_client_secret),
+    )
+    // set the redirect url to where we will be listening
+    .set_redirect_uri(
+        RedirectUrl::new(format!("http://localhost:{}", config.redirect_port))
+            .with_context(|| "Invalid redirect URL")?,
+    );
+
+    // Generate the authorization URL to which we'll redirect the user.
+    let (authorize_url, csrf_state, nonce) = client
+        .authorize_url(
+            AuthenticationFlow::<CoreResponseType>::AuthorizationCode,
+            CsrfToken::new_random,
+            Nonce::new_random,
+        )
+        .add_scope(Scope::new("api".to_string()))
+        .url();
+
+    // ask the OS to open the url, or the user to visit it when there is no display around
+    let url = authorize_url.to_string();
+    if opts.verbose {
+        println!("redirect to {}", &url)
+    }
+    let prompt = Prompt::new(!opts.non_interactive);
+    let redirect_url = if browser.is_some() || !is_headless() {
+        // A very naive implementation of the redirect server.
+        let listener = TcpListener::bind(format!("127.0.0.1:{}", config.redirect_port))
+            .with_context(|| "Failed to listen to redirect url")?;
+        match open_browser(&url, browser) {
+            Ok(()) => wait_redirect(&listener)?,
+            Err(_) => paste_redirect(&url, prompt)?,
+        }
+    } else {
+        paste_redirect(&url, prompt)?
+    };
+    let (code, state) = parse_redirect(&redirect_url)?;
+
     if state.secret() != csrf_state.secret() {
         bail!("CSRF test failed")
     }
 
-    let http_client = HttpClient::try_new(ca)?.http_client();
+    let http_client = HttpClient::new(http).http_client();
     // Exchange the code with a token.
     let token_response = client
         .exchange_code(code)
@@ -0,0 +1,69 @@
+//! Questions asked on the terminal
+
+use anyhow::{anyhow, bail, Result};
+use std::{
+	fmt::Display,
+	io::{self, Write},
+};
+
+/// Ask questions on the terminal, or fail instead of waiting for an answer when running non
+/// interactively (--non-interactive), so that scripts and CI jobs never hang on a prompt
+#[derive(Debug, Clone, Copy)]
+pub struct Prompt {
+	interactive: bool,
+}
+
+impl Prompt {
+	/// Initializer
+	pub fn new(interactive: bool) -> Self {
+		Self { interactive }
+	}
+
+	/// Fail if the question can't be asked
+	fn ensure_interactive(&self, question: &str) -> Result<()> {
+		if !self.interactive {
+			bail!(
+				"{} (can't ask with --non-interactive)",
+				question.trim_end_matches(':')
+			);
+		}
+		Ok(())
+	}
+
+	/// Print the question and read the answer
+	fn ask(&self, question: &str) -> Result<String> {
+		self.ensure_interactive(question)?;
+		print!("{} ", question);
+		io::stdout().flush()?;
+		let mut answer = String::new();
+		io::stdin().read_line(&mut answer)?;
+		Ok(answer.trim().to_owned())
+	}
+
+	/// Ask for a line of text
+	pub fn input(&self, question: &str) -> Result<String> {
+		self.ask(question)
+	}
+
+	/// Ask a yes/no question (default no)
+	pub fn confirm(&self, question: &str) -> Result<bool> {
+		let answer = self.ask(&format!("{} [y/N]", question))?;
+		Ok(matches!(answer.as_str(), "y" | "Y" | "yes"))
+	}
+
+	/// Ask to choose one of the items and returns its index
+	pub fn select<T: Display>(&self, question: &str, items: &[T]) -> Result<usize> {
+		self.ensure_interactive(question)?;
+		println!("{}", question);
+		for (i, item) in items.iter().enumerate() {
+			println!("{}) {}", i + 1, item);
+		}
+		let answer = self.ask(&format!("Choice [1-{}]:", items.len()))?;
+		answer
+			.parse::<usize>()
+			.ok()
+			.filter(|i| (1..=items.len()).contains(i))
+			.map(|i| i - 1)
+			.ok_or_else(|| anyhow!("Invalid choice {}", answer))
+	}
+}
@@ -0,0 +1,510 @@
+//! Parser of the collapsible sections of job logs
+//! (<https://docs.gitlab.com/ee/ci/jobs/#expand-and-collapse-job-log-sections>)
+
+use anyhow::{anyhow, bail, Context, Error, Result};
+use std::{
+	collections::{HashMap, VecDeque},
+	io::{self, BufRead},
+	str::FromStr,
+};
+
+/// Sections added by the runner around the job script, which don't tell why a job failed
+const RUNNER_SECTIONS: [&str; 12] = [
+	"resolve_secrets",
+	"prepare_executor",
+	"prepare_script",
+	"get_sources",
+	"clear_worktree",
+	"restore_cache",
+	"download_artifacts",
+	"archive_cache",
+	"archive_cache_on_failure",
+	"upload_artifacts_on_success",
+	"upload_artifacts_on_failure",
+	"cleanup_file_variables",
+];
+
+/// Marker for section start and end
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SectionType {
+	Start,
+	End,
+}
+
+impl FromStr for SectionType {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"section_start" => Ok(Self::Start),
+			"section_end" => Ok(Self::End),
+			_ => Err(anyhow!("Section delimiter not found")),
+		}
+	}
+}
+
+/// Parsing result of a log section marker
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Section {
+	pub type_: SectionType,
+	/// unix timestamp of the marker
+	pub timestamp: i64,
+	pub name: String,
+	/// the section is collapsed by default (start markers only)
+	pub collapsed: bool,
+}
+
+impl FromStr for Section {
+	type Err = Error;
+
+	/// Parse a `section_start:<timestamp>:<name>[<flags>]` or `section_end:<timestamp>:<name>`
+	/// marker. Anything following a carriage return (the section header) is ignored
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let marker = s
+			.trim_start()
+			.split(['\r', '\n'])
+			.next()
+			.unwrap_or_default();
+		let mut info = marker.splitn(3, ':');
+		let type_ = SectionType::from_str(info.next().unwrap_or_default())?;
+		let (Some(timestamp), Some(name)) = (info.next(), info.next()) else {
+			bail!("Malformed section marker {:?}", marker);
+		};
+		let timestamp = timestamp
+			.parse()
+			.with_context(|| format!("Invalid timestamp in section marker {:?}", marker))?;
+		// separate the name from the optional flags
+		let (name, flags) = match name.split_once('[') {
+			Some((name, flags)) => match flags.strip_suffix(']') {
+				Some(flags) => (name, flags),
+				None => bail!("Unterminated flags in section marker {:?}", marker),
+			},
+			None => (name.trim_end(), ""),
+		};
+		if name.is_empty() {
+			bail!("Missing name in section marker {:?}", marker);
+		}
+		Ok(Self {
+			type_,
+			timestamp,
+			name: name.to_owned(),
+			collapsed: flags.split(',').any(|flag| flag.trim() == "collapsed=true"),
+		})
+	}
+}
+
+impl Section {
+	/// Returns true if the section is designated by its name, or by its index (from 1) among the
+	/// sections started in the log
+	pub fn matches(&self, spec: &str, index: usize) -> bool {
+		spec == self.name || spec.parse() == Ok(index)
+	}
+}
+
+/// Stack of the sections enclosing the current line of a log
+#[derive(Debug, Default)]
+pub struct Sections(Vec<Section>);
+
+impl Sections {
+	/// Enter a section
+	pub fn start(&mut self, section: Section) {
+		self.0.push(section);
+	}
+
+	/// Leave the section with the name of the end marker and returns its start marker. Sections
+	/// nested inside it and left open are closed as well. Unknown sections are ignored
+	pub fn end(&mut self, end: &Section) -> Option<Section> {
+		let i = self
+			.0
+			.iter()
+			.rposition(|section| section.name == end.name)?;
+		self.0.drain(i..).next()
+	}
+
+	/// Returns true outside of any section
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	/// Iterate over the enclosing sections, from the outermost
+	pub fn iter(&self) -> impl Iterator<Item = &Section> {
+		self.0.iter()
+	}
+
+	/// Sections enclosing the beginning of an excerpt of a log, deduced from the end markers of
+	/// the sections started before it. Their start markers being unknown, they are considered
+	/// expanded and started at their end
+	pub fn enclosing(mut log: impl BufRead) -> io::Result<Self> {
+		let mut started = Self::default();
+		// innermost first
+		let mut enclosing = Vec::new();
+		let mut buf = Vec::new();
+		while log.read_until(b'\n', &mut buf)? > 0 {
+			let line = strip_escapes(&String::from_utf8_lossy(&buf));
+			buf.clear();
+			for section in line
+				.split('\r')
+				.filter_map(|part| part.parse::<Section>().ok())
+			{
+				match section.type_ {
+					SectionType::Start => started.start(section),
+					SectionType::End => {
+						if started.end(&section).is_none() {
+							// the sections started since were nested inside it
+							started = Self::default();
+							enclosing.push(Section {
+								type_: SectionType::Start,
+								..section
+							});
+						}
+					}
+				}
+			}
+		}
+		enclosing.reverse();
+		Ok(Self(enclosing))
+	}
+}
+
+/// Excerpt of the log of a failed job
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Failure {
+	/// the innermost section of the job script with the last output, if any
+	pub section: Option<String>,
+	/// the last lines of that section (or of the log outside the runner sections), without
+	/// escape sequences
+	pub lines: Vec<String>,
+	/// the error reported by the runner
+	pub error: Option<String>,
+}
+
+impl Failure {
+	/// Extract the last failing section of a job log and its last `max_lines` lines
+	pub fn from_log(mut log: impl BufRead, max_lines: usize) -> io::Result<Self> {
+		let mut failure = Self::default();
+		let mut sections = Sections::default();
+		// last lines of the sections of the job script, and of the log outside any section
+		let mut excerpts: HashMap<String, VecDeque<String>> = HashMap::new();
+		let mut outside = VecDeque::new();
+		let mut buf = Vec::new();
+		while log.read_until(b'\n', &mut buf)? > 0 {
+			let line = strip_escapes(&String::from_utf8_lossy(&buf));
+			buf.clear();
+			// a carriage return separates the section markers from the text
+			let mut text = None;
+			for part in line.trim_end_matches(['\r', '\n']).split('\r') {
+				match Section::from_str(part) {
+					Ok(section) if section.type_ == SectionType::Start => {
+						if !is_runner_section(&section.name) {
+							excerpts.insert(section.name.clone(), VecDeque::new());
+						}
+						sections.start(section);
+					}
+					Ok(section) => {
+						sections.end(&section);
+					}
+					Err(_) if !part.trim().is_empty() => text = Some(part.to_owned()),
+					Err(_) => (),
+				}
+			}
+			let Some(text) = text else {
+				continue;
+			};
+			if text.starts_with("ERROR: ") {
+				failure.error = Some(text);
+				continue;
+			}
+			if sections
+				.iter()
+				.any(|section| is_runner_section(&section.name))
+			{
+				continue;
+			}
+			let mut inner = None;
+			for section in sections.iter() {
+				if let Some(excerpt) = excerpts.get_mut(&section.name) {
+					push_line(excerpt, text.clone(), max_lines);
+					inner = Some(&section.name);
+				}
+			}
+			failure.section = inner.cloned();
+			push_line(&mut outside, text, max_lines);
+		}
+		failure.lines = failure
+			.section
+			.as_ref()
+			.and_then(|name| excerpts.remove(name))
+			.unwrap_or(outside)
+			.into();
+		Ok(failure)
+	}
+}
+
+/// Returns true for the sections added by the runner around the job script
+fn is_runner_section(name: &str) -> bool {
+	RUNNER_SECTIONS.contains(&name)
+}
+
+/// Append a line to an excerpt keeping at most `max_lines` lines
+fn push_line(excerpt: &mut VecDeque<String>, line: String, max_lines: usize) {
+	if excerpt.len() == max_lines {
+		excerpt.pop_front();
+	}
+	excerpt.push_back(line);
+}
+
+/// Remove the escape sequences (colors, line erasing) of a log line
+fn strip_escapes(line: &str) -> String {
+	let mut text = String::with_capacity(line.len());
+	let mut chars = line.chars();
+	while let Some(c) = chars.next() {
+		if c != '\x1b' {
+			text.push(c);
+		} else if chars.next() == Some('[') {
+			// skip the parameters up to the final byte of the sequence
+			for c in chars.by_ref() {
+				if ('@'..='~').contains(&c) {
+					break;
+				}
+			}
+		}
+	}
+	text
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use proptest::prelude::*;
+
+	fn start(timestamp: i64, name: &str) -> Section {
+		Section {
+			type_: SectionType::Start,
+			timestamp,
+			name: name.to_owned(),
+			collapsed: false,
+		}
+	}
+
+	fn end(timestamp: i64, name: &str) -> Section {
+		Section {
+			type_: SectionType::End,
+			..start(timestamp, name)
+		}
+	}
+
+	#[test]
+	fn parse_markers() {
+		assert_eq!(
+			"section_start:1560896352:build".parse::<Section>().unwrap(),
+			start(1560896352, "build")
+		);
+		assert_eq!(
+			"section_end:1560896353:build\r".parse::<Section>().unwrap(),
+			end(1560896353, "build")
+		);
+		let section: Section = "\rsection_start:1560896352:test[collapsed=true]\r\n"
+			.parse()
+			.unwrap();
+		assert_eq!(section.name, "test");
+		assert!(section.collapsed);
+	}
+
+	#[test]
+	fn parse_flags() {
+		let section: Section = "section_start:1:test[hide_duration=true,collapsed=true]"
+			.parse()
+			.unwrap();
+		assert!(section.collapsed);
+		let section: Section = "section_start:1:test[collapsed=false]".parse().unwrap();
+		assert!(!section.collapsed);
+	}
+
+	#[test]
+	fn ignore_header() {
+		let section: Section = "section_start:1:build[collapsed=true]\r\x1b[0KBuilding"
+			.parse()
+			.unwrap();
+		assert_eq!(
+			section,
+			Section {
+				collapsed: true,
+				..start(1, "build")
+			}
+		);
+	}
+
+	#[test]
+	fn reject_malformed_markers() {
+		for marker in [
+			"",
+			"section_start",
+			"section_start:",
+			"section_start:1560896352",
+			"section_start:1560896352:",
+			"section_start:now:build",
+			"section_start:1:build[collapsed=true",
+			"section_middle:1:build",
+			"Running with gitlab-runner 16.3.0",
+		] {
+			assert!(marker.parse::<Section>().is_err(), "{:?}", marker);
+		}
+	}
+
+	#[test]
+	fn designate_sections() {
+		let section = start(1, "step_script");
+		assert!(section.matches("step_script", 3));
+		assert!(section.matches("3", 3));
+		assert!(!section.matches("step", 3));
+		assert!(!section.matches("2", 3));
+	}
+
+	#[test]
+	fn nested_sections() {
+		let mut sections = Sections::default();
+		sections.start(start(1, "build"));
+		sections.start(Section {
+			collapsed: true,
+			..start(2, "deps")
+		});
+		assert_eq!(sections.iter().count(), 2);
+		assert_eq!(
+			sections.end(&end(3, "deps")),
+			Some(Section {
+				collapsed: true,
+				..start(2, "deps")
+			})
+		);
+		assert_eq!(sections.end(&end(4, "build")), Some(start(1, "build")));
+		assert!(sections.is_empty());
+	}
+
+	#[test]
+	fn unbalanced_sections() {
+		let mut sections = Sections::default();
+		sections.start(start(1, "build"));
+		sections.start(start(2, "deps"));
+		// unknown sections are ignored
+		assert_eq!(sections.end(&end(3, "test")), None);
+		assert_eq!(sections.iter().count(), 2);
+		// ending the outer section closes the inner one
+		assert_eq!(sections.end(&end(4, "build")), Some(start(1, "build")));
+		assert!(sections.is_empty());
+		assert_eq!(sections.end(&end(5, "build")), None);
+	}
+
+	#[test]
+	fn enclosing_sections() {
+		let log = concat!(
+			"fetching\n",
+			"section_end:2:deps\r\x1b[0K\n",
+			"section_start:2:test\r\x1b[0KTesting\n",
+			"section_end:3:test\r\x1b[0K\n",
+			"section_start:3:lint\r\x1b[0KLinting\n",
+			"section_end:4:step_script\r\x1b[0K\n",
+			"section_start:4:after_script\r\x1b[0K\n",
+		);
+		let sections = Sections::enclosing(log.as_bytes()).unwrap();
+		assert_eq!(
+			sections.iter().collect::<Vec<_>>(),
+			[&start(4, "step_script"), &start(2, "deps")]
+		);
+		assert!(Sections::enclosing("a\nb\n".as_bytes()).unwrap().is_empty());
+	}
+
+	#[test]
+	fn failure_excerpt() {
+		let log = concat!(
+			"\x1b[0KRunning with gitlab-runner 16.3.0\n",
+			"section_start:1:get_sources\r\x1b[0K\x1b[36;1mGetting source\x1b[0;m\n",
+			"Fetching changes\n",
+			"section_end:2:get_sources\r\x1b[0K\n",
+			"section_start:2:step_script\r\x1b[0K\x1b[36;1mExecuting step_script\x1b[0;m\n",
+			"$ make deps\n",
+			"section_start:3:deps[collapsed=true]\r\x1b[0KDependencies\n",
+			"fetching\n",
+			"section_end:4:deps\r\x1b[0K\n",
+			"$ make test\n",
+			"\x1b[31;1mtest failed\x1b[0;m\n",
+			"section_end:5:step_script\r\x1b[0K\n",
+			"section_start:5:cleanup_file_variables\r\x1b[0KCleaning up\n",
+			"section_end:6:cleanup_file_variables\r\x1b[0K\n",
+			"\x1b[31;1mERROR: Job failed: exit code 1\n",
+		);
+		let failure = Failure::from_log(log.as_bytes(), 3).unwrap();
+		assert_eq!(failure.section.as_deref(), Some("step_script"));
+		assert_eq!(failure.lines, ["fetching", "$ make test", "test failed"]);
+		assert_eq!(
+			failure.error.as_deref(),
+			Some("ERROR: Job failed: exit code 1")
+		);
+
+		// the innermost section of the job script with the last output
+		let failure =
+			Failure::from_log(&log.as_bytes()[..log.find("section_end:4").unwrap()], 50).unwrap();
+		assert_eq!(failure.section.as_deref(), Some("deps"));
+		assert_eq!(failure.lines, ["Dependencies", "fetching"]);
+		assert_eq!(failure.error, None);
+	}
+
+	#[test]
+	fn failure_without_sections() {
+		let failure = Failure::from_log("one\ntwo\n\nthree".as_bytes(), 2).unwrap();
+		assert_eq!(failure.section, None);
+		assert_eq!(failure.lines, ["two", "three"]);
+	}
+
+	proptest! {
+		#[test]
+		fn parse_never_panics(s in "\\PC*") {
+			let _ = s.parse::<Section>();
+		}
+
+		#[test]
+		fn parse_markers_like_strings(
+			s in "section_(start|end)(:[0-9a-z]{0,4}){0,3}(\\[[a-z=,]*\\]?)?[\r\n]?",
+		) {
+			let _ = s.parse::<Section>();
+		}
+
+		#[test]
+		fn roundtrip(
+			timestamp in any::<i64>(),
+			name in "[a-zA-Z0-9_.-]{1,32}",
+			collapsed in any::<bool>(),
+			crlf in "(\r|\r\n)?",
+		) {
+			let flags = if collapsed { "[collapsed=true]" } else { "" };
+			let marker = format!("section_start:{}:{}{}{}", timestamp, name, flags, crlf);
+			let section: Section = marker.parse().unwrap();
+			prop_assert_eq!(
+				section,
+				Section { type_: SectionType::Start, timestamp, name, collapsed }
+			);
+		}
+
+		#[test]
+		fn end_closes_nested(ops in prop::collection::vec((any::<bool>(), 0..4u8), 0..32)) {
+			let mut sections = Sections::default();
+			let mut names: Vec<String> = Vec::new();
+			for (is_start, name) in ops {
+				let name = name.to_string();
+				if is_start {
+					sections.start(start(0, &name));
+					names.push(name);
+				} else {
+					let closed = names
+						.iter()
+						.rposition(|n| *n == name)
+						.map(|i| names.drain(i..).count());
+					prop_assert_eq!(
+						sections.end(&end(0, &name)).map(|s| s.name),
+						closed.map(|_| name)
+					);
+					prop_assert!(sections.iter().map(|s| &s.name).eq(names.iter()));
+				}
+			}
+		}
+	}
+}
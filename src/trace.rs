@@ -0,0 +1,132 @@
+//! Incremental reader of job traces
+
+use bytes::Bytes;
+use gitlab::api::{self, ApiError, Endpoint};
+use http::{
+	header::{CONTENT_RANGE, RANGE},
+	Method, Request, StatusCode,
+};
+use std::io::{self, Read};
+use url::Url;
+
+/// Number of bytes fetched by request
+pub const CHUNK_SIZE: u64 = 1 << 20;
+
+/// Reader fetching a job trace (or any other raw file) by chunks with byte range requests, so
+/// that large traces don't need to be held in memory. Servers ignoring the range get the whole
+/// trace at once
+pub struct TraceReader<'a, C> {
+	client: &'a C,
+	url: Url,
+	/// offset of the next chunk in the trace
+	offset: u64,
+	/// remaining bytes of the current chunk
+	chunk: Bytes,
+	/// the last chunk has been fetched
+	done: bool,
+}
+
+impl<'a, C> TraceReader<'a, C>
+where
+	C: api::Client,
+{
+	/// Initializer from the trace endpoint of a job
+	pub fn new<E>(client: &'a C, endpoint: &E) -> Result<Self, ApiError<C::Error>>
+	where
+		E: Endpoint,
+	{
+		Ok(Self {
+			client,
+			url: client.rest_endpoint(&endpoint.endpoint())?,
+			offset: 0,
+			chunk: Bytes::new(),
+			done: false,
+		})
+	}
+
+	/// Start reading the trace at the given byte offset
+	pub fn from_offset(mut self, offset: u64) -> Self {
+		self.offset = offset;
+		self.chunk = Bytes::new();
+		self.done = false;
+		self
+	}
+
+	/// Returns the size of the trace in bytes, or None if the server doesn't support range
+	/// requests
+	pub fn size(&self) -> io::Result<Option<u64>> {
+		let rsp = self.request(0, 0)?;
+		match rsp.status() {
+			// the total size follows the range ("bytes 0-0/1234" or "bytes */0")
+			StatusCode::PARTIAL_CONTENT | StatusCode::RANGE_NOT_SATISFIABLE => Ok(rsp
+				.headers()
+				.get(CONTENT_RANGE)
+				.and_then(|range| range.to_str().ok())
+				.and_then(|range| range.rsplit('/').next())
+				.and_then(|size| size.parse().ok())),
+			status if status.is_success() => Ok(None),
+			status => Err(io::Error::other(format!(
+				"Failed to get {}: {}",
+				&self.url, status
+			))),
+		}
+	}
+
+	/// Request a byte range of the trace
+	fn request(&self, first: u64, last: u64) -> io::Result<http::Response<Bytes>> {
+		let request = Request::builder()
+			.method(Method::GET)
+			.uri(self.url.as_str())
+			.header(RANGE, format!("bytes={}-{}", first, last));
+		self.client
+			.rest(request, Vec::new())
+			.map_err(io::Error::other)
+	}
+
+	/// Fetch the next chunk of the trace
+	fn fetch(&mut self) -> io::Result<()> {
+		let rsp = self.request(self.offset, self.offset + CHUNK_SIZE - 1)?;
+		match rsp.status() {
+			StatusCode::PARTIAL_CONTENT => {
+				let chunk = rsp.into_body();
+				self.offset += chunk.len() as u64;
+				self.done = (chunk.len() as u64) < CHUNK_SIZE;
+				self.chunk = chunk;
+			}
+			// the offset is at the end of the trace
+			StatusCode::RANGE_NOT_SATISFIABLE => self.done = true,
+			// the range was ignored and the whole trace returned
+			status if status.is_success() => {
+				let trace = rsp.into_body();
+				let start = trace.len().min(self.offset as usize);
+				self.offset = trace.len() as u64;
+				self.done = true;
+				self.chunk = trace.slice(start..);
+			}
+			status => {
+				return Err(io::Error::other(format!(
+					"Failed to get {}: {}",
+					&self.url, status
+				)))
+			}
+		}
+		Ok(())
+	}
+}
+
+impl<C> Read for TraceReader<'_, C>
+where
+	C: api::Client,
+{
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		while self.chunk.is_empty() {
+			if self.done {
+				return Ok(0);
+			}
+			self.fetch()?;
+		}
+		let len = buf.len().min(self.chunk.len());
+		buf[..len].copy_from_slice(&self.chunk.split_to(len));
+		Ok(len)
+	}
+}
@@ -1,16 +1,46 @@
+pub mod board;
+pub mod commit;
+pub mod environment;
+pub mod error_tracking;
+pub mod group;
+pub mod hook;
+pub mod instance;
+pub mod issue;
+pub mod iteration;
 pub mod keys;
+pub mod merge_request;
+pub mod mirror;
 pub mod pipeline;
 pub mod project;
 pub mod repository;
+pub mod secure_file;
+pub mod todo;
 pub mod token;
+pub mod trigger;
 pub mod user;
+pub mod variable;
 
+pub use board::{Board, BoardList};
+pub use commit::{Commit, CommitSignature, Signatures};
+pub use environment::{Deployment, Environment};
+pub use error_tracking::{DetailedError, SentryError};
+pub use group::Group;
+pub use hook::{HookDelivery, ProjectHook, SystemHook};
+pub use instance::{ApplicationStatistics, HealthStatus, License, Version};
+pub use issue::{Issue, MilestoneTime, TimeStats};
+pub use iteration::Iteration;
 pub use keys::SshKey;
-pub use pipeline::{Job, Pipeline, StatusState};
-pub use project::Project;
-pub use repository::{ProtectedRepoBranch, ProtectedTag, RepoBranch, Tag};
+pub use merge_request::MergeRequest;
+pub use mirror::RemoteMirror;
+pub use pipeline::{Job, Pipeline, PipelineSchedule, StatusState};
+pub use project::{Project, ProjectSettings};
+pub use repository::{Comparison, Diff, ProtectedRepoBranch, ProtectedTag, RepoBranch, Tag};
+pub use secure_file::SecureFile;
+pub use todo::Todo;
 pub use token::PersonalAccessToken;
+pub use trigger::PipelineTrigger;
 pub use user::User;
+pub use variable::{PipelineVariable, Variable};
 
 use serde::Deserialize;
 
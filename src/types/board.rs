@@ -0,0 +1,45 @@
+use serde::Deserialize;
+
+use super::{issue::Milestone, User};
+
+/// A label of a project
+#[derive(Deserialize, Debug, Clone)]
+pub struct Label {
+	pub id: u64,
+	pub name: String,
+}
+
+/// A list of an issue board
+#[derive(Deserialize, Debug, Clone)]
+pub struct BoardList {
+	pub id: u64,
+	/// label, assignee, milestone or iteration
+	pub list_type: String,
+	pub label: Option<Label>,
+	pub assignee: Option<User>,
+	pub milestone: Option<Milestone>,
+}
+
+impl BoardList {
+	/// The name of the list as displayed on the board
+	pub fn name(&self) -> &str {
+		if let Some(label) = &self.label {
+			&label.name
+		} else if let Some(assignee) = &self.assignee {
+			&assignee.username
+		} else if let Some(milestone) = &self.milestone {
+			&milestone.title
+		} else {
+			&self.list_type
+		}
+	}
+}
+
+/// An issue board of a project
+#[derive(Deserialize, Debug, Clone)]
+pub struct Board {
+	pub id: u64,
+	pub name: String,
+	#[serde(default)]
+	pub lists: Vec<BoardList>,
+}
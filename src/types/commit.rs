@@ -0,0 +1,75 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use super::ObjectId;
+
+/// A commit of a repository
+#[derive(Deserialize, Debug, Clone)]
+pub struct Commit {
+	pub id: ObjectId,
+	pub short_id: String,
+	pub title: String,
+	pub author_name: String,
+	pub committed_date: DateTime<Utc>,
+	pub web_url: String,
+	/// More than one parent for a merge commit
+	#[serde(default)]
+	pub parent_ids: Vec<ObjectId>,
+}
+
+/// The ssh key used to sign a commit
+#[derive(Deserialize, Debug, Clone)]
+pub struct SignatureKey {
+	pub id: u64,
+	pub title: String,
+}
+
+/// The x509 certificate used to sign a commit
+#[derive(Deserialize, Debug, Clone)]
+pub struct SignatureCertificate {
+	pub subject: String,
+	pub email: Option<String>,
+}
+
+/// The signature of a commit
+#[derive(Deserialize, Debug, Clone)]
+pub struct CommitSignature {
+	/// PGP, SSH or X509
+	pub signature_type: String,
+	/// verified, verified_system, unverified, unverified_key, unknown_key, other_user, ...
+	pub verification_status: String,
+	pub gpg_key_user_name: Option<String>,
+	pub gpg_key_user_email: Option<String>,
+	pub gpg_key_primary_keyid: Option<String>,
+	pub key: Option<SignatureKey>,
+	pub x509_certificate: Option<SignatureCertificate>,
+}
+
+impl CommitSignature {
+	/// Returns true if GitLab verified the signature
+	pub fn verified(&self) -> bool {
+		self.verification_status.starts_with("verified")
+	}
+
+	/// Describe the key or certificate which signed the commit
+	pub fn signer(&self) -> Option<String> {
+		if let Some(key) = &self.key {
+			Some(format!("ssh key {}", &key.title))
+		} else if let Some(certificate) = &self.x509_certificate {
+			Some(format!("certificate {}", &certificate.subject))
+		} else {
+			self.gpg_key_primary_keyid.as_ref().map(|keyid| {
+				match (&self.gpg_key_user_name, &self.gpg_key_user_email) {
+					(Some(name), Some(email)) => {
+						format!("gpg key {} ({} <{}>)", keyid, name, email)
+					}
+					_ => format!("gpg key {}", keyid),
+				}
+			})
+		}
+	}
+}
+
+/// The signatures of commits by sha (None for unsigned commits)
+pub type Signatures = HashMap<ObjectId, Option<CommitSignature>>;
@@ -0,0 +1,31 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use super::{Job, ObjectId};
+
+/// An environment of a project
+#[derive(Deserialize, Debug, Clone)]
+pub struct Environment {
+	pub id: u64,
+	pub name: String,
+	/// available, stopping or stopped (not set for the environment of a deployment)
+	#[serde(default)]
+	pub state: String,
+	pub external_url: Option<String>,
+}
+
+/// A deployment to an environment
+#[derive(Deserialize, Debug, Clone)]
+pub struct Deployment {
+	pub id: u64,
+	pub iid: u64,
+	#[serde(rename = "ref")]
+	pub ref_: String,
+	pub sha: ObjectId,
+	/// created, running, success, failed, canceled or blocked
+	pub status: String,
+	pub created_at: DateTime<Utc>,
+	/// The job which made the deployment
+	pub deployable: Option<Job>,
+	pub environment: Option<Environment>,
+}
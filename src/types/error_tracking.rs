@@ -0,0 +1,36 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+/// An error group of the error tracking
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SentryError {
+	pub sentry_id: String,
+	pub title: String,
+	#[serde(rename = "type")]
+	pub type_: String,
+	/// Number of occurrences
+	pub count: u64,
+	/// Number of users affected
+	pub user_count: u64,
+	pub first_seen: DateTime<Utc>,
+	pub last_seen: DateTime<Utc>,
+	/// UNRESOLVED, RESOLVED or IGNORED
+	pub status: String,
+	/// The function or file where the error occurred
+	pub culprit: String,
+	pub external_url: String,
+}
+
+/// An error group with its details
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DetailedError {
+	#[serde(flatten)]
+	pub error: SentryError,
+	pub message: Option<String>,
+	pub first_release_version: Option<String>,
+	pub last_release_version: Option<String>,
+	/// The path of the issue created from the error
+	pub gitlab_issue_path: Option<String>,
+}
@@ -0,0 +1,14 @@
+use serde::Deserialize;
+
+/// Group information.
+#[derive(Deserialize, Debug, Clone)]
+pub struct Group {
+	/// The ID of the group.
+	pub id: u64,
+	/// The display name of the group.
+	pub name: String,
+	/// The path of the group with its parents.
+	pub full_path: String,
+	/// The URL for the group's homepage.
+	pub web_url: String,
+}
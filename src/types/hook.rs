@@ -0,0 +1,108 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+/// A system hook of the instance
+#[derive(Deserialize, Debug, Clone)]
+pub struct SystemHook {
+	pub id: u64,
+	pub url: String,
+	pub name: Option<String>,
+	pub created_at: DateTime<Utc>,
+	#[serde(default)]
+	pub push_events: bool,
+	#[serde(default)]
+	pub tag_push_events: bool,
+	#[serde(default)]
+	pub merge_requests_events: bool,
+	#[serde(default)]
+	pub repository_update_events: bool,
+	#[serde(default)]
+	pub enable_ssl_verification: bool,
+}
+
+impl SystemHook {
+	/// The names of the events triggering the hook
+	pub fn events(&self) -> Vec<&'static str> {
+		[
+			(self.push_events, "push"),
+			(self.tag_push_events, "tag_push"),
+			(self.merge_requests_events, "merge_requests"),
+			(self.repository_update_events, "repository_update"),
+		]
+		.into_iter()
+		.filter_map(|(enabled, name)| enabled.then_some(name))
+		.collect()
+	}
+}
+
+/// A webhook of a project
+#[derive(Deserialize, Debug, Clone)]
+pub struct ProjectHook {
+	pub id: u64,
+	pub url: String,
+	pub name: Option<String>,
+	pub created_at: DateTime<Utc>,
+	#[serde(default)]
+	pub push_events: bool,
+	#[serde(default)]
+	pub tag_push_events: bool,
+	#[serde(default)]
+	pub merge_requests_events: bool,
+	#[serde(default)]
+	pub issues_events: bool,
+	#[serde(default)]
+	pub note_events: bool,
+	#[serde(default)]
+	pub job_events: bool,
+	#[serde(default)]
+	pub pipeline_events: bool,
+	#[serde(default)]
+	pub wiki_page_events: bool,
+	#[serde(default)]
+	pub releases_events: bool,
+	#[serde(default)]
+	pub enable_ssl_verification: bool,
+	/// executable, temporarily_disabled or disabled after repeated failures
+	pub alert_status: Option<String>,
+}
+
+impl ProjectHook {
+	/// The names of the events triggering the hook
+	pub fn events(&self) -> Vec<&'static str> {
+		[
+			(self.push_events, "push"),
+			(self.tag_push_events, "tag_push"),
+			(self.merge_requests_events, "merge_requests"),
+			(self.issues_events, "issues"),
+			(self.note_events, "note"),
+			(self.job_events, "job"),
+			(self.pipeline_events, "pipeline"),
+			(self.wiki_page_events, "wiki_page"),
+			(self.releases_events, "releases"),
+		]
+		.into_iter()
+		.filter_map(|(enabled, name)| enabled.then_some(name))
+		.collect()
+	}
+}
+
+/// A recent call of a project webhook
+#[derive(Deserialize, Debug, Clone)]
+pub struct HookDelivery {
+	pub id: u64,
+	/// The event that triggered the call (push_hooks, merge_request_hooks, ...)
+	pub trigger: String,
+	/// The http status code, or the error when there was no response
+	pub response_status: String,
+	pub response_body: Option<String>,
+	/// The duration of the call in seconds
+	pub execution_duration: f64,
+	pub created_at: Option<DateTime<Utc>>,
+}
+
+impl HookDelivery {
+	/// Whether the hook answered with a 2xx status code
+	pub fn is_success(&self) -> bool {
+		self.response_status.starts_with('2') && self.response_status.len() == 3
+	}
+}
@@ -0,0 +1,105 @@
+use chrono::NaiveDate;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// The owner of a license
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct Licensee {
+	#[serde(rename = "Name")]
+	pub name: Option<String>,
+	#[serde(rename = "Email")]
+	pub email: Option<String>,
+	#[serde(rename = "Company")]
+	pub company: Option<String>,
+}
+
+/// The license of a self-managed instance
+#[derive(Deserialize, Debug, Clone)]
+pub struct License {
+	pub id: u64,
+	/// premium or ultimate
+	pub plan: String,
+	pub starts_at: Option<NaiveDate>,
+	pub expires_at: Option<NaiveDate>,
+	#[serde(default)]
+	pub expired: bool,
+	/// The number of seats (0 for no limit)
+	#[serde(default)]
+	pub user_limit: u64,
+	/// The number of billable users
+	#[serde(default)]
+	pub active_users: u64,
+	/// The maximum number of billable users of the license period
+	#[serde(default)]
+	pub historical_max: u64,
+	/// The number of users over the limit
+	#[serde(default)]
+	pub overage: u64,
+	#[serde(default)]
+	pub licensee: Licensee,
+}
+
+/// Counts of the objects of the instance (the numbers are formatted with delimiters)
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ApplicationStatistics {
+	pub projects: String,
+	pub groups: String,
+	pub users: String,
+	pub active_users: String,
+	pub forks: String,
+	pub issues: String,
+	pub merge_requests: String,
+	pub notes: String,
+	pub snippets: String,
+	pub ssh_keys: String,
+	pub milestones: String,
+}
+
+/// The version of the instance
+#[derive(Deserialize, Debug, Clone)]
+pub struct Version {
+	pub version: String,
+	pub revision: String,
+}
+
+impl Version {
+	/// Returns the major and minor numbers of the version (`16.11.2-ee` gives 16 and 11)
+	pub fn release(&self) -> Option<(u32, u32)> {
+		let mut numbers = self.version.split(['.', '-']);
+		let major = numbers.next()?.parse().ok()?;
+		let minor = numbers.next()?.parse().ok()?;
+		Some((major, minor))
+	}
+}
+
+/// The result of a health check
+#[derive(Deserialize, Debug, Clone)]
+pub struct HealthCheck {
+	/// ok or failed
+	pub status: String,
+	pub message: Option<String>,
+}
+
+/// The answer of a health probe (liveness or readiness)
+#[derive(Deserialize, Debug, Clone)]
+pub struct HealthStatus {
+	/// ok or failed
+	pub status: String,
+	/// The checks of the subsystems (db_check, redis_check...) with readiness probes
+	#[serde(flatten)]
+	pub checks: BTreeMap<String, Vec<HealthCheck>>,
+}
+
+impl HealthStatus {
+	/// Returns true if the probe and all the subsystems are healthy
+	pub fn is_healthy(&self) -> bool {
+		self.status == "ok"
+			&& self
+				.checks
+				.values()
+				.flatten()
+				.all(|check| check.status == "ok")
+	}
+}
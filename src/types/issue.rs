@@ -0,0 +1,59 @@
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+use super::User;
+
+/// A milestone of a project or a group
+#[derive(Deserialize, Debug, Clone)]
+pub struct Milestone {
+	pub id: u64,
+	pub iid: u64,
+	pub title: String,
+}
+
+/// The ways to refer to an issue or a merge request
+#[derive(Deserialize, Debug, Clone)]
+pub struct References {
+	/// Reference from anywhere (group/project#iid or group/project!iid)
+	pub full: String,
+}
+
+/// Time tracking statistics of an issue or a merge request in seconds
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct TimeStats {
+	pub time_estimate: i64,
+	pub total_time_spent: i64,
+}
+
+/// An issue of a project
+#[derive(Deserialize, Debug, Clone)]
+pub struct Issue {
+	pub id: u64,
+	pub iid: u64,
+	pub title: String,
+	/// opened or closed
+	pub state: String,
+	pub web_url: String,
+	pub milestone: Option<Milestone>,
+	#[serde(default)]
+	pub time_stats: TimeStats,
+	#[serde(default)]
+	pub labels: Vec<String>,
+	#[serde(default)]
+	pub assignees: Vec<User>,
+	/// The email of the requester of a Service Desk issue
+	pub service_desk_reply_to: Option<String>,
+	pub due_date: Option<NaiveDate>,
+	pub references: Option<References>,
+}
+
+/// Time tracked on the issues and merge requests of a milestone
+#[derive(Debug, Clone, Default)]
+pub struct MilestoneTime {
+	/// The milestone title (None for the issues and merge requests without milestone)
+	pub milestone: Option<String>,
+	pub issues: usize,
+	pub merge_requests: usize,
+	pub time_stats: TimeStats,
+}
@@ -0,0 +1,39 @@
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+/// An iteration (sprint) of a group
+#[derive(Deserialize, Debug, Clone)]
+pub struct Iteration {
+	pub id: u64,
+	pub iid: u64,
+	/// The position of the iteration in its cadence
+	pub sequence: u64,
+	pub group_id: u64,
+	/// Iterations of automatic cadences have no title
+	pub title: Option<String>,
+	pub description: Option<String>,
+	/// 1 upcoming, 2 current or 3 closed
+	pub state: u8,
+	pub start_date: NaiveDate,
+	pub due_date: NaiveDate,
+	pub web_url: String,
+}
+
+impl Iteration {
+	/// The title of the iteration or its period
+	pub fn name(&self) -> String {
+		match &self.title {
+			Some(title) if !title.is_empty() => title.to_owned(),
+			_ => format!("{} - {}", self.start_date, self.due_date),
+		}
+	}
+
+	/// The name of the state
+	pub fn state_name(&self) -> &'static str {
+		match self.state {
+			1 => "upcoming",
+			2 => "current",
+			_ => "closed",
+		}
+	}
+}
@@ -4,9 +4,11 @@ use anyhow::{anyhow, Error};
 use chrono::{DateTime, Utc};
 use gitlab::api::ParamValue;
 use serde::Deserialize;
+use ssh_key::{HashAlg, PublicKey};
 use std::{borrow::Cow, str::FromStr};
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Deserialize, Debug, Copy, Clone)]
+#[serde(rename_all = "snake_case")]
 pub enum KeyUsage {
 	Auth,
 	Signing,
@@ -65,5 +67,57 @@ pub struct SshKey {
 	pub title: String,
 	pub key: String,
 	pub created_at: DateTime<Utc>,
+	pub expires_at: Option<DateTime<Utc>>,
+	pub last_used_at: Option<DateTime<Utc>>,
+	pub usage_type: Option<KeyUsage>,
 	pub user: Option<types::User>,
 }
+
+impl SshKey {
+	pub fn expired(&self) -> bool {
+		if let Some(d) = self.expires_at {
+			Utc::now() > d
+		} else {
+			false
+		}
+	}
+
+	/// Parse the public key
+	pub fn public_key(&self) -> Option<PublicKey> {
+		PublicKey::from_openssh(&self.key).ok()
+	}
+
+	/// Returns the SHA256 fingerprint of the key
+	pub fn fingerprint(&self) -> Option<String> {
+		self.public_key()
+			.map(|key| key.fingerprint(HashAlg::Sha256).to_string())
+	}
+
+	/// Returns the algorithm and the size in bits of the key
+	pub fn key_type(&self) -> Option<(String, usize)> {
+		let key = self.public_key()?;
+		let data = key.key_data();
+		let bits = if let Some(rsa) = data.rsa() {
+			mpint_bits(rsa.n.as_positive_bytes()?)
+		} else if let Some(dsa) = data.dsa() {
+			mpint_bits(dsa.p.as_positive_bytes()?)
+		} else if let Some(ecdsa) = data.ecdsa() {
+			match ecdsa.curve() {
+				ssh_key::EcdsaCurve::NistP256 => 256,
+				ssh_key::EcdsaCurve::NistP384 => 384,
+				ssh_key::EcdsaCurve::NistP521 => 521,
+			}
+		} else {
+			256
+		};
+		Some((key.algorithm().as_str().to_owned(), bits))
+	}
+}
+
+/// Number of significant bits of a big endian positive integer
+fn mpint_bits(bytes: &[u8]) -> usize {
+	bytes
+		.first()
+		.map(|first| bytes.len() * 8 - first.leading_zeros() as usize)
+		.unwrap_or_default()
+}
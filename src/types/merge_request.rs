@@ -0,0 +1,75 @@
+use serde::Deserialize;
+
+use super::{
+	issue::{Milestone, References, TimeStats},
+	ObjectId, User,
+};
+
+/// A merge request of a project
+#[derive(Deserialize, Debug, Clone)]
+pub struct MergeRequest {
+	pub id: u64,
+	pub iid: u64,
+	pub title: String,
+	/// opened, closed, locked or merged
+	pub state: String,
+	pub source_branch: String,
+	pub target_branch: String,
+	pub web_url: String,
+	/// Whether the merge request is a draft (its title starts with a draft prefix)
+	#[serde(default)]
+	pub draft: bool,
+	/// Whether the source branch conflicts with the target branch
+	#[serde(default)]
+	pub has_conflicts: bool,
+	/// Why the merge request can or can't be merged (mergeable, conflict, need_rebase, ...)
+	pub detailed_merge_status: Option<String>,
+	/// Whether a rebase is running (only when requested)
+	pub rebase_in_progress: Option<bool>,
+	/// The error of the last merge or rebase attempt
+	pub merge_error: Option<String>,
+	/// The head of the source branch
+	pub sha: Option<ObjectId>,
+	/// The merge commit (not set for fast-forward merges)
+	pub merge_commit_sha: Option<ObjectId>,
+	/// The squashed commit when the merge request was squashed
+	pub squash_commit_sha: Option<ObjectId>,
+	pub milestone: Option<Milestone>,
+	#[serde(default)]
+	pub time_stats: TimeStats,
+	#[serde(default)]
+	pub reviewers: Vec<User>,
+	pub references: Option<References>,
+}
+
+impl MergeRequest {
+	/// The title without the draft prefixes recognized by gitlab (Draft:, [Draft], (Draft))
+	pub fn ready_title(&self) -> &str {
+		let mut title = self.title.as_str();
+		loop {
+			let stripped = ["draft:", "[draft]", "(draft)"].into_iter().find_map(|prefix| {
+				title
+					.get(..prefix.len())
+					.filter(|start| start.eq_ignore_ascii_case(prefix))
+					.map(|_| title[prefix.len()..].trim_start())
+			});
+			match stripped {
+				Some(stripped) => title = stripped,
+				None => return title,
+			}
+		}
+	}
+
+	/// The path of the project of the merge request (when listed across projects)
+	pub fn project_path(&self) -> Option<&str> {
+		self.references
+			.as_ref()
+			.and_then(|references| references.full.rsplit_once('!'))
+			.map(|(path, _)| path)
+	}
+
+	/// The title marked as draft
+	pub fn draft_title(&self) -> String {
+		format!("Draft: {}", self.ready_title())
+	}
+}
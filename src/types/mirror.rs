@@ -0,0 +1,18 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+/// A push mirror of a project
+#[derive(Deserialize, Debug, Clone)]
+pub struct RemoteMirror {
+	pub id: u64,
+	/// The mirror url with credentials masked
+	pub url: String,
+	pub enabled: bool,
+	/// none, scheduled, started, finished or failed
+	pub update_status: String,
+	pub last_update_at: Option<DateTime<Utc>>,
+	pub last_successful_update_at: Option<DateTime<Utc>>,
+	pub last_error: Option<String>,
+	pub only_protected_branches: bool,
+	pub keep_divergent_refs: Option<bool>,
+}
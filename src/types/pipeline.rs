@@ -1,6 +1,11 @@
-use std::fmt::{self, Display, Formatter};
+use std::{
+	fmt::{self, Display, Formatter},
+	str::FromStr,
+};
 
+use anyhow::{bail, Error};
 use chrono::{DateTime, Utc};
+use gitlab::api::projects::pipelines::PipelineStatus;
 use serde::Deserialize;
 
 use super::ObjectId;
@@ -59,6 +64,49 @@ pub enum StatusState {
 	Scheduled,
 }
 
+impl FromStr for StatusState {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Ok(match s {
+			"created" => Self::Created,
+			"waiting_for_resource" => Self::WaitingForResource,
+			"preparing" => Self::Preparing,
+			"pending" => Self::Pending,
+			"running" => Self::Running,
+			"success" => Self::Success,
+			"failed" => Self::Failed,
+			"canceled" => Self::Canceled,
+			"skipped" => Self::Skipped,
+			"manual" => Self::Manual,
+			"scheduled" => Self::Scheduled,
+			_ => bail!(
+				"Unknown status {}, use success, failed, canceled, skipped, manual...",
+				s
+			),
+		})
+	}
+}
+
+/// Convert to the status used to filter pipelines
+impl From<StatusState> for PipelineStatus {
+	fn from(value: StatusState) -> Self {
+		match value {
+			StatusState::Created => Self::Created,
+			StatusState::WaitingForResource => Self::WaitingForResource,
+			StatusState::Preparing => Self::Preparing,
+			StatusState::Pending => Self::Pending,
+			StatusState::Running => Self::Running,
+			StatusState::Success => Self::Success,
+			StatusState::Failed => Self::Failed,
+			StatusState::Canceled => Self::Canceled,
+			StatusState::Skipped => Self::Skipped,
+			StatusState::Manual => Self::Manual,
+			StatusState::Scheduled => Self::Scheduled,
+		}
+	}
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct JobId(u64);
 
@@ -85,6 +133,9 @@ pub struct Job {
 	/// The status of the job.
 	pub status: StatusState,
 	pub stage: String,
+	/// Whether the failure of the job doesn't fail the pipeline.
+	#[serde(default)]
+	pub allow_failure: bool,
 	/// The URL to the job page.
 	pub web_url: String,
 	/// When the job was created or marked as pending.
@@ -93,6 +144,30 @@ pub struct Job {
 	pub started_at: Option<DateTime<Utc>>,
 	/// When the job completed.
 	pub finished_at: Option<DateTime<Utc>>,
+	/// The artifacts of the job (including its log).
+	#[serde(default)]
+	pub artifacts: Vec<JobArtifact>,
+	/// When the artifacts expire.
+	pub artifacts_expire_at: Option<DateTime<Utc>>,
+}
+
+impl Job {
+	/// The artifacts of the job that can be deleted (all but the log)
+	pub fn deletable_artifacts(&self) -> impl Iterator<Item = &JobArtifact> {
+		self.artifacts.iter().filter(|artifact| artifact.file_type != "trace")
+	}
+}
+
+/// An artifact file of a job.
+#[derive(Deserialize, Debug, Clone)]
+pub struct JobArtifact {
+	/// The type of artifact (archive, metadata, trace, junit...).
+	pub file_type: String,
+	/// The size of the file in bytes.
+	#[serde(default)]
+	pub size: u64,
+	/// The name of the file.
+	pub filename: String,
 }
 
 /// More information about a pipeline in Gitlab CI.
@@ -109,6 +184,30 @@ pub struct Pipeline {
 	pub sha: ObjectId,
 	/// When the pipeline was created.
 	pub created_at: Option<DateTime<Utc>>,
+	/// What triggered the pipeline (push, schedule, trigger, web, api...).
+	pub source: Option<String>,
 	/// The URL to the pipeline page.
 	pub web_url: String,
 }
+
+/// A pipeline schedule of a project.
+#[derive(Deserialize, Debug, Clone)]
+pub struct PipelineSchedule {
+	/// The ID of the schedule.
+	pub id: u64,
+	/// The description of the schedule.
+	pub description: String,
+	/// The reference the pipelines are built upon.
+	#[serde(rename = "ref")]
+	pub ref_: String,
+	/// The cron expression of the schedule.
+	pub cron: String,
+	/// The timezone of the cron expression.
+	pub cron_timezone: String,
+	/// When the next pipeline will be created.
+	pub next_run_at: Option<DateTime<Utc>>,
+	/// Whether the schedule is active.
+	pub active: bool,
+	/// The last pipeline created by the schedule.
+	pub last_pipeline: Option<Pipeline>,
+}
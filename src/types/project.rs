@@ -1,6 +1,6 @@
 use std::fmt::{self, Display, Formatter};
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct ProjectId(u64);
@@ -31,4 +31,143 @@ pub struct Project {
 	pub name_with_namespace: String,
 	/// The path to the project's repository with its namespace.
 	pub path_with_namespace: String,
+	/// The default branch of the repository (none for empty repositories).
+	pub default_branch: Option<String>,
+	/// Whether the project is archived.
+	#[serde(default)]
+	pub archived: bool,
+	/// The number of users who starred the project.
+	#[serde(default)]
+	pub star_count: u64,
+	/// The URL to clone the repository over SSH.
+	pub ssh_url_to_repo: Option<String>,
+	/// The URL to clone the repository over HTTPS.
+	pub http_url_to_repo: Option<String>,
+	/// Whether Service Desk is enabled.
+	#[serde(default)]
+	pub service_desk_enabled: bool,
+	/// The email address of the Service Desk.
+	pub service_desk_address: Option<String>,
+	/// The storage statistics of the project (only when requested).
+	pub statistics: Option<ProjectStatistics>,
+}
+
+/// Storage statistics of a project in bytes.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ProjectStatistics {
+	/// The total storage used by the project.
+	pub storage_size: u64,
+	/// The size of the git repository.
+	pub repository_size: u64,
+	/// The size of the LFS objects.
+	pub lfs_objects_size: u64,
+	/// The size of the job artifacts.
+	pub job_artifacts_size: u64,
+	/// The size of the packages.
+	pub packages_size: u64,
+	/// The size of the container registry.
+	pub container_registry_size: u64,
+	/// The size of the wiki.
+	pub wiki_size: u64,
+	/// The size of the snippets.
+	pub snippets_size: u64,
+	/// The size of the uploads.
+	pub uploads_size: u64,
+}
+
+/// The settings of a project that can be copied to another one, grouped as on the settings
+/// pages. Unset settings are left unchanged
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct ProjectSettings {
+	#[serde(flatten)]
+	pub general: GeneralSettings,
+	#[serde(flatten)]
+	pub merge: MergeSettings,
+	#[serde(flatten)]
+	pub ci: CiSettings,
+}
+
+/// Visibility and features of a project
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct GeneralSettings {
+	/// private, internal or public
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub visibility: Option<String>,
+	/// Access levels of the features: disabled, private or enabled
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub issues_access_level: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub repository_access_level: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub merge_requests_access_level: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub forking_access_level: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub builds_access_level: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub wiki_access_level: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub snippets_access_level: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub pages_access_level: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub container_registry_access_level: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub lfs_enabled: Option<bool>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub request_access_enabled: Option<bool>,
+}
+
+/// How merge requests are merged
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct MergeSettings {
+	/// merge, rebase_merge or ff
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub merge_method: Option<String>,
+	/// never, always, default_on or default_off
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub squash_option: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub only_allow_merge_if_pipeline_succeeds: Option<bool>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub allow_merge_on_skipped_pipeline: Option<bool>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub only_allow_merge_if_all_discussions_are_resolved: Option<bool>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub remove_source_branch_after_merge: Option<bool>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub resolve_outdated_diff_discussions: Option<bool>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub printing_merge_request_link_enabled: Option<bool>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub merge_commit_template: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub squash_commit_template: Option<String>,
+}
+
+/// How pipelines are run
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct CiSettings {
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub ci_config_path: Option<String>,
+	/// Maximum duration of the jobs in seconds
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub build_timeout: Option<u64>,
+	/// enabled or disabled
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub auto_cancel_pending_pipelines: Option<String>,
+	/// fetch or clone
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub build_git_strategy: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub ci_default_git_depth: Option<u64>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub shared_runners_enabled: Option<bool>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub public_jobs: Option<bool>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub keep_latest_artifact: Option<bool>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub ci_forward_deployment_enabled: Option<bool>,
 }
@@ -1,6 +1,25 @@
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
 
-use super::ObjectId;
+use super::{Commit, ObjectId};
+
+/// A file changed between two revisions.
+#[derive(Deserialize, Debug, Clone)]
+pub struct Diff {
+	/// The path of the file before the change.
+	pub old_path: String,
+	/// The path of the file after the change.
+	pub new_path: String,
+}
+
+/// The comparison of two revisions.
+#[derive(Deserialize, Debug, Clone)]
+pub struct Comparison {
+	/// The files changed.
+	pub diffs: Vec<Diff>,
+	/// The commits, oldest first.
+	pub commits: Vec<Commit>,
+}
 
 /// A commit in a project.
 #[derive(Deserialize, Debug, Clone)]
@@ -31,12 +50,18 @@ pub struct Tag {
 pub struct ProtectedTag {
 	/// The name or wildcard
 	pub name: String,
+	/// Who can create matching tags
+	#[serde(default)]
+	pub create_access_levels: Vec<PRBAccessLevel>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct PRBAccessLevel {
-	pub access_level: u64,
+	pub access_level: Option<u64>,
 	pub access_level_description: String,
+	pub user_id: Option<u64>,
+	pub group_id: Option<u64>,
+	pub deploy_key_id: Option<u64>,
 }
 
 /// A protected branch on a repository
@@ -45,6 +70,11 @@ pub struct ProtectedRepoBranch {
 	pub name: String,
 	pub push_access_levels: Vec<PRBAccessLevel>,
 	pub merge_access_levels: Vec<PRBAccessLevel>,
+	/// Who can unprotect the branch
+	#[serde(default)]
+	pub unprotect_access_levels: Vec<PRBAccessLevel>,
+	#[serde(default)]
+	pub allow_force_push: bool,
 	pub code_owner_approval_required: Option<bool>,
 }
 
@@ -53,4 +83,13 @@ pub struct ProtectedRepoBranch {
 pub struct RepoBranch {
 	/// The name of the branch.
 	pub name: String,
+	/// The last commit of the branch.
+	pub commit: Option<BranchCommit>,
+}
+
+/// The last commit of a branch.
+#[derive(Deserialize, Debug, Clone)]
+pub struct BranchCommit {
+	/// When the commit was committed.
+	pub committed_date: Option<DateTime<Utc>>,
 }
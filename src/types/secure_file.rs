@@ -0,0 +1,16 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+/// A file (certificate, provisioning profile...) made available to the CI jobs
+#[derive(Deserialize, Debug, Clone)]
+pub struct SecureFile {
+	pub id: u64,
+	pub name: String,
+	/// Checksum of the content
+	pub checksum: String,
+	/// Algorithm of the checksum (sha256)
+	pub checksum_algorithm: String,
+	pub created_at: DateTime<Utc>,
+	/// Expiration of the certificate the file contains, if any
+	pub expires_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,34 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+/// The project of a todo
+#[derive(Deserialize, Debug, Clone)]
+pub struct TodoProject {
+	pub id: u64,
+	pub path_with_namespace: String,
+}
+
+/// The issue, merge request, commit... a todo is about
+#[derive(Deserialize, Debug, Clone)]
+pub struct TodoTarget {
+	/// Not set for commits
+	pub iid: Option<u64>,
+	/// Not set for commits
+	pub title: Option<String>,
+}
+
+/// Something the current user has to act on (review requested, mention, assignment...)
+#[derive(Deserialize, Debug, Clone)]
+pub struct Todo {
+	pub id: u64,
+	/// Not set for the todos of groups
+	pub project: Option<TodoProject>,
+	/// Why the todo was created (assigned, mentioned, review_requested...)
+	pub action_name: String,
+	/// Issue, MergeRequest, Commit...
+	pub target_type: String,
+	pub target: Option<TodoTarget>,
+	pub target_url: String,
+	pub body: String,
+	pub created_at: DateTime<Utc>,
+}
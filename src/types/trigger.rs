@@ -0,0 +1,16 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use super::User;
+
+/// A pipeline trigger token
+#[derive(Deserialize, Debug, Clone)]
+pub struct PipelineTrigger {
+	pub id: u64,
+	pub description: String,
+	/// Only entirely visible to the owner of the trigger
+	pub token: String,
+	pub created_at: DateTime<Utc>,
+	pub last_used: Option<DateTime<Utc>>,
+	pub owner: Option<User>,
+}
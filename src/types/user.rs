@@ -1,3 +1,4 @@
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::Deserialize;
 
 #[derive(Deserialize, Debug, Clone)]
@@ -23,3 +24,63 @@ pub struct User {
 	/// Only available when talking to GitLab as an admin.
 	pub is_admin: Option<bool>,
 }
+
+/// Full user profile
+#[derive(Deserialize, Debug)]
+pub struct UserProfile {
+	/// The username.
+	pub username: String,
+	/// The display name.
+	pub name: String,
+	/// The user's ID.
+	pub id: UserId,
+	/// The state of the account (active, blocked, ...).
+	pub state: String,
+	/// The URL of the user's profile page.
+	pub web_url: String,
+	pub created_at: Option<DateTime<Utc>>,
+	pub bio: Option<String>,
+	pub location: Option<String>,
+	pub organization: Option<String>,
+	pub public_email: Option<String>,
+	/// Only available for the current user or as an admin.
+	pub email: Option<String>,
+	pub last_sign_in_at: Option<DateTime<Utc>>,
+	pub last_activity_on: Option<NaiveDate>,
+	#[serde(default)]
+	pub bot: bool,
+	/// Only available for the current user or as an admin.
+	pub is_admin: Option<bool>,
+	/// Only available for the current user or as an admin.
+	pub two_factor_enabled: Option<bool>,
+}
+
+/// Push information of a push event
+#[derive(Deserialize, Debug)]
+pub struct PushData {
+	pub commit_count: u64,
+	pub action: String,
+	pub ref_type: String,
+	#[serde(rename = "ref")]
+	pub ref_: Option<String>,
+	pub commit_title: Option<String>,
+}
+
+/// Contribution event of a user
+#[derive(Deserialize, Debug)]
+pub struct Event {
+	pub action_name: String,
+	pub target_type: Option<String>,
+	pub target_title: Option<String>,
+	pub project_id: Option<u64>,
+	pub created_at: DateTime<Utc>,
+	pub push_data: Option<PushData>,
+}
+
+/// Project or group membership of a user
+#[derive(Deserialize, Debug)]
+pub struct Membership {
+	pub source_id: u64,
+	pub source_name: String,
+	pub source_type: String,
+}
@@ -0,0 +1,37 @@
+use serde::Deserialize;
+
+/// How a variable is given to the jobs
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VariableType {
+	/// An environment variable
+	EnvVar,
+	/// A file whose path is in the environment variable
+	File,
+}
+
+/// A CI/CD variable
+#[derive(Deserialize, Debug, Clone)]
+pub struct Variable {
+	pub key: String,
+	/// None for the hidden variables, whose value can't be read back
+	pub value: Option<String>,
+	pub variable_type: VariableType,
+	/// Only given to the pipelines of protected branches and tags
+	pub protected: bool,
+	/// Hidden in the job logs
+	pub masked: bool,
+	/// Not expanded
+	#[serde(default)]
+	pub raw: bool,
+	/// The environments the variable is available in
+	pub environment_scope: String,
+}
+
+/// A variable given to a pipeline when it was run
+#[derive(Deserialize, Debug, Clone)]
+pub struct PipelineVariable {
+	pub key: String,
+	pub value: String,
+	pub variable_type: VariableType,
+}
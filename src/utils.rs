@@ -1,3 +1,10 @@
+use crate::args::DurationFormat;
+use std::env;
+use std::io;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+use std::{panic, thread};
+
 /// take an element from a vec
 pub fn take_from_vec<T>(mut vec: Vec<T>, i: usize) -> Option<T> {
     if vec.get(i).is_none() {
@@ -7,15 +14,137 @@ pub fn take_from_vec<T>(mut vec: Vec<T>, i: usize) -> Option<T> {
     }
 }
 
-pub fn format_duration(duration: i64) -> String {
+/// number of single character edits (insertions, deletions or substitutions) to change a
+/// string into another one (Levenshtein distance)
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// format a duration in seconds
+pub fn format_duration(duration: i64, format: DurationFormat) -> String {
     let h = (duration / 60) / 60;
     let m = (duration / 60) % 60;
     let s = duration % 60;
-    if h != 0 {
-        format!("{}:{:02}:{:02}s", h, m, s)
-    } else if m != 0 {
-        format!("{}:{:02}s", m, s)
+    match format {
+        DurationFormat::Seconds => duration.to_string(),
+        DurationFormat::Hms => format!("{:02}:{:02}:{:02}", h, m, s),
+        DurationFormat::Human if h != 0 => format!("{}:{:02}:{:02}s", h, m, s),
+        DurationFormat::Human if m != 0 => format!("{}:{:02}s", m, s),
+        DurationFormat::Human => format!("{}s", s),
+    }
+}
+
+/// format the latency of a request in milliseconds, or in seconds above one second
+pub fn format_latency(duration: Duration) -> String {
+    if duration.as_secs() == 0 {
+        format!("{}ms", duration.as_millis())
+    } else {
+        format!("{:.2}s", duration.as_secs_f64())
+    }
+}
+
+/// format a tracked time in seconds the way gitlab does (1w = 5d, 1d = 8h)
+pub fn format_time_tracking(seconds: i64) -> String {
+    const UNITS: [(&str, i64); 5] = [
+        ("w", 5 * 8 * 3600),
+        ("d", 8 * 3600),
+        ("h", 3600),
+        ("m", 60),
+        ("s", 1),
+    ];
+    let sign = if seconds < 0 { "-" } else { "" };
+    let mut rest = seconds.abs();
+    let mut parts = Vec::new();
+    for (unit, size) in UNITS {
+        if rest >= size {
+            parts.push(format!("{}{}", rest / size, unit));
+            rest %= size;
+        }
+    }
+    if parts.is_empty() {
+        "0h".to_owned()
     } else {
-        format!("{}s", s)
+        format!("{}{}", sign, parts.join(" "))
+    }
+}
+
+/// run two functions at the same time (typically independent api queries) and returns both
+/// results
+pub fn join<A, B, RA, RB>(a: A, b: B) -> (RA, RB)
+where
+    A: FnOnce() -> RA,
+    B: FnOnce() -> RB + Send,
+    RB: Send,
+{
+    thread::scope(|scope| {
+        let b = scope.spawn(b);
+        let a = a();
+        (a, b.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+    })
+}
+
+/// open an url with the given browser command (split on whitespaces, url appended) or with the
+/// default browser of the system
+pub fn open_browser(url: &str, browser: Option<&str>) -> io::Result<()> {
+    let Some(browser) = browser else {
+        return open::that(url);
+    };
+    let mut words = browser.split_whitespace();
+    Command::new(words.next().unwrap_or_default())
+        .args(words)
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+}
+
+/// guess if there is no display to show the default browser on: linux and bsd without X11 nor
+/// wayland display (ssh sessions without forwarding included), or ssh sessions elsewhere
+pub fn is_headless() -> bool {
+    let set = |var| env::var_os(var).is_some_and(|value| !value.is_empty());
+    if cfg!(any(target_os = "macos", windows)) {
+        set("SSH_CONNECTION") || set("SSH_TTY")
+    } else {
+        !set("DISPLAY") && !set("WAYLAND_DISPLAY")
+    }
+}
+
+/// match a text against a glob pattern where `*` matches any sequence of characters and `?`
+/// any single character
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // position of the last star in pattern and of the text it matched from
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((sp, st)) = star {
+            // let the star absorb one more character
+            p = sp + 1;
+            t = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
     }
+    pattern[p..].iter().all(|c| *c == '*')
 }
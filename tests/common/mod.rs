@@ -0,0 +1,121 @@
+//! Fake Gitlab instance answering with recorded responses, to run the commands end-to-end
+//! without a live instance
+
+use argh::FromArgs;
+use gil::{args::Opts, config::Config, context::CliContext, git::GitProject};
+use mockito::{Matcher, Mock, Server, ServerGuard};
+use std::{fs, path::PathBuf, thread, time::Duration};
+
+/// Returns the content of a recorded response in `tests/fixtures`
+pub fn fixture(name: &str) -> String {
+	let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+		.join("tests")
+		.join("fixtures")
+		.join(name)
+		.with_extension("json");
+	fs::read_to_string(&path).unwrap_or_else(|e| panic!("Can't read {:?}: {}", &path, e))
+}
+
+/// A fake Gitlab serving the REST API under `/api/v4`
+pub struct FakeGitlab {
+	server: ServerGuard,
+}
+
+impl FakeGitlab {
+	/// Start a new instance. The current user is always served to the `glpat-test` token as the
+	/// client checks the connection with it, and `glpat-wrong` is rejected
+	pub fn new() -> Self {
+		let mut server = Server::new();
+		server
+			.mock("GET", "/api/v4/user")
+			.match_query(Matcher::Any)
+			.match_header("private-token", "glpat-test")
+			.with_header("content-type", "application/json")
+			.with_body(fixture("user"))
+			.create();
+		server
+			.mock("GET", "/api/v4/user")
+			.match_query(Matcher::Any)
+			.match_header("private-token", "glpat-wrong")
+			.with_status(401)
+			.with_header("content-type", "application/json")
+			.with_body("{\"message\":\"401 Unauthorized\"}")
+			.create();
+		Self { server }
+	}
+
+	/// Answer GET requests of an endpoint (whatever the query parameters) with a fixture
+	pub fn get(&mut self, endpoint: &str, fixture_name: &str) -> Mock {
+		self.server
+			.mock("GET", format!("/api/v4/{}", endpoint).as_str())
+			.match_query(Matcher::Any)
+			.with_header("content-type", "application/json")
+			.with_body(fixture(fixture_name))
+			.create()
+	}
+
+	/// Answer GET requests of an endpoint with a fixture after a delay
+	pub fn get_slow(&mut self, endpoint: &str, fixture_name: &str, delay: Duration) -> Mock {
+		let body = fixture(fixture_name);
+		self.server
+			.mock("GET", format!("/api/v4/{}", endpoint).as_str())
+			.match_query(Matcher::Any)
+			.with_header("content-type", "application/json")
+			.with_body_from_request(move |_| {
+				thread::sleep(delay);
+				body.clone().into_bytes()
+			})
+			.create()
+	}
+
+	/// Answer GET requests of an endpoint with an error status
+	pub fn get_error(&mut self, endpoint: &str, status: usize, message: &str) -> Mock {
+		self.server
+			.mock("GET", format!("/api/v4/{}", endpoint).as_str())
+			.match_query(Matcher::Any)
+			.with_status(status)
+			.with_header("content-type", "application/json")
+			.with_body(format!("{{\"message\":\"{}\"}}", message))
+			.create()
+	}
+
+	/// Answer a health probe of the instance (outside of the API) with a fixture
+	pub fn probe(&mut self, probe: &str, fixture_name: &str) -> Mock {
+		self.server
+			.mock("GET", format!("/-/{}", probe).as_str())
+			.match_query(Matcher::Any)
+			.with_header("content-type", "application/json")
+			.with_body(fixture(fixture_name))
+			.create()
+	}
+
+	/// Answer the GraphQL queries with a status and a body
+	pub fn graphql(&mut self, status: usize, body: &str) -> Mock {
+		self.server
+			.mock("POST", "/api/graphql")
+			.with_status(status)
+			.with_header("content-type", "application/json")
+			.with_body(body)
+			.create()
+	}
+
+	/// Returns a context connected to the fake instance for the given command line arguments
+	/// (without colors nor questions)
+	pub fn context(&self, args: &[&str]) -> CliContext {
+		self.try_context(args, "glpat-test")
+			.expect("can't connect to the fake gitlab")
+	}
+
+	/// Returns a context connected to the fake instance with a token, or the connection error
+	pub fn try_context(&self, args: &[&str], token: &str) -> anyhow::Result<CliContext> {
+		let args: Vec<&str> = ["--color", "never", "--non-interactive"]
+			.iter()
+			.chain(args)
+			.copied()
+			.collect();
+		let opts = Opts::from_args(&["gil"], &args).expect("invalid command line");
+		let config: Config = serde_yaml::from_str("hosts: {}").expect("invalid configuration");
+		let repo = GitProject::from_host(&self.server.host_with_port());
+		CliContext::with_url(opts, config, repo, &self.server.url(), token)
+	}
+}
@@ -0,0 +1,316 @@
+mod common;
+
+use common::FakeGitlab;
+use gil::{
+	api::projects::error_tracking::errors::{SentryErrors, SentryErrorsVariables},
+	args::KeyIdType,
+	types::StatusState,
+};
+use graphql_client::GraphQLQuery;
+use gitlab::api::projects::jobs::JobScope;
+use std::{
+	iter,
+	time::{Duration, Instant},
+};
+
+#[test]
+fn get_project() {
+	let mut gitlab = FakeGitlab::new();
+	gitlab.get("projects/group%2Ffoo", "project");
+	let context = gitlab.context(&["project", "info"]);
+
+	let project = context.get_project(Some("group/foo")).unwrap();
+	assert_eq!(project.id.value(), 1234);
+	assert_eq!(project.path_with_namespace, "group/foo");
+	assert_eq!(project.default_branch.as_deref(), Some("main"));
+}
+
+#[test]
+fn get_missing_project() {
+	let mut gitlab = FakeGitlab::new();
+	gitlab.get_error("projects/group%2Fbar", 404, "404 Project Not Found");
+	let context = gitlab.context(&["project", "info"]);
+
+	let err = context.get_project(Some("group/bar")).unwrap_err();
+	assert_eq!(err.to_string(), "Can't find a project named group/bar");
+	assert!(format!("{:#}", err).contains("404 Project Not Found"));
+}
+
+#[test]
+fn suggest_similar_projects() {
+	let mut gitlab = FakeGitlab::new();
+	gitlab.get_error("projects/grop%2Ffo", 404, "404 Project Not Found");
+	gitlab.get_error("projects/fooo", 404, "404 Project Not Found");
+	gitlab.get("projects", "projects");
+	let context = gitlab.context(&["project", "info"]);
+
+	let err = context.get_project(Some("grop/fo")).unwrap_err();
+	assert_eq!(
+		err.to_string(),
+		"Can't find a project named grop/fo, did you mean group/foo?"
+	);
+	// without namespace only the project names are compared
+	let err = context.get_project(Some("fooo")).unwrap_err();
+	assert_eq!(
+		err.to_string(),
+		"Can't find a project named fooo, did you mean group/foo or other/foo?"
+	);
+}
+
+#[test]
+fn request_timeout() {
+	let mut gitlab = FakeGitlab::new();
+	gitlab.get_slow("projects/group%2Ffoo", "project", Duration::from_secs(2));
+	let context = gitlab.context(&["--timeout", "1", "project", "info"]);
+
+	let start = Instant::now();
+	let err = context.get_project(Some("group/foo")).unwrap_err();
+	assert!(start.elapsed() < Duration::from_secs(2));
+	assert!(format!("{:#}", err).contains("timed out"), "{:#}", err);
+}
+
+#[test]
+fn connect_with_wrong_token() {
+	let gitlab = FakeGitlab::new();
+
+	let err = gitlab
+		.try_context(&["project", "info"], "glpat-wrong")
+		.err()
+		.unwrap();
+	assert!(err.to_string().starts_with("Can't connect to"));
+	assert!(format!("{:#}", err).contains("401 Unauthorized"), "{:#}", err);
+}
+
+/// Query the errors of a project with GraphQL
+fn sentry_errors(gitlab: &FakeGitlab) -> anyhow::Result<Option<usize>> {
+	let context = gitlab.context(&["errors", "list"]);
+	let query = SentryErrors::build_query(SentryErrorsVariables {
+		full_path: "group/foo".to_owned(),
+		first: 20,
+		search_term: None,
+		sort: None,
+	});
+	let data = context.gitlab.graphql::<SentryErrors>(&query)?;
+	Ok(data.errors().map(|errors| errors.len()))
+}
+
+#[test]
+fn graphql_errors() {
+	let mut gitlab = FakeGitlab::new();
+	let query = gitlab.graphql(200, r#"{"data":{"project":{"sentryErrors":null}}}"#);
+	assert_eq!(sentry_errors(&gitlab).unwrap(), None);
+	query.assert();
+
+	let mut gitlab = FakeGitlab::new();
+	gitlab.graphql(200, r#"{"errors":[{"message":"Field 'sentryErrors' doesn't exist"}]}"#);
+	let err = sentry_errors(&gitlab).unwrap_err();
+	assert!(err.to_string().contains("doesn't exist"), "{}", err);
+
+	let mut gitlab = FakeGitlab::new();
+	gitlab.graphql(502, "{}");
+	let err = sentry_errors(&gitlab).unwrap_err();
+	assert!(err.to_string().contains("502"), "{}", err);
+
+	let mut gitlab = FakeGitlab::new();
+	gitlab.graphql(200, "{}");
+	let err = sentry_errors(&gitlab).unwrap_err();
+	assert_eq!(err.to_string(), "no response from gitlab");
+}
+
+#[test]
+fn get_latest_pipeline() {
+	let mut gitlab = FakeGitlab::new();
+	gitlab.get("projects/group%2Ffoo", "project");
+	let pipelines = gitlab.get("projects/group%2Ffoo/pipelines", "pipelines");
+	let context = gitlab.context(&["project", "info"]);
+
+	let project = context.get_project(Some("group/foo")).unwrap();
+	let pipeline = context.get_pipeline(None, &project, "main").unwrap();
+	pipelines.assert();
+	assert_eq!(pipeline.id.value(), 5678);
+	assert_eq!(pipeline.status, StatusState::Success);
+	assert_eq!(pipeline.source.as_deref(), Some("push"));
+}
+
+#[test]
+fn get_jobs() {
+	let mut gitlab = FakeGitlab::new();
+	gitlab.get("projects/group%2Ffoo", "project");
+	gitlab.get("projects/group%2Ffoo/pipelines/5678/jobs", "jobs");
+	let context = gitlab.context(&["project", "info"]);
+
+	let project = context.get_project(Some("group/foo")).unwrap();
+	let jobs = context.get_jobs(&project, 5678).unwrap();
+	let names: Vec<_> = jobs.iter().map(|job| job.name.as_str()).collect();
+	assert_eq!(names, ["build", "test"]);
+	assert_eq!(jobs[0].deletable_artifacts().count(), 0);
+}
+
+#[test]
+fn get_named_jobs() {
+	let mut gitlab = FakeGitlab::new();
+	gitlab.get("projects/group%2Ffoo", "project");
+	gitlab.get("projects/group%2Ffoo/pipelines", "pipelines");
+	gitlab.get("projects/group%2Ffoo/pipelines/5678/jobs", "jobs");
+	let context = gitlab.context(&["project", "info"]);
+
+	let project = context.get_project(Some("group/foo")).unwrap();
+	let names = |pattern| -> Vec<String> {
+		context
+			.get_named_jobs(pattern, None, &project, "main", iter::empty::<JobScope>())
+			.unwrap()
+			.into_iter()
+			.map(|job| job.name)
+			.collect()
+	};
+	assert_eq!(names("*"), ["build", "test"]);
+	assert_eq!(names("te?t"), ["test"]);
+	let err = context
+		.get_named_jobs("deploy*", None, &project, "main", iter::empty::<JobScope>())
+		.unwrap_err();
+	assert_eq!(
+		err.to_string(),
+		"No job matching deploy* with a log in pipeline 5678 (group/foo @ main)"
+	);
+}
+
+#[test]
+fn get_manual_job() {
+	let mut gitlab = FakeGitlab::new();
+	gitlab.get("projects/group%2Ffoo", "project");
+	gitlab.get("projects/group%2Ffoo/pipelines", "pipelines").expect(2);
+	gitlab.get("projects/group%2Ffoo/pipelines/5678/jobs", "manual_jobs").expect(2);
+	let context = gitlab.context(&["project", "info"]);
+
+	let project = context.get_project(Some("group/foo")).unwrap();
+	// the latest attempt of a job played again
+	let job = context.get_manual_job("deploy", None, &project, "main").unwrap();
+	assert_eq!(job.id.value(), 9016);
+	let err = context.get_manual_job("rollback", None, &project, "main").unwrap_err();
+	assert_eq!(
+		err.to_string(),
+		"No manual job named rollback in pipeline 5678 (group/foo @ main)"
+	);
+}
+
+#[test]
+fn get_token_by_id() {
+	let mut gitlab = FakeGitlab::new();
+	gitlab.get("personal_access_tokens", "tokens");
+	let context = gitlab.context(&["project", "info"]);
+
+	let token = context.get_token(78).unwrap();
+	assert_eq!(token.name, "ci-readonly");
+}
+
+#[test]
+fn get_ambiguous_token() {
+	let mut gitlab = FakeGitlab::new();
+	// the search is done by the server which returns every token containing the name
+	gitlab.get("personal_access_tokens", "tokens");
+	let context = gitlab.context(&["project", "info"]);
+
+	// choosing one is impossible without prompt
+	let err = context.get_token("ci").unwrap_err();
+	assert!(err.to_string().starts_with("More than one token matching ci"));
+	assert!(err.to_string().contains("--non-interactive"));
+}
+
+#[test]
+fn get_key_by_name() {
+	let mut gitlab = FakeGitlab::new();
+	gitlab.get("users/jdoe/keys", "keys");
+	let context = gitlab.context(&["project", "info"]);
+
+	let key = context
+		.get_key(&KeyIdType::Name("desktop".to_owned()))
+		.unwrap();
+	assert_eq!(key.id.value(), 4);
+	assert!(key.expires_at.is_some());
+}
+
+#[test]
+fn require_version() {
+	let mut gitlab = FakeGitlab::new();
+	let version = gitlab.get("version", "version");
+	let context = gitlab.context(&["project", "info"]);
+
+	assert!(context.require_version((15, 7), "Key usage type").is_ok());
+	let err = context.require_version((16, 0), "Token rotation").unwrap_err();
+	assert_eq!(
+		err.to_string(),
+		format!(
+			"Token rotation requires GitLab >= 16.0 ({} runs 15.11.13-ee)",
+			&context.repo.host
+		)
+	);
+	// the version is only queried once
+	version.assert();
+}
+
+#[test]
+fn unknown_version() {
+	let mut gitlab = FakeGitlab::new();
+	gitlab.get_error("version", 403, "403 Forbidden");
+	let context = gitlab.context(&["project", "info"]);
+
+	assert!(context.gitlab_version().is_none());
+	assert!(context.require_version((16, 0), "Token rotation").is_ok());
+}
+
+#[test]
+fn record_timings() {
+	let mut gitlab = FakeGitlab::new();
+	gitlab.get("projects/group%2Ffoo", "project");
+	gitlab.get("projects/group%2Ffoo/pipelines/5678/jobs", "jobs");
+	let context = gitlab.context(&["--timing", "project", "info"]);
+
+	let project = context.get_project(Some("group/foo")).unwrap();
+	context.get_jobs(&project, 5678).unwrap();
+	let timings = context.gitlab.timings().unwrap();
+	let endpoints: Vec<_> = timings
+		.iter()
+		.map(|timing| format!("{} {}", timing.method, timing.endpoint))
+		.collect();
+	assert_eq!(
+		endpoints,
+		[
+			"GET projects/group%2Ffoo",
+			"GET projects/group%2Ffoo/pipelines/5678/jobs"
+		]
+	);
+	// nothing is recorded without the flag
+	let context = gitlab.context(&["project", "info"]);
+	assert!(context.gitlab.timings().is_none());
+}
+
+#[test]
+fn get_health() {
+	let mut gitlab = FakeGitlab::new();
+	let readiness = gitlab.probe("readiness", "readiness");
+	let context = gitlab.context(&["admin", "health"]);
+
+	// the probes are queried on the instance url, not under the api
+	let health = context.get_health("readiness?all=1").unwrap();
+	readiness.assert();
+	assert_eq!(health.status, "ok");
+	assert_eq!(health.checks.len(), 3);
+	assert!(!health.is_healthy());
+	assert!(context.get_health("liveness").is_err());
+}
+
+#[test]
+fn rate_limited_without_throttle() {
+	let mut gitlab = FakeGitlab::new();
+	gitlab.get_error("projects/group%2Ffoo", 429, "Too Many Requests");
+	let context = gitlab.context(&["--no-throttle", "project", "info"]);
+
+	let start = Instant::now();
+	let err = context.get_project(Some("group/foo")).unwrap_err();
+	assert!(start.elapsed() < Duration::from_secs(1));
+	assert!(
+		format!("{:#}", err).contains("rate limited by gitlab, giving up after 0 retries"),
+		"{:#}",
+		err
+	);
+}